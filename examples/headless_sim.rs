@@ -0,0 +1,18 @@
+//! Balance testing: auto-play the battle side headlessly and print the results.
+//!
+//! cargo run --example headless_sim -- [waves] [seed]
+
+use bevy::prelude::*;
+use puzzle_tactics::battle::HeadlessSimPlugin;
+
+fn main() {
+    let mut args = std::env::args().skip(1);
+    let defaults = HeadlessSimPlugin::default();
+    let waves = args.next().and_then(|arg| arg.parse().ok()).unwrap_or(defaults.waves);
+    let seed = args.next().and_then(|arg| arg.parse().ok()).unwrap_or(defaults.seed);
+
+    App::new()
+        .add_plugins(MinimalPlugins)
+        .add_plugins(HeadlessSimPlugin { waves, seed, ..defaults })
+        .run();
+}
@@ -1,33 +1,186 @@
 use crate::prelude::*;
-use bevy::audio::AudioSource;
+use bevy::audio::{AudioSource, Volume};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Where audio settings are persisted, relative to the working directory
+pub const SETTINGS_FILE: &str = "settings.json";
+
+/// Whether the looping tracks in `MusicTrack::path` ship in `assets/audio`.
+/// Music stays off until they do, so the asset server isn't asked for missing files.
+pub const MUSIC_ASSETS_AVAILABLE: bool = false;
 
 pub struct AudioPlugin;
 
 impl Plugin for AudioPlugin {
     fn build(&self, app: &mut App) {
-        app.init_resource::<AudioSettings>()
+        app.insert_resource(AudioSettings::load(Path::new(SETTINGS_FILE)))
+            .init_resource::<MusicState>()
+            .add_systems(Update, save_audio_settings)
             .add_observer(handle_match_sound)
             .add_observer(handle_attack_sound)
             .add_observer(handle_victory_sound)
             .add_observer(handle_defeat_sound);
+
+        if MUSIC_ASSETS_AVAILABLE {
+            app.add_systems(
+                Update,
+                sync_music.run_if(
+                    state_changed::<GameState>
+                        .or(state_changed::<PhaseState>)
+                        .or(resource_changed::<AudioSettings>),
+                ),
+            );
+        }
     }
 }
 
-#[derive(Resource)]
+/// Player audio preferences. `enabled: false` is the mute toggle; it silences
+/// both categories without losing the chosen volumes.
+#[derive(Resource, Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct AudioSettings {
     pub enabled: bool,
-    pub volume: f32,
+    pub sfx_volume: f32,
+    pub music_volume: f32,
 }
 
 impl Default for AudioSettings {
     fn default() -> Self {
         Self {
             enabled: true,
-            volume: 1.0,
+            sfx_volume: 1.0,
+            music_volume: 1.0,
+        }
+    }
+}
+
+impl AudioSettings {
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("AudioSettings always serializes")
+    }
+
+    /// Parse settings; missing fields fall back to defaults and volumes are clamped to 0..=1
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        let mut settings: Self = serde_json::from_str(json)?;
+        settings.sfx_volume = settings.sfx_volume.clamp(0.0, 1.0);
+        settings.music_volume = settings.music_volume.clamp(0.0, 1.0);
+        Ok(settings)
+    }
+
+    /// Read settings from disk; a missing or unreadable file gives the defaults
+    pub fn load(path: &Path) -> Self {
+        let Ok(json) = std::fs::read_to_string(path) else {
+            return Self::default();
+        };
+        Self::from_json(&json).unwrap_or_else(|err| {
+            warn!("Ignoring malformed {}: {}", path.display(), err);
+            Self::default()
+        })
+    }
+
+    pub fn save(&self, path: &Path) -> std::io::Result<()> {
+        std::fs::write(path, self.to_json())
+    }
+
+    /// Playback settings for a one-shot sound effect
+    fn sfx_playback(&self) -> PlaybackSettings {
+        PlaybackSettings::DESPAWN.with_volume(Volume::new(self.sfx_volume))
+    }
+}
+
+/// Write settings back to disk whenever the settings menu changes them
+fn save_audio_settings(settings: Res<AudioSettings>) {
+    if !settings.is_changed() || settings.is_added() {
+        return;
+    }
+    if let Err(err) = settings.save(Path::new(SETTINGS_FILE)) {
+        warn!("Failed to save {}: {}", SETTINGS_FILE, err);
+    }
+}
+
+/// Looping background track
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MusicTrack {
+    /// Wave breaks: time to reposition
+    Calm,
+    /// Active waves
+    Battle,
+}
+
+impl MusicTrack {
+    pub fn path(&self) -> &'static str {
+        match self {
+            MusicTrack::Calm => "audio/music_calm.ogg",
+            MusicTrack::Battle => "audio/music_battle.ogg",
         }
     }
 }
 
+/// Track that should be playing for the given states, or `None` for silence.
+/// Pausing keeps the current phase's track going.
+pub fn music_for(game_state: &GameState, phase: &PhaseState) -> Option<MusicTrack> {
+    match game_state {
+        GameState::Playing | GameState::Paused => match phase {
+            PhaseState::WaveBreak => Some(MusicTrack::Calm),
+            _ => Some(MusicTrack::Battle),
+        },
+        GameState::Loading | GameState::Title | GameState::GameOver => None,
+    }
+}
+
+/// The music entity currently playing, if any. At most one exists at a time.
+#[derive(Resource, Default)]
+pub struct MusicState {
+    pub track: Option<MusicTrack>,
+    pub handle: Option<Handle<AudioSource>>,
+    pub entity: Option<Entity>,
+}
+
+#[derive(Component)]
+pub struct MusicPlayer;
+
+/// Swap the looping track when the game/phase state or audio settings change.
+/// The old music entity is always despawned first so tracks never stack.
+fn sync_music(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    settings: Res<AudioSettings>,
+    game_state: Res<State<GameState>>,
+    phase: Res<State<PhaseState>>,
+    mut music: ResMut<MusicState>,
+    sinks: Query<&AudioSink, With<MusicPlayer>>,
+) {
+    let wanted = music_for(game_state.get(), phase.get()).filter(|_| settings.enabled);
+
+    if wanted == music.track {
+        // Same track: only follow volume changes from the settings menu
+        if let Some(sink) = music.entity.and_then(|e| sinks.get(e).ok()) {
+            sink.set_volume(settings.music_volume);
+        }
+        return;
+    }
+
+    if let Some(entity) = music.entity.take() {
+        commands.entity(entity).despawn_recursive();
+    }
+    music.handle = None;
+    music.track = wanted;
+
+    if let Some(track) = wanted {
+        let handle: Handle<AudioSource> = asset_server.load(track.path());
+        let entity = commands
+            .spawn((
+                AudioPlayer::new(handle.clone()),
+                PlaybackSettings::LOOP.with_volume(Volume::new(settings.music_volume)),
+                MusicPlayer,
+            ))
+            .id();
+        music.handle = Some(handle);
+        music.entity = Some(entity);
+    }
+}
+
 #[derive(Event)]
 pub struct MatchSoundEvent {
     pub combo_count: u32,
@@ -64,7 +217,7 @@ fn handle_match_sound(
     let handle: Handle<AudioSource> = asset_server.load(sound_path);
     commands.spawn((
         AudioPlayer::new(handle),
-        PlaybackSettings::DESPAWN.with_volume(bevy::audio::Volume::new(settings.volume)),
+        settings.sfx_playback(),
     ));
 }
 
@@ -88,7 +241,7 @@ fn handle_attack_sound(
     let handle: Handle<AudioSource> = asset_server.load(sound_path);
     commands.spawn((
         AudioPlayer::new(handle),
-        PlaybackSettings::DESPAWN.with_volume(bevy::audio::Volume::new(settings.volume)),
+        settings.sfx_playback(),
     ));
 }
 
@@ -105,7 +258,7 @@ fn handle_victory_sound(
     let handle: Handle<AudioSource> = asset_server.load("audio/victory.ogg");
     commands.spawn((
         AudioPlayer::new(handle),
-        PlaybackSettings::DESPAWN.with_volume(bevy::audio::Volume::new(settings.volume)),
+        settings.sfx_playback(),
     ));
 }
 
@@ -122,7 +275,7 @@ fn handle_defeat_sound(
     let handle: Handle<AudioSource> = asset_server.load("audio/defeat.ogg");
     commands.spawn((
         AudioPlayer::new(handle),
-        PlaybackSettings::DESPAWN.with_volume(bevy::audio::Volume::new(settings.volume)),
+        settings.sfx_playback(),
     ));
 }
 
@@ -134,7 +287,8 @@ mod tests {
     fn test_audio_settings_default() {
         let settings = AudioSettings::default();
         assert!(settings.enabled);
-        assert!((settings.volume - 1.0).abs() < f32::EPSILON);
+        assert!((settings.sfx_volume - 1.0).abs() < f32::EPSILON);
+        assert!((settings.music_volume - 1.0).abs() < f32::EPSILON);
     }
 
     #[test]
@@ -184,10 +338,132 @@ mod tests {
     #[test]
     fn test_audio_settings_volume_range() {
         let mut settings = AudioSettings::default();
-        settings.volume = 0.5;
-        assert!((settings.volume - 0.5).abs() < f32::EPSILON);
+        settings.sfx_volume = 0.5;
+        assert!((settings.sfx_volume - 0.5).abs() < f32::EPSILON);
+
+        settings.sfx_volume = 0.0;
+        assert!((settings.sfx_volume - 0.0).abs() < f32::EPSILON);
+    }
 
-        settings.volume = 0.0;
-        assert!((settings.volume - 0.0).abs() < f32::EPSILON);
+    #[test]
+    fn test_music_for_phase() {
+        assert_eq!(music_for(&GameState::Playing, &PhaseState::WaveBreak), Some(MusicTrack::Calm));
+        for phase in [PhaseState::Idle, PhaseState::Matching, PhaseState::Cascading, PhaseState::Combating] {
+            assert_eq!(music_for(&GameState::Playing, &phase), Some(MusicTrack::Battle), "{:?}", phase);
+        }
+        assert_eq!(music_for(&GameState::Paused, &PhaseState::WaveBreak), Some(MusicTrack::Calm));
+        assert_eq!(music_for(&GameState::Title, &PhaseState::Idle), None);
+        assert_eq!(music_for(&GameState::GameOver, &PhaseState::Combating), None);
+    }
+
+    fn setup_music_app() -> App {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default(), bevy::state::app::StatesPlugin))
+            .init_asset::<AudioSource>()
+            .init_state::<GameState>()
+            .init_state::<PhaseState>()
+            .init_resource::<AudioSettings>()
+            .init_resource::<MusicState>()
+            .add_systems(Update, sync_music);
+        app
+    }
+
+    fn set_states(app: &mut App, game_state: GameState, phase: PhaseState) {
+        app.world_mut().resource_mut::<NextState<GameState>>().set(game_state);
+        app.world_mut().resource_mut::<NextState<PhaseState>>().set(phase);
+        app.update();
+    }
+
+    fn music_players(app: &mut App) -> usize {
+        let world = app.world_mut();
+        world.query_filtered::<(), With<MusicPlayer>>().iter(world).count()
+    }
+
+    #[test]
+    fn test_phase_change_swaps_music_without_stacking() {
+        let mut app = setup_music_app();
+        set_states(&mut app, GameState::Playing, PhaseState::Combating);
+        app.update();
+        let battle = app.world().resource::<MusicState>().entity.unwrap();
+        assert_eq!(app.world().resource::<MusicState>().track, Some(MusicTrack::Battle));
+
+        set_states(&mut app, GameState::Playing, PhaseState::WaveBreak);
+        app.update();
+        assert_eq!(app.world().resource::<MusicState>().track, Some(MusicTrack::Calm));
+        assert!(app.world().get_entity(battle).is_err(), "old track despawned");
+        assert_eq!(music_players(&mut app), 1);
+    }
+
+    #[test]
+    fn test_plugin_plays_music_only_when_assets_ship() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default(), bevy::state::app::StatesPlugin))
+            .init_asset::<AudioSource>()
+            .init_state::<GameState>()
+            .init_state::<PhaseState>()
+            .add_plugins(AudioPlugin);
+        set_states(&mut app, GameState::Playing, PhaseState::Combating);
+        app.update();
+        assert_eq!(music_players(&mut app) > 0, MUSIC_ASSETS_AVAILABLE);
+    }
+
+    #[test]
+    fn test_muting_stops_music() {
+        let mut app = setup_music_app();
+        set_states(&mut app, GameState::Playing, PhaseState::Idle);
+        app.update();
+        assert_eq!(music_players(&mut app), 1);
+
+        app.world_mut().resource_mut::<AudioSettings>().enabled = false;
+        app.update();
+        assert_eq!(music_players(&mut app), 0);
+        assert_eq!(app.world().resource::<MusicState>().track, None);
+    }
+
+    #[test]
+    fn test_audio_settings_json_round_trip() {
+        let settings = AudioSettings {
+            enabled: false,
+            sfx_volume: 0.25,
+            music_volume: 0.75,
+        };
+        let restored = AudioSettings::from_json(&settings.to_json()).unwrap();
+        assert_eq!(restored, settings);
+    }
+
+    #[test]
+    fn test_audio_settings_missing_fields_use_defaults() {
+        let restored = AudioSettings::from_json(r#"{ "music_volume": 0.3 }"#).unwrap();
+        assert!(restored.enabled);
+        assert_eq!(restored.sfx_volume, 1.0);
+        assert_eq!(restored.music_volume, 0.3);
+    }
+
+    #[test]
+    fn test_audio_settings_volumes_are_clamped() {
+        let restored = AudioSettings::from_json(r#"{ "sfx_volume": 3.0, "music_volume": -1.0 }"#).unwrap();
+        assert_eq!(restored.sfx_volume, 1.0);
+        assert_eq!(restored.music_volume, 0.0);
+    }
+
+    #[test]
+    fn test_audio_settings_file_round_trip() {
+        let path = std::env::temp_dir().join(format!("puzzle_tactics_settings_{}.json", std::process::id()));
+        let settings = AudioSettings {
+            enabled: true,
+            sfx_volume: 0.4,
+            music_volume: 0.6,
+        };
+        settings.save(&path).unwrap();
+        let loaded = AudioSettings::load(&path);
+        std::fs::remove_file(&path).ok();
+        assert_eq!(loaded, settings);
+    }
+
+    #[test]
+    fn test_audio_settings_load_falls_back_to_default() {
+        let missing = std::env::temp_dir().join("puzzle_tactics_settings_does_not_exist.json");
+        assert_eq!(AudioSettings::load(&missing), AudioSettings::default());
+        assert!(AudioSettings::from_json("not json").is_err());
     }
 }
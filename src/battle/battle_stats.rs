@@ -128,6 +128,11 @@ impl BattleStats {
         }
     }
 
+    /// Kills credited to an ally unit type so far
+    pub fn ally_kills(&self, unit_type: TileType) -> u32 {
+        self.ally_performance_map.get(&unit_type).map_or(0, |(kills, _)| *kills)
+    }
+
     /// Get unit type name for display
     pub fn unit_type_name(unit_type: Option<TileType>) -> &'static str {
         match unit_type {
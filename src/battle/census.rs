@@ -0,0 +1,127 @@
+use crate::prelude::*;
+use super::{Unit, UnitType, Team};
+use std::collections::HashMap;
+
+/// Per-frame snapshot of living units, counted by team and type.
+/// Rebuilt once per frame by `update_unit_census` so consumers don't re-query units.
+#[derive(Resource, Default, Debug)]
+pub struct UnitCensus {
+    by_team_type: HashMap<(Team, TileType), usize>,
+    player_total: usize,
+    enemy_total: usize,
+}
+
+impl UnitCensus {
+    pub fn count(&self, team: Team) -> usize {
+        match team {
+            Team::Player => self.player_total,
+            Team::Enemy => self.enemy_total,
+        }
+    }
+
+    pub fn count_of(&self, team: Team, tile_type: TileType) -> usize {
+        self.by_team_type.get(&(team, tile_type)).copied().unwrap_or(0)
+    }
+
+    pub fn total(&self) -> usize {
+        self.player_total + self.enemy_total
+    }
+
+    /// Non-zero type counts for one team
+    pub fn types_for(&self, team: Team) -> impl Iterator<Item = (TileType, usize)> + '_ {
+        self.by_team_type
+            .iter()
+            .filter(move |((t, _), _)| *t == team)
+            .map(|((_, tile_type), count)| (*tile_type, *count))
+    }
+
+    fn clear(&mut self) {
+        self.by_team_type.clear();
+        self.player_total = 0;
+        self.enemy_total = 0;
+    }
+
+    fn add(&mut self, team: Team, tile_type: TileType) {
+        *self.by_team_type.entry((team, tile_type)).or_insert(0) += 1;
+        match team {
+            Team::Player => self.player_total += 1,
+            Team::Enemy => self.enemy_total += 1,
+        }
+    }
+}
+
+pub fn update_unit_census(
+    mut census: ResMut<UnitCensus>,
+    units: Query<(&UnitType, &Team), With<Unit>>,
+) {
+    census.clear();
+    for (unit_type, team) in units.iter() {
+        census.add(*team, unit_type.0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn setup_census_app() -> App {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .init_resource::<UnitCensus>()
+            .add_systems(Update, update_unit_census);
+        app
+    }
+
+    fn spawn_unit(app: &mut App, tile_type: TileType, team: Team) -> Entity {
+        app.world_mut().spawn((Unit, UnitType(tile_type), team)).id()
+    }
+
+    fn direct_count(app: &mut App, team: Team, tile_type: Option<TileType>) -> usize {
+        app.world_mut()
+            .query_filtered::<(&UnitType, &Team), With<Unit>>()
+            .iter(app.world())
+            .filter(|(u, t)| **t == team && tile_type.is_none_or(|tt| u.0 == tt))
+            .count()
+    }
+
+    #[test]
+    fn test_census_matches_direct_count() {
+        let mut app = setup_census_app();
+        spawn_unit(&mut app, TileType::Red, Team::Player);
+        spawn_unit(&mut app, TileType::Red, Team::Player);
+        spawn_unit(&mut app, TileType::Blue, Team::Player);
+        spawn_unit(&mut app, TileType::Red, Team::Enemy);
+        spawn_unit(&mut app, TileType::Purple, Team::Enemy);
+
+        app.update();
+
+        let census_player = app.world().resource::<UnitCensus>().count(Team::Player);
+        let census_enemy = app.world().resource::<UnitCensus>().count(Team::Enemy);
+        assert_eq!(census_player, direct_count(&mut app, Team::Player, None));
+        assert_eq!(census_enemy, direct_count(&mut app, Team::Enemy, None));
+
+        for tile_type in [TileType::Red, TileType::Blue, TileType::Green, TileType::Yellow, TileType::Purple] {
+            for team in [Team::Player, Team::Enemy] {
+                let census = app.world().resource::<UnitCensus>().count_of(team, tile_type);
+                assert_eq!(census, direct_count(&mut app, team, Some(tile_type)));
+            }
+        }
+        assert_eq!(app.world().resource::<UnitCensus>().total(), 5);
+    }
+
+    #[test]
+    fn test_census_drops_despawned_units() {
+        let mut app = setup_census_app();
+        let unit = spawn_unit(&mut app, TileType::Green, Team::Player);
+        spawn_unit(&mut app, TileType::Green, Team::Player);
+        app.update();
+        assert_eq!(app.world().resource::<UnitCensus>().count_of(Team::Player, TileType::Green), 2);
+
+        app.world_mut().despawn(unit);
+        app.update();
+
+        let census = app.world().resource::<UnitCensus>();
+        assert_eq!(census.count(Team::Player), 1);
+        assert_eq!(census.count_of(Team::Player, TileType::Green), 1);
+    }
+}
@@ -0,0 +1,256 @@
+use crate::prelude::*;
+use super::{Unit, DamageType, UnitStats, Shield, take_shielded_damage, HexPosition, BattleGrid, Team, LastHitBy, StealthBuff, DamagePopupEvent, BattleStats};
+use super::combat::spawn_attack_line;
+use super::projectile::ProjectileHitEvent;
+use super::experience::{ExperienceGainEvent, XP_PER_DAMAGE};
+
+/// Hexes from the primary target a bolt can jump to
+pub const CHAIN_RANGE: i32 = 2;
+/// Extra targets hit after the primary one
+pub const CHAIN_BOUNCES: usize = 2;
+/// Share of the previous hit's damage each bounce deals
+pub const CHAIN_FALLOFF: f32 = 0.6;
+
+/// Mage (Purple) basic attacks arc on to nearby enemies
+pub fn chains_lightning(unit_type: TileType) -> bool {
+    unit_type == TileType::Purple
+}
+
+/// Damage of the `bounce`-th jump (1 = first bounce) off a `base` hit
+pub fn chain_damage(base: f32, bounce: usize) -> f32 {
+    base * CHAIN_FALLOFF.powi(bounce as i32)
+}
+
+/// Follow the chain from `start`: each hop goes to the nearest candidate within
+/// `CHAIN_RANGE` of the primary target, never the same unit twice. Ties are broken
+/// by entity id.
+pub fn chain_targets(start: (Entity, HexPosition), candidates: &[(Entity, HexPosition)]) -> Vec<(Entity, HexPosition)> {
+    let mut hit = vec![start.0];
+    let mut chain = Vec::new();
+    let primary = start.1;
+
+    for _ in 0..CHAIN_BOUNCES {
+        let next = candidates
+            .iter()
+            .filter(|(entity, pos)| !hit.contains(entity) && primary.distance(pos) <= CHAIN_RANGE)
+            .min_by(|(a, a_pos), (b, b_pos)| primary.distance(a_pos).cmp(&primary.distance(b_pos)).then(a.cmp(b)));
+        let Some(&(entity, pos)) = next else { break };
+
+        hit.push(entity);
+        chain.push((entity, pos));
+    }
+    chain
+}
+
+type ChainUnitQuery<'w, 's> = Query<
+    'w,
+    's,
+    (Entity, &'static HexPosition, &'static Team, &'static mut UnitStats, Option<&'static mut Shield>, Has<StealthBuff>),
+    With<Unit>,
+>;
+
+/// Arc a mage's landed shot on to the primary target's nearby allies. Stealthed and
+/// dead units are skipped, so the bolt never reveals or overkills anyone. Bounces
+/// earn damage XP and kill credit just like the primary hit.
+pub fn handle_chain_lightning(
+    trigger: Trigger<ProjectileHitEvent>,
+    mut commands: Commands,
+    grid: Res<BattleGrid>,
+    mut battle_stats: ResMut<BattleStats>,
+    mut units: ChainUnitQuery,
+) {
+    let event = trigger.event();
+    if !chains_lightning(event.unit_type) {
+        return;
+    }
+    let Ok((_, &start_pos, &victim_team, ..)) = units.get(event.target) else { return };
+
+    let candidates: Vec<(Entity, HexPosition)> = units
+        .iter()
+        .filter(|(_, _, team, stats, _, stealthed)| **team == victim_team && !stats.is_dead() && !stealthed)
+        .map(|(entity, pos, ..)| (entity, *pos))
+        .collect();
+
+    let mut from = grid.axial_to_pixel(&start_pos);
+    for (bounce, (entity, pos)) in chain_targets((event.target, start_pos), &candidates).into_iter().enumerate() {
+        let damage = chain_damage(event.damage, bounce + 1);
+        let Ok((_, _, _, mut stats, mut shield, _)) = units.get_mut(entity) else { continue };
+
+        take_shielded_damage(&mut stats, shield.as_deref_mut(), damage, DamageType::for_unit(event.unit_type));
+        if shield.is_some_and(|shield| shield.is_depleted()) {
+            commands.entity(entity).remove::<Shield>();
+        }
+        commands.entity(entity).insert(LastHitBy(event.attacker));
+
+        let to = grid.axial_to_pixel(&pos);
+        spawn_attack_line(&mut commands, from, to);
+        commands.trigger(DamagePopupEvent {
+            position: to.extend(0.0),
+            damage: damage as i32,
+            is_critical: false,
+            is_poison: false,
+            is_execute: false,
+        });
+        from = to;
+
+        match event.team {
+            Team::Enemy => battle_stats.record_enemy_damage(event.unit_type, damage),
+            Team::Player => {
+                battle_stats.record_ally_damage(event.unit_type, damage);
+                commands.trigger(ExperienceGainEvent { unit: event.attacker, amount: damage * XP_PER_DAMAGE });
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::UnitType;
+    use super::super::combat::death_system;
+    use super::super::experience::{handle_experience_gain, UnitExperience, XP_PER_KILL};
+
+    fn entity(n: u32) -> Entity {
+        Entity::from_raw(n)
+    }
+
+    #[test]
+    fn test_chain_picks_nearest_within_range_without_repeats() {
+        let start = (entity(0), HexPosition::new(0, 0));
+        let candidates = [
+            start,
+            (entity(1), HexPosition::new(2, 0)),
+            (entity(2), HexPosition::new(1, 0)),
+            (entity(3), HexPosition::new(5, 0)),
+        ];
+
+        let chain = chain_targets(start, &candidates);
+
+        // Nearest to the primary target first; the primary target is never hit again
+        assert_eq!(chain, vec![(entity(2), HexPosition::new(1, 0)), (entity(1), HexPosition::new(2, 0))]);
+    }
+
+    #[test]
+    fn test_chain_range_is_measured_from_the_primary_target() {
+        let start = (entity(0), HexPosition::new(0, 0));
+        let first = (entity(1), HexPosition::new(CHAIN_RANGE, 0));
+        // In range of the first bounce, but not of the primary target
+        let beyond = (entity(2), HexPosition::new(CHAIN_RANGE * 2, 0));
+
+        assert_eq!(chain_targets(start, &[start, first, beyond]), vec![first]);
+    }
+
+    #[test]
+    fn test_chain_stops_when_nothing_in_range() {
+        let start = (entity(0), HexPosition::new(0, 0));
+        let candidates = [(entity(1), HexPosition::new(CHAIN_RANGE + 1, 0))];
+        assert!(chain_targets(start, &candidates).is_empty());
+    }
+
+    #[test]
+    fn test_chain_caps_bounces() {
+        let start = (entity(0), HexPosition::new(0, 0));
+        let candidates: Vec<(Entity, HexPosition)> =
+            (1..=5).map(|q| (entity(q as u32), HexPosition::new(q, 0))).collect();
+        assert_eq!(chain_targets(start, &candidates).len(), CHAIN_BOUNCES);
+    }
+
+    #[test]
+    fn test_chain_damage_falls_off_per_bounce() {
+        assert_eq!(chain_damage(50.0, 0), 50.0);
+        assert!((chain_damage(50.0, 1) - 30.0).abs() < 1e-4);
+        assert!((chain_damage(50.0, 2) - 18.0).abs() < 1e-4);
+    }
+
+    fn spawn_enemy(app: &mut App, pos: HexPosition) -> Entity {
+        app.world_mut().spawn((Unit, pos, Team::Enemy, UnitStats::default())).id()
+    }
+
+    fn health(app: &App, entity: Entity) -> f32 {
+        app.world().get::<UnitStats>(entity).unwrap().health
+    }
+
+    #[test]
+    fn test_mage_hit_chains_past_stealthed_units() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .insert_resource(BattleGrid::new())
+            .init_resource::<BattleStats>()
+            .add_observer(handle_chain_lightning);
+        let primary = spawn_enemy(&mut app, HexPosition::new(0, 2));
+        let hidden = spawn_enemy(&mut app, HexPosition::new(1, 2));
+        app.world_mut().entity_mut(hidden).insert(StealthBuff::new());
+        let bounce = spawn_enemy(&mut app, HexPosition::new(2, 2));
+        let ally = app
+            .world_mut()
+            .spawn((Unit, HexPosition::new(-1, 2), Team::Player, UnitStats::default()))
+            .id();
+        app.world_mut().flush();
+
+        let hit = |unit_type| ProjectileHitEvent {
+            attacker: ally,
+            target: primary,
+            damage: 50.0,
+            is_critical: false,
+            team: Team::Player,
+            unit_type,
+        };
+        app.world_mut().trigger(hit(TileType::Green));
+        app.world_mut().flush();
+        assert_eq!(health(&app, bounce), 100.0, "only mage shots chain");
+
+        app.world_mut().trigger(hit(TileType::Purple));
+        app.world_mut().flush();
+
+        assert_eq!(health(&app, primary), 100.0, "the primary hit itself is handled by the projectile");
+        assert_eq!(health(&app, hidden), 100.0, "stealthed units aren't chained to");
+        assert_eq!(health(&app, ally), 100.0, "the bolt only jumps between the target's team");
+        assert!(health(&app, bounce) < 100.0);
+        assert_eq!(app.world().get::<LastHitBy>(bounce).map(|hit| hit.0), Some(ally));
+    }
+
+    #[test]
+    fn test_bounce_kill_grants_mage_xp() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .insert_resource(BattleGrid::new())
+            .init_resource::<BattleStats>()
+            .init_resource::<Gold>()
+            .init_resource::<BoardConfig>()
+            .insert_resource(GameRng::from_seed(0))
+            .add_observer(handle_chain_lightning)
+            .add_observer(handle_experience_gain)
+            .add_systems(Update, death_system);
+        let primary = spawn_enemy(&mut app, HexPosition::new(0, 2));
+        let bounce = spawn_enemy(&mut app, HexPosition::new(1, 2));
+        app.world_mut().entity_mut(bounce).insert(UnitStats { health: 10.0, ..default() });
+        let mage = app
+            .world_mut()
+            .spawn((
+                Unit,
+                HexPosition::new(-1, 2),
+                Team::Player,
+                UnitStats::default(),
+                UnitType(TileType::Purple),
+                UnitExperience::default(),
+            ))
+            .id();
+        app.world_mut().flush();
+
+        app.world_mut().trigger(ProjectileHitEvent {
+            attacker: mage,
+            target: primary,
+            damage: 50.0,
+            is_critical: false,
+            team: Team::Player,
+            unit_type: TileType::Purple,
+        });
+        app.update();
+
+        assert!(app.world().get_entity(bounce).is_err(), "the bounce finished the enemy off");
+        assert_eq!(app.world().resource::<BattleStats>().ally_kills(TileType::Purple), 1);
+        let xp = app.world().get::<UnitExperience>(mage).unwrap().xp;
+        let expected = chain_damage(50.0, 1) * XP_PER_DAMAGE + XP_PER_KILL;
+        assert!((xp - expected).abs() < 1e-4, "bounce damage and kill both earn XP, got {xp}");
+    }
+}
@@ -1,8 +1,15 @@
 use crate::prelude::*;
 // TileType, ObstacleType are now imported via prelude
-use crate::bridge::ObstacleSpawnEvent;
+use crate::bridge::{ObstacleSpawnEvent, BurstAttackEvent};
 use crate::audio::AttackSoundEvent;
-use super::{Unit, UnitStats, UnitType, HexPosition, BattleGrid, Team, Target, AttackCooldown, WaveManager, RageBuff, SnipeBuff, StealthBuff, MeteorAbility, DamagePopupEvent, BattleStats};
+use rand::Rng;
+use super::{Unit, DamageType, UnitStats, UnitType, StarRank, TargetingMode, HexPosition, BattleGrid, Team, Target, AttackCooldown, LastHitBy, CombatActivity, WaveManager, RageBuff, SnipeBuff, StealthBuff, PoisonDebuff, MeteorAbility, Shield, take_shielded_damage, DamagePopupEvent, HealPopupEvent, BattleStats};
+use super::wave::{EnemyArchetype, EnemyGoal, ExplosiveOnDeath, spawn_death_bomb};
+use super::game_result::BASE_ROW;
+use super::projectile::{fires_projectile, spawn_projectile, ProjectileShot};
+use super::knockback::KnockbackEvent;
+use super::experience::{ExperienceGainEvent, XP_PER_DAMAGE, XP_PER_KILL};
+use super::placement::Selected;
 
 // ============================================================
 // Damage Calculator
@@ -41,6 +48,15 @@ impl DamageCalculator {
         damage * (1.0 - reduction)
     }
 
+    /// Applies the resistance matching the damage type:
+    /// `defense` for physical, `magic_resist` for magical
+    pub fn apply_typed_defense(damage: f32, damage_type: DamageType, defense: f32, magic_resist: f32) -> f32 {
+        match damage_type {
+            DamageType::Physical => Self::apply_defense(damage, defense),
+            DamageType::Magical => Self::apply_defense(damage, magic_resist),
+        }
+    }
+
     /// Full damage calculation: base * crit_multiplier * defense_reduction
     /// Ensures minimum damage of 1.0
     pub fn calculate(base_damage: f32, is_crit: bool, defense: f32) -> f32 {
@@ -48,6 +64,12 @@ impl DamageCalculator {
         let final_damage = Self::apply_defense(after_crit, defense);
         final_damage.max(Self::MIN_DAMAGE)
     }
+
+    /// `calculate` with the resistance picked by damage type
+    pub fn calculate_typed(base_damage: f32, is_crit: bool, damage_type: DamageType, defense: f32, magic_resist: f32) -> f32 {
+        let after_crit = Self::apply_crit(base_damage, is_crit);
+        Self::apply_typed_defense(after_crit, damage_type, defense, magic_resist).max(Self::MIN_DAMAGE)
+    }
 }
 
 #[derive(Component)]
@@ -55,120 +77,268 @@ pub struct AttackLine {
     pub timer: Timer,
 }
 
+/// Critical hits dealing at least this much damage trigger a brief slow-mo
+pub const BIG_CRIT_DAMAGE: f32 = 40.0;
+const BIG_CRIT_SLOWMO_SCALE: f32 = 0.4;
+const BIG_CRIT_SLOWMO_DURATION: f32 = 0.2;
+
+/// Slow-mo to play for a hit, if it is a big enough crit
+pub fn big_crit_slowmo(damage: f32, is_crit: bool) -> Option<SlowMoEvent> {
+    (is_crit && damage >= BIG_CRIT_DAMAGE).then_some(SlowMoEvent {
+        scale: BIG_CRIT_SLOWMO_SCALE,
+        duration: BIG_CRIT_SLOWMO_DURATION,
+    })
+}
+
+/// Assassin hits finish off targets left below this share of their max health
+pub const EXECUTE_THRESHOLD: f32 = 0.15;
+
+/// Assassin (Yellow) basic attacks execute badly wounded targets
+pub fn executes(unit_type: TileType) -> bool {
+    unit_type == TileType::Yellow
+}
+
+/// Whether a unit the hit left at `stats` is low enough to be executed
+pub fn should_execute(stats: &UnitStats) -> bool {
+    !stats.is_dead() && stats.health / stats.max_health < EXECUTE_THRESHOLD
+}
+
+/// How long an enemy telegraphs before its hit lands (seconds)
+pub const ENEMY_WINDUP_DURATION: f32 = 0.4;
+
+const TELEGRAPH_COLOR: Color = Color::srgba(1.0, 0.15, 0.1, 0.45);
+const TELEGRAPH_SIZE: f32 = 48.0;
+
+/// Enemy attack being wound up; damage lands on `target` when the timer finishes
+#[derive(Component)]
+pub struct AttackWindup {
+    pub target: Entity,
+    pub telegraph: Entity,
+    pub timer: Timer,
+}
+
+/// Warning marker parented to the player unit an enemy is about to hit
+#[derive(Component)]
+pub struct AttackTelegraph {
+    pub attacker: Entity,
+}
+
 pub fn targeting_system(
-    units: Query<(Entity, &HexPosition, &Team), With<Unit>>,
+    units: Query<(Entity, &HexPosition, &Team, &UnitStats, Option<&TargetingMode>), With<Unit>>,
     stealth_units: Query<Entity, With<StealthBuff>>,
     mut targets: Query<&mut Target, With<Unit>>,
 ) {
-    let unit_data: Vec<(Entity, HexPosition, Team)> = units
-        .iter()
-        .map(|(e, p, t)| (e, *p, *t))
-        .collect();
-
     // Collect stealthed entities
     let stealthed: std::collections::HashSet<Entity> = stealth_units.iter().collect();
 
-    for (entity, pos, team) in &unit_data {
-        let mut closest: Option<(Entity, i32)> = None;
+    for (entity, pos, team, stats, mode) in units.iter() {
+        // Stealthed units cannot be targeted
+        let candidates = units.iter().filter(|(other, _, other_team, _, _)| {
+            *other != entity && *other_team != team && !stealthed.contains(other)
+        });
+        let best = pick_target(
+            pos,
+            stats.attack_range,
+            mode.copied().unwrap_or_default(),
+            candidates.map(|(other, other_pos, _, other_stats, _)| (other, *other_pos, other_stats)),
+        );
 
-        for (other_entity, other_pos, other_team) in &unit_data {
-            if entity == other_entity || team == other_team {
-                continue;
-            }
+        if let Ok(mut target) = targets.get_mut(entity) {
+            target.0 = best;
+        }
+    }
+}
 
-            // Skip stealthed units - they cannot be targeted
-            if stealthed.contains(other_entity) {
-                continue;
-            }
+/// Rank candidates by `mode` among those within `range`; with none in range, the
+/// nearest one. Ties fall back to distance, then spawn order.
+fn pick_target<'a>(
+    from: &HexPosition,
+    range: i32,
+    mode: TargetingMode,
+    candidates: impl Iterator<Item = (Entity, HexPosition, &'a UnitStats)>,
+) -> Option<Entity> {
+    let candidates: Vec<(Entity, i32, &UnitStats)> = candidates
+        .map(|(entity, pos, stats)| (entity, from.distance(&pos), stats))
+        .collect();
+    let nearest = |(a, a_dist, _): &&(Entity, i32, &UnitStats), (b, b_dist, _): &&(Entity, i32, &UnitStats)| {
+        a_dist.cmp(b_dist).then(a.cmp(b))
+    };
 
-            let dist = pos.distance(other_pos);
-            if closest.is_none() || dist < closest.unwrap().1 {
-                closest = Some((*other_entity, dist));
-            }
-        }
+    let in_range = candidates.iter().filter(|(_, dist, _)| *dist <= range);
+    let ranked = match mode {
+        TargetingMode::Nearest => in_range.min_by(nearest),
+        TargetingMode::LowestHealth => in_range.min_by(|a, b| {
+            a.2.health.total_cmp(&b.2.health).then_with(|| nearest(a, b))
+        }),
+        TargetingMode::HighestAttack => in_range.min_by(|a, b| {
+            b.2.attack.total_cmp(&a.2.attack).then_with(|| nearest(a, b))
+        }),
+    };
+    ranked
+        .or_else(|| candidates.iter().min_by(nearest))
+        .map(|(entity, ..)| *entity)
+}
 
-        if let Ok(mut target) = targets.get_mut(*entity) {
-            target.0 = closest.map(|(e, _)| e);
-        }
-    }
+/// Whether `attacker` can hit `target` from where it stands. Movement stops and attacks
+/// become eligible at exactly this distance, so a unit never parks where it can't swing.
+/// Reads the live `attack_range`, so synergy range bonuses count for both.
+pub fn in_attack_range(attacker: &HexPosition, target: &HexPosition, stats: &UnitStats) -> bool {
+    within_range(attacker, target, stats.attack_range)
+}
+
+fn within_range(from: &HexPosition, to: &HexPosition, range: i32) -> bool {
+    from.distance(to) <= range
 }
 
 pub fn movement_system(
     mut grid: ResMut<BattleGrid>,
-    mut units: Query<(Entity, &mut HexPosition, &UnitStats, &Target, &mut Transform), With<Unit>>,
+    mut units: Query<(Entity, &mut HexPosition, &UnitStats, &mut Target, Option<&EnemyGoal>, &mut Transform), With<Unit>>,
 ) {
     let unit_positions: std::collections::HashMap<Entity, HexPosition> = units
         .iter()
-        .map(|(e, pos, _, _, _)| (e, *pos))
+        .map(|(e, pos, ..)| (e, *pos))
         .collect();
 
-    let mut movements: Vec<(Entity, HexPosition, HexPosition)> = Vec::new();
-
-    for (entity, pos, stats, target, _) in units.iter() {
-        let Some(target_entity) = target.0 else { continue };
-        let Some(target_pos) = unit_positions.get(&target_entity) else { continue };
-
-        let distance = pos.distance(target_pos);
-        if distance <= stats.attack_range {
+    for (_, mut pos, stats, mut target, goal, mut transform) in units.iter_mut() {
+        if goal == Some(&EnemyGoal::RushBase) {
+            rush_base(&mut grid, &mut pos, stats, &mut target, &mut transform);
             continue;
         }
+        let target_pos = target.0.and_then(|t| unit_positions.get(&t)).copied();
+
+        // Fast units take several one-hex steps per tick, re-checking range after each
+        for _ in 0..stats.move_speed.max(1.0) as usize {
+            if target_pos.is_some_and(|target_pos| in_attack_range(&pos, &target_pos, stats)) {
+                break;
+            }
+
+            let Some(next_pos) = target_pos.and_then(|target_pos| find_best_move(&grid, &pos, &target_pos, stats.attack_range)) else { break };
 
-        if let Some(next_pos) = find_best_move(&grid, &pos, target_pos) {
-            if !grid.is_occupied(&next_pos) {
-                movements.push((entity, *pos, next_pos));
+            if !grid.move_unit(&pos, &next_pos) {
+                break;
             }
+            *pos = next_pos;
+            transform.translation = grid.axial_to_pixel(&next_pos).extend(1.0);
         }
     }
+}
 
-    for (entity, from, to) in movements {
-        if grid.move_unit(&from, &to) {
-            if let Ok((_, mut pos, _, _, mut transform)) = units.get_mut(entity) {
-                *pos = to;
-                let world_pos = grid.axial_to_pixel(&to);
-                transform.translation = world_pos.extend(1.0);
-            }
+/// Step a base rusher along its path, ignoring combat. Its target is dropped on any
+/// tick it moves, so `attack_system` only swings when no path is left (or at the base).
+fn rush_base(
+    grid: &mut BattleGrid,
+    pos: &mut HexPosition,
+    stats: &UnitStats,
+    target: &mut Target,
+    transform: &mut Transform,
+) {
+    let mut moved = false;
+    for _ in 0..stats.move_speed.max(1.0) as usize {
+        let Some(next_pos) = find_step_toward_base(grid, pos) else { break };
+        if !grid.move_unit(pos, &next_pos) {
+            break;
         }
+        *pos = next_pos;
+        transform.translation = grid.axial_to_pixel(&next_pos).extend(1.0);
+        moved = true;
+    }
+    if moved {
+        target.0 = None;
     }
 }
 
+/// First step of the shortest free path to the base row. `None` once there or fully blocked.
+fn find_step_toward_base(grid: &BattleGrid, start: &HexPosition) -> Option<HexPosition> {
+    if start.r <= BASE_ROW {
+        return None;
+    }
+    find_first_step(grid, start, |pos| pos.r <= BASE_ROW)
+}
+
+/// Breadth-first search over free hexes; returns the first step along the
+/// shortest path to any hex within `range` of `target`.
+/// Occupied hexes are walls. `None` when already in range or fully blocked.
 fn find_best_move(
     grid: &BattleGrid,
-    from: &HexPosition,
+    start: &HexPosition,
     target: &HexPosition,
+    range: i32,
 ) -> Option<HexPosition> {
-    let neighbors = from.neighbors();
-    let mut best: Option<(HexPosition, i32)> = None;
+    if within_range(start, target, range) {
+        return None;
+    }
+    find_first_step(grid, start, |pos| within_range(pos, target, range))
+}
 
-    for neighbor in neighbors {
-        if !grid.is_valid_position(&neighbor) || grid.is_occupied(&neighbor) {
-            continue;
+/// Breadth-first search over free hexes for the nearest hex satisfying `is_goal`;
+/// returns the first step along that path
+fn find_first_step(
+    grid: &BattleGrid,
+    start: &HexPosition,
+    is_goal: impl Fn(&HexPosition) -> bool,
+) -> Option<HexPosition> {
+    let mut came_from: std::collections::HashMap<HexPosition, HexPosition> = std::collections::HashMap::new();
+    let mut frontier = std::collections::VecDeque::from([*start]);
+
+    while let Some(current) = frontier.pop_front() {
+        if current != *start && is_goal(&current) {
+            // Walk back to the hex adjacent to start
+            let mut step = current;
+            while let Some(&prev) = came_from.get(&step) {
+                if prev == *start {
+                    break;
+                }
+                step = prev;
+            }
+            return Some(step);
         }
 
-        let dist = neighbor.distance(target);
-        if best.is_none() || dist < best.unwrap().1 {
-            best = Some((neighbor, dist));
+        for neighbor in current.neighbors() {
+            if neighbor == *start
+                || came_from.contains_key(&neighbor)
+                || !grid.is_valid_position(&neighbor)
+                || grid.is_occupied(&neighbor)
+            {
+                continue;
+            }
+            came_from.insert(neighbor, current);
+            frontier.push_back(neighbor);
         }
     }
 
-    best.map(|(pos, _)| pos)
+    None
 }
 
 pub fn attack_system(
     mut commands: Commands,
     grid: Res<BattleGrid>,
-    time: Res<Time>,
+    time: ScaledTime,
     wave_manager: Res<WaveManager>,
+    board_config: Res<BoardConfig>,
+    difficulty: Res<Difficulty>,
+    mut rng: ResMut<GameRng>,
     mut battle_stats: ResMut<BattleStats>,
     positions: Query<&HexPosition, With<Unit>>,
     rage_buffs: Query<(Entity, &RageBuff), With<Unit>>,
+    archetypes: Query<&EnemyArchetype, With<Unit>>,
     mut snipe_buffs: Query<(Entity, &mut SnipeBuff), With<Unit>>,
     mut param_set: ParamSet<(
-        Query<(Entity, &HexPosition, &UnitStats, &Target, &mut AttackCooldown, &Team, &UnitType), With<Unit>>,
-        Query<&mut UnitStats, With<Unit>>,
+        Query<(Entity, &HexPosition, &UnitStats, &Target, &mut AttackCooldown, &Team, &UnitType, Option<&AttackWindup>), With<Unit>>,
+        Query<(&mut UnitStats, Option<&mut Shield>), With<Unit>>,
     )>,
 ) {
     let current_wave = wave_manager.current_wave;
 
+    // Cooldowns tick before eligibility, so a unit fires once its full interval has elapsed
+    let delta = time.delta_secs();
+    for (.., mut cooldown, _, _, _) in param_set.p0().iter_mut() {
+        cooldown.0 = (cooldown.0 - delta).max(0.0);
+    }
+
+    // Enemies telegraph first: ready enemies start a windup, finished windups resolve below
+    let mut windups_to_start: Vec<(Entity, Entity)> = Vec::new();
+    let mut resolved_windups: Vec<(Entity, Entity)> = Vec::new();
+
     // Collect rage buff entities for damage calculation
     let rage_entities: std::collections::HashSet<Entity> = rage_buffs.iter().map(|(e, _)| e).collect();
 
@@ -178,29 +348,78 @@ pub fn attack_system(
         let attackers = param_set.p0();
         attackers
             .iter()
-            .filter_map(|(entity, pos, stats, target, cooldown, team, unit_type)| {
-                if cooldown.0 <= 0.0 {
-                    target.0.map(|t| {
-                        let is_crit = rand::random::<f32>() < stats.crit_chance;
-                        let mut damage = if is_crit { stats.attack * 1.5 } else { stats.attack };
-
-                        // Apply Rage buff (ATK +20%)
-                        if rage_entities.contains(&entity) {
-                            damage *= RageBuff::ATTACK_MULTIPLIER;
+            .filter_map(|(entity, pos, stats, target, cooldown, team, unit_type, windup)| {
+                if cooldown.0 > 0.0 {
+                    return None;
+                }
+                let target_in_range = target.0.filter(|t| {
+                    positions.get(*t).is_ok_and(|target_pos| in_attack_range(pos, target_pos, stats))
+                });
+                let target = match (team, windup) {
+                    (Team::Player, _) => target_in_range,
+                    (Team::Enemy, None) => {
+                        if let Some(t) = target_in_range {
+                            windups_to_start.push((entity, t));
                         }
+                        None
+                    }
+                    (Team::Enemy, Some(windup)) if windup.timer.finished() => {
+                        resolved_windups.push((entity, windup.telegraph));
+                        // A target knocked or moved out of range during the windup is
+                        // missed; the enemy winds up again on whatever is in range next
+                        positions
+                            .get(windup.target)
+                            .is_ok_and(|target_pos| in_attack_range(pos, target_pos, stats))
+                            .then_some(windup.target)
+                    }
+                    (Team::Enemy, Some(_)) => None,
+                };
+                target.map(|t| {
+                    let is_crit = rng.gen::<f32>() < stats.crit_chance;
+                    let mut damage = if is_crit { stats.attack * 1.5 } else { stats.attack };
 
-                        (entity, *pos, t, damage, *team, is_crit, unit_type.0)
-                    })
-                } else {
-                    None
-                }
+                    // Apply Rage buff (ATK +20%)
+                    if rage_entities.contains(&entity) {
+                        damage *= RageBuff::ATTACK_MULTIPLIER;
+                    }
+
+                    (entity, *pos, t, damage, *team, is_crit, unit_type.0)
+                })
             })
             .collect()
     };
 
+    for (attacker, target) in windups_to_start {
+        let telegraph = commands
+            .spawn((
+                AttackTelegraph { attacker },
+                Sprite {
+                    color: TELEGRAPH_COLOR,
+                    custom_size: Some(Vec2::splat(TELEGRAPH_SIZE)),
+                    ..default()
+                },
+                // Drawn just behind the targeted unit, follows it if it moves
+                Transform::from_xyz(0.0, 0.0, -0.1),
+            ))
+            .set_parent(target)
+            .id();
+        commands.entity(attacker).insert(AttackWindup {
+            target,
+            telegraph,
+            timer: Timer::from_seconds(ENEMY_WINDUP_DURATION, TimerMode::Once),
+        });
+    }
+
+    for (attacker, telegraph) in &resolved_windups {
+        commands.entity(*attacker).remove::<AttackWindup>();
+        commands.entity(*telegraph).despawn_recursive();
+    }
+
     // Apply Snipe buff (2x damage on next attack) and consume it
-    // Tuple: (attacker_pos, target_entity, damage, team, is_critical, unit_type)
-    let mut final_attacks: Vec<(HexPosition, Entity, f32, Team, bool, TileType)> = Vec::new();
+    // Tuple: (attacker_entity, attacker_pos, target_entity, damage, team, is_critical, unit_type)
+    let mut final_attacks: Vec<(Entity, HexPosition, Entity, f32, Team, bool, TileType)> = Vec::new();
+    let swung: std::collections::HashSet<Entity> = attacks.iter().map(|attack| attack.0).collect();
+
     for (attacker_entity, attacker_pos, target_entity, mut damage, team, is_crit, unit_type) in attacks {
         if let Ok((_, mut snipe)) = snipe_buffs.get_mut(attacker_entity) {
             if !snipe.is_consumed() {
@@ -214,21 +433,58 @@ pub fn attack_system(
                 commands.entity(attacker_entity).remove::<SnipeBuff>();
             }
         }
-        final_attacks.push((attacker_pos, target_entity, damage, team, is_crit, unit_type));
+        final_attacks.push((attacker_entity, attacker_pos, target_entity, damage, team, is_crit, unit_type));
     }
 
     // Trigger attack sound if there are attacks
     if !final_attacks.is_empty() {
         // Check if any attack was critical
-        let has_critical = final_attacks.iter().any(|(_, _, _, _, is_crit, _)| *is_crit);
+        let has_critical = final_attacks.iter().any(|(_, _, _, _, _, is_crit, _)| *is_crit);
         commands.trigger(AttackSoundEvent { is_critical: has_critical });
     }
 
     {
         let mut targets = param_set.p1();
-        for (attacker_pos, target_entity, damage, team, is_crit, unit_type) in &final_attacks {
-            if let Ok(mut target_stats) = targets.get_mut(*target_entity) {
-                target_stats.take_damage(*damage);
+        for (attacker, attacker_pos, target_entity, damage, team, is_crit, unit_type) in &final_attacks {
+            // Ranged shots resolve on arrival via ProjectileHitEvent
+            if fires_projectile(*unit_type) || archetypes.get(*attacker).is_ok_and(EnemyArchetype::is_ranged) {
+                if let Ok(target_pos) = positions.get(*target_entity) {
+                    spawn_projectile(&mut commands, &grid, ProjectileShot {
+                        attacker: *attacker,
+                        from: *attacker_pos,
+                        target: *target_entity,
+                        target_pos: *target_pos,
+                        damage: *damage,
+                        is_critical: *is_crit,
+                        team: *team,
+                        unit_type: *unit_type,
+                    });
+                }
+                if *team == Team::Enemy {
+                    maybe_spawn_obstacle_on_attack(&mut commands, &mut rng, current_wave, board_config.size, *difficulty);
+                }
+                continue;
+            }
+
+            if let Some(slowmo) = big_crit_slowmo(*damage, *is_crit) {
+                commands.trigger(slowmo);
+            }
+            // Player melee crits shove the enemy a hex back
+            if *team == Team::Player && *is_crit {
+                commands.trigger(KnockbackEvent { target: *target_entity, from: *attacker_pos });
+            }
+            let mut executed = false;
+            if let Ok((mut target_stats, mut shield)) = targets.get_mut(*target_entity) {
+                take_shielded_damage(&mut target_stats, shield.as_deref_mut(), *damage, DamageType::for_unit(*unit_type));
+                if shield.is_some_and(|shield| shield.is_depleted()) {
+                    commands.entity(*target_entity).remove::<Shield>();
+                }
+                // Only player assassins execute; death_system removes the unit and credits the kill via LastHitBy
+                if *team == Team::Player && executes(*unit_type) && should_execute(&target_stats) {
+                    target_stats.health = 0.0;
+                    executed = true;
+                }
+                commands.entity(*target_entity).try_insert(LastHitBy(*attacker));
             }
             if let Ok(target_pos) = positions.get(*target_entity) {
                 let from = grid.axial_to_pixel(attacker_pos);
@@ -240,6 +496,8 @@ pub fn attack_system(
                     position: to.extend(0.0),
                     damage: *damage as i32,
                     is_critical: *is_crit,
+                    is_poison: false,
+                    is_execute: executed,
                 });
             }
 
@@ -252,36 +510,41 @@ pub fn attack_system(
                 Team::Player => {
                     // Player unit dealt damage to enemy
                     battle_stats.record_ally_damage(*unit_type, *damage);
+                    commands.trigger(ExperienceGainEvent { unit: *attacker, amount: damage * XP_PER_DAMAGE });
                 }
             }
 
             // Enemy attack triggers obstacle spawn based on wave
             if *team == Team::Enemy {
-                maybe_spawn_obstacle_on_attack(&mut commands, current_wave);
+                maybe_spawn_obstacle_on_attack(&mut commands, &mut rng, current_wave, board_config.size, *difficulty);
             }
         }
     }
 
-    {
-        let mut attackers = param_set.p0();
-        for (_entity, _pos, stats, _target, mut cooldown, _team, _unit_type) in attackers.iter_mut() {
-            cooldown.0 -= time.delta_secs();
-            if cooldown.0 <= 0.0 {
-                cooldown.0 = 1.0 / stats.attack_speed;
-            }
+    // Units that didn't swing stay ready until a target is in range (or, for enemies, the windup resolves)
+    let mut attackers = param_set.p0();
+    for entity in swung {
+        if let Ok((_, _, stats, _, mut cooldown, ..)) = attackers.get_mut(entity) {
+            *cooldown = AttackCooldown::for_stats(stats);
         }
     }
 }
 
-/// Spawns obstacles on the puzzle board when enemies attack
-fn maybe_spawn_obstacle_on_attack(commands: &mut Commands, current_wave: u32) {
-    use rand::Rng;
-    let mut rng = rand::thread_rng();
+/// Spawns obstacles on the puzzle board when enemies attack.
+/// Each chance below is scaled by the difficulty's obstacle multiplier.
+fn maybe_spawn_obstacle_on_attack(
+    commands: &mut Commands,
+    rng: &mut GameRng,
+    current_wave: u32,
+    board_size: usize,
+    difficulty: Difficulty,
+) {
+    let chance_scale = difficulty.obstacle_chance_multiplier();
 
     // Wave 5+: 15% chance to spawn bomb
-    if current_wave >= 5 && rng.gen::<f32>() < 0.15 {
-        let x = rng.gen_range(0..PUZZLE_BOARD_SIZE);
-        let y = rng.gen_range(0..PUZZLE_BOARD_SIZE);
+    if current_wave >= 5 && rng.gen::<f32>() < 0.15 * chance_scale {
+        let x = rng.gen_range(0..board_size);
+        let y = rng.gen_range(0..board_size);
         commands.trigger(ObstacleSpawnEvent {
             position: (x, y),
             obstacle_type: ObstacleType::Bomb,
@@ -290,10 +553,22 @@ fn maybe_spawn_obstacle_on_attack(commands: &mut Commands, current_wave: u32) {
         return;
     }
 
+    // Wave 7+: 8% chance to spawn stone
+    if current_wave >= 7 && rng.gen::<f32>() < 0.08 * chance_scale {
+        let x = rng.gen_range(0..board_size);
+        let y = rng.gen_range(0..board_size);
+        commands.trigger(ObstacleSpawnEvent {
+            position: (x, y),
+            obstacle_type: ObstacleType::Stone,
+            countdown: None,
+        });
+        return;
+    }
+
     // Wave 3+: 10% chance to spawn ice
-    if current_wave >= 3 && rng.gen::<f32>() < 0.10 {
-        let x = rng.gen_range(0..PUZZLE_BOARD_SIZE);
-        let y = rng.gen_range(0..PUZZLE_BOARD_SIZE);
+    if current_wave >= 3 && rng.gen::<f32>() < 0.10 * chance_scale {
+        let x = rng.gen_range(0..board_size);
+        let y = rng.gen_range(0..board_size);
         commands.trigger(ObstacleSpawnEvent {
             position: (x, y),
             obstacle_type: ObstacleType::Ice,
@@ -302,7 +577,40 @@ fn maybe_spawn_obstacle_on_attack(commands: &mut Commands, current_wave: u32) {
     }
 }
 
-fn spawn_attack_line(commands: &mut Commands, from: Vec2, to: Vec2) {
+/// Advance enemy windups; drop any whose target is already gone
+pub fn tick_attack_windups(
+    mut commands: Commands,
+    time: ScaledTime,
+    units: Query<(), With<Unit>>,
+    mut windups: Query<(Entity, &mut AttackWindup)>,
+) {
+    let delta = time.delta();
+    for (entity, mut windup) in windups.iter_mut() {
+        if !units.contains(windup.target) {
+            commands.entity(entity).remove::<AttackWindup>();
+            continue;
+        }
+        windup.timer.tick(delta);
+    }
+}
+
+/// Remove telegraphs whose attacker died (or stopped winding up) before the hit landed
+pub fn cancel_orphaned_telegraphs(
+    mut commands: Commands,
+    telegraphs: Query<(Entity, &AttackTelegraph)>,
+    windups: Query<&AttackWindup>,
+) {
+    for (entity, telegraph) in telegraphs.iter() {
+        let still_winding_up = windups
+            .get(telegraph.attacker)
+            .is_ok_and(|windup| windup.telegraph == entity);
+        if !still_winding_up {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
+
+pub(super) fn spawn_attack_line(commands: &mut Commands, from: Vec2, to: Vec2) {
     let diff = to - from;
     let length = diff.length();
     let angle = diff.y.atan2(diff.x);
@@ -322,109 +630,263 @@ fn spawn_attack_line(commands: &mut Commands, from: Vec2, to: Vec2) {
     ));
 }
 
-pub fn ability_system(
-    mut commands: Commands,
-    mut param_set: ParamSet<(
-        Query<(Entity, &HexPosition, &mut UnitStats, &UnitType, &Team), With<Unit>>,
-        Query<(Entity, &HexPosition, &mut UnitStats, &Team), With<Unit>>,
-    )>,
-) {
-    // Collect caster data first
-    let casters: Vec<(Entity, HexPosition, f32, f32, f32, TileType, Team)> = {
-        let units = param_set.p0();
-        units
-            .iter()
-            .filter(|(_, _, stats, _, _)| stats.can_cast())
-            .map(|(e, pos, stats, ut, team)| {
-                (e, *pos, stats.attack, stats.ability_power, stats.max_health, ut.0, *team)
-            })
-            .collect()
-    };
+/// Opt-in manual casting: player units hold full mana until a cast key is pressed
+/// with them selected, instead of casting automatically. Toggled from the settings
+/// menu. Enemies always cast automatically.
+#[derive(Resource, Default)]
+pub struct ManualCast {
+    pub enabled: bool,
+}
 
-    // Collect potential targets for offensive abilities (enemies only for Meteor)
-    let all_units: Vec<(Entity, HexPosition, f32, Team)> = {
-        let units = param_set.p1();
-        units
-            .iter()
-            .map(|(e, pos, stats, team)| (e, *pos, stats.health, *team))
-            .collect()
-    };
+/// Cast `entity`'s ability now (ignored unless its mana is full)
+#[derive(Event)]
+pub struct ManualCastEvent {
+    pub entity: Entity,
+}
 
-    // Determine damage to apply (for Purple/Meteor only now)
-    let mut damage_list: Vec<(Entity, f32)> = Vec::new();
+/// Units an ability can read and hit
+pub type CasterQuery<'w, 's> = Query<
+    'w,
+    's,
+    (Entity, &'static mut UnitStats, Option<&'static UnitType>, &'static Team, Option<&'static mut Shield>),
+    With<Unit>,
+>;
 
-    // Track which buffs to apply
-    let mut rage_buffs_to_add: Vec<Entity> = Vec::new();
-    let mut snipe_buffs_to_add: Vec<Entity> = Vec::new();
-    let mut stealth_buffs_to_add: Vec<Entity> = Vec::new();
+/// Fire `caster`'s ability and empty its mana. Returns false (and does nothing) if the
+/// unit has no type or its mana isn't full yet.
+pub fn cast_ability(
+    commands: &mut Commands,
+    caster: Entity,
+    units: &mut CasterQuery,
+    targets: &Query<&Target, With<Unit>>,
+    transforms: &Query<&Transform, With<Unit>>,
+    battle_stats: &mut BattleStats,
+) -> bool {
+    let Ok((_, stats, Some(unit_type), team, _)) = units.get(caster) else { return false };
+    if !stats.can_cast() {
+        return false;
+    }
+    let (tile_type, caster_team, ability_power) = (unit_type.0, *team, stats.ability_power);
 
-    for (caster_entity, _caster_pos, _attack, _ability_power, _max_health, tile_type, caster_team) in &casters {
-        match tile_type {
-            TileType::Red => {
-                // Warrior: Rage - ATK +20% for 5 seconds
-                rage_buffs_to_add.push(*caster_entity);
-            }
-            TileType::Green => {
-                // Ranger: Snipe - next attack deals 2x damage
-                snipe_buffs_to_add.push(*caster_entity);
-            }
-            TileType::Yellow => {
-                // Assassin: Stealth - untargetable for 3 seconds
-                stealth_buffs_to_add.push(*caster_entity);
+    match tile_type {
+        TileType::Red => {
+            // Warrior: Rage - ATK +20% for 5 seconds
+            commands.entity(caster).insert(RageBuff::new());
+        }
+        TileType::Green => {
+            // Ranger: Snipe - next attack deals 2x damage
+            commands.entity(caster).insert(SnipeBuff::new());
+        }
+        TileType::Yellow => {
+            // Assassin: Stealth - untargetable for 3 seconds, and poisons its current target
+            commands.entity(caster).insert(StealthBuff::new());
+            if let Some(target) = targets.get(caster).ok().and_then(|t| t.0) {
+                commands.entity(target).insert(PoisonDebuff::assassin());
             }
-            TileType::Purple => {
-                // Mage: Meteor - 15 damage to ALL enemies
-                for (target_entity, _, _, target_team) in &all_units {
-                    if target_team != caster_team {
-                        damage_list.push((*target_entity, MeteorAbility::damage()));
-                    }
+        }
+        TileType::Purple => {
+            // Mage: Meteor - 15 + AP scaling damage to ALL enemies
+            let meteor_damage = MeteorAbility::damage(ability_power);
+            for (target_entity, mut target_stats, _, target_team, mut shield) in units.iter_mut() {
+                if *target_team == caster_team {
+                    continue;
+                }
+                // Abilities deal magic damage
+                take_shielded_damage(&mut target_stats, shield.as_deref_mut(), meteor_damage, DamageType::Magical);
+                if shield.is_some_and(|shield| shield.is_depleted()) {
+                    commands.entity(target_entity).remove::<Shield>();
+                }
+                commands.entity(target_entity).try_insert(LastHitBy(caster));
+                match caster_team {
+                    Team::Enemy => battle_stats.record_enemy_damage(tile_type, meteor_damage),
+                    Team::Player => battle_stats.record_ally_damage(tile_type, meteor_damage),
                 }
             }
-            _ => {}
         }
+        TileType::Blue => {}
     }
 
-    // Apply Meteor damage
-    {
-        let mut units = param_set.p1();
-        for (target_entity, damage) in damage_list {
-            if let Ok((_, _, mut stats, _)) = units.get_mut(target_entity) {
-                stats.take_damage(damage);
+    let Ok((_, mut stats, ..)) = units.get_mut(caster) else { return true };
+    if tile_type == TileType::Blue {
+        // Tank: Heal 20% max HP
+        let max_health = stats.max_health;
+        let healed = stats.heal(max_health * 0.2);
+        if healed > 0.0 {
+            if let Ok(transform) = transforms.get(caster) {
+                commands.trigger(HealPopupEvent {
+                    position: transform.translation,
+                    amount: healed.round() as i32,
+                });
             }
         }
     }
+    stats.mana = 0.0;
+    true
+}
 
-    // Apply Blue heal and reset mana for all casters
-    {
-        let mut units = param_set.p0();
-        for (caster_entity, _, _, _, max_health, tile_type, _) in casters {
-            if let Ok((_, _, mut stats, _, _)) = units.get_mut(caster_entity) {
-                if tile_type == TileType::Blue {
-                    // Tank: Heal 20% max HP
-                    let heal = max_health * 0.2;
-                    stats.health = (stats.health + heal).min(stats.max_health);
-                }
-                stats.mana = 0.0;
-            }
+/// Every unit with full mana casts, except player units while `ManualCast` holds them
+pub fn ability_system(
+    mut commands: Commands,
+    mut units: CasterQuery,
+    targets: Query<&Target, With<Unit>>,
+    transforms: Query<&Transform, With<Unit>>,
+    manual_cast: Res<ManualCast>,
+    mut battle_stats: ResMut<BattleStats>,
+) {
+    let casters: Vec<Entity> = units
+        .iter()
+        .filter(|(_, stats, _, team, _)| stats.can_cast() && !(manual_cast.enabled && **team == Team::Player))
+        .map(|(entity, ..)| entity)
+        .collect();
+
+    for caster in casters {
+        cast_ability(&mut commands, caster, &mut units, &targets, &transforms, &mut battle_stats);
+    }
+}
+
+/// Any cast key fires the selected player unit's ability; `cast_ability` ignores
+/// it until the unit's mana is full
+pub fn manual_cast_input_system(
+    mut commands: Commands,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    bindings: Res<KeyBindings>,
+    manual_cast: Res<ManualCast>,
+    selected: Query<(Entity, &Team), With<Selected>>,
+) {
+    if !manual_cast.enabled || !keyboard.any_just_pressed(bindings.manual_cast) {
+        return;
+    }
+
+    for (entity, team) in selected.iter() {
+        if *team == Team::Player {
+            commands.trigger(ManualCastEvent { entity });
+        }
+    }
+}
+
+pub fn handle_manual_cast(
+    trigger: Trigger<ManualCastEvent>,
+    mut commands: Commands,
+    mut units: CasterQuery,
+    targets: Query<&Target, With<Unit>>,
+    transforms: Query<&Transform, With<Unit>>,
+    mut battle_stats: ResMut<BattleStats>,
+) {
+    cast_ability(&mut commands, trigger.event().entity, &mut units, &targets, &transforms, &mut battle_stats);
+}
+
+/// Passive mana: every living player unit gains `mana_regen` per second, capped at max.
+/// Puzzle combos still top mana up through `ManaSupplyEvent`.
+pub fn mana_regen_system(
+    time: ScaledTime,
+    mut units: Query<(&mut UnitStats, &Team), With<Unit>>,
+) {
+    let delta = time.delta_secs();
+
+    for (mut stats, team) in units.iter_mut() {
+        if *team != Team::Player || stats.is_dead() {
+            continue;
         }
+        let regen = stats.mana_regen * delta;
+        stats.gain_mana(regen);
     }
+}
 
-    // Add buff components
-    for entity in rage_buffs_to_add {
-        commands.entity(entity).insert(RageBuff::new());
+/// Burst from a huge cascade: every living player unit with a target has its cooldown
+/// cleared so `attack_system` swings on the next tick, then cools down normally.
+/// Units still walking into range just keep their (already held) zero cooldown.
+pub fn handle_burst_attack(
+    _trigger: Trigger<BurstAttackEvent>,
+    mut units: Query<(&Team, &Target, &UnitStats, &mut AttackCooldown), With<Unit>>,
+) {
+    for (team, target, stats, mut cooldown) in units.iter_mut() {
+        if *team == Team::Player && target.0.is_some() && !stats.is_dead() {
+            cooldown.0 = 0.0;
+        }
     }
-    for entity in snipe_buffs_to_add {
-        commands.entity(entity).insert(SnipeBuff::new());
+}
+
+/// Seconds a player unit must go without taking damage or holding a target before it regenerates
+pub const OUT_OF_COMBAT_DELAY: f32 = 3.0;
+/// Out-of-combat regeneration per second, as a fraction of max health
+pub const OUT_OF_COMBAT_REGEN_RATIO: f32 = 0.05;
+/// Regen popups are batched until at least this much health has come back
+const REGEN_POPUP_THRESHOLD: f32 = 5.0;
+
+/// Idle player units slowly heal up to max. Taking damage or picking a target
+/// restarts the `OUT_OF_COMBAT_DELAY` wait, so regen stops the moment a fight starts.
+pub fn out_of_combat_regen_system(
+    mut commands: Commands,
+    time: ScaledTime,
+    grid: Res<BattleGrid>,
+    mut units: Query<(Entity, &HexPosition, &Team, &Target, &mut UnitStats, Option<&mut CombatActivity>), With<Unit>>,
+) {
+    let delta = time.delta_secs();
+
+    for (entity, pos, team, target, mut stats, activity) in units.iter_mut() {
+        if *team != Team::Player || stats.is_dead() {
+            continue;
+        }
+        let Some(mut activity) = activity else {
+            commands.entity(entity).insert(CombatActivity::new(stats.health));
+            continue;
+        };
+
+        if stats.health < activity.last_health || target.0.is_some() {
+            activity.out_of_combat = 0.0;
+            activity.unshown_heal = 0.0;
+        } else if activity.out_of_combat >= OUT_OF_COMBAT_DELAY {
+            let max_health = stats.max_health;
+            activity.unshown_heal += stats.heal(max_health * OUT_OF_COMBAT_REGEN_RATIO * delta);
+            let topped_off = stats.health >= stats.max_health;
+            if activity.unshown_heal >= REGEN_POPUP_THRESHOLD || (topped_off && activity.unshown_heal >= 0.5) {
+                commands.trigger(HealPopupEvent {
+                    position: grid.axial_to_pixel(pos).extend(0.0),
+                    amount: activity.unshown_heal.round() as i32,
+                });
+                activity.unshown_heal = 0.0;
+            }
+        } else {
+            activity.out_of_combat += delta;
+        }
+        activity.last_health = stats.health;
     }
-    for entity in stealth_buffs_to_add {
-        commands.entity(entity).insert(StealthBuff::new());
+}
+
+/// Deal poison damage every frame and drop expired poison.
+/// A popup shows the per-second damage each time a whole second of poison elapses.
+pub fn poison_tick_system(
+    mut commands: Commands,
+    time: ScaledTime,
+    grid: Res<BattleGrid>,
+    mut poisoned: Query<(Entity, &HexPosition, &mut UnitStats, &mut PoisonDebuff), With<Unit>>,
+) {
+    let delta = time.delta_secs();
+
+    for (entity, pos, mut stats, mut poison) in poisoned.iter_mut() {
+        let before = poison.remaining.ceil();
+        let damage = poison.tick(delta);
+        stats.take_true_damage(damage);
+
+        if poison.remaining.ceil() < before {
+            commands.trigger(DamagePopupEvent {
+                position: grid.axial_to_pixel(pos).extend(0.0),
+                damage: poison.dps.round() as i32,
+                is_critical: false,
+                is_poison: true,
+                is_execute: false,
+            });
+        }
+        if poison.is_expired() {
+            commands.entity(entity).remove::<PoisonDebuff>();
+        }
     }
 }
 
 /// System to tick and expire buff timers
 pub fn buff_timer_system(
     mut commands: Commands,
-    time: Res<Time>,
+    time: ScaledTime,
     mut rage_buffs: Query<(Entity, &mut RageBuff)>,
     mut stealth_buffs: Query<(Entity, &mut StealthBuff)>,
 ) {
@@ -447,17 +909,43 @@ pub fn buff_timer_system(
     }
 }
 
+/// Gold per star of a defeated enemy
+pub const GOLD_PER_ENEMY_STAR: u32 = 1;
+
+/// Gold paid out for killing an enemy of `star_rank`
+pub fn kill_gold(star_rank: u8) -> u32 {
+    star_rank as u32 * GOLD_PER_ENEMY_STAR
+}
+
 pub fn death_system(
     mut commands: Commands,
     mut grid: ResMut<BattleGrid>,
     mut battle_stats: ResMut<BattleStats>,
-    units: Query<(Entity, &HexPosition, &UnitStats, &Team), With<Unit>>,
+    mut rng: ResMut<GameRng>,
+    mut gold: ResMut<Gold>,
+    board_config: Res<BoardConfig>,
+    units: Query<(Entity, &HexPosition, &UnitStats, &Team, Option<&StarRank>, Option<&LastHitBy>, Has<ExplosiveOnDeath>), With<Unit>>,
+    killers: Query<(&UnitType, &Team), With<Unit>>,
 ) {
-    for (entity, pos, stats, team) in units.iter() {
+    for (entity, pos, stats, team, star_rank, last_hit, explosive) in units.iter() {
         if stats.is_dead() {
-            // Record kill when enemy dies
+            if explosive {
+                spawn_death_bomb(&mut commands, &mut rng, board_config.size);
+            }
+            // Credit the kill to the last player unit that hit this enemy;
+            // fall back to the top damage dealer if the killer is already gone
             if *team == Team::Enemy {
-                battle_stats.record_kill_for_top_ally();
+                let killer = last_hit
+                    .and_then(|hit| killers.get(hit.0).ok().map(|killer| (hit.0, killer)))
+                    .filter(|(_, (_, killer_team))| **killer_team == Team::Player);
+                match killer {
+                    Some((killer, (unit_type, _))) => {
+                        battle_stats.record_ally_kill(unit_type.0, 0.0);
+                        commands.trigger(ExperienceGainEvent { unit: killer, amount: XP_PER_KILL });
+                    }
+                    None => battle_stats.record_kill_for_top_ally(),
+                }
+                gold.earn(kill_gold(star_rank.map_or(1, |rank| rank.0)));
             }
             grid.remove_unit(pos);
             commands.entity(entity).despawn_recursive();
@@ -477,3 +965,1139 @@ pub fn despawn_attack_lines(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::time::TimeUpdateStrategy;
+    use std::time::Duration;
+
+    const STEP: f32 = 0.1;
+
+    fn setup_windup_app() -> App {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f32(STEP)))
+            .insert_resource(BattleGrid::new())
+            .init_resource::<WaveManager>()
+            .init_resource::<BoardConfig>()
+            .init_resource::<Difficulty>()
+            .insert_resource(GameRng::from_seed(0))
+            .init_resource::<BattleStats>()
+            .init_resource::<TimeScale>()
+            .init_resource::<GameSpeed>()
+            .add_systems(
+                Update,
+                (tick_attack_windups, attack_system, cancel_orphaned_telegraphs).chain(),
+            );
+        app
+    }
+
+    /// Spawns a player that never swings back and an enemy ready to attack it
+    fn spawn_duel(app: &mut App) -> (Entity, Entity) {
+        let player = app
+            .world_mut()
+            .spawn((
+                Unit,
+                HexPosition::new(0, 0),
+                UnitStats::default(),
+                Team::Player,
+                Target(None),
+                AttackCooldown(100.0),
+                UnitType(TileType::Blue),
+            ))
+            .id();
+        let enemy = app
+            .world_mut()
+            .spawn((
+                Unit,
+                HexPosition::new(1, 0),
+                UnitStats::default(),
+                Team::Enemy,
+                Target(Some(player)),
+                AttackCooldown(0.0),
+                UnitType(TileType::Red),
+            ))
+            .id();
+        (player, enemy)
+    }
+
+    fn player_health(app: &App, player: Entity) -> f32 {
+        app.world().get::<UnitStats>(player).unwrap().health
+    }
+
+    fn telegraph_count(app: &mut App) -> usize {
+        app.world_mut()
+            .query::<&AttackTelegraph>()
+            .iter(app.world())
+            .count()
+    }
+
+    fn elapsed(app: &App) -> f32 {
+        app.world().resource::<Time>().elapsed_secs()
+    }
+
+    #[test]
+    fn test_enemy_hit_lands_after_windup() {
+        let mut app = setup_windup_app();
+        let (player, enemy) = spawn_duel(&mut app);
+
+        app.update();
+        assert_eq!(telegraph_count(&mut app), 1, "telegraph appears when the windup starts");
+        assert!(app.world().get::<AttackWindup>(enemy).is_some());
+        assert_eq!(player_health(&app, player), 100.0, "no damage before the windup");
+        let windup_started = elapsed(&app);
+
+        let mut hit_at = None;
+        for _ in 0..20 {
+            app.update();
+            if player_health(&app, player) < 100.0 {
+                hit_at = Some(elapsed(&app));
+                break;
+            }
+            assert_eq!(telegraph_count(&mut app), 1);
+        }
+
+        let delay = hit_at.expect("enemy hit never landed") - windup_started;
+        assert!(
+            (ENEMY_WINDUP_DURATION - 0.01..=ENEMY_WINDUP_DURATION + STEP + 0.01).contains(&delay),
+            "hit landed {delay}s after telegraph"
+        );
+        assert_eq!(player_health(&app, player), 90.0);
+        assert_eq!(telegraph_count(&mut app), 0, "telegraph clears once the hit lands");
+        assert!(app.world().get::<AttackWindup>(enemy).is_none());
+    }
+
+    #[test]
+    fn test_telegraph_cancels_when_enemy_dies() {
+        let mut app = setup_windup_app();
+        let (player, enemy) = spawn_duel(&mut app);
+
+        app.update();
+        assert_eq!(telegraph_count(&mut app), 1);
+
+        app.world_mut().entity_mut(enemy).despawn_recursive();
+        for _ in 0..10 {
+            app.update();
+        }
+
+        assert_eq!(telegraph_count(&mut app), 0);
+        assert_eq!(player_health(&app, player), 100.0);
+    }
+
+    #[test]
+    fn test_windup_misses_target_that_left_range() {
+        let mut app = setup_windup_app();
+        let (player, enemy) = spawn_duel(&mut app);
+
+        app.update();
+        assert!(app.world().get::<AttackWindup>(enemy).is_some());
+
+        // Knocked out of reach while the enemy winds up
+        app.world_mut().entity_mut(player).insert(HexPosition::new(-2, 0));
+        for _ in 0..10 {
+            app.update();
+        }
+
+        assert_eq!(player_health(&app, player), 100.0, "no hit from out of range");
+        assert!(app.world().get::<AttackWindup>(enemy).is_none());
+        assert_eq!(telegraph_count(&mut app), 0);
+    }
+
+    #[test]
+    fn test_player_attacks_stay_instant() {
+        let mut app = setup_windup_app();
+        let (player, enemy) = spawn_duel(&mut app);
+        app.world_mut().entity_mut(enemy).insert(AttackCooldown(100.0));
+        app.world_mut()
+            .entity_mut(player)
+            .insert((Target(Some(enemy)), AttackCooldown(0.0)));
+
+        app.update();
+
+        assert_eq!(app.world().get::<UnitStats>(enemy).unwrap().health, 90.0);
+        assert_eq!(telegraph_count(&mut app), 0);
+    }
+
+    /// Player at the origin with range 2; enemies in range at distance 1 and 2 plus one
+    /// weak, hard-hitting enemy out of range
+    fn targeting_candidates() -> Vec<(Entity, HexPosition, UnitStats)> {
+        vec![
+            (Entity::from_raw(1), HexPosition::new(1, 0), UnitStats { health: 80.0, attack: 10.0, ..default() }),
+            (Entity::from_raw(2), HexPosition::new(2, 0), UnitStats { health: 30.0, attack: 15.0, ..default() }),
+            (Entity::from_raw(3), HexPosition::new(0, 2), UnitStats { health: 60.0, attack: 40.0, ..default() }),
+            (Entity::from_raw(4), HexPosition::new(3, 0), UnitStats { health: 5.0, attack: 99.0, ..default() }),
+        ]
+    }
+
+    fn pick(mode: TargetingMode, range: i32) -> Option<Entity> {
+        let candidates = targeting_candidates();
+        pick_target(&HexPosition::new(0, 0), range, mode, candidates.iter().map(|(e, p, s)| (*e, *p, s)))
+    }
+
+    #[test]
+    fn test_nearest_mode_picks_closest() {
+        assert_eq!(pick(TargetingMode::Nearest, 2), Some(Entity::from_raw(1)));
+    }
+
+    #[test]
+    fn test_lowest_health_mode_ranks_enemies_in_range() {
+        // Entity 4 is weaker still but out of range
+        assert_eq!(pick(TargetingMode::LowestHealth, 2), Some(Entity::from_raw(2)));
+        assert_eq!(pick(TargetingMode::LowestHealth, 3), Some(Entity::from_raw(4)));
+    }
+
+    #[test]
+    fn test_highest_attack_mode_ranks_enemies_in_range() {
+        assert_eq!(pick(TargetingMode::HighestAttack, 2), Some(Entity::from_raw(3)));
+    }
+
+    #[test]
+    fn test_modes_fall_back_to_nearest_when_nothing_in_range() {
+        let candidates = targeting_candidates();
+        let far = HexPosition::new(-3, 0);
+        for mode in [TargetingMode::Nearest, TargetingMode::LowestHealth, TargetingMode::HighestAttack] {
+            let picked = pick_target(&far, 1, mode, candidates.iter().map(|(e, p, s)| (*e, *p, s)));
+            assert_eq!(picked, Some(Entity::from_raw(1)), "{mode:?}");
+        }
+        assert_eq!(pick_target(&far, 1, TargetingMode::Nearest, std::iter::empty()), None);
+    }
+
+    #[test]
+    fn test_targeting_system_uses_mode_and_skips_stealth() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins).add_systems(Update, targeting_system);
+        let player = app
+            .world_mut()
+            .spawn((Unit, HexPosition::new(0, 0), Team::Player, UnitStats { attack_range: 2, ..default() }, Target(None), TargetingMode::LowestHealth))
+            .id();
+        let healthy = app
+            .world_mut()
+            .spawn((Unit, HexPosition::new(1, 0), Team::Enemy, UnitStats::default(), Target(None)))
+            .id();
+        let weak = app
+            .world_mut()
+            .spawn((Unit, HexPosition::new(2, 0), Team::Enemy, UnitStats { health: 10.0, ..default() }, Target(None)))
+            .id();
+
+        app.update();
+        assert_eq!(app.world().get::<Target>(player).unwrap().0, Some(weak));
+
+        app.world_mut().entity_mut(weak).insert(StealthBuff::new());
+        app.update();
+        assert_eq!(app.world().get::<Target>(player).unwrap().0, Some(healthy));
+    }
+
+    #[test]
+    fn test_in_attack_range_boundary() {
+        let origin = HexPosition::new(0, 0);
+        for range in 1..=3 {
+            let stats = UnitStats { attack_range: range, ..default() };
+            assert!(in_attack_range(&origin, &HexPosition::new(range, 0), &stats));
+            assert!(!in_attack_range(&origin, &HexPosition::new(range + 1, 0), &stats));
+            // Diagonal hexes use the same cube distance
+            assert!(in_attack_range(&origin, &HexPosition::new(range, -range), &stats));
+        }
+    }
+
+    #[test]
+    fn test_movement_stops_exactly_where_attacks_become_eligible() {
+        for range in 1..=4 {
+            let mut app = setup_windup_app();
+            app.add_systems(Update, movement_system.before(attack_system));
+            let start = HexPosition::new(-3, 0);
+            let enemy_pos = HexPosition::new(3, 0);
+            let enemy = app
+                .world_mut()
+                .spawn((Unit, enemy_pos, UnitStats::default(), Team::Enemy, Target(None), AttackCooldown(100.0), UnitType(TileType::Red), Transform::default()))
+                .id();
+            let attacker = app
+                .world_mut()
+                .spawn((Unit, start, UnitStats { attack_range: range, ..default() }, Team::Player, Target(Some(enemy)), AttackCooldown(0.0), UnitType(TileType::Red), Transform::default()))
+                .id();
+            {
+                let mut grid = app.world_mut().resource_mut::<BattleGrid>();
+                grid.place_unit(start, attacker);
+                grid.place_unit(enemy_pos, enemy);
+            }
+
+            let mut stopped_at = None;
+            for _ in 0..10 {
+                app.update();
+                let pos = *app.world().get::<HexPosition>(attacker).unwrap();
+                let hit = app.world().get::<UnitStats>(enemy).unwrap().health < 100.0;
+                if hit {
+                    stopped_at = Some(pos);
+                    break;
+                }
+                assert!(pos.distance(&enemy_pos) > range, "range {range}: in range at {:?} but no attack", pos);
+            }
+
+            let pos = stopped_at.unwrap_or_else(|| panic!("range {range}: never attacked"));
+            assert_eq!(pos.distance(&enemy_pos), range, "range {range}: stopped at wrong distance");
+            app.update();
+            assert_eq!(*app.world().get::<HexPosition>(attacker).unwrap(), pos, "range {range}: kept moving after attacking");
+        }
+    }
+
+    #[test]
+    fn test_fresh_unit_waits_one_interval_before_first_attack() {
+        let mut app = setup_windup_app();
+        let (player, enemy) = spawn_duel(&mut app);
+        let stats = UnitStats { attack_speed: 2.0, ..default() };
+        app.world_mut()
+            .entity_mut(player)
+            .insert((AttackCooldown::for_stats(&stats), stats, Target(Some(enemy))));
+        app.world_mut().entity_mut(enemy).insert(AttackCooldown(100.0));
+
+        let mut first_hit_at = None;
+        for _ in 0..20 {
+            app.update();
+            if app.world().get::<UnitStats>(enemy).unwrap().health < 100.0 {
+                first_hit_at = Some(app.world().resource::<Time>().elapsed_secs());
+                break;
+            }
+        }
+
+        let first_hit_at = first_hit_at.expect("never attacked");
+        assert!(
+            (first_hit_at - 0.5).abs() <= STEP + 1e-4,
+            "first shot after {first_hit_at}s, expected about one 0.5s interval"
+        );
+    }
+
+    #[test]
+    fn test_big_crit_slowmo_threshold() {
+        assert!(big_crit_slowmo(BIG_CRIT_DAMAGE, true).is_some());
+        assert!(big_crit_slowmo(BIG_CRIT_DAMAGE - 1.0, true).is_none(), "small crits don't slow time");
+        assert!(big_crit_slowmo(BIG_CRIT_DAMAGE * 2.0, false).is_none(), "big non-crits don't either");
+    }
+
+    #[derive(Resource, Default)]
+    struct SlowMoLog(Vec<(f32, f32)>);
+
+    fn record_slowmo(trigger: Trigger<SlowMoEvent>, mut log: ResMut<SlowMoLog>) {
+        log.0.push((trigger.event().scale, trigger.event().duration));
+    }
+
+    /// Player crits the enemy on its first swing with the given attack
+    fn crit_swing_slowmos(attack: f32) -> Vec<(f32, f32)> {
+        let mut app = setup_windup_app();
+        app.init_resource::<SlowMoLog>().add_observer(record_slowmo);
+        let (player, enemy) = spawn_duel(&mut app);
+        let stats = UnitStats { attack, crit_chance: 1.0, ..default() };
+        app.world_mut()
+            .entity_mut(player)
+            .insert((stats, Target(Some(enemy)), AttackCooldown(0.0)));
+        app.world_mut().entity_mut(enemy).insert(AttackCooldown(100.0));
+
+        app.update();
+        assert!(app.world().get::<UnitStats>(enemy).unwrap().health < 100.0, "player swung");
+        std::mem::take(&mut app.world_mut().resource_mut::<SlowMoLog>().0)
+    }
+
+    #[test]
+    fn test_player_crit_knocks_enemy_back() {
+        let mut app = setup_windup_app();
+        app.add_observer(super::super::knockback::handle_knockback);
+        let (player, enemy) = spawn_duel(&mut app);
+        app.world_mut().resource_mut::<BattleGrid>().place_unit(HexPosition::new(0, 0), player);
+        app.world_mut().resource_mut::<BattleGrid>().place_unit(HexPosition::new(1, 0), enemy);
+        let stats = UnitStats { crit_chance: 1.0, ..default() };
+        app.world_mut()
+            .entity_mut(player)
+            .insert((stats, Target(Some(enemy)), AttackCooldown(0.0)));
+        app.world_mut().entity_mut(enemy).insert(AttackCooldown(100.0));
+
+        app.update();
+
+        assert_eq!(*app.world().get::<HexPosition>(enemy).unwrap(), HexPosition::new(2, 0));
+        assert_eq!(app.world().resource::<BattleGrid>().units.get(&HexPosition::new(2, 0)), Some(&enemy));
+    }
+
+    #[test]
+    fn test_big_crit_triggers_slowmo() {
+        assert_eq!(
+            crit_swing_slowmos(BIG_CRIT_DAMAGE),
+            vec![(BIG_CRIT_SLOWMO_SCALE, BIG_CRIT_SLOWMO_DURATION)]
+        );
+        assert!(crit_swing_slowmos(5.0).is_empty(), "a small crit plays normally");
+    }
+
+    /// Cooldown the player has ticked off after a few frames at `scale` and `speed`
+    fn cooldown_progress(scale: f32, speed: f32) -> f32 {
+        let mut app = setup_windup_app();
+        app.world_mut().resource_mut::<TimeScale>().scale = scale;
+        app.insert_resource(GameSpeed(speed));
+        let (player, _) = spawn_duel(&mut app);
+        app.world_mut().entity_mut(player).insert(AttackCooldown(10.0));
+
+        for _ in 0..5 {
+            app.update();
+        }
+        10.0 - app.world().get::<AttackCooldown>(player).unwrap().0
+    }
+
+    #[test]
+    fn test_time_scale_slows_attack_cooldowns() {
+        let normal = cooldown_progress(1.0, 1.0);
+        let slowed = cooldown_progress(0.5, 1.0);
+        assert!(normal > 0.0);
+        assert!((slowed - normal * 0.5).abs() < 1e-4, "half scale ticked {slowed}s vs {normal}s");
+    }
+
+    #[test]
+    fn test_game_speed_composes_with_slowmo() {
+        let normal = cooldown_progress(1.0, 1.0);
+        let doubled = cooldown_progress(1.0, 2.0);
+        assert!((doubled - normal * 2.0).abs() < 1e-4, "2x speed ticked {doubled}s vs {normal}s");
+        let slowed_fast = cooldown_progress(0.5, 2.0);
+        assert!((slowed_fast - normal).abs() < 1e-4, "slow-mo at 2x cancels out");
+    }
+
+    #[test]
+    fn test_execute_threshold_boundary() {
+        let at = |health: f32| UnitStats { health, max_health: 100.0, ..default() };
+        assert!(should_execute(&at(14.9)), "just below the threshold");
+        assert!(!should_execute(&at(15.0)), "the threshold itself survives");
+        assert!(!should_execute(&at(15.1)));
+        assert!(!should_execute(&at(0.0)), "already dead units aren't executed again");
+        assert!(executes(TileType::Yellow));
+        assert!(!executes(TileType::Red));
+    }
+
+    /// Yellow player swinging once at an enemy left with `health`; returns the app and enemy
+    fn assassin_hits_enemy_at(health: f32) -> (App, Entity) {
+        let mut app = setup_windup_app();
+        app.init_resource::<Gold>()
+            .add_systems(Update, death_system.after(attack_system));
+        let (player, enemy) = spawn_duel(&mut app);
+        app.world_mut().entity_mut(enemy).insert((
+            AttackCooldown(100.0),
+            UnitStats { health, ..default() },
+        ));
+        app.world_mut()
+            .entity_mut(player)
+            .insert((Target(Some(enemy)), AttackCooldown(0.0), UnitType(TileType::Yellow)));
+
+        app.update();
+        (app, enemy)
+    }
+
+    #[test]
+    fn test_assassin_executes_target_left_below_threshold() {
+        // Default attack is 10: 24 -> 14 (14%) is executed, 26 -> 16 (16%) is not
+        let (app, survivor) = assassin_hits_enemy_at(26.0);
+        assert_eq!(app.world().get::<UnitStats>(survivor).unwrap().health, 16.0);
+
+        let (app, executed) = assassin_hits_enemy_at(24.0);
+        assert!(app.world().get_entity(executed).is_err(), "death_system cleans up the executed unit");
+        assert_eq!(app.world().resource::<BattleStats>().ally_kills(TileType::Yellow), 1);
+    }
+
+    #[test]
+    fn test_enemy_assassin_does_not_execute() {
+        let mut app = setup_windup_app();
+        let (player, enemy) = spawn_duel(&mut app);
+        app.world_mut().entity_mut(player).insert(UnitStats { health: 24.0, ..default() });
+        app.world_mut().entity_mut(enemy).insert(UnitType(TileType::Yellow));
+
+        for _ in 0..20 {
+            app.update();
+            if player_health(&app, player) < 24.0 {
+                break;
+            }
+        }
+
+        assert_eq!(player_health(&app, player), 14.0, "the hit lands but doesn't execute");
+    }
+
+    #[test]
+    fn test_player_out_of_range_does_not_attack() {
+        let mut app = setup_windup_app();
+        let (player, enemy) = spawn_duel(&mut app);
+        app.world_mut().entity_mut(enemy).insert((AttackCooldown(100.0), HexPosition::new(3, 0)));
+        app.world_mut()
+            .entity_mut(player)
+            .insert((Target(Some(enemy)), AttackCooldown(0.0)));
+
+        app.update();
+
+        assert_eq!(app.world().get::<UnitStats>(enemy).unwrap().health, 100.0);
+    }
+
+    /// Grid with a wall of occupied hexes along q = 0, open only at r = 2
+    fn walled_grid() -> BattleGrid {
+        let mut grid = BattleGrid::new();
+        for r in -2..=1 {
+            grid.place_unit(HexPosition::new(0, r), Entity::from_raw((100 + r + 2) as u32));
+        }
+        grid
+    }
+
+    #[test]
+    fn test_find_best_move_routes_around_wall() {
+        let mut grid = walled_grid();
+        let target = HexPosition::new(3, 0);
+        let mover = Entity::from_raw(1);
+        let mut pos = HexPosition::new(-1, 0);
+        grid.place_unit(pos, mover);
+        grid.place_unit(target, Entity::from_raw(2));
+
+        for _ in 0..10 {
+            let Some(next) = find_best_move(&grid, &pos, &target, 1) else { break };
+            assert_eq!(pos.distance(&next), 1, "moves one hex at a time");
+            assert!(grid.move_unit(&pos, &next));
+            pos = next;
+        }
+
+        assert!(pos.distance(&target) <= 1, "unit stalled at {:?}", pos);
+    }
+
+    #[test]
+    fn test_find_best_move_first_step_heads_for_gap() {
+        let grid = walled_grid();
+        let next = find_best_move(&grid, &HexPosition::new(-1, 0), &HexPosition::new(3, 0), 1).unwrap();
+
+        // Only way through the wall is (0, 2), reached via (-1, 1)
+        assert_eq!(next, HexPosition::new(-1, 1));
+    }
+
+    #[test]
+    fn test_find_best_move_none_when_in_range_or_blocked() {
+        let mut grid = walled_grid();
+        let target = HexPosition::new(3, 0);
+        assert_eq!(find_best_move(&grid, &HexPosition::new(2, 0), &target, 1), None);
+
+        grid.place_unit(HexPosition::new(0, 2), Entity::from_raw(99));
+        assert_eq!(find_best_move(&grid, &HexPosition::new(-1, 0), &target, 1), None);
+    }
+
+    fn spawn_mover(app: &mut App, pos: HexPosition, team: Team, stats: UnitStats, target: Option<Entity>) -> Entity {
+        let entity = app
+            .world_mut()
+            .spawn((Unit, pos, stats, team, Target(target), AttackCooldown(100.0), UnitType(TileType::Red), Transform::default()))
+            .id();
+        app.world_mut().resource_mut::<BattleGrid>().place_unit(pos, entity);
+        entity
+    }
+
+    #[test]
+    fn test_runner_heads_for_base_instead_of_target() {
+        let mut app = setup_windup_app();
+        app.add_systems(Update, movement_system.before(attack_system));
+        let player = spawn_mover(&mut app, HexPosition::new(3, -1), Team::Player, UnitStats::default(), None);
+        let runner_stats = EnemyArchetype::Runner.apply(UnitStats::default());
+        let runner = spawn_mover(&mut app, HexPosition::new(-3, 2), Team::Enemy, runner_stats, Some(player));
+        app.world_mut().entity_mut(runner).insert((EnemyArchetype::Runner, EnemyArchetype::Runner.goal()));
+
+        app.update();
+        let pos = *app.world().get::<HexPosition>(runner).unwrap();
+        assert_eq!(pos.r, 0, "two steps per tick straight down, got {:?}", pos);
+
+        app.update();
+        let pos = *app.world().get::<HexPosition>(runner).unwrap();
+        assert_eq!(pos.r, BASE_ROW, "reached the base row");
+        assert!(pos.distance(&HexPosition::new(3, -1)) > 1, "never detoured to its target");
+
+        app.update();
+        assert_eq!(*app.world().get::<HexPosition>(runner).unwrap(), pos, "stops once at the base");
+    }
+
+    #[test]
+    fn test_rush_base_enemy_moves_past_unit_in_range() {
+        let mut app = setup_windup_app();
+        app.add_systems(Update, movement_system.before(attack_system));
+        let fighter = spawn_mover(&mut app, HexPosition::new(0, 0), Team::Player, UnitStats::default(), None);
+        let rusher = spawn_mover(&mut app, HexPosition::new(0, 1), Team::Enemy, UnitStats::default(), Some(fighter));
+        app.world_mut().entity_mut(rusher).insert(EnemyGoal::RushBase);
+
+        app.update();
+
+        let pos = *app.world().get::<HexPosition>(rusher).unwrap();
+        assert_eq!(pos.r, 0, "stepped toward the base instead of fighting, got {:?}", pos);
+        assert_eq!(app.world().get::<Target>(rusher).unwrap().0, None, "no swing on a tick it moved");
+    }
+
+    #[test]
+    fn test_rush_base_enemy_attacks_only_when_walled_off() {
+        let mut app = setup_windup_app();
+        app.add_systems(Update, movement_system.before(attack_system));
+        let wall: Vec<Entity> = (-BATTLE_GRID_COLS / 2..=BATTLE_GRID_COLS / 2)
+            .map(|q| spawn_mover(&mut app, HexPosition::new(q, -1), Team::Player, UnitStats::default(), None))
+            .collect();
+        let rusher = spawn_mover(&mut app, HexPosition::new(0, 0), Team::Enemy, UnitStats::default(), Some(wall[3]));
+        app.world_mut().entity_mut(rusher).insert(EnemyGoal::RushBase);
+
+        for _ in 0..3 {
+            app.update();
+        }
+
+        assert_eq!(*app.world().get::<HexPosition>(rusher).unwrap(), HexPosition::new(0, 0));
+        assert_eq!(app.world().get::<Target>(rusher).unwrap().0, Some(wall[3]), "keeps its target to break through");
+    }
+
+    #[test]
+    fn test_enemy_without_goal_fights() {
+        let mut app = setup_windup_app();
+        app.add_systems(Update, movement_system.before(attack_system));
+        let blocker = spawn_mover(&mut app, HexPosition::new(0, 0), Team::Player, UnitStats::default(), None);
+        let runner_stats = EnemyArchetype::Runner.apply(UnitStats::default());
+        let fighter = spawn_mover(&mut app, HexPosition::new(0, 1), Team::Enemy, runner_stats, Some(blocker));
+
+        for _ in 0..3 {
+            app.update();
+        }
+
+        assert_eq!(*app.world().get::<HexPosition>(fighter).unwrap(), HexPosition::new(0, 1), "stops for the unit in range");
+    }
+
+    #[test]
+    fn test_caster_enemy_fires_projectile() {
+        let mut app = setup_windup_app();
+        app.add_observer(super::super::projectile::handle_projectile_hit)
+            .add_systems(Update, super::super::projectile::projectile_movement_system.after(attack_system));
+        let player = spawn_mover(&mut app, HexPosition::new(0, -1), Team::Player, UnitStats::default(), None);
+        let caster_stats = EnemyArchetype::Caster.apply(UnitStats::default());
+        let caster = spawn_mover(&mut app, HexPosition::new(0, 2), Team::Enemy, caster_stats, Some(player));
+        app.world_mut().entity_mut(caster).insert((EnemyArchetype::Caster, AttackCooldown(0.0)));
+
+        let mut saw_projectile = false;
+        for _ in 0..10 {
+            app.update();
+            let world = app.world_mut();
+            saw_projectile |= world.query::<&super::super::projectile::Projectile>().iter(world).next().is_some();
+        }
+
+        assert!(saw_projectile, "a Red caster still attacks with a projectile");
+        assert!(app.world().get::<UnitStats>(player).unwrap().health < 100.0, "hit from 3 hexes away");
+    }
+
+    fn setup_mana_regen_app() -> App {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f32(STEP)))
+            .init_resource::<TimeScale>()
+            .init_resource::<GameSpeed>()
+            .add_systems(Update, mana_regen_system);
+        app
+    }
+
+    #[test]
+    fn test_mana_regen_rate_and_cap() {
+        let mut app = setup_mana_regen_app();
+        let stats = UnitStats { mana_regen: 5.0, max_mana: 2.0, ..default() };
+        let ally = app.world_mut().spawn((Unit, stats.clone(), Team::Player)).id();
+        let enemy = app.world_mut().spawn((Unit, stats, Team::Enemy)).id();
+        // First update only primes the clock
+        app.update();
+
+        for _ in 0..3 {
+            app.update();
+        }
+        let mana = app.world().get::<UnitStats>(ally).unwrap().mana;
+        assert!((mana - 1.5).abs() < 1e-3, "5 mana/s over 0.3s, got {}", mana);
+
+        for _ in 0..10 {
+            app.update();
+        }
+        assert_eq!(app.world().get::<UnitStats>(ally).unwrap().mana, 2.0, "clamped at max");
+        assert_eq!(app.world().get::<UnitStats>(enemy).unwrap().mana, 0.0, "enemies don't regenerate");
+    }
+
+    fn setup_poison_app() -> App {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f32(STEP)))
+            .insert_resource(BattleGrid::new())
+            .init_resource::<TimeScale>()
+            .init_resource::<GameSpeed>()
+            .add_systems(Update, poison_tick_system);
+        app
+    }
+
+    fn spawn_poisoned(app: &mut App, poison: PoisonDebuff) -> Entity {
+        app.world_mut()
+            .spawn((Unit, HexPosition::new(0, 0), UnitStats::default(), Team::Enemy, poison))
+            .id()
+    }
+
+    #[test]
+    fn test_poison_expires_after_duration_with_expected_total() {
+        let mut app = setup_poison_app();
+        let unit = spawn_poisoned(&mut app, PoisonDebuff::new(10.0, 1.0));
+        // First update only primes the clock
+        app.update();
+
+        for _ in 0..5 {
+            app.update();
+        }
+        assert!(app.world().get::<PoisonDebuff>(unit).is_some(), "still ticking at 0.5s");
+
+        for _ in 0..10 {
+            app.update();
+        }
+        assert!(app.world().get::<PoisonDebuff>(unit).is_none());
+        let health = app.world().get::<UnitStats>(unit).unwrap().health;
+        assert!((health - 90.0).abs() < 1e-3, "10 dps over 1s, got {}", health);
+    }
+
+    #[test]
+    fn test_poison_can_kill() {
+        let mut app = setup_poison_app();
+        let unit = spawn_poisoned(&mut app, PoisonDebuff::new(2000.0, 1.0));
+        app.update();
+
+        app.update();
+
+        assert!(app.world().get::<UnitStats>(unit).unwrap().is_dead());
+    }
+
+    #[test]
+    fn test_assassin_ability_poisons_its_target() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .init_resource::<BattleStats>()
+            .init_resource::<ManualCast>()
+            .add_systems(Update, ability_system);
+        let enemy = app
+            .world_mut()
+            .spawn((Unit, HexPosition::new(1, 0), UnitStats::default(), UnitType(TileType::Red), Team::Enemy, Target(None)))
+            .id();
+        let mut stats = UnitStats::for_type(TileType::Yellow, 1);
+        stats.mana = stats.max_mana;
+        let assassin = app
+            .world_mut()
+            .spawn((Unit, HexPosition::new(0, 0), stats, UnitType(TileType::Yellow), Team::Player, Target(Some(enemy))))
+            .id();
+
+        app.update();
+
+        assert!(app.world().get::<StealthBuff>(assassin).is_some());
+        assert!(app.world().get::<PoisonDebuff>(enemy).is_some());
+    }
+
+    #[test]
+    fn test_enemy_meteor_spills_through_player_shield() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .init_resource::<BattleStats>()
+            .init_resource::<ManualCast>()
+            .add_systems(Update, ability_system);
+        let mut stats = UnitStats::for_type(TileType::Purple, 1);
+        stats.mana = stats.max_mana;
+        let meteor = MeteorAbility::damage(stats.ability_power);
+        app.world_mut()
+            .spawn((Unit, HexPosition::new(1, 0), stats, UnitType(TileType::Purple), Team::Enemy, Target(None)));
+        let player = app
+            .world_mut()
+            .spawn((
+                Unit,
+                HexPosition::new(0, 0),
+                UnitStats::default(),
+                UnitType(TileType::Blue),
+                Team::Player,
+                Target(None),
+                Shield::new(10.0),
+            ))
+            .id();
+
+        app.update();
+
+        let mut expected = UnitStats::default();
+        expected.take_typed_damage(meteor - 10.0, DamageType::Magical);
+        assert_eq!(app.world().get::<UnitStats>(player).unwrap().health, expected.health, "only the spill-over hurts");
+        assert!(app.world().get::<Shield>(player).is_none());
+    }
+
+    #[test]
+    fn test_death_credits_kill_to_last_hitter() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .insert_resource(BattleGrid::new())
+            .init_resource::<BattleStats>()
+            .init_resource::<GameRng>()
+            .init_resource::<Gold>()
+            .init_resource::<BoardConfig>()
+            .add_observer(super::super::experience::handle_experience_gain)
+            .add_systems(Update, death_system);
+        // Red has out-damaged Blue overall, but Blue lands the final blow
+        app.world_mut().resource_mut::<BattleStats>().record_ally_damage(TileType::Red, 500.0);
+        let blue = app
+            .world_mut()
+            .spawn((
+                Unit,
+                HexPosition::new(0, 0),
+                UnitStats::default(),
+                UnitType(TileType::Blue),
+                Team::Player,
+                super::super::UnitExperience::default(),
+            ))
+            .id();
+        let dead_stats = UnitStats { health: 0.0, ..UnitStats::default() };
+        app.world_mut().spawn((
+            Unit,
+            HexPosition::new(1, 0),
+            dead_stats,
+            UnitType(TileType::Green),
+            Team::Enemy,
+            LastHitBy(blue),
+        ));
+
+        app.update();
+
+        let stats = app.world().resource::<BattleStats>();
+        assert_eq!(stats.ally_kills(TileType::Blue), 1);
+        assert_eq!(stats.ally_kills(TileType::Red), 0);
+        assert_eq!(app.world().get::<super::super::UnitExperience>(blue).unwrap().xp, XP_PER_KILL);
+    }
+
+    #[test]
+    fn test_explosive_enemy_drops_bomb_on_death() {
+        #[derive(Resource, Default)]
+        struct Spawned(Vec<(ObstacleType, Option<u8>)>);
+
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .insert_resource(BattleGrid::new())
+            .init_resource::<BattleStats>()
+            .init_resource::<GameRng>()
+            .init_resource::<Gold>()
+            .init_resource::<BoardConfig>()
+            .init_resource::<Spawned>()
+            .add_observer(|trigger: Trigger<ObstacleSpawnEvent>, mut spawned: ResMut<Spawned>| {
+                spawned.0.push((trigger.event().obstacle_type, trigger.event().countdown));
+            })
+            .add_systems(Update, death_system);
+        let dead_stats = UnitStats { health: 0.0, ..UnitStats::default() };
+        app.world_mut().spawn((Unit, HexPosition::new(1, 0), dead_stats.clone(), UnitType(TileType::Red), Team::Enemy, ExplosiveOnDeath));
+        app.world_mut().spawn((Unit, HexPosition::new(2, 0), dead_stats, UnitType(TileType::Red), Team::Enemy));
+
+        app.update();
+
+        assert_eq!(app.world().resource::<Spawned>().0, vec![(ObstacleType::Bomb, Some(3))]);
+    }
+
+    #[test]
+    fn test_kill_gold_scales_with_star_rank() {
+        assert_eq!(kill_gold(1), GOLD_PER_ENEMY_STAR);
+        assert_eq!(kill_gold(2), 2 * GOLD_PER_ENEMY_STAR);
+        assert_eq!(kill_gold(3), 3 * GOLD_PER_ENEMY_STAR);
+    }
+
+    #[test]
+    fn test_only_enemy_deaths_award_gold() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .insert_resource(BattleGrid::new())
+            .init_resource::<BattleStats>()
+            .init_resource::<GameRng>()
+            .init_resource::<Gold>()
+            .init_resource::<BoardConfig>()
+            .add_systems(Update, death_system);
+        let dead_stats = UnitStats { health: 0.0, ..UnitStats::default() };
+        app.world_mut().spawn((Unit, HexPosition::new(0, 0), dead_stats.clone(), UnitType(TileType::Red), StarRank(3), Team::Player));
+        app.update();
+        assert_eq!(*app.world().resource::<Gold>(), Gold(0), "losing a unit pays nothing");
+
+        app.world_mut().spawn((Unit, HexPosition::new(1, 0), dead_stats.clone(), UnitType(TileType::Red), StarRank(1), Team::Enemy));
+        app.world_mut().spawn((Unit, HexPosition::new(2, 0), dead_stats, UnitType(TileType::Blue), StarRank(2), Team::Enemy));
+        app.update();
+        assert_eq!(*app.world().resource::<Gold>(), Gold(kill_gold(1) + kill_gold(2)));
+    }
+
+    #[derive(Resource, Default)]
+    struct HealPopups(Vec<i32>);
+
+    fn setup_tank_heal_app(health: f32) -> App {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .init_resource::<BattleStats>()
+            .init_resource::<ManualCast>()
+            .init_resource::<HealPopups>()
+            .add_observer(|trigger: Trigger<HealPopupEvent>, mut popups: ResMut<HealPopups>| {
+                popups.0.push(trigger.event().amount);
+            })
+            .add_systems(Update, ability_system);
+        let stats = UnitStats {
+            health,
+            max_health: 200.0,
+            mana: 100.0,
+            ..UnitStats::default()
+        };
+        app.world_mut().spawn((
+            Unit,
+            HexPosition::new(0, 0),
+            stats,
+            UnitType(TileType::Blue),
+            Team::Player,
+            Transform::default(),
+        ));
+        app
+    }
+
+    #[test]
+    fn test_tank_heal_fires_popup_with_amount_healed() {
+        let mut app = setup_tank_heal_app(100.0);
+
+        app.update();
+
+        // 20% of 200 max HP
+        assert_eq!(app.world().resource::<HealPopups>().0, vec![40]);
+    }
+
+    #[test]
+    fn test_tank_heal_at_full_hp_shows_no_popup() {
+        let mut app = setup_tank_heal_app(200.0);
+
+        app.update();
+
+        assert!(app.world().resource::<HealPopups>().0.is_empty());
+    }
+
+    fn setup_manual_cast_app() -> App {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .init_resource::<BattleStats>()
+            .insert_resource(ManualCast { enabled: true })
+            .init_resource::<ButtonInput<KeyCode>>()
+            .init_resource::<KeyBindings>()
+            .add_observer(handle_manual_cast)
+            .add_systems(Update, (manual_cast_input_system, ability_system));
+        app.world_mut().flush();
+        app
+    }
+
+    fn spawn_warrior(app: &mut App, mana: f32, selected: bool) -> Entity {
+        let stats = UnitStats { mana, ..UnitStats::for_type(TileType::Red, 1) };
+        let mut unit = app
+            .world_mut()
+            .spawn((Unit, HexPosition::new(0, 0), stats, UnitType(TileType::Red), Team::Player, Target(None)));
+        if selected {
+            unit.insert(Selected);
+        }
+        unit.id()
+    }
+
+    fn press(app: &mut App, key: KeyCode) {
+        let mut keyboard = app.world_mut().resource_mut::<ButtonInput<KeyCode>>();
+        keyboard.clear();
+        keyboard.press(key);
+        app.update();
+    }
+
+    #[test]
+    fn test_manual_cast_holds_full_mana_until_key() {
+        let mut app = setup_manual_cast_app();
+        let warrior = spawn_warrior(&mut app, 100.0, true);
+
+        app.update();
+        assert!(app.world().get::<RageBuff>(warrior).is_none(), "player units don't auto-cast");
+        assert_eq!(app.world().get::<UnitStats>(warrior).unwrap().mana, 100.0);
+
+        press(&mut app, KeyCode::Digit1);
+
+        assert!(app.world().get::<RageBuff>(warrior).is_some());
+        assert_eq!(app.world().get::<UnitStats>(warrior).unwrap().mana, 0.0);
+    }
+
+    #[test]
+    fn test_manual_cast_key_ignored_by_unit_without_full_mana() {
+        let mut app = setup_manual_cast_app();
+        let warrior = spawn_warrior(&mut app, 60.0, true);
+
+        press(&mut app, KeyCode::Digit1);
+
+        assert!(app.world().get::<RageBuff>(warrior).is_none());
+        assert_eq!(app.world().get::<UnitStats>(warrior).unwrap().mana, 60.0, "mana untouched");
+    }
+
+    #[test]
+    fn test_manual_cast_key_only_casts_selected_unit() {
+        let mut app = setup_manual_cast_app();
+        let idle = spawn_warrior(&mut app, 100.0, false);
+
+        press(&mut app, KeyCode::Digit1);
+        assert!(app.world().get::<RageBuff>(idle).is_none(), "nothing selected, nothing cast");
+
+        let selected = spawn_warrior(&mut app, 100.0, true);
+        press(&mut app, KeyCode::Digit5);
+        assert!(app.world().get::<RageBuff>(selected).is_some(), "every cast key fires the selection");
+        assert!(app.world().get::<RageBuff>(idle).is_none());
+    }
+
+    #[test]
+    fn test_manual_cast_keys_do_nothing_while_disabled() {
+        let mut app = setup_manual_cast_app();
+        app.insert_resource(ManualCast { enabled: false });
+        let warrior = spawn_warrior(&mut app, 60.0, true);
+
+        press(&mut app, KeyCode::Digit1);
+
+        assert_eq!(app.world().get::<UnitStats>(warrior).unwrap().mana, 60.0);
+    }
+
+    #[test]
+    fn test_enemies_still_auto_cast_in_manual_mode() {
+        let mut app = setup_manual_cast_app();
+        let stats = UnitStats { mana: 100.0, ..UnitStats::for_type(TileType::Red, 1) };
+        let enemy = app
+            .world_mut()
+            .spawn((Unit, HexPosition::new(0, 1), stats, UnitType(TileType::Red), Team::Enemy, Target(None)))
+            .id();
+
+        app.update();
+
+        assert!(app.world().get::<RageBuff>(enemy).is_some());
+    }
+
+    fn setup_regen_app() -> App {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f32(STEP)))
+            .insert_resource(BattleGrid::new())
+            .init_resource::<TimeScale>()
+            .init_resource::<GameSpeed>()
+            .init_resource::<HealPopups>()
+            .add_observer(|trigger: Trigger<HealPopupEvent>, mut popups: ResMut<HealPopups>| {
+                popups.0.push(trigger.event().amount);
+            })
+            .add_systems(Update, out_of_combat_regen_system);
+        app
+    }
+
+    fn spawn_wounded_ally(app: &mut App) -> Entity {
+        let stats = UnitStats { health: 50.0, max_health: 100.0, ..default() };
+        app.world_mut()
+            .spawn((Unit, HexPosition::new(0, 0), stats, Team::Player, Target(None)))
+            .id()
+    }
+
+    fn run_secs(app: &mut App, seconds: f32) {
+        for _ in 0..(seconds / STEP).round() as usize {
+            app.update();
+        }
+    }
+
+    fn health(app: &App, entity: Entity) -> f32 {
+        app.world().get::<UnitStats>(entity).unwrap().health
+    }
+
+    #[test]
+    fn test_idle_unit_regenerates_after_delay() {
+        let mut app = setup_regen_app();
+        let ally = spawn_wounded_ally(&mut app);
+        // First update only primes the clock
+        app.update();
+
+        run_secs(&mut app, OUT_OF_COMBAT_DELAY - 0.5);
+        assert_eq!(health(&app, ally), 50.0, "no regen before the delay");
+
+        run_secs(&mut app, 2.0);
+        assert!(health(&app, ally) > 50.0);
+        assert!(!app.world().resource::<HealPopups>().0.is_empty());
+    }
+
+    #[test]
+    fn test_regen_delay_follows_game_speed() {
+        let mut app = setup_regen_app();
+        app.insert_resource(GameSpeed(2.0));
+        let ally = spawn_wounded_ally(&mut app);
+        app.update();
+
+        // Half the delay in real time is the full delay at 2x
+        run_secs(&mut app, OUT_OF_COMBAT_DELAY / 2.0 + 0.4);
+        assert!(health(&app, ally) > 50.0, "the out-of-combat wait runs on scaled time");
+    }
+
+    #[test]
+    fn test_recently_damaged_unit_does_not_regenerate() {
+        let mut app = setup_regen_app();
+        let ally = spawn_wounded_ally(&mut app);
+        app.update();
+
+        // Take a hit every second: the delay never runs out
+        for _ in 0..6 {
+            run_secs(&mut app, 1.0);
+            app.world_mut().get_mut::<UnitStats>(ally).unwrap().health -= 1.0;
+        }
+        assert_eq!(health(&app, ally), 44.0);
+
+        // Idle again: regen resumes only after a fresh delay
+        run_secs(&mut app, OUT_OF_COMBAT_DELAY - 0.5);
+        assert_eq!(health(&app, ally), 44.0);
+        run_secs(&mut app, 1.0);
+        assert!(health(&app, ally) > 44.0);
+    }
+
+    #[test]
+    fn test_unit_with_target_does_not_regenerate() {
+        let mut app = setup_regen_app();
+        let ally = spawn_wounded_ally(&mut app);
+        app.world_mut().entity_mut(ally).insert(Target(Some(Entity::PLACEHOLDER)));
+        app.update();
+
+        run_secs(&mut app, OUT_OF_COMBAT_DELAY * 2.0);
+
+        assert_eq!(health(&app, ally), 50.0);
+    }
+
+    #[test]
+    fn test_regen_stops_at_max_health() {
+        let mut app = setup_regen_app();
+        let ally = spawn_wounded_ally(&mut app);
+        app.update();
+
+        // 5%/s from half health needs 10s after the delay
+        run_secs(&mut app, OUT_OF_COMBAT_DELAY + 15.0);
+
+        assert_eq!(health(&app, ally), 100.0);
+        let popups = &app.world().resource::<HealPopups>().0;
+        assert_eq!(popups.iter().sum::<i32>(), 50, "popups add up to the health restored");
+    }
+
+    #[test]
+    fn test_enemies_do_not_regenerate() {
+        let mut app = setup_regen_app();
+        let enemy = spawn_wounded_ally(&mut app);
+        app.world_mut().entity_mut(enemy).insert(Team::Enemy);
+        app.update();
+
+        run_secs(&mut app, OUT_OF_COMBAT_DELAY + 2.0);
+
+        assert_eq!(health(&app, enemy), 50.0);
+    }
+
+    #[test]
+    fn test_burst_resets_player_cooldowns_once() {
+        let mut app = setup_windup_app();
+        app.add_observer(handle_burst_attack);
+        app.world_mut().flush();
+        let enemy = app
+            .world_mut()
+            .spawn((Unit, HexPosition::new(1, 0), UnitStats::default(), Target(None), AttackCooldown(0.8), Team::Enemy, UnitType(TileType::Red)))
+            .id();
+        let player = app
+            .world_mut()
+            .spawn((Unit, HexPosition::new(0, 0), UnitStats::default(), Target(Some(enemy)), AttackCooldown(0.8), Team::Player, UnitType(TileType::Red)))
+            .id();
+        let idle_player = app
+            .world_mut()
+            .spawn((Unit, HexPosition::new(-3, 0), UnitStats::default(), Target(None), AttackCooldown(0.8), Team::Player, UnitType(TileType::Red)))
+            .id();
+
+        app.world_mut().trigger(BurstAttackEvent { cleared: 25 });
+
+        let cooldown = |app: &App, entity| app.world().get::<AttackCooldown>(entity).unwrap().0;
+        assert_eq!(cooldown(&app, player), 0.0);
+        assert_eq!(cooldown(&app, enemy), 0.8, "enemies don't burst");
+        assert_eq!(cooldown(&app, idle_player), 0.8, "no target, nothing to fire at");
+
+        // The burst swing happens right away and puts the unit back on its normal cooldown
+        app.update();
+        let enemy_health = app.world().get::<UnitStats>(enemy).unwrap().health;
+        assert!(enemy_health < UnitStats::default().max_health);
+        let after_swing = cooldown(&app, player);
+        assert!(after_swing > 0.0 && after_swing <= 1.0 / UnitStats::default().attack_speed);
+    }
+}
@@ -1,4 +1,5 @@
 use crate::prelude::*;
+use super::{BattleStats, SynergyLevel, SynergyLevelUpEvent};
 
 #[derive(Component)]
 pub struct DamagePopup {
@@ -11,6 +12,10 @@ pub struct DamagePopupEvent {
     pub position: Vec3,
     pub damage: i32,
     pub is_critical: bool,
+    /// Damage-over-time tick; drawn in the poison color
+    pub is_poison: bool,
+    /// Finishing blow from an assassin's execute; shown as "EXECUTE!" instead of a number
+    pub is_execute: bool,
 }
 
 #[derive(Event)]
@@ -26,11 +31,22 @@ pub fn spawn_damage_popup(
     let event = trigger.event();
     let spawn_pos = event.position + Vec3::new(0.0, 20.0, 10.0);
 
-    let color = get_damage_color(event.is_critical);
-    let font_size = get_popup_font_size(event.is_critical);
+    let color = if event.is_execute {
+        EXECUTE_COLOR
+    } else if event.is_poison {
+        get_poison_color()
+    } else {
+        get_damage_color(event.is_critical)
+    };
+    let font_size = get_popup_font_size(event.is_critical || event.is_execute);
+    let text = if event.is_execute {
+        EXECUTE_TEXT.to_string()
+    } else {
+        format!("{}", event.damage)
+    };
 
     commands.spawn((
-        Text2d::new(format!("{}", event.damage)),
+        Text2d::new(text),
         TextFont {
             font_size,
             ..default()
@@ -69,9 +85,32 @@ pub fn spawn_heal_popup(
     ));
 }
 
+/// Centered banner for a synergy tier-up; floats and fades like any other popup
+pub fn spawn_synergy_banner(
+    trigger: Trigger<SynergyLevelUpEvent>,
+    mut commands: Commands,
+) {
+    let event = trigger.event();
+    let spawn_pos = Vec3::new(0.0, 0.0, SYNERGY_BANNER_Z);
+
+    commands.spawn((
+        Text2d::new(synergy_banner_text(event.tile_type, event.level)),
+        TextFont {
+            font_size: SYNERGY_BANNER_FONT_SIZE,
+            ..default()
+        },
+        TextColor(CRITICAL_COLOR),
+        Transform::from_translation(spawn_pos),
+        DamagePopup {
+            timer: Timer::from_seconds(SYNERGY_BANNER_DURATION, TimerMode::Once),
+            start_pos: spawn_pos,
+        },
+    ));
+}
+
 pub fn animate_damage_popup(
     mut commands: Commands,
-    time: Res<Time>,
+    time: ScaledTime,
     mut query: Query<(Entity, &mut Transform, &mut TextColor, &mut DamagePopup)>,
 ) {
     for (entity, mut transform, mut text_color, mut popup) in &mut query {
@@ -99,6 +138,23 @@ pub const NORMAL_FONT_SIZE: f32 = 24.0;
 pub const DAMAGE_COLOR: Color = Color::WHITE;
 pub const CRITICAL_COLOR: Color = Color::srgb(1.0, 0.84, 0.0);
 pub const HEAL_COLOR: Color = Color::srgb(0.2, 0.9, 0.2);
+pub const POISON_COLOR: Color = Color::srgb(0.6, 0.3, 0.9);
+pub const EXECUTE_COLOR: Color = Color::srgb(0.9, 0.1, 0.1);
+pub const EXECUTE_TEXT: &str = "EXECUTE!";
+
+pub const SYNERGY_BANNER_DURATION: f32 = 1.5;
+pub const SYNERGY_BANNER_FONT_SIZE: f32 = 40.0;
+/// Above units and damage numbers
+const SYNERGY_BANNER_Z: f32 = 50.0;
+
+/// Banner text for a synergy tier-up, e.g. "WARRIOR SILVER!"
+pub fn synergy_banner_text(tile_type: TileType, level: SynergyLevel) -> String {
+    format!(
+        "{} {}!",
+        BattleStats::unit_type_name(Some(tile_type)).to_uppercase(),
+        level.label().to_uppercase()
+    )
+}
 
 /// Calculate the Y offset for damage popup based on animation progress
 pub fn calculate_popup_y_offset(progress: f32) -> f32 {
@@ -133,6 +189,11 @@ pub fn get_heal_color() -> Color {
     HEAL_COLOR
 }
 
+/// Get poison tick popup color
+pub fn get_poison_color() -> Color {
+    POISON_COLOR
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -197,6 +258,12 @@ mod tests {
         assert_eq!(color, Color::srgb(1.0, 0.84, 0.0));
     }
 
+    #[test]
+    fn test_synergy_banner_text() {
+        assert_eq!(synergy_banner_text(TileType::Red, SynergyLevel::Silver), "WARRIOR SILVER!");
+        assert_eq!(synergy_banner_text(TileType::Purple, SynergyLevel::Gold), "MAGE GOLD!");
+    }
+
     #[test]
     fn test_heal_color_is_green() {
         let color = get_heal_color();
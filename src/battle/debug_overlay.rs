@@ -0,0 +1,148 @@
+use crate::prelude::*;
+use super::BattleGrid;
+
+const LABEL_COLOR: Color = Color::srgba(1.0, 1.0, 1.0, 0.8);
+
+/// Debug overlay showing each hex's axial `(q, r)` coordinate (off by default)
+#[derive(Resource, Default)]
+pub struct HexDebugOverlay {
+    pub enabled: bool,
+}
+
+#[derive(Component)]
+pub struct HexCoordLabel;
+
+pub fn toggle_hex_debug_overlay(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    bindings: Res<KeyBindings>,
+    mut overlay: ResMut<HexDebugOverlay>,
+) {
+    if keyboard.just_pressed(bindings.hex_debug) {
+        overlay.enabled = !overlay.enabled;
+    }
+}
+
+pub fn sync_hex_coord_labels(
+    mut commands: Commands,
+    overlay: Res<HexDebugOverlay>,
+    grid: Res<BattleGrid>,
+    labels: Query<Entity, With<HexCoordLabel>>,
+) {
+    if !overlay.is_changed() {
+        return;
+    }
+
+    for entity in labels.iter() {
+        commands.entity(entity).despawn();
+    }
+
+    if !overlay.enabled {
+        return;
+    }
+
+    for pos in grid.valid_positions() {
+        let pixel = grid.axial_to_pixel(&pos);
+        commands.spawn((
+            Text2d::new(format!("{},{}", pos.q, pos.r)),
+            TextFont {
+                font_size: 14.0,
+                ..default()
+            },
+            TextColor(LABEL_COLOR),
+            Transform::from_translation(pixel.extend(5.0)),
+            pos,
+            HexCoordLabel,
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::battle::HexPosition;
+    use std::collections::HashSet;
+
+    fn setup_overlay_app() -> App {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .init_resource::<ButtonInput<KeyCode>>()
+            .init_resource::<KeyBindings>()
+            .init_resource::<HexDebugOverlay>()
+            .insert_resource(BattleGrid::new())
+            .add_systems(Update, (toggle_hex_debug_overlay, sync_hex_coord_labels).chain());
+        app
+    }
+
+    fn labeled_positions(app: &mut App) -> HashSet<HexPosition> {
+        app.world_mut()
+            .query_filtered::<&HexPosition, With<HexCoordLabel>>()
+            .iter(app.world())
+            .copied()
+            .collect()
+    }
+
+    fn press_toggle(app: &mut App) {
+        let key = app.world().resource::<KeyBindings>().hex_debug;
+        app.world_mut()
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .press(key);
+        app.update();
+        // No InputPlugin here, so release and clear by hand to end the press
+        let mut keyboard = app.world_mut().resource_mut::<ButtonInput<KeyCode>>();
+        keyboard.release(key);
+        keyboard.clear();
+    }
+
+    #[test]
+    fn test_overlay_off_by_default() {
+        let mut app = setup_overlay_app();
+        app.update();
+        assert!(labeled_positions(&mut app).is_empty());
+    }
+
+    #[test]
+    fn test_labels_cover_exactly_valid_positions() {
+        let mut app = setup_overlay_app();
+        press_toggle(&mut app);
+
+        let grid = app.world().resource::<BattleGrid>();
+        let expected: HashSet<HexPosition> = grid.valid_positions().into_iter().collect();
+        assert_eq!(
+            expected.len(),
+            ((BATTLE_GRID_COLS / 2 * 2 + 1) * (BATTLE_GRID_ROWS / 2 * 2 + 1)) as usize
+        );
+        assert!(expected.iter().all(|pos| grid.is_valid_position(pos)));
+
+        let labeled = labeled_positions(&mut app);
+        assert_eq!(labeled, expected);
+        let label_count = app
+            .world_mut()
+            .query_filtered::<Entity, With<HexCoordLabel>>()
+            .iter(app.world())
+            .count();
+        assert_eq!(label_count, expected.len(), "one label per hex");
+    }
+
+    #[test]
+    fn test_rebound_key_toggles_overlay() {
+        let mut app = setup_overlay_app();
+        app.world_mut().resource_mut::<KeyBindings>().hex_debug = KeyCode::F8;
+        app.world_mut().resource_mut::<ButtonInput<KeyCode>>().press(KeyCode::F3);
+        app.update();
+        assert!(!app.world().resource::<HexDebugOverlay>().enabled, "old key no longer toggles");
+
+        press_toggle(&mut app);
+        assert!(app.world().resource::<HexDebugOverlay>().enabled);
+    }
+
+    #[test]
+    fn test_toggle_off_despawns_labels() {
+        let mut app = setup_overlay_app();
+        press_toggle(&mut app);
+        assert!(!labeled_positions(&mut app).is_empty());
+
+        press_toggle(&mut app);
+        app.update();
+        assert!(labeled_positions(&mut app).is_empty());
+    }
+}
@@ -0,0 +1,216 @@
+//! In-battle unit experience
+//!
+//! Player units earn XP from the damage they deal and the kills they land, and
+//! level up along `xp_for_level`. Each level is a small attack and max-health bump
+//! on top of whatever the star rank gives; merging into a higher star starts a
+//! fresh unit at level 1.
+
+use crate::prelude::*;
+use super::{Unit, UnitStats, Team};
+
+/// XP per point of damage dealt
+pub const XP_PER_DAMAGE: f32 = 0.1;
+/// XP for landing the killing blow
+pub const XP_PER_KILL: f32 = 10.0;
+pub const MAX_UNIT_LEVEL: u32 = 5;
+/// Attack and max health gained per level, compounding
+pub const LEVEL_UP_STAT_BONUS: f32 = 0.05;
+
+const LEVEL_LABEL_OFFSET_Y: f32 = -25.0;
+const LEVEL_LABEL_COLOR: Color = Color::srgb(1.0, 0.9, 0.4);
+
+/// Total XP needed to reach `level`: 20 for level 2, then 40, 60, ... more per level
+pub fn xp_for_level(level: u32) -> f32 {
+    10.0 * (level * level.saturating_sub(1)) as f32
+}
+
+/// Highest level `xp` reaches, capped at `MAX_UNIT_LEVEL`
+pub fn level_for_xp(xp: f32) -> u32 {
+    (1..=MAX_UNIT_LEVEL).take_while(|level| xp >= xp_for_level(*level)).last().unwrap_or(1)
+}
+
+#[derive(Component, Clone, Copy, Debug, PartialEq)]
+pub struct UnitExperience {
+    pub xp: f32,
+    pub level: u32,
+}
+
+impl Default for UnitExperience {
+    fn default() -> Self {
+        Self { xp: 0.0, level: 1 }
+    }
+}
+
+impl UnitExperience {
+    /// Add XP and return how many levels that gained
+    pub fn gain(&mut self, amount: f32) -> u32 {
+        self.xp += amount;
+        let level = level_for_xp(self.xp);
+        let gained = level.saturating_sub(self.level);
+        self.level = self.level.max(level);
+        gained
+    }
+}
+
+/// Stat bump for `levels` level-ups; the extra max health is healed too
+pub fn apply_level_ups(stats: &mut UnitStats, levels: u32) {
+    let multiplier = (1.0 + LEVEL_UP_STAT_BONUS).powi(levels as i32);
+    let extra_health = stats.max_health * (multiplier - 1.0);
+    stats.attack *= multiplier;
+    stats.max_health += extra_health;
+    stats.health += extra_health;
+}
+
+/// `unit` earned `amount` XP; ignored for enemies and units without `UnitExperience`
+#[derive(Event, Debug)]
+pub struct ExperienceGainEvent {
+    pub unit: Entity,
+    pub amount: f32,
+}
+
+pub fn handle_experience_gain(
+    trigger: Trigger<ExperienceGainEvent>,
+    mut units: Query<(&mut UnitExperience, &mut UnitStats, &Team), With<Unit>>,
+) {
+    let event = trigger.event();
+    let Ok((mut experience, mut stats, team)) = units.get_mut(event.unit) else { return };
+    if *team != Team::Player || stats.is_dead() {
+        return;
+    }
+
+    let levels = experience.gain(event.amount);
+    if levels > 0 {
+        apply_level_ups(&mut stats, levels);
+    }
+}
+
+/// "Lv2" tag under a unit that has levelled up
+#[derive(Component)]
+pub struct UnitLevelLabel;
+
+type LevelledUnitQuery<'w, 's> = Query<
+    'w,
+    's,
+    (Entity, &'static UnitExperience, Option<&'static Children>),
+    (With<Unit>, Changed<UnitExperience>),
+>;
+
+pub fn update_level_labels(
+    mut commands: Commands,
+    units: LevelledUnitQuery,
+    mut labels: Query<&mut Text2d, With<UnitLevelLabel>>,
+) {
+    for (entity, experience, children) in units.iter() {
+        if experience.level <= 1 {
+            continue;
+        }
+        let text = format!("Lv{}", experience.level);
+        let existing = children
+            .into_iter()
+            .flatten()
+            .find(|child| labels.contains(**child));
+        match existing {
+            Some(&label) => {
+                if let Ok(mut label) = labels.get_mut(label) {
+                    label.0 = text;
+                }
+            }
+            None => {
+                commands.entity(entity).with_children(|parent| {
+                    parent.spawn((
+                        UnitLevelLabel,
+                        Text2d::new(text),
+                        TextFont {
+                            font_size: 12.0,
+                            ..default()
+                        },
+                        TextColor(LEVEL_LABEL_COLOR),
+                        Transform::from_xyz(0.0, LEVEL_LABEL_OFFSET_Y, 0.5),
+                    ));
+                });
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_xp_curve() {
+        assert_eq!(xp_for_level(1), 0.0);
+        assert_eq!(xp_for_level(2), 20.0);
+        assert_eq!(xp_for_level(3), 60.0);
+        assert_eq!(xp_for_level(5), 200.0);
+
+        assert_eq!(level_for_xp(0.0), 1);
+        assert_eq!(level_for_xp(19.9), 1);
+        assert_eq!(level_for_xp(20.0), 2);
+        assert_eq!(level_for_xp(59.0), 2);
+        assert_eq!(level_for_xp(60.0), 3);
+        assert_eq!(level_for_xp(10_000.0), MAX_UNIT_LEVEL, "levels are capped");
+    }
+
+    #[test]
+    fn test_gain_reports_levels_crossed() {
+        let mut experience = UnitExperience::default();
+        assert_eq!(experience.gain(15.0), 0);
+        assert_eq!(experience.gain(50.0), 2, "one big gain can cross several levels");
+        assert_eq!(experience, UnitExperience { xp: 65.0, level: 3 });
+    }
+
+    fn setup_experience_app() -> App {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .add_observer(handle_experience_gain)
+            .add_systems(Update, update_level_labels);
+        app.world_mut().flush();
+        app
+    }
+
+    #[test]
+    fn test_level_up_bumps_stats_and_shows_label() {
+        let mut app = setup_experience_app();
+        let base = UnitStats::for_type(TileType::Red, 1);
+        let unit = app
+            .world_mut()
+            .spawn((Unit, base.clone(), Team::Player, UnitExperience::default()))
+            .id();
+
+        app.world_mut().trigger(ExperienceGainEvent { unit, amount: XP_PER_KILL });
+        app.world_mut().flush();
+        assert_eq!(app.world().get::<UnitStats>(unit).unwrap().attack, base.attack, "not a level yet");
+
+        app.world_mut().trigger(ExperienceGainEvent { unit, amount: XP_PER_KILL });
+        app.world_mut().flush();
+        app.update();
+
+        let stats = app.world().get::<UnitStats>(unit).unwrap();
+        assert!((stats.attack - base.attack * 1.05).abs() < 1e-4);
+        assert!((stats.max_health - base.max_health * 1.05).abs() < 1e-4);
+        assert!((stats.health - stats.max_health).abs() < 1e-4, "the extra health comes filled");
+
+        let world = app.world_mut();
+        let labels: Vec<String> = world
+            .query_filtered::<&Text2d, With<UnitLevelLabel>>()
+            .iter(world)
+            .map(|text| text.0.clone())
+            .collect();
+        assert_eq!(labels, vec!["Lv2".to_string()]);
+    }
+
+    #[test]
+    fn test_enemies_gain_no_experience() {
+        let mut app = setup_experience_app();
+        let enemy = app
+            .world_mut()
+            .spawn((Unit, UnitStats::default(), Team::Enemy, UnitExperience::default()))
+            .id();
+
+        app.world_mut().trigger(ExperienceGainEvent { unit: enemy, amount: 100.0 });
+        app.world_mut().flush();
+
+        assert_eq!(app.world().get::<UnitExperience>(enemy), Some(&UnitExperience::default()));
+    }
+}
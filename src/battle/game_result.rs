@@ -1,6 +1,11 @@
+use std::path::Path;
+use serde::{Deserialize, Serialize};
 use crate::prelude::*;
 use crate::audio::{VictorySoundEvent, DefeatSoundEvent};
-use super::{Unit, Team, WaveManager, HexPosition};
+use super::{Unit, Team, StarRank, WaveManager, HexPosition, BattleGrid, UnitCensus, Boss};
+
+/// Lifetime stats file, next to `settings.json`
+pub const STATS_FILE: &str = "stats.json";
 
 #[derive(Event)]
 pub struct WaveCompleteEvent {
@@ -13,7 +18,7 @@ pub struct GameOverEvent {
     pub waves_survived: u32,
 }
 
-#[derive(Resource)]
+#[derive(Resource, Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct GameResult {
     pub game_ended: bool,
     pub victory: bool,
@@ -35,12 +40,126 @@ impl Default for GameResult {
 }
 
 const DEFENSELESS_TIMEOUT: f32 = 5.0;
+/// An enemy standing on this row (or below) has reached the player's base
+pub const BASE_ROW: i32 = -2;
+/// Base health at the start of a run; the game is lost when it reaches zero
+pub const BASE_MAX_HEALTH: f32 = 20.0;
+/// Base damage per star of an enemy that gets through
+pub const BASE_DAMAGE_PER_STAR: f32 = 2.0;
+/// A boss reaching the base takes half of it
+const BOSS_BASE_DAMAGE: f32 = 10.0;
+
+#[derive(Resource, Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct BaseHealth {
+    pub current: f32,
+    pub max: f32,
+}
+
+impl Default for BaseHealth {
+    fn default() -> Self {
+        Self {
+            current: BASE_MAX_HEALTH,
+            max: BASE_MAX_HEALTH,
+        }
+    }
+}
+
+impl BaseHealth {
+    pub fn take_damage(&mut self, amount: f32) {
+        self.current = (self.current - amount).max(0.0);
+    }
+
+    pub fn is_depleted(&self) -> bool {
+        self.current <= 0.0
+    }
+
+    /// Health left in 0..=1 for the HUD bar
+    pub fn fraction(&self) -> f32 {
+        if self.max <= 0.0 {
+            return 0.0;
+        }
+        (self.current / self.max).clamp(0.0, 1.0)
+    }
+}
+
+/// Damage an enemy deals to the base when it gets through
+pub fn base_damage(star_rank: u8, is_boss: bool) -> f32 {
+    if is_boss {
+        BOSS_BASE_DAMAGE
+    } else {
+        star_rank as f32 * BASE_DAMAGE_PER_STAR
+    }
+}
+
+/// Records kept across sessions in `stats.json`
+#[derive(Resource, Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(default)]
+pub struct PersistentStats {
+    pub best_wave: u32,
+    pub high_score: u32,
+    pub games_played: u32,
+}
+
+impl PersistentStats {
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("PersistentStats always serializes")
+    }
+
+    /// Parse stats; missing fields count as zero
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
+    /// Read stats from disk. First run (no file) starts at zero; a corrupt file is
+    /// reported and reset to zero rather than blocking the game.
+    pub fn load(path: &Path) -> Self {
+        let Ok(json) = std::fs::read_to_string(path) else {
+            return Self::default();
+        };
+        Self::from_json(&json).unwrap_or_else(|err| {
+            warn!("Resetting malformed {}: {}", path.display(), err);
+            Self::default()
+        })
+    }
+
+    pub fn save(&self, path: &Path) -> std::io::Result<()> {
+        std::fs::write(path, self.to_json())
+    }
+
+    /// Fold one finished game into the records; returns true when `score` beat the high score
+    pub fn record_game(&mut self, waves_survived: u32, score: u32) -> bool {
+        self.games_played += 1;
+        self.best_wave = self.best_wave.max(waves_survived);
+        let new_high_score = score > self.high_score;
+        self.high_score = self.high_score.max(score);
+        new_high_score
+    }
+}
+
+/// Enemies that reached the base damage it once, then leave the field
+pub fn enemy_reach_base_system(
+    mut commands: Commands,
+    mut grid: ResMut<BattleGrid>,
+    mut base_health: ResMut<BaseHealth>,
+    enemies: Query<(Entity, &Team, &HexPosition, &StarRank, Has<Boss>), With<Unit>>,
+) {
+    for (entity, team, pos, star_rank, is_boss) in enemies.iter() {
+        if *team != Team::Enemy || pos.r > BASE_ROW {
+            continue;
+        }
+        base_health.take_damage(base_damage(star_rank.0, is_boss));
+        grid.remove_unit(pos);
+        commands.entity(entity).despawn_recursive();
+    }
+}
 
 pub fn check_game_result(
-    time: Res<Time>,
+    time: ScaledTime,
     mut commands: Commands,
-    units: Query<(&Team, &HexPosition), With<Unit>>,
+    census: Res<UnitCensus>,
+    base_health: Res<BaseHealth>,
     wave_manager: Res<WaveManager>,
+    game_mode: Res<GameMode>,
     mut game_result: ResMut<GameResult>,
     mut next_state: ResMut<NextState<GameState>>,
 ) {
@@ -48,28 +167,20 @@ pub fn check_game_result(
         return;
     }
 
-    let mut player_count = 0;
-    let mut enemy_count = 0;
-    let mut enemy_reached_base = false;
-
-    for (team, pos) in units.iter() {
-        match team {
-            Team::Player => player_count += 1,
-            Team::Enemy => {
-                enemy_count += 1;
-                if pos.r <= -2 {
-                    enemy_reached_base = true;
-                }
-            }
-        }
-    }
+    let player_count = census.count(Team::Player);
+    let enemy_count = census.count(Team::Enemy);
 
     if player_count > 0 {
         game_result.player_had_units = true;
         game_result.defenseless_timer = 0.0;
     }
 
-    if wave_manager.wave_active && enemy_count == 0 && wave_manager.enemies_remaining == 0 {
+    // Once per wave: every enemy spawned (the spawner then clears wave_active) and defeated
+    if !wave_manager.wave_active
+        && wave_manager.current_wave > game_result.waves_completed
+        && enemy_count == 0
+        && wave_manager.enemies_remaining == 0
+    {
         game_result.waves_completed = wave_manager.current_wave;
         commands.trigger(WaveCompleteEvent {
             wave_number: wave_manager.current_wave,
@@ -78,7 +189,7 @@ pub fn check_game_result(
 
     let should_lose =
         (game_result.player_had_units && player_count == 0 && wave_manager.current_wave > 0) ||
-        enemy_reached_base ||
+        base_health.is_depleted() ||
         (player_count == 0 && enemy_count > 0 && {
             game_result.defenseless_timer += time.delta_secs();
             game_result.defenseless_timer >= DEFENSELESS_TIMEOUT
@@ -95,7 +206,7 @@ pub fn check_game_result(
         next_state.set(GameState::GameOver);
     }
 
-    if wave_manager.current_wave >= 10 && enemy_count == 0 && !wave_manager.wave_active {
+    if game_mode.has_victory_wave() && wave_manager.current_wave >= 10 && enemy_count == 0 && !wave_manager.wave_active {
         game_result.game_ended = true;
         game_result.victory = true;
         game_result.waves_completed = wave_manager.current_wave;
@@ -125,3 +236,169 @@ pub fn handle_game_over(
         info!("Game Over! You survived {} waves.", event.waves_survived);
     }
 }
+
+/// Write stats back to disk after each recorded game
+pub fn save_persistent_stats(stats: Res<PersistentStats>) {
+    if !stats.is_changed() || stats.is_added() {
+        return;
+    }
+    if let Err(err) = stats.save(Path::new(STATS_FILE)) {
+        warn!("Failed to save {}: {}", STATS_FILE, err);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::state::app::StatesPlugin;
+
+    #[derive(Resource, Default)]
+    struct CompletedWaves(Vec<u32>);
+
+    #[test]
+    fn test_wave_complete_fires_once_after_wave_is_cleared() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, StatesPlugin))
+            .init_state::<GameState>()
+            .init_resource::<UnitCensus>()
+            .init_resource::<GameMode>()
+            .init_resource::<GameResult>()
+            .init_resource::<BaseHealth>()
+            .init_resource::<TimeScale>()
+            .init_resource::<GameSpeed>()
+            .init_resource::<CompletedWaves>()
+            .add_observer(|trigger: Trigger<WaveCompleteEvent>, mut waves: ResMut<CompletedWaves>| {
+                waves.0.push(trigger.event().wave_number);
+            })
+            .add_systems(Update, check_game_result);
+        let mut wave_manager = WaveManager::default();
+        wave_manager.start_wave(2, Difficulty::Normal);
+        // Spawner has sent every enemy and switched the wave off; all of them are dead
+        wave_manager.enemies_remaining = 0;
+        wave_manager.wave_active = false;
+        app.insert_resource(wave_manager);
+
+        for _ in 0..3 {
+            app.update();
+        }
+
+        assert_eq!(app.world().resource::<CompletedWaves>().0, vec![2]);
+        assert_eq!(app.world().resource::<GameResult>().waves_completed, 2);
+    }
+
+    fn setup_base_app() -> App {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, StatesPlugin))
+            .init_state::<GameState>()
+            .init_resource::<UnitCensus>()
+            .init_resource::<GameMode>()
+            .init_resource::<GameResult>()
+            .init_resource::<WaveManager>()
+            .init_resource::<BaseHealth>()
+            .init_resource::<TimeScale>()
+            .init_resource::<GameSpeed>()
+            .insert_resource(BattleGrid::new())
+            .add_systems(Update, (enemy_reach_base_system, check_game_result).chain());
+        app
+    }
+
+    fn spawn_enemy_at(app: &mut App, pos: HexPosition, star_rank: u8) -> Entity {
+        let entity = app.world_mut().spawn((Unit, Team::Enemy, pos, StarRank(star_rank))).id();
+        app.world_mut().resource_mut::<BattleGrid>().place_unit(pos, entity);
+        entity
+    }
+
+    #[test]
+    fn test_enemies_at_base_deal_damage_and_leave() {
+        let mut app = setup_base_app();
+        let short = spawn_enemy_at(&mut app, HexPosition::new(0, BASE_ROW + 1), 1);
+        let through = spawn_enemy_at(&mut app, HexPosition::new(1, BASE_ROW), 2);
+
+        app.update();
+
+        let base = *app.world().resource::<BaseHealth>();
+        assert_eq!(base.current, BASE_MAX_HEALTH - 2.0 * BASE_DAMAGE_PER_STAR);
+        assert!(app.world().get_entity(through).is_err(), "despawned after hitting the base");
+        assert!(!app.world().resource::<BattleGrid>().is_occupied(&HexPosition::new(1, BASE_ROW)));
+        assert!(app.world().get_entity(short).is_ok(), "one row short does nothing");
+
+        // Damage accumulates across arrivals
+        app.world_mut().entity_mut(short).insert(HexPosition::new(0, BASE_ROW));
+        app.update();
+        let base = *app.world().resource::<BaseHealth>();
+        assert_eq!(base.current, BASE_MAX_HEALTH - 3.0 * BASE_DAMAGE_PER_STAR);
+        assert!(!app.world().resource::<GameResult>().game_ended, "base still standing");
+    }
+
+    #[test]
+    fn test_game_lost_only_when_base_health_runs_out() {
+        let mut app = setup_base_app();
+        app.world_mut().resource_mut::<BaseHealth>().current = BASE_DAMAGE_PER_STAR + 1.0;
+
+        spawn_enemy_at(&mut app, HexPosition::new(0, BASE_ROW), 1);
+        app.update();
+        assert!(!app.world().resource::<GameResult>().game_ended);
+
+        spawn_enemy_at(&mut app, HexPosition::new(2, BASE_ROW), 1);
+        app.update();
+        let result = app.world().resource::<GameResult>();
+        assert!(result.game_ended && !result.victory);
+        assert_eq!(app.world().resource::<BaseHealth>().current, 0.0);
+    }
+
+    #[test]
+    fn test_boss_hits_base_harder() {
+        assert_eq!(base_damage(1, false), BASE_DAMAGE_PER_STAR);
+        assert_eq!(base_damage(2, false), 2.0 * BASE_DAMAGE_PER_STAR);
+        assert!(base_damage(1, true) > base_damage(3, false));
+    }
+
+    #[test]
+    fn test_record_game_keeps_bests_and_counts_games() {
+        let mut stats = PersistentStats::default();
+        assert!(stats.record_game(4, 1200), "first score is a new high");
+        assert!(stats.record_game(2, 3000));
+        assert!(!stats.record_game(6, 500));
+        assert!(!stats.record_game(1, 3000), "tying the record doesn't beat it");
+
+        assert_eq!(stats, PersistentStats { best_wave: 6, high_score: 3000, games_played: 4 });
+    }
+
+    #[test]
+    fn test_persistent_stats_json_round_trip() {
+        let stats = PersistentStats { best_wave: 7, high_score: 4200, games_played: 12 };
+        assert_eq!(PersistentStats::from_json(&stats.to_json()).unwrap(), stats);
+    }
+
+    #[test]
+    fn test_persistent_stats_missing_fields_are_zero() {
+        let stats = PersistentStats::from_json(r#"{ "best_wave": 3 }"#).unwrap();
+        assert_eq!(stats, PersistentStats { best_wave: 3, ..default() });
+    }
+
+    #[test]
+    fn test_persistent_stats_first_run_and_corrupt_file_start_at_zero() {
+        let missing = std::env::temp_dir().join("puzzle_tactics_stats_does_not_exist.json");
+        assert_eq!(PersistentStats::load(&missing), PersistentStats::default());
+
+        let corrupt = std::env::temp_dir().join(format!("puzzle_tactics_stats_corrupt_{}.json", std::process::id()));
+        std::fs::write(&corrupt, "{ best_wave: oops").unwrap();
+        let loaded = PersistentStats::load(&corrupt);
+        std::fs::remove_file(&corrupt).ok();
+        assert_eq!(loaded, PersistentStats::default());
+    }
+
+    #[test]
+    fn test_persistent_stats_file_round_trip() {
+        let path = std::env::temp_dir().join(format!("puzzle_tactics_stats_{}.json", std::process::id()));
+        let mut stats = PersistentStats::load(&path);
+        stats.record_game(5, 900);
+        stats.save(&path).unwrap();
+
+        let mut reloaded = PersistentStats::load(&path);
+        reloaded.record_game(3, 1500);
+        std::fs::remove_file(&path).ok();
+
+        assert_eq!(reloaded, PersistentStats { best_wave: 5, high_score: 1500, games_played: 2 });
+    }
+}
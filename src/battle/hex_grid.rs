@@ -1,7 +1,8 @@
 use crate::prelude::*;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
-#[derive(Component, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[derive(Component, Clone, Copy, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)]
 pub struct HexPosition {
     pub q: i32,
     pub r: i32,
@@ -67,6 +68,19 @@ impl BattleGrid {
             && pos.r <= BATTLE_GRID_ROWS / 2
     }
 
+    /// Player half of the battlefield (rows r <= 0); enemies spawn on the other half
+    pub fn is_player_zone(&self, pos: &HexPosition) -> bool {
+        self.is_valid_position(pos) && pos.r <= 0
+    }
+
+    /// Every cell on the battlefield, row by row
+    pub fn valid_positions(&self) -> Vec<HexPosition> {
+        (-BATTLE_GRID_ROWS / 2..=BATTLE_GRID_ROWS / 2)
+            .flat_map(|r| (-BATTLE_GRID_COLS / 2..=BATTLE_GRID_COLS / 2).map(move |q| HexPosition::new(q, r)))
+            .filter(|pos| self.is_valid_position(pos))
+            .collect()
+    }
+
     pub fn is_occupied(&self, pos: &HexPosition) -> bool {
         self.units.contains_key(pos)
     }
@@ -0,0 +1,231 @@
+use crate::prelude::*;
+use super::{Unit, UnitStats, HexPosition, BattleGrid};
+
+/// Seconds the pushed unit takes to slide into its new hex
+pub const KNOCKBACK_DURATION: f32 = 0.15;
+
+/// Push `target` one hex away from the hex `from` the hit came from
+#[derive(Event, Debug)]
+pub struct KnockbackEvent {
+    pub target: Entity,
+    pub from: HexPosition,
+}
+
+/// Slide from the old hex to the new one after a knockback. Endpoints are stored as
+/// hexes and converted to pixels every frame, so a grid origin change mid-slide stays smooth.
+#[derive(Component)]
+pub struct KnockbackAnimation {
+    pub origin: HexPosition,
+    /// Hex the slide ends on; a unit that walks off it mid-slide drops the animation
+    pub destination: HexPosition,
+    pub timer: Timer,
+}
+
+impl KnockbackAnimation {
+    pub fn new(origin: HexPosition, destination: HexPosition) -> Self {
+        Self {
+            origin,
+            destination,
+            timer: Timer::from_seconds(KNOCKBACK_DURATION, TimerMode::Once),
+        }
+    }
+
+    /// Current pixel start/end for the grid's present layout
+    pub fn endpoints(&self, grid: &BattleGrid) -> (Vec2, Vec2) {
+        (grid.axial_to_pixel(&self.origin), grid.axial_to_pixel(&self.destination))
+    }
+
+    /// Eased pixel position at the timer's current progress
+    pub fn current_position(&self, grid: &BattleGrid) -> Vec2 {
+        let (start, end) = self.endpoints(grid);
+        let progress = self.timer.fraction();
+        // Ease out so the hit reads as a shove rather than a walk
+        let eased = 1.0 - (1.0 - progress) * (1.0 - progress);
+        start.lerp(end, eased)
+    }
+}
+
+/// Neighbor of `target` that lies furthest along the line from `from` through `target`.
+/// Compared in cube coordinates so a straight push stays straight; `None` when the two coincide.
+pub fn knockback_step(from: &HexPosition, target: &HexPosition) -> Option<HexPosition> {
+    let (dq, dr) = (target.q - from.q, target.r - from.r);
+    if dq == 0 && dr == 0 {
+        return None;
+    }
+    let ds = -dq - dr;
+
+    target.neighbors().into_iter().rev().max_by_key(|neighbor| {
+        let (nq, nr) = (neighbor.q - target.q, neighbor.r - target.r);
+        nq * dq + nr * dr + (-nq - nr) * ds
+    })
+}
+
+/// Hex the target is pushed into, if it is on the grid and free
+pub fn knockback_destination(grid: &BattleGrid, from: &HexPosition, target: &HexPosition) -> Option<HexPosition> {
+    knockback_step(from, target).filter(|to| grid.is_valid_position(to) && !grid.is_occupied(to))
+}
+
+/// Move a knocked-back unit on the grid right away; its sprite catches up in `animate_knockback`
+pub fn handle_knockback(
+    trigger: Trigger<KnockbackEvent>,
+    mut commands: Commands,
+    mut grid: ResMut<BattleGrid>,
+    mut units: Query<(&mut HexPosition, &UnitStats), With<Unit>>,
+) {
+    let event = trigger.event();
+    let Ok((mut pos, stats)) = units.get_mut(event.target) else { return };
+    if stats.is_dead() {
+        return;
+    }
+    let Some(to) = knockback_destination(&grid, &event.from, &pos) else { return };
+    if !grid.move_unit(&pos, &to) {
+        return;
+    }
+
+    commands.entity(event.target).try_insert(KnockbackAnimation::new(*pos, to));
+    *pos = to;
+}
+
+pub fn animate_knockback(
+    mut commands: Commands,
+    time: ScaledTime,
+    grid: Res<BattleGrid>,
+    mut units: Query<(Entity, &HexPosition, &mut Transform, &mut KnockbackAnimation)>,
+) {
+    for (entity, pos, mut transform, mut anim) in units.iter_mut() {
+        if *pos != anim.destination {
+            commands.entity(entity).remove::<KnockbackAnimation>();
+            continue;
+        }
+
+        anim.timer.tick(time.delta());
+        transform.translation = anim.current_position(&grid).extend(transform.translation.z);
+
+        if anim.timer.finished() {
+            commands.entity(entity).remove::<KnockbackAnimation>();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn setup_knockback_app() -> App {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .insert_resource(BattleGrid::new())
+            .add_observer(handle_knockback);
+        app.world_mut().flush();
+        app
+    }
+
+    fn spawn_at(app: &mut App, pos: HexPosition) -> Entity {
+        let entity = app
+            .world_mut()
+            .spawn((Unit, pos, UnitStats::default(), Transform::default()))
+            .id();
+        app.world_mut().resource_mut::<BattleGrid>().place_unit(pos, entity);
+        entity
+    }
+
+    #[test]
+    fn test_knockback_pushes_straight_away_from_attacker() {
+        let target = HexPosition::new(0, 0);
+        for from in target.neighbors() {
+            let expected = HexPosition::new(2 * target.q - from.q, 2 * target.r - from.r);
+            assert_eq!(knockback_step(&from, &target), Some(expected), "hit from {from:?}");
+        }
+        // Further away along a straight line still pushes along that line
+        assert_eq!(
+            knockback_step(&HexPosition::new(-2, 0), &HexPosition::new(0, 0)),
+            Some(HexPosition::new(1, 0))
+        );
+        assert_eq!(knockback_step(&target, &target), None);
+    }
+
+    #[test]
+    fn test_knockback_moves_unit_on_grid() {
+        let mut app = setup_knockback_app();
+        let enemy = spawn_at(&mut app, HexPosition::new(0, 1));
+
+        app.world_mut().trigger(KnockbackEvent { target: enemy, from: HexPosition::new(0, 0) });
+        app.world_mut().flush();
+
+        assert_eq!(*app.world().get::<HexPosition>(enemy).unwrap(), HexPosition::new(0, 2));
+        let grid = app.world().resource::<BattleGrid>();
+        assert_eq!(grid.units.get(&HexPosition::new(0, 2)), Some(&enemy));
+        assert!(!grid.is_occupied(&HexPosition::new(0, 1)));
+        assert!(app.world().get::<KnockbackAnimation>(enemy).is_some());
+    }
+
+    #[test]
+    fn test_in_flight_knockback_follows_grid_origin() {
+        let mut app = setup_knockback_app();
+        app.init_resource::<TimeScale>()
+            .init_resource::<GameSpeed>()
+            .add_systems(Update, animate_knockback);
+        let enemy = spawn_at(&mut app, HexPosition::new(0, 1));
+        app.world_mut().trigger(KnockbackEvent { target: enemy, from: HexPosition::new(0, 0) });
+        app.world_mut().flush();
+        app.world_mut()
+            .get_mut::<KnockbackAnimation>(enemy)
+            .unwrap()
+            .timer
+            .set_elapsed(std::time::Duration::from_secs_f32(KNOCKBACK_DURATION * 0.5));
+
+        // The window is resized while the unit is halfway through the slide
+        app.world_mut().resource_mut::<BattleGrid>().origin += Vec2::new(80.0, -30.0);
+        app.update();
+
+        let grid = app.world().resource::<BattleGrid>();
+        let anim = app.world().get::<KnockbackAnimation>(enemy).unwrap();
+        let translation = app.world().get::<Transform>(enemy).unwrap().translation.truncate();
+        assert_eq!(translation, anim.current_position(grid));
+        let (start, end) = anim.endpoints(grid);
+        assert_eq!(start, grid.axial_to_pixel(&HexPosition::new(0, 1)));
+        assert_eq!(end, grid.axial_to_pixel(&HexPosition::new(0, 2)));
+        assert!(translation.y >= start.y.min(end.y) && translation.y <= start.y.max(end.y));
+
+        app.world_mut()
+            .get_mut::<KnockbackAnimation>(enemy)
+            .unwrap()
+            .timer
+            .set_elapsed(std::time::Duration::from_secs_f32(KNOCKBACK_DURATION));
+        app.update();
+
+        let grid = app.world().resource::<BattleGrid>();
+        let translation = app.world().get::<Transform>(enemy).unwrap().translation.truncate();
+        assert_eq!(translation, grid.axial_to_pixel(&HexPosition::new(0, 2)), "lands on the moved hex");
+        assert!(app.world().get::<KnockbackAnimation>(enemy).is_none());
+    }
+
+    #[test]
+    fn test_blocked_knockback_is_noop() {
+        let mut app = setup_knockback_app();
+        let enemy = spawn_at(&mut app, HexPosition::new(0, 1));
+        let blocker = spawn_at(&mut app, HexPosition::new(0, 2));
+
+        app.world_mut().trigger(KnockbackEvent { target: enemy, from: HexPosition::new(0, 0) });
+        app.world_mut().flush();
+
+        assert_eq!(*app.world().get::<HexPosition>(enemy).unwrap(), HexPosition::new(0, 1));
+        let grid = app.world().resource::<BattleGrid>();
+        assert_eq!(grid.units.get(&HexPosition::new(0, 1)), Some(&enemy));
+        assert_eq!(grid.units.get(&HexPosition::new(0, 2)), Some(&blocker));
+        assert!(app.world().get::<KnockbackAnimation>(enemy).is_none());
+    }
+
+    #[test]
+    fn test_knockback_off_grid_is_noop() {
+        let mut app = setup_knockback_app();
+        let edge = HexPosition::new(0, BATTLE_GRID_ROWS / 2);
+        let enemy = spawn_at(&mut app, edge);
+
+        app.world_mut().trigger(KnockbackEvent { target: enemy, from: HexPosition::new(0, edge.r - 1) });
+        app.world_mut().flush();
+
+        assert_eq!(*app.world().get::<HexPosition>(enemy).unwrap(), edge);
+        assert_eq!(app.world().resource::<BattleGrid>().units.get(&edge), Some(&enemy));
+    }
+}
@@ -7,18 +7,35 @@ mod game_result;
 mod damage_popup;
 mod battle_stats;
 mod placement;
+mod census;
+mod debug_overlay;
+mod range_ring;
+mod projectile;
+mod knockback;
+mod chain_lightning;
+mod experience;
+mod step;
+mod sim;
 
 use crate::prelude::*;
 
 pub use hex_grid::{BattleGrid, HexPosition};
-pub use unit::{Unit, UnitStats, UnitType, StarRank, Team, Target, AttackCooldown, HealthBar, HealthBarBackground, RageBuff, SnipeBuff, StealthBuff, MeteorAbility};
-pub use synergy::{ActiveSynergies, SynergyLevel};
-pub use wave::{WaveManager, BombDamageEvent, BombExplosionEffect, BombCountdownTimer, BOMB_COUNTDOWN_INTERVAL, WaveBreakStartEvent, WaveBreakEndEvent};
-pub use game_result::{GameResult, WaveCompleteEvent, GameOverEvent};
-pub use damage_popup::{DamagePopup, DamagePopupEvent};
-pub use combat::DamageCalculator;
+pub use unit::{Unit, DamageType, UnitStats, UnitType, StarRank, Team, Target, TargetingMode, AttackCooldown, LastHitBy, CombatActivity, HealthBar, HealthBarBackground, ManaBar, ManaBarBackground, CastReady, CastRing, RageBuff, SnipeBuff, StealthBuff, PoisonDebuff, MeteorAbility, Shield, ShieldBar, take_shielded_damage, take_shielded_calculated_damage};
+pub use synergy::{ActiveSynergies, SynergyLevel, SynergyThresholds, SynergyConfig, SynergyActivationEvent, SynergyLevelUpEvent, UnitTrait, TraitBonuses};
+pub use wave::{WaveManager, SpawnTelegraph, SPAWN_TELEGRAPH_DURATION, WaveModifier, EnemyArchetype, EnemyGoal, ExplosiveOnDeath, WaveStartEvent, Summoner, Minion, Boss, BombDamageEvent, BombExplosionEffect, BombCountdownTimer, BOMB_COUNTDOWN_INTERVAL, WaveBreakStartEvent, WaveBreakEndEvent};
+pub use game_result::{GameResult, BaseHealth, check_game_result, BASE_ROW, WaveCompleteEvent, GameOverEvent, PersistentStats, STATS_FILE};
+pub use damage_popup::{DamagePopup, DamagePopupEvent, HealPopupEvent};
+pub use combat::{DamageCalculator, AttackLine, in_attack_range, ManualCast, ManualCastEvent, cast_ability};
 pub use battle_stats::BattleStats;
-pub use placement::{Selected, SelectableUnit, MovementHighlight, UnitSelectEvent, UnitMoveEvent};
+pub use census::UnitCensus;
+pub use debug_overlay::{HexDebugOverlay, HexCoordLabel};
+pub use range_ring::{ShowRangeRings, RangeRing, range_ring_radius};
+pub use projectile::{Projectile, ProjectileHitEvent};
+pub use knockback::{KnockbackEvent, KnockbackAnimation};
+pub use experience::{UnitExperience, ExperienceGainEvent, UnitLevelLabel, apply_level_ups, xp_for_level, level_for_xp, XP_PER_DAMAGE, XP_PER_KILL, MAX_UNIT_LEVEL};
+pub use step::{CombatStep, combat_systems, init_combat_world, step_combat};
+pub use sim::{HeadlessSimPlugin, SimConfig, SimReport, sim_finished, SIM_ROSTER, SIM_TIMESTEP, SIM_TIME_LIMIT};
+pub use placement::{Selected, SelectableUnit, MovementHighlight, AttackRangeHighlight, attack_range_hexes, DragGhost, UnitTooltip, DragPreview, UnitDrag, UnitSelectEvent, UnitMoveEvent, SellUnitEvent};
 
 pub struct BattlePlugin;
 
@@ -26,39 +43,71 @@ impl Plugin for BattlePlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<BattleGrid>()
             .init_resource::<ActiveSynergies>()
+            .init_resource::<SynergyConfig>()
             .init_resource::<WaveManager>()
             .init_resource::<GameResult>()
+            .init_resource::<Gold>()
+            .init_resource::<BaseHealth>()
+            .insert_resource(PersistentStats::load(std::path::Path::new(STATS_FILE)))
             .init_resource::<BattleStats>()
+            .init_resource::<UnitCensus>()
+            .init_resource::<HexDebugOverlay>()
+            .init_resource::<ShowRangeRings>()
             .init_resource::<wave::BombCountdownTimer>()
             .init_resource::<WaveBreakTimer>()
+            .init_resource::<DragPreview>()
+            .init_resource::<ManualCast>()
+            .init_resource::<UnitDrag>()
+            .init_resource::<placement::PlacementCursor>()
             .add_observer(game_result::handle_wave_complete)
             .add_observer(game_result::handle_game_over)
             .add_observer(wave::handle_bomb_damage)
             .add_observer(damage_popup::spawn_damage_popup)
+            .add_observer(damage_popup::spawn_heal_popup)
+            .add_observer(damage_popup::spawn_synergy_banner)
+            .add_observer(projectile::handle_projectile_hit)
+            .add_observer(knockback::handle_knockback)
+            .add_observer(chain_lightning::handle_chain_lightning)
+            .add_observer(combat::handle_burst_attack)
+            .add_observer(combat::handle_manual_cast)
+            .add_observer(synergy::handle_synergy_activation)
+            .add_observer(experience::handle_experience_gain)
             .add_observer(placement::handle_unit_move)
+            .add_observer(placement::handle_sell_unit)
             .add_systems(Startup, hex_grid::setup_battle_grid)
             .add_systems(
                 Update,
                 (
                     wave::wave_spawner_system,
+                    wave::materialize_spawn_telegraphs,
+                    wave::summoner_system,
+                    wave::boss_obstacle_system,
                     wave::bomb_countdown_system,
                     wave::check_wave_complete_system,
-                    combat::targeting_system,
-                    combat::movement_system,
-                    combat::attack_system,
-                    combat::ability_system,
-                    combat::death_system,
+                    step::combat_systems(),
                     combat::despawn_attack_lines,
                     unit::spawn_health_bars,
                     unit::update_health_bars,
+                    unit::update_shield_bars,
+                    unit::update_mana_bars,
+                    unit::update_cast_ready,
+                    unit::animate_cast_rings,
+                    experience::update_level_labels,
                     synergy::update_synergies,
                     synergy::apply_synergy_bonuses,
+                    game_result::enemy_reach_base_system,
                     game_result::check_game_result,
                     damage_popup::animate_damage_popup,
                 )
                     .chain()
                     .run_if(in_state(GameState::Playing)),
             )
+            .add_systems(Update, game_result::save_persistent_stats)
+            .add_systems(
+                Update,
+                combat::manual_cast_input_system
+                    .run_if(in_state(GameState::Playing)),
+            )
             .add_systems(
                 Update,
                 wave::animate_bomb_explosion
@@ -66,7 +115,7 @@ impl Plugin for BattlePlugin {
             )
             .add_systems(
                 Update,
-                combat::buff_timer_system
+                knockback::animate_knockback
                     .run_if(in_state(GameState::Playing)),
             )
             .add_systems(
@@ -74,16 +123,39 @@ impl Plugin for BattlePlugin {
                 wave::wave_break_timer_system
                     .run_if(in_state(GameState::Playing)),
             )
+            .add_systems(
+                Update,
+                (
+                    debug_overlay::toggle_hex_debug_overlay,
+                    debug_overlay::sync_hex_coord_labels,
+                )
+                    .chain(),
+            )
+            .add_systems(
+                Update,
+                (range_ring::toggle_range_rings, range_ring::sync_range_rings)
+                    .chain()
+                    .run_if(in_state(GameState::Playing)),
+            )
             // WaveBreak placement systems
             .add_systems(
                 Update,
                 (
                     placement::mark_units_selectable,
                     placement::placement_input_system,
+                    placement::sell_input_system,
+                    placement::cycle_targeting_mode_system,
+                    (placement::update_placement_cursor, placement::drag_placement_system)
+                        .chain()
+                        .run_if(in_state(PhaseState::WaveBreak)),
+                    placement::cancel_drag_outside_wave_break,
+                    placement::unit_tooltip_system,
                     placement::spawn_movement_highlights,
+                    placement::spawn_attack_range_highlights,
                     placement::update_selected_visual,
                     placement::restore_deselected_visual,
                     placement::despawn_movement_highlights,
+                    placement::despawn_attack_range_highlights,
                     placement::unmark_units_selectable,
                 )
                     .chain()
@@ -3,9 +3,20 @@
 //! During Wave Break, players can reposition their units by:
 //! 1. Clicking a friendly unit to select it
 //! 2. Clicking an empty hex to move the selected unit
+//!
+//! With `DragPreview` enabled, a unit can also be dragged: a ghost snaps to the
+//! hex under the cursor (red when the drop would be rejected) and the move is
+//! made on release.
+//!
+//! The selected unit's attack range is shaded with `AttackRangeHighlight` overlays.
+//!
+//! Hovering any unit shows a `UnitTooltip` panel with its stats next to the cursor.
+//!
+//! A selected unit can be sold with the `sell` binding or by right-clicking it, refunding
+//! `SELL_REFUND_PER_STAR` gold per star. The `targeting_mode` binding cycles its `TargetingMode`.
 
 use crate::prelude::*;
-use super::{Unit, Team, BattleGrid, HexPosition};
+use super::{Unit, UnitType, StarRank, UnitStats, Team, TargetingMode, BattleGrid, HexPosition, ActiveSynergies, BattleStats};
 
 // ============================================================
 // Components
@@ -23,6 +34,60 @@ pub struct SelectableUnit;
 #[derive(Component)]
 pub struct MovementHighlight;
 
+/// Translucent overlay on a hex within the selected unit's attack range
+#[derive(Component)]
+pub struct AttackRangeHighlight;
+
+/// Ghost sprite that follows the cursor while a unit is being dragged
+#[derive(Component)]
+pub struct DragGhost;
+
+/// Stats panel for the unit under the cursor
+#[derive(Component)]
+pub struct UnitTooltip {
+    pub unit: Entity,
+}
+
+// ============================================================
+// Resources
+// ============================================================
+
+/// Drag placement with a snapped ghost preview. On by default; click-select then
+/// click-destination keeps working alongside it.
+#[derive(Resource)]
+pub struct DragPreview {
+    pub enabled: bool,
+}
+
+impl Default for DragPreview {
+    fn default() -> Self {
+        Self { enabled: true }
+    }
+}
+
+/// Unit currently being dragged and the hex it was picked up from
+#[derive(Resource, Default)]
+pub struct UnitDrag {
+    pub dragging: Option<(Entity, HexPosition)>,
+}
+
+/// Cursor position in world space, refreshed each frame during WaveBreak
+#[derive(Resource, Default)]
+pub struct PlacementCursor(pub Option<Vec2>);
+
+const GHOST_VALID_COLOR: Color = Color::srgba(0.3, 0.9, 0.4, 0.5);
+const GHOST_INVALID_COLOR: Color = Color::srgba(1.0, 0.2, 0.2, 0.5);
+const GHOST_SIZE: f32 = 40.0;
+/// Fill for hexes within the selected unit's attack range
+const ATTACK_RANGE_COLOR: Color = Color::srgba(0.9, 0.3, 0.2, 0.2);
+
+/// Screen-space gap between the cursor and the tooltip's top-left corner
+const TOOLTIP_OFFSET: Vec2 = Vec2::new(16.0, 16.0);
+const TOOLTIP_BACKGROUND: Color = Color::srgba(0.05, 0.05, 0.1, 0.85);
+
+/// Gold returned per star of a sold unit
+pub const SELL_REFUND_PER_STAR: u32 = 1;
+
 // ============================================================
 // Events
 // ============================================================
@@ -40,6 +105,12 @@ pub struct UnitMoveEvent {
     pub target_pos: HexPosition,
 }
 
+/// Sell a player unit: it leaves the field and refunds gold
+#[derive(Event)]
+pub struct SellUnitEvent {
+    pub entity: Entity,
+}
+
 // ============================================================
 // Systems
 // ============================================================
@@ -130,6 +201,74 @@ pub fn handle_unit_move(
     }
 }
 
+/// Sell the selected unit with the `sell` binding, or by right-clicking it
+pub fn sell_input_system(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    bindings: Res<KeyBindings>,
+    mouse_button: Res<ButtonInput<MouseButton>>,
+    windows: Query<&Window>,
+    camera: Query<(&Camera, &GlobalTransform)>,
+    grid: Res<BattleGrid>,
+    current_phase: Res<State<PhaseState>>,
+    selected_query: Query<(Entity, &HexPosition), (With<Selected>, With<SelectableUnit>)>,
+    mut commands: Commands,
+) {
+    if *current_phase.get() != PhaseState::WaveBreak {
+        return;
+    }
+    let Ok((entity, unit_pos)) = selected_query.get_single() else { return };
+
+    let right_clicked_unit = mouse_button.just_pressed(MouseButton::Right)
+        && get_cursor_world_position(&windows, &camera)
+            .is_some_and(|world_pos| grid.pixel_to_axial(world_pos) == *unit_pos);
+    if keyboard.just_pressed(bindings.sell) || right_clicked_unit {
+        commands.trigger(SellUnitEvent { entity });
+    }
+}
+
+/// Cycle the selected unit's targeting mode with the `targeting_mode` binding
+pub fn cycle_targeting_mode_system(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    bindings: Res<KeyBindings>,
+    current_phase: Res<State<PhaseState>>,
+    selected_query: Query<(Entity, Option<&TargetingMode>), (With<Selected>, With<SelectableUnit>)>,
+    mut commands: Commands,
+) {
+    if *current_phase.get() != PhaseState::WaveBreak || !keyboard.just_pressed(bindings.targeting_mode) {
+        return;
+    }
+    for (entity, mode) in selected_query.iter() {
+        let next = mode.copied().unwrap_or_default().next();
+        info!("Targeting mode: {}", next.label());
+        commands.entity(entity).insert(next);
+    }
+}
+
+/// Despawn a sold unit (health/mana bars included) and free its hex.
+/// Synergies follow on their own: the census is rebuilt from the units left next tick.
+pub fn handle_sell_unit(
+    trigger: Trigger<SellUnitEvent>,
+    mut grid: ResMut<BattleGrid>,
+    mut gold: ResMut<Gold>,
+    mut drag: ResMut<UnitDrag>,
+    units: Query<(&HexPosition, &StarRank, &Team), With<Unit>>,
+    mut commands: Commands,
+) {
+    let entity = trigger.event().entity;
+    let Ok((pos, star_rank, team)) = units.get(entity) else { return };
+    if *team != Team::Player {
+        return;
+    }
+
+    grid.remove_unit(pos);
+    gold.earn(star_rank.0 as u32 * SELL_REFUND_PER_STAR);
+    if drag.dragging.is_some_and(|(dragged, _)| dragged == entity) {
+        drag.dragging = None;
+    }
+    commands.entity(entity).remove::<(Selected, SelectableUnit)>();
+    commands.entity(entity).despawn_recursive();
+}
+
 /// System to spawn movement highlights for selected unit
 pub fn spawn_movement_highlights(
     selected_query: Query<&HexPosition, (With<Selected>, Added<Selected>)>,
@@ -176,6 +315,59 @@ pub fn despawn_movement_highlights(
     }
 }
 
+/// Hexes on the grid that a unit at `center` can hit with `range`, excluding its own
+pub fn attack_range_hexes(grid: &BattleGrid, center: &HexPosition, range: i32) -> Vec<HexPosition> {
+    grid.valid_positions()
+        .into_iter()
+        .filter(|pos| pos != center && center.distance(pos) <= range)
+        .collect()
+}
+
+/// System to shade the attack range of a newly selected unit, replacing any previous overlay
+pub fn spawn_attack_range_highlights(
+    selected_query: Query<(&HexPosition, &UnitStats), Added<Selected>>,
+    highlights: Query<Entity, With<AttackRangeHighlight>>,
+    grid: Res<BattleGrid>,
+    current_phase: Res<State<PhaseState>>,
+    mut commands: Commands,
+) {
+    if *current_phase.get() != PhaseState::WaveBreak || selected_query.is_empty() {
+        return;
+    }
+
+    for entity in highlights.iter() {
+        commands.entity(entity).despawn();
+    }
+    for (selected_pos, stats) in selected_query.iter() {
+        for pos in attack_range_hexes(&grid, selected_pos, stats.attack_range) {
+            commands.spawn((
+                AttackRangeHighlight,
+                Sprite {
+                    color: ATTACK_RANGE_COLOR,
+                    custom_size: Some(Vec2::splat(50.0)),
+                    ..default()
+                },
+                // Under the movement highlights so both stay readable
+                Transform::from_translation(grid.axial_to_pixel(&pos).extend(0.4)),
+            ));
+        }
+    }
+}
+
+/// System to despawn the attack range overlay when selection clears or WaveBreak ends
+pub fn despawn_attack_range_highlights(
+    highlights: Query<Entity, With<AttackRangeHighlight>>,
+    selected_query: Query<(), With<Selected>>,
+    current_phase: Res<State<PhaseState>>,
+    mut commands: Commands,
+) {
+    if selected_query.is_empty() || *current_phase.get() != PhaseState::WaveBreak {
+        for entity in highlights.iter() {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
 /// System to update selected unit visual (highlight effect)
 pub fn update_selected_visual(
     mut selected_units: Query<&mut Sprite, Added<Selected>>,
@@ -247,16 +439,214 @@ pub fn unmark_units_selectable(
     }
 }
 
+/// Track the cursor in world space for the drag preview
+pub fn update_placement_cursor(
+    windows: Query<&Window>,
+    camera: Query<(&Camera, &GlobalTransform)>,
+    drag_preview: Res<DragPreview>,
+    mut cursor: ResMut<PlacementCursor>,
+) {
+    cursor.0 = if drag_preview.enabled {
+        get_cursor_world_position(&windows, &camera)
+    } else {
+        None
+    };
+}
+
+/// Press on a selectable unit starts a drag, the ghost follows the snapped hex,
+/// release drops the unit there (or cancels on an invalid hex)
+pub fn drag_placement_system(
+    mut commands: Commands,
+    mouse_button: Res<ButtonInput<MouseButton>>,
+    cursor: Res<PlacementCursor>,
+    grid: Res<BattleGrid>,
+    mut drag: ResMut<UnitDrag>,
+    selectable_units: Query<(Entity, &HexPosition), (With<SelectableUnit>, With<Unit>)>,
+    mut ghosts: Query<(Entity, &mut Transform, &mut Sprite), With<DragGhost>>,
+) {
+    // Cursor is only tracked while the preview is enabled
+    let Some(cursor_pos) = cursor.0 else { return };
+    let target = drag_snap_target(&grid, cursor_pos);
+
+    if drag.dragging.is_none() && mouse_button.just_pressed(MouseButton::Left) {
+        let Some((entity, origin)) = selectable_units.iter().find(|(_, pos)| **pos == target) else { return };
+        drag.dragging = Some((entity, *origin));
+        commands.spawn((
+            DragGhost,
+            Sprite {
+                color: ghost_color(true),
+                custom_size: Some(Vec2::splat(GHOST_SIZE)),
+                ..default()
+            },
+            Transform::from_translation(grid.axial_to_pixel(origin).extend(5.0)),
+        ));
+        return;
+    }
+
+    let Some((entity, origin)) = drag.dragging else { return };
+    let valid = is_valid_drop(&grid, &target, &origin);
+
+    if mouse_button.just_released(MouseButton::Left) {
+        if valid && target != origin {
+            commands.trigger(UnitMoveEvent { entity, target_pos: target });
+        }
+        drag.dragging = None;
+        for (ghost, ..) in ghosts.iter() {
+            commands.entity(ghost).despawn();
+        }
+        return;
+    }
+
+    for (_, mut transform, mut sprite) in ghosts.iter_mut() {
+        transform.translation = grid.axial_to_pixel(&target).extend(transform.translation.z);
+        sprite.color = ghost_color(valid);
+    }
+}
+
+/// Drop any in-progress drag and its ghost when leaving WaveBreak
+pub fn cancel_drag_outside_wave_break(
+    mut commands: Commands,
+    current_phase: Res<State<PhaseState>>,
+    mut drag: ResMut<UnitDrag>,
+    ghosts: Query<Entity, With<DragGhost>>,
+) {
+    if *current_phase.get() == PhaseState::WaveBreak {
+        return;
+    }
+    drag.dragging = None;
+    for entity in ghosts.iter() {
+        commands.entity(entity).despawn();
+    }
+}
+
+/// Show a stats panel for the unit under the cursor during WaveBreak. The panel is
+/// rebuilt when the hovered unit or its stats change and otherwise just follows the
+/// cursor; it is removed when nothing is hovered or the phase ends.
+pub fn unit_tooltip_system(
+    mut commands: Commands,
+    windows: Query<&Window>,
+    camera: Query<(&Camera, &GlobalTransform)>,
+    grid: Res<BattleGrid>,
+    current_phase: Res<State<PhaseState>>,
+    synergies: Res<ActiveSynergies>,
+    units: Query<(&UnitType, &StarRank, Ref<UnitStats>, &Team), With<Unit>>,
+    mut tooltips: Query<(Entity, &UnitTooltip, &mut Node)>,
+) {
+    let screen_cursor = windows.get_single().ok().and_then(|window| window.cursor_position());
+    let hovered = if *current_phase.get() == PhaseState::WaveBreak {
+        get_cursor_world_position(&windows, &camera)
+            .and_then(|world_pos| hovered_unit(&grid, world_pos))
+            .and_then(|entity| units.get(entity).ok().map(|unit| (entity, unit)))
+            .zip(screen_cursor)
+    } else {
+        None
+    };
+
+    let Some(((entity, (unit_type, star_rank, stats, team)), cursor)) = hovered else {
+        for (tooltip, ..) in tooltips.iter() {
+            commands.entity(tooltip).despawn_recursive();
+        }
+        return;
+    };
+
+    let position = cursor + TOOLTIP_OFFSET;
+    let mut up_to_date = false;
+    for (tooltip, shown, mut node) in tooltips.iter_mut() {
+        if shown.unit == entity && !stats.is_changed() {
+            node.left = Val::Px(position.x);
+            node.top = Val::Px(position.y);
+            up_to_date = true;
+        } else {
+            commands.entity(tooltip).despawn_recursive();
+        }
+    }
+    if up_to_date {
+        return;
+    }
+
+    let mut lines = vec![
+        format!("{} {}-Star", BattleStats::unit_type_name(Some(unit_type.0)), star_rank.0),
+        format!("HP: {:.0}/{:.0}", stats.health, stats.max_health),
+        format!("ATK: {:.0}", stats.attack),
+    ];
+    if *team == Team::Player {
+        lines.push(format!("Synergy: {}", synergies.get_level(unit_type.0).label()));
+    }
+
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                left: Val::Px(position.x),
+                top: Val::Px(position.y),
+                padding: UiRect::all(Val::Px(6.0)),
+                flex_direction: FlexDirection::Column,
+                ..default()
+            },
+            BackgroundColor(TOOLTIP_BACKGROUND),
+            GlobalZIndex(10),
+            UnitTooltip { unit: entity },
+        ))
+        .with_children(|parent| {
+            for (i, line) in lines.into_iter().enumerate() {
+                parent.spawn((
+                    Text::new(line),
+                    TextFont {
+                        font_size: 14.0,
+                        ..default()
+                    },
+                    TextColor(if i == 0 { unit_type.0.color() } else { Color::WHITE }),
+                ));
+            }
+        });
+}
+
 // ============================================================
 // Helper Functions
 // ============================================================
 
+/// Unit standing on the hex under `cursor`, if any
+pub fn hovered_unit(grid: &BattleGrid, cursor: Vec2) -> Option<Entity> {
+    let hex = grid.pixel_to_axial(cursor);
+    grid.units.get(&hex).copied()
+}
+
+/// Hex the ghost snaps to: the one under the cursor, pulled back onto the grid
+/// when the cursor strays past its edge
+pub fn drag_snap_target(grid: &BattleGrid, cursor: Vec2) -> HexPosition {
+    let hex = grid.pixel_to_axial(cursor);
+    if grid.is_valid_position(&hex) {
+        return hex;
+    }
+    grid.valid_positions()
+        .into_iter()
+        .min_by(|a, b| {
+            let da = grid.axial_to_pixel(a).distance_squared(cursor);
+            let db = grid.axial_to_pixel(b).distance_squared(cursor);
+            da.total_cmp(&db)
+        })
+        .unwrap_or(hex)
+}
+
+/// A drop is allowed on a free hex in the player zone, or back where the unit started
+pub fn is_valid_drop(grid: &BattleGrid, target: &HexPosition, origin: &HexPosition) -> bool {
+    target == origin || (grid.is_player_zone(target) && !grid.is_occupied(target))
+}
+
+pub fn ghost_color(valid: bool) -> Color {
+    if valid {
+        GHOST_VALID_COLOR
+    } else {
+        GHOST_INVALID_COLOR
+    }
+}
+
 fn get_cursor_world_position(
     windows: &Query<&Window>,
     camera: &Query<(&Camera, &GlobalTransform)>,
 ) -> Option<Vec2> {
-    let window = windows.single();
-    let (camera, camera_transform) = camera.single();
+    let window = windows.get_single().ok()?;
+    let (camera, camera_transform) = camera.get_single().ok()?;
 
     window
         .cursor_position()
@@ -309,4 +699,274 @@ mod tests {
         assert_eq!(event.target_pos.q, 2);
         assert_eq!(event.target_pos.r, -1);
     }
+
+    #[test]
+    fn test_drag_snap_target_is_hex_under_cursor() {
+        let grid = BattleGrid::new();
+        let hex = HexPosition::new(1, -1);
+        let near_center = grid.axial_to_pixel(&hex) + Vec2::new(5.0, -4.0);
+        assert_eq!(drag_snap_target(&grid, near_center), hex);
+    }
+
+    #[test]
+    fn test_drag_snap_target_pulls_off_grid_cursor_to_edge() {
+        let grid = BattleGrid::new();
+        let edge = HexPosition::new(BATTLE_GRID_COLS / 2, 0);
+        let beyond = grid.axial_to_pixel(&edge) + Vec2::new(HEX_SIZE * 3.0, 0.0);
+        let target = drag_snap_target(&grid, beyond);
+        assert!(grid.is_valid_position(&target));
+        assert_eq!(target.q, edge.q, "lands on the right-hand edge, got {:?}", target);
+    }
+
+    #[test]
+    fn test_is_valid_drop_rules() {
+        let mut grid = BattleGrid::new();
+        let origin = HexPosition::new(0, 0);
+        let occupied = HexPosition::new(1, 0);
+        grid.place_unit(origin, Entity::from_raw(1));
+        grid.place_unit(occupied, Entity::from_raw(2));
+
+        assert!(is_valid_drop(&grid, &HexPosition::new(-1, 0), &origin), "free player hex");
+        assert!(is_valid_drop(&grid, &origin, &origin), "dropping back in place");
+        assert!(!is_valid_drop(&grid, &occupied, &origin), "occupied");
+        assert!(!is_valid_drop(&grid, &HexPosition::new(0, 1), &origin), "enemy zone");
+    }
+
+    #[test]
+    fn test_hovered_unit_looks_up_hex_under_cursor() {
+        let mut grid = BattleGrid::new();
+        let unit = Entity::from_raw(7);
+        let pos = HexPosition::new(1, -1);
+        grid.place_unit(pos, unit);
+
+        let center = grid.axial_to_pixel(&pos);
+        assert_eq!(hovered_unit(&grid, center), Some(unit));
+        assert_eq!(hovered_unit(&grid, center + Vec2::new(8.0, -6.0)), Some(unit), "anywhere inside the hex");
+        assert_eq!(hovered_unit(&grid, grid.axial_to_pixel(&HexPosition::new(0, -1))), None, "empty neighbour");
+        assert_eq!(hovered_unit(&grid, center + Vec2::new(1000.0, 0.0)), None, "off the grid");
+    }
+
+    #[test]
+    fn test_drag_preview_is_on_by_default() {
+        assert!(DragPreview::default().enabled);
+    }
+
+    #[test]
+    fn test_ghost_color_by_validity() {
+        assert_eq!(ghost_color(true), GHOST_VALID_COLOR);
+        assert_eq!(ghost_color(false), GHOST_INVALID_COLOR);
+        assert_ne!(ghost_color(true), ghost_color(false));
+    }
+
+    fn setup_drag_app() -> (App, Entity) {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .insert_resource(BattleGrid::new())
+            .init_resource::<UnitDrag>()
+            .init_resource::<PlacementCursor>()
+            .init_resource::<ButtonInput<MouseButton>>()
+            .add_observer(handle_unit_move)
+            .add_systems(Update, drag_placement_system);
+        let origin = HexPosition::new(0, 0);
+        let unit = app
+            .world_mut()
+            .spawn((Unit, SelectableUnit, origin, Transform::default()))
+            .id();
+        app.world_mut().resource_mut::<BattleGrid>().place_unit(origin, unit);
+        (app, unit)
+    }
+
+    /// Press on the unit, then release with the cursor over `drop`
+    fn drag_unit_to(app: &mut App, drop: HexPosition) {
+        let grid = BattleGrid::new();
+        app.world_mut().resource_mut::<PlacementCursor>().0 = Some(grid.axial_to_pixel(&HexPosition::new(0, 0)));
+        app.world_mut().resource_mut::<ButtonInput<MouseButton>>().press(MouseButton::Left);
+        app.update();
+        assert_eq!(ghost_count(app), 1, "ghost appears on pickup");
+
+        app.world_mut().resource_mut::<PlacementCursor>().0 = Some(grid.axial_to_pixel(&drop));
+        let mut mouse = app.world_mut().resource_mut::<ButtonInput<MouseButton>>();
+        mouse.clear();
+        mouse.release(MouseButton::Left);
+        app.update();
+    }
+
+    fn ghost_count(app: &mut App) -> usize {
+        let world = app.world_mut();
+        world.query::<&DragGhost>().iter(world).count()
+    }
+
+    #[test]
+    fn test_drop_on_valid_hex_moves_unit_and_despawns_ghost() {
+        let (mut app, unit) = setup_drag_app();
+        let drop = HexPosition::new(-1, 0);
+
+        drag_unit_to(&mut app, drop);
+
+        assert_eq!(*app.world().get::<HexPosition>(unit).unwrap(), drop);
+        assert_eq!(ghost_count(&mut app), 0);
+        assert!(app.world().resource::<UnitDrag>().dragging.is_none());
+    }
+
+    #[test]
+    fn test_drop_on_invalid_hex_cancels() {
+        let (mut app, unit) = setup_drag_app();
+
+        drag_unit_to(&mut app, HexPosition::new(0, 1));
+
+        assert_eq!(*app.world().get::<HexPosition>(unit).unwrap(), HexPosition::new(0, 0));
+        assert!(app.world().resource::<BattleGrid>().is_occupied(&HexPosition::new(0, 0)));
+        assert_eq!(ghost_count(&mut app), 0);
+    }
+
+    fn setup_sell_app() -> App {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .insert_resource(BattleGrid::new())
+            .init_resource::<Gold>()
+            .init_resource::<UnitDrag>()
+            .add_observer(handle_sell_unit);
+        app.world_mut().flush();
+        app
+    }
+
+    fn spawn_sellable(app: &mut App, pos: HexPosition, team: Team, star_rank: u8) -> (Entity, Entity) {
+        let unit = app
+            .world_mut()
+            .spawn((Unit, pos, team, StarRank(star_rank), Selected, SelectableUnit))
+            .id();
+        let bar = app.world_mut().spawn(Transform::default()).set_parent(unit).id();
+        app.world_mut().resource_mut::<BattleGrid>().place_unit(pos, unit);
+        (unit, bar)
+    }
+
+    #[test]
+    fn test_selling_removes_grid_entry_and_entity() {
+        let mut app = setup_sell_app();
+        let pos = HexPosition::new(1, -1);
+        let (unit, bar) = spawn_sellable(&mut app, pos, Team::Player, 2);
+
+        app.world_mut().trigger(SellUnitEvent { entity: unit });
+        app.world_mut().flush();
+
+        assert!(app.world().get_entity(unit).is_err());
+        assert!(app.world().get_entity(bar).is_err(), "health bar children go with it");
+        assert!(!app.world().resource::<BattleGrid>().is_occupied(&pos));
+        assert_eq!(*app.world().resource::<Gold>(), Gold(2 * SELL_REFUND_PER_STAR));
+    }
+
+    #[test]
+    fn test_enemy_units_cannot_be_sold() {
+        let mut app = setup_sell_app();
+        let pos = HexPosition::new(0, 1);
+        let (enemy, _) = spawn_sellable(&mut app, pos, Team::Enemy, 1);
+
+        app.world_mut().trigger(SellUnitEvent { entity: enemy });
+        app.world_mut().flush();
+
+        assert!(app.world().get_entity(enemy).is_ok());
+        assert!(app.world().resource::<BattleGrid>().is_occupied(&pos));
+        assert_eq!(*app.world().resource::<Gold>(), Gold(0));
+    }
+
+    #[test]
+    fn test_targeting_mode_key_cycles_selected_unit() {
+        use bevy::state::app::StatesPlugin;
+
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, StatesPlugin))
+            .insert_state(PhaseState::WaveBreak)
+            .init_resource::<ButtonInput<KeyCode>>()
+            .insert_resource(KeyBindings { targeting_mode: KeyCode::KeyG, ..default() })
+            .add_systems(Update, cycle_targeting_mode_system);
+        let selected = app.world_mut().spawn((Unit, Selected, SelectableUnit)).id();
+        let other = app.world_mut().spawn((Unit, SelectableUnit)).id();
+
+        let mut press = |app: &mut App| {
+            app.world_mut().resource_mut::<ButtonInput<KeyCode>>().press(KeyCode::KeyG);
+            app.update();
+            app.world_mut().resource_mut::<ButtonInput<KeyCode>>().reset_all();
+            *app.world().get::<TargetingMode>(selected).unwrap()
+        };
+
+        assert_eq!(press(&mut app), TargetingMode::LowestHealth);
+        assert_eq!(press(&mut app), TargetingMode::HighestAttack);
+        assert_eq!(press(&mut app), TargetingMode::Nearest);
+        assert!(app.world().get::<TargetingMode>(other).is_none());
+    }
+
+    #[test]
+    fn test_attack_range_hexes_within_range() {
+        let grid = BattleGrid::new();
+        let center = HexPosition::new(0, 0);
+
+        let melee = attack_range_hexes(&grid, &center, 1);
+        assert_eq!(melee.len(), 6);
+        assert!(center.neighbors().iter().all(|pos| melee.contains(pos)));
+        assert!(!melee.contains(&center), "the unit's own hex isn't shaded");
+
+        let ranged = attack_range_hexes(&grid, &center, 3);
+        assert!(ranged.iter().all(|pos| center.distance(pos) <= 3 && grid.is_valid_position(pos)));
+        assert!(ranged.contains(&HexPosition::new(3, 0)));
+        assert!(!ranged.contains(&HexPosition::new(3, 1)), "distance 4 is out of range");
+
+        assert!(attack_range_hexes(&grid, &center, 0).is_empty());
+    }
+
+    #[test]
+    fn test_attack_range_hexes_clipped_to_grid() {
+        let grid = BattleGrid::new();
+        let corner = HexPosition::new(-BATTLE_GRID_COLS / 2, -BATTLE_GRID_ROWS / 2);
+
+        let hexes = attack_range_hexes(&grid, &corner, 1);
+
+        let expected: Vec<HexPosition> = corner
+            .neighbors()
+            .into_iter()
+            .filter(|pos| grid.is_valid_position(pos))
+            .collect();
+        assert_eq!(hexes.len(), expected.len());
+        assert!(hexes.len() < 6);
+        assert!(hexes.iter().all(|pos| expected.contains(pos)));
+    }
+
+    fn range_highlight_count(app: &mut App) -> usize {
+        let world = app.world_mut();
+        world.query::<&AttackRangeHighlight>().iter(world).count()
+    }
+
+    #[test]
+    fn test_attack_range_overlay_follows_selection() {
+        use bevy::state::app::StatesPlugin;
+
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, StatesPlugin))
+            .insert_state(PhaseState::WaveBreak)
+            .insert_resource(BattleGrid::new())
+            .add_systems(
+                Update,
+                (spawn_attack_range_highlights, despawn_attack_range_highlights).chain(),
+            );
+        let stats = UnitStats { attack_range: 1, ..default() };
+        let unit = app
+            .world_mut()
+            .spawn((Unit, SelectableUnit, HexPosition::new(0, 0), stats, Selected))
+            .id();
+
+        app.update();
+        assert_eq!(range_highlight_count(&mut app), 6);
+
+        app.world_mut().entity_mut(unit).remove::<Selected>();
+        app.update();
+        assert_eq!(range_highlight_count(&mut app), 0, "cleared with the selection");
+
+        app.world_mut().entity_mut(unit).insert(Selected);
+        app.update();
+        assert_eq!(range_highlight_count(&mut app), 6);
+
+        app.world_mut().resource_mut::<NextState<PhaseState>>().set(PhaseState::Idle);
+        app.update();
+        app.update();
+        assert_eq!(range_highlight_count(&mut app), 0, "cleared when WaveBreak ends");
+    }
 }
@@ -0,0 +1,282 @@
+use crate::prelude::*;
+use super::{Unit, DamageType, UnitStats, Shield, take_shielded_damage, HexPosition, BattleGrid, Team, LastHitBy, DamagePopupEvent, BattleStats};
+use super::combat::big_crit_slowmo;
+use super::experience::{ExperienceGainEvent, XP_PER_DAMAGE};
+
+/// Seconds a ranged shot takes to reach its target
+pub const PROJECTILE_TRAVEL_TIME: f32 = 0.25;
+
+const PROJECTILE_COLOR: Color = Color::srgb(1.0, 0.85, 0.3);
+const PROJECTILE_SIZE: f32 = 8.0;
+
+/// Ranger (Green) and Mage (Purple) attacks travel as projectiles instead of landing instantly
+pub fn fires_projectile(unit_type: TileType) -> bool {
+    matches!(unit_type, TileType::Green | TileType::Purple)
+}
+
+/// Shot in flight. Aimed at the hex the target stood on when fired;
+/// fizzles if the target dies or leaves that hex before impact.
+#[derive(Component)]
+pub struct Projectile {
+    pub attacker: Entity,
+    pub target: Entity,
+    pub target_pos: HexPosition,
+    pub from: Vec2,
+    pub to: Vec2,
+    pub damage: f32,
+    pub is_critical: bool,
+    pub team: Team,
+    pub unit_type: TileType,
+    pub timer: Timer,
+}
+
+/// Fired when a projectile reaches a still-valid target
+#[derive(Event)]
+pub struct ProjectileHitEvent {
+    pub attacker: Entity,
+    pub target: Entity,
+    pub damage: f32,
+    pub is_critical: bool,
+    pub team: Team,
+    pub unit_type: TileType,
+}
+
+/// Pending ranged attack, resolved into a `Projectile` entity by `spawn_projectile`
+pub struct ProjectileShot {
+    pub attacker: Entity,
+    pub from: HexPosition,
+    pub target: Entity,
+    pub target_pos: HexPosition,
+    pub damage: f32,
+    pub is_critical: bool,
+    pub team: Team,
+    pub unit_type: TileType,
+}
+
+pub fn spawn_projectile(commands: &mut Commands, grid: &BattleGrid, shot: ProjectileShot) {
+    let from = grid.axial_to_pixel(&shot.from);
+    let to = grid.axial_to_pixel(&shot.target_pos);
+
+    commands.spawn((
+        Projectile {
+            attacker: shot.attacker,
+            target: shot.target,
+            target_pos: shot.target_pos,
+            from,
+            to,
+            damage: shot.damage,
+            is_critical: shot.is_critical,
+            team: shot.team,
+            unit_type: shot.unit_type,
+            timer: Timer::from_seconds(PROJECTILE_TRAVEL_TIME, TimerMode::Once),
+        },
+        Sprite {
+            color: PROJECTILE_COLOR,
+            custom_size: Some(Vec2::splat(PROJECTILE_SIZE)),
+            ..default()
+        },
+        Transform::from_translation(from.extend(10.0)),
+    ));
+}
+
+pub fn projectile_movement_system(
+    mut commands: Commands,
+    time: ScaledTime,
+    targets: Query<(&HexPosition, &UnitStats), With<Unit>>,
+    mut projectiles: Query<(Entity, &mut Projectile, &mut Transform)>,
+) {
+    let delta = time.delta();
+    for (entity, mut projectile, mut transform) in projectiles.iter_mut() {
+        let target_still_there = targets
+            .get(projectile.target)
+            .is_ok_and(|(pos, stats)| *pos == projectile.target_pos && !stats.is_dead());
+        if !target_still_there {
+            commands.entity(entity).despawn_recursive();
+            continue;
+        }
+
+        projectile.timer.tick(delta);
+        let position = projectile.from.lerp(projectile.to, projectile.timer.fraction());
+        transform.translation = position.extend(transform.translation.z);
+
+        if projectile.timer.finished() {
+            commands.trigger(ProjectileHitEvent {
+                attacker: projectile.attacker,
+                target: projectile.target,
+                damage: projectile.damage,
+                is_critical: projectile.is_critical,
+                team: projectile.team,
+                unit_type: projectile.unit_type,
+            });
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
+
+pub fn handle_projectile_hit(
+    trigger: Trigger<ProjectileHitEvent>,
+    mut commands: Commands,
+    grid: Res<BattleGrid>,
+    mut battle_stats: ResMut<BattleStats>,
+    mut targets: Query<(&HexPosition, &mut UnitStats, Option<&mut Shield>), With<Unit>>,
+) {
+    let event = trigger.event();
+    let Ok((pos, mut stats, mut shield)) = targets.get_mut(event.target) else { return };
+
+    take_shielded_damage(&mut stats, shield.as_deref_mut(), event.damage, DamageType::for_unit(event.unit_type));
+    if shield.is_some_and(|shield| shield.is_depleted()) {
+        commands.entity(event.target).remove::<Shield>();
+    }
+    commands.entity(event.target).insert(LastHitBy(event.attacker));
+    if let Some(slowmo) = big_crit_slowmo(event.damage, event.is_critical) {
+        commands.trigger(slowmo);
+    }
+    commands.trigger(DamagePopupEvent {
+        position: grid.axial_to_pixel(pos).extend(0.0),
+        damage: event.damage as i32,
+        is_critical: event.is_critical,
+        is_poison: false,
+        is_execute: false,
+    });
+
+    match event.team {
+        Team::Enemy => battle_stats.record_enemy_damage(event.unit_type, event.damage),
+        Team::Player => {
+            battle_stats.record_ally_damage(event.unit_type, event.damage);
+            commands.trigger(ExperienceGainEvent { unit: event.attacker, amount: event.damage * XP_PER_DAMAGE });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::time::TimeUpdateStrategy;
+    use std::time::Duration;
+
+    const STEP: f32 = 0.1;
+
+    fn setup_projectile_app() -> App {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f32(STEP)))
+            .insert_resource(BattleGrid::new())
+            .init_resource::<BattleStats>()
+            .init_resource::<TimeScale>()
+            .init_resource::<GameSpeed>()
+            .add_observer(handle_projectile_hit)
+            .add_systems(Update, projectile_movement_system);
+        app
+    }
+
+    fn spawn_target(app: &mut App, pos: HexPosition) -> Entity {
+        app.world_mut()
+            .spawn((Unit, pos, UnitStats::default(), Team::Enemy))
+            .id()
+    }
+
+    fn fire(app: &mut App, target: Entity, target_pos: HexPosition) {
+        let world = app.world_mut();
+        let grid = BattleGrid::new();
+        let mut commands = world.commands();
+        spawn_projectile(
+            &mut commands,
+            &grid,
+            ProjectileShot {
+                attacker: Entity::PLACEHOLDER,
+                from: HexPosition::new(-2, 0),
+                target,
+                target_pos,
+                damage: 10.0,
+                is_critical: false,
+                team: Team::Player,
+                unit_type: TileType::Green,
+            },
+        );
+        world.flush();
+    }
+
+    fn projectile_count(app: &mut App) -> usize {
+        let world = app.world_mut();
+        world.query::<&Projectile>().iter(world).count()
+    }
+
+    fn health(app: &App, entity: Entity) -> f32 {
+        app.world().get::<UnitStats>(entity).unwrap().health
+    }
+
+    #[test]
+    fn test_only_ranger_and_mage_fire_projectiles() {
+        assert!(fires_projectile(TileType::Green));
+        assert!(fires_projectile(TileType::Purple));
+        assert!(!fires_projectile(TileType::Red));
+        assert!(!fires_projectile(TileType::Blue));
+        assert!(!fires_projectile(TileType::Yellow));
+    }
+
+    #[test]
+    fn test_projectile_hits_after_travel_time() {
+        let mut app = setup_projectile_app();
+        let target_pos = HexPosition::new(1, 0);
+        let target = spawn_target(&mut app, target_pos);
+        fire(&mut app, target, target_pos);
+        // First update only primes the clock
+        app.update();
+
+        app.update();
+        app.update();
+        assert_eq!(health(&app, target), 100.0, "still in flight at 0.2s");
+        assert_eq!(projectile_count(&mut app), 1);
+
+        app.update();
+        assert_eq!(health(&app, target), 90.0, "lands once 0.25s have passed");
+        assert_eq!(projectile_count(&mut app), 0);
+    }
+
+    #[test]
+    fn test_projectile_moves_toward_target() {
+        let mut app = setup_projectile_app();
+        let target_pos = HexPosition::new(1, 0);
+        let target = spawn_target(&mut app, target_pos);
+        fire(&mut app, target, target_pos);
+        app.update();
+        app.update();
+
+        let world = app.world_mut();
+        let (projectile, transform) = world.query::<(&Projectile, &Transform)>().single(world);
+        let start_gap = projectile.from.distance(projectile.to);
+        let gap = transform.translation.truncate().distance(projectile.to);
+        assert!(gap < start_gap && gap > 0.0);
+    }
+
+    #[test]
+    fn test_projectile_fizzles_on_dead_target() {
+        let mut app = setup_projectile_app();
+        let target_pos = HexPosition::new(1, 0);
+        let target = spawn_target(&mut app, target_pos);
+        fire(&mut app, target, target_pos);
+        app.update();
+
+        app.world_mut().entity_mut(target).despawn();
+        app.update();
+
+        assert_eq!(projectile_count(&mut app), 0);
+    }
+
+    #[test]
+    fn test_projectile_fizzles_when_target_moves() {
+        let mut app = setup_projectile_app();
+        let target_pos = HexPosition::new(1, 0);
+        let target = spawn_target(&mut app, target_pos);
+        fire(&mut app, target, target_pos);
+        app.update();
+
+        *app.world_mut().get_mut::<HexPosition>(target).unwrap() = HexPosition::new(2, 0);
+        for _ in 0..4 {
+            app.update();
+        }
+
+        assert_eq!(projectile_count(&mut app), 0);
+        assert_eq!(health(&app, target), 100.0);
+    }
+}
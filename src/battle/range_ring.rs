@@ -0,0 +1,140 @@
+use crate::prelude::*;
+use super::{BattleGrid, Unit, UnitStats, Team};
+
+const RANGE_RING_COLOR: Color = Color::srgba(0.4, 0.8, 1.0, 0.25);
+const RANGE_RING_THICKNESS: f32 = 2.0;
+/// Just under the unit sprite
+const RANGE_RING_Z: f32 = -0.5;
+
+/// Faint attack-range ring under every player unit (off by default)
+#[derive(Resource, Default)]
+pub struct ShowRangeRings(pub bool);
+
+/// Ring child of a unit, drawn for `range`
+#[derive(Component)]
+pub struct RangeRing {
+    pub range: i32,
+}
+
+/// Ring radius in pixels for `attack_range` hexes: out to the far edge of the last
+/// hex in range. Adjacent hex centers are `sqrt(3) * hex_size` apart.
+pub fn range_ring_radius(attack_range: i32, hex_size: f32) -> f32 {
+    (attack_range.max(0) as f32 + 0.5) * 3.0_f32.sqrt() * hex_size
+}
+
+pub fn toggle_range_rings(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    bindings: Res<KeyBindings>,
+    mut show: ResMut<ShowRangeRings>,
+) {
+    if keyboard.just_pressed(bindings.range_rings) {
+        show.0 = !show.0;
+    }
+}
+
+/// Give each living player unit a ring matching its current range, and drop rings
+/// when the toggle is off. Rings are children, so they go when their unit despawns.
+pub fn sync_range_rings(
+    mut commands: Commands,
+    show: Res<ShowRangeRings>,
+    grid: Res<BattleGrid>,
+    units: Query<(Entity, &UnitStats, &Team, Option<&Children>), With<Unit>>,
+    rings: Query<(Entity, &RangeRing)>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    if !show.0 {
+        for (ring, _) in rings.iter() {
+            commands.entity(ring).despawn_recursive();
+        }
+        return;
+    }
+
+    for (entity, stats, team, children) in units.iter() {
+        let existing = children
+            .into_iter()
+            .flatten()
+            .find_map(|child| rings.get(*child).ok());
+        let wanted = (*team == Team::Player && !stats.is_dead()).then_some(stats.attack_range);
+        match (existing, wanted) {
+            (Some((_, ring)), Some(range)) if ring.range == range => continue,
+            (Some((ring, _)), _) => commands.entity(ring).despawn_recursive(),
+            (None, None) => continue,
+            (None, Some(_)) => {}
+        }
+        let Some(range) = wanted else { continue };
+
+        let outer = range_ring_radius(range, grid.hex_size);
+        commands.entity(entity).with_children(|parent| {
+            parent.spawn((
+                RangeRing { range },
+                Mesh2d(meshes.add(Annulus::new(outer - RANGE_RING_THICKNESS, outer))),
+                MeshMaterial2d(materials.add(ColorMaterial::from_color(RANGE_RING_COLOR))),
+                Transform::from_xyz(0.0, 0.0, RANGE_RING_Z),
+            ));
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_range_ring_radius_from_attack_range() {
+        let hex_size = 30.0;
+        let step = 3.0_f32.sqrt() * hex_size;
+        assert!((range_ring_radius(1, hex_size) - 1.5 * step).abs() < 1e-4, "melee covers the neighbouring hexes");
+        assert!((range_ring_radius(3, hex_size) - 3.5 * step).abs() < 1e-4);
+        assert!((range_ring_radius(0, hex_size) - 0.5 * step).abs() < 1e-4);
+        assert!((range_ring_radius(-2, hex_size) - 0.5 * step).abs() < 1e-4, "negative range is treated as zero");
+    }
+
+    fn ring_ranges(app: &mut App) -> Vec<i32> {
+        let world = app.world_mut();
+        world.query::<&RangeRing>().iter(world).map(|ring| ring.range).collect()
+    }
+
+    #[test]
+    fn test_rings_follow_toggle_range_and_death() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .init_resource::<ButtonInput<KeyCode>>()
+            .init_resource::<KeyBindings>()
+            .init_resource::<ShowRangeRings>()
+            .insert_resource(BattleGrid::new())
+            .init_resource::<Assets<Mesh>>()
+            .init_resource::<Assets<ColorMaterial>>()
+            .add_systems(Update, (toggle_range_rings, sync_range_rings).chain());
+        let range_rings_key = KeyBindings::default().range_rings;
+        let archer = app
+            .world_mut()
+            .spawn((Unit, Team::Player, UnitStats { attack_range: 3, ..default() }))
+            .id();
+        app.world_mut().spawn((Unit, Team::Enemy, UnitStats::default()));
+
+        app.update();
+        assert!(ring_ranges(&mut app).is_empty(), "off by default");
+
+        app.world_mut().resource_mut::<ButtonInput<KeyCode>>().press(range_rings_key);
+        app.update();
+        assert_eq!(ring_ranges(&mut app), vec![3], "only player units get a ring");
+        let mut keyboard = app.world_mut().resource_mut::<ButtonInput<KeyCode>>();
+        keyboard.release(range_rings_key);
+        keyboard.clear();
+
+        app.world_mut().get_mut::<UnitStats>(archer).unwrap().attack_range = 4;
+        app.update();
+        assert_eq!(ring_ranges(&mut app), vec![4], "the ring is redrawn for the new range");
+
+        app.world_mut().get_mut::<UnitStats>(archer).unwrap().health = 0.0;
+        app.update();
+        assert!(ring_ranges(&mut app).is_empty(), "dead units lose their ring");
+
+        app.world_mut().get_mut::<UnitStats>(archer).unwrap().health = 50.0;
+        app.update();
+        app.world_mut().resource_mut::<ButtonInput<KeyCode>>().press(range_rings_key);
+        app.update();
+        assert!(ring_ranges(&mut app).is_empty(), "toggling off clears every ring");
+    }
+}
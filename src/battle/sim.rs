@@ -0,0 +1,245 @@
+//! Headless battle simulation
+//!
+//! Auto-plays the battle side with no puzzle input and no rendering, for balance
+//! testing: a fixed roster is summoned at startup and the wave and combat systems
+//! run on a fixed timestep until the roster falls or `waves` waves are cleared.
+//! Nobody repositions units, so there is no wave break: waves follow the spawner's timer.
+//! The report and `BattleStats` are printed at the end. Add it next to
+//! `MinimalPlugins` (see `examples/headless_sim.rs`).
+
+use crate::prelude::*;
+use bevy::state::app::StatesPlugin;
+use bevy::time::TimeUpdateStrategy;
+use std::time::Duration;
+use crate::bridge::spawn_unit_at;
+use super::{
+    BattleGrid, WaveManager, GameResult, BaseHealth, BattleStats, UnitCensus, ActiveSynergies,
+    SynergyConfig, ManualCast, Team, combat_systems,
+};
+use super::{combat, wave, synergy, game_result, projectile, knockback, chain_lightning, experience};
+
+/// Seconds of game time each simulated frame advances
+pub const SIM_TIMESTEP: f32 = 0.05;
+/// Game-time cap so a stalemate can't spin forever
+pub const SIM_TIME_LIMIT: f32 = 3600.0;
+/// Roster summoned when none is given: one ★2 unit of every class
+pub const SIM_ROSTER: [(TileType, u8); 5] = [
+    (TileType::Red, 2),
+    (TileType::Blue, 2),
+    (TileType::Green, 2),
+    (TileType::Yellow, 2),
+    (TileType::Purple, 2),
+];
+
+#[derive(Clone)]
+pub struct HeadlessSimPlugin {
+    pub seed: u64,
+    /// Waves to clear before the run counts as survived
+    pub waves: u32,
+    /// Unit type and star rank of each player unit, placed in summon order
+    pub roster: Vec<(TileType, u8)>,
+}
+
+impl Default for HeadlessSimPlugin {
+    fn default() -> Self {
+        Self {
+            seed: 0,
+            waves: 5,
+            roster: SIM_ROSTER.to_vec(),
+        }
+    }
+}
+
+#[derive(Resource, Clone, Debug)]
+pub struct SimConfig {
+    pub seed: u64,
+    pub waves: u32,
+    pub roster: Vec<(TileType, u8)>,
+}
+
+/// Outcome of a headless run; `finished` flips once, when the run reaches a terminal state
+#[derive(Resource, Clone, Debug, Default, PartialEq)]
+pub struct SimReport {
+    pub finished: bool,
+    pub defeated: bool,
+    pub timed_out: bool,
+    pub waves_survived: u32,
+    pub base_health: f32,
+    pub sim_seconds: f32,
+}
+
+impl SimReport {
+    pub fn summary(&self, config: &SimConfig, stats: &BattleStats) -> String {
+        let result = if self.defeated {
+            "Defeat"
+        } else if self.timed_out {
+            "Timed out"
+        } else {
+            "Survived"
+        };
+        let mvp = &stats.mvp_ally;
+        let enemy = &stats.most_dangerous_enemy;
+        [
+            format!("Headless sim (seed {}): {}", config.seed, result),
+            format!("Waves survived: {}/{}", self.waves_survived, config.waves),
+            format!("Base health: {:.0}", self.base_health),
+            format!("Game time: {:.1}s", self.sim_seconds),
+            format!(
+                "MVP: {} ({} kills, {:.0} damage)",
+                BattleStats::unit_type_name(mvp.unit_type),
+                mvp.kills,
+                mvp.damage_dealt
+            ),
+            format!(
+                "Most dangerous enemy: {} ({:.0} damage)",
+                BattleStats::unit_type_name(enemy.unit_type),
+                enemy.total_damage
+            ),
+        ]
+        .join("\n")
+    }
+}
+
+/// A run ends when the game itself ends or once the target number of waves is cleared
+pub fn sim_finished(result: &GameResult, waves: u32) -> bool {
+    result.game_ended || result.waves_completed >= waves
+}
+
+impl Plugin for HeadlessSimPlugin {
+    fn build(&self, app: &mut App) {
+        if !app.is_plugin_added::<StatesPlugin>() {
+            app.add_plugins(StatesPlugin);
+        }
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f32(SIM_TIMESTEP)))
+            .insert_resource(GameRng::from_seed(self.seed))
+            .insert_resource(SimConfig {
+                seed: self.seed,
+                waves: self.waves,
+                roster: self.roster.clone(),
+            })
+            .insert_state(GameState::Playing)
+            .init_state::<PhaseState>()
+            .init_resource::<TimeScale>()
+            .init_resource::<GameSpeed>()
+            .init_resource::<GameMode>()
+            .init_resource::<Difficulty>()
+            .init_resource::<BoardConfig>()
+            .init_resource::<Assets<Mesh>>()
+            .init_resource::<Assets<ColorMaterial>>()
+            .insert_resource(BattleGrid::new())
+            .init_resource::<ActiveSynergies>()
+            .init_resource::<SynergyConfig>()
+            .init_resource::<WaveManager>()
+            .init_resource::<GameResult>()
+            .init_resource::<Gold>()
+            .init_resource::<BaseHealth>()
+            .init_resource::<BattleStats>()
+            .init_resource::<UnitCensus>()
+            .init_resource::<ManualCast>()
+            .init_resource::<SimReport>()
+            .add_observer(projectile::handle_projectile_hit)
+            .add_observer(knockback::handle_knockback)
+            .add_observer(chain_lightning::handle_chain_lightning)
+            .add_observer(synergy::handle_synergy_activation)
+            .add_observer(experience::handle_experience_gain)
+            .add_systems(Startup, summon_sim_roster)
+            .add_systems(
+                Update,
+                (
+                    wave::wave_spawner_system,
+                    wave::materialize_spawn_telegraphs,
+                    wave::summoner_system,
+                    combat_systems(),
+                    combat::despawn_attack_lines,
+                    synergy::update_synergies,
+                    synergy::apply_synergy_bonuses,
+                    game_result::enemy_reach_base_system,
+                    game_result::check_game_result,
+                )
+                    .chain()
+                    .run_if(in_state(GameState::Playing).and(sim_running)),
+            )
+            .add_systems(Update, finish_sim);
+    }
+}
+
+fn summon_sim_roster(
+    mut commands: Commands,
+    config: Res<SimConfig>,
+    mut grid: ResMut<BattleGrid>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    for &(unit_type, star_rank) in &config.roster {
+        let Some(pos) = grid.find_empty_position() else { break };
+        spawn_unit_at(&mut commands, &mut grid, unit_type, star_rank, pos, Team::Player, &mut meshes, &mut materials);
+    }
+}
+
+fn sim_running(report: Res<SimReport>) -> bool {
+    !report.finished
+}
+
+/// Stop the run on a terminal state: print the report and exit
+fn finish_sim(
+    time: Res<Time>,
+    config: Res<SimConfig>,
+    game_result: Res<GameResult>,
+    base_health: Res<BaseHealth>,
+    stats: Res<BattleStats>,
+    mut report: ResMut<SimReport>,
+    mut exit: EventWriter<AppExit>,
+) {
+    if report.finished {
+        return;
+    }
+    let timed_out = time.elapsed_secs() >= SIM_TIME_LIMIT;
+    if !sim_finished(&game_result, config.waves) && !timed_out {
+        return;
+    }
+
+    *report = SimReport {
+        finished: true,
+        defeated: game_result.game_ended && !game_result.victory,
+        timed_out,
+        waves_survived: game_result.waves_completed,
+        base_health: base_health.current,
+        sim_seconds: time.elapsed_secs(),
+    };
+    println!("{}", report.summary(&config, &stats));
+    exit.send(AppExit::Success);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sim_finishes_on_game_over_or_target_wave() {
+        let mut result = GameResult::default();
+        assert!(!sim_finished(&result, 3));
+
+        result.waves_completed = 2;
+        assert!(!sim_finished(&result, 3));
+        result.waves_completed = 3;
+        assert!(sim_finished(&result, 3), "clearing the target wave ends the run");
+
+        let lost = GameResult { game_ended: true, waves_completed: 1, ..default() };
+        assert!(sim_finished(&lost, 3), "a lost game ends the run early");
+    }
+
+    #[test]
+    fn test_summary_reports_waves_and_mvp() {
+        let config = SimConfig { seed: 7, waves: 3, roster: SIM_ROSTER.to_vec() };
+        let mut stats = BattleStats::new();
+        stats.record_ally_kill(TileType::Green, 40.0);
+        let report = SimReport { finished: true, waves_survived: 3, base_health: 80.0, ..default() };
+
+        let summary = report.summary(&config, &stats);
+
+        assert!(summary.contains("seed 7"));
+        assert!(summary.contains("Survived"));
+        assert!(summary.contains("Waves survived: 3/3"));
+        assert!(summary.contains("MVP: Ranger (1 kills"));
+    }
+}
@@ -0,0 +1,71 @@
+//! Fixed-order combat tick
+//!
+//! `combat_systems` is the single source of truth for the order combat runs in.
+//! `BattlePlugin` schedules it every frame; `step_combat` runs it once on a bare
+//! `World` so tests can advance a fight by an exact delta without building an `App`.
+
+use crate::prelude::*;
+use bevy::ecs::schedule::{ScheduleLabel, SystemConfigs};
+use std::time::Duration;
+use super::{BattleGrid, WaveManager, BattleStats, UnitCensus};
+use super::{combat, projectile, knockback, chain_lightning, census, experience};
+
+/// Schedule that `step_combat` runs: one tick of `combat_systems`
+#[derive(ScheduleLabel, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct CombatStep;
+
+/// Targeting → movement → attacks → mana/abilities/DoT → deaths → census, in order
+pub fn combat_systems() -> SystemConfigs {
+    (
+        combat::targeting_system,
+        combat::movement_system,
+        combat::tick_attack_windups,
+        combat::attack_system,
+        projectile::projectile_movement_system,
+        combat::mana_regen_system,
+        combat::ability_system,
+        combat::poison_tick_system,
+        combat::out_of_combat_regen_system,
+        combat::buff_timer_system,
+        combat::death_system,
+        combat::cancel_orphaned_telegraphs,
+        // Census is rebuilt after deaths so everything below sees this frame's survivors
+        census::update_unit_census,
+    )
+        .chain()
+}
+
+/// Insert everything `step_combat` needs into a bare world, with a seeded RNG.
+/// Resources that already exist are left untouched.
+pub fn init_combat_world(world: &mut World, seed: u64) {
+    world.insert_resource(GameRng::from_seed(seed));
+    world.init_resource::<Time>();
+    world.init_resource::<TimeScale>();
+    world.init_resource::<GameSpeed>();
+    if !world.contains_resource::<BattleGrid>() {
+        world.insert_resource(BattleGrid::new());
+    }
+    world.init_resource::<WaveManager>();
+    world.init_resource::<BoardConfig>();
+    world.init_resource::<Difficulty>();
+    world.init_resource::<combat::ManualCast>();
+    world.init_resource::<BattleStats>();
+    world.init_resource::<Gold>();
+    world.init_resource::<UnitCensus>();
+    world.add_observer(projectile::handle_projectile_hit);
+    world.add_observer(knockback::handle_knockback);
+    world.add_observer(chain_lightning::handle_chain_lightning);
+    world.add_observer(experience::handle_experience_gain);
+
+    let mut schedule = Schedule::new(CombatStep);
+    schedule.add_systems(combat_systems());
+    world.add_schedule(schedule);
+}
+
+/// Advance the world's clock by `dt` seconds and run one combat tick
+pub fn step_combat(world: &mut World, dt: f32) {
+    world
+        .resource_mut::<Time>()
+        .advance_by(Duration::from_secs_f32(dt));
+    world.run_schedule(CombatStep);
+}
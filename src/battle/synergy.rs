@@ -1,9 +1,9 @@
 use crate::prelude::*;
 // TileType is now imported via prelude
-use super::{Unit, UnitType, UnitStats, Team};
+use super::{Unit, UnitType, UnitStats, Team, UnitCensus, RageBuff, MeteorAbility, DamageType, BattleStats, Shield, take_shielded_damage};
 use std::collections::HashMap;
 
-#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
 pub enum SynergyLevel {
     None,
     Bronze,
@@ -12,12 +12,17 @@ pub enum SynergyLevel {
 }
 
 impl SynergyLevel {
+    /// Level under the default thresholds
     pub fn from_count(count: usize) -> Self {
-        match count {
-            0..=1 => SynergyLevel::None,
-            2..=3 => SynergyLevel::Bronze,
-            4..=5 => SynergyLevel::Silver,
-            _ => SynergyLevel::Gold,
+        SynergyThresholds::default().level(count)
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            SynergyLevel::None => "None",
+            SynergyLevel::Bronze => "Bronze",
+            SynergyLevel::Silver => "Silver",
+            SynergyLevel::Gold => "Gold",
         }
     }
 
@@ -31,47 +36,313 @@ impl SynergyLevel {
     }
 }
 
+/// Fewest units of one color needed for each level
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SynergyThresholds {
+    pub bronze: usize,
+    pub silver: usize,
+    pub gold: usize,
+}
+
+impl Default for SynergyThresholds {
+    fn default() -> Self {
+        Self { bronze: 2, silver: 4, gold: 6 }
+    }
+}
+
+impl SynergyThresholds {
+    pub fn level(&self, count: usize) -> SynergyLevel {
+        if count >= self.gold {
+            SynergyLevel::Gold
+        } else if count >= self.silver {
+            SynergyLevel::Silver
+        } else if count >= self.bronze {
+            SynergyLevel::Bronze
+        } else {
+            SynergyLevel::None
+        }
+    }
+}
+
+/// Synergy thresholds for balancing; colors without an override use `default`
+#[derive(Resource, Clone, Debug, Default)]
+pub struct SynergyConfig {
+    pub default: SynergyThresholds,
+    pub per_type: HashMap<TileType, SynergyThresholds>,
+}
+
+impl SynergyConfig {
+    /// Override the thresholds for a single color
+    pub fn with_thresholds(mut self, tile_type: TileType, thresholds: SynergyThresholds) -> Self {
+        self.per_type.insert(tile_type, thresholds);
+        self
+    }
+
+    pub fn thresholds(&self, tile_type: TileType) -> SynergyThresholds {
+        self.per_type.get(&tile_type).copied().unwrap_or(self.default)
+    }
+
+    pub fn level(&self, tile_type: TileType, count: usize) -> SynergyLevel {
+        self.thresholds(tile_type).level(count)
+    }
+}
+
+/// Cross-color role; a trait synergy counts every player unit sharing it
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, PartialOrd, Ord)]
+pub enum UnitTrait {
+    Melee,
+    Ranged,
+    Caster,
+}
+
+impl UnitTrait {
+    pub const ALL: [UnitTrait; 3] = [UnitTrait::Melee, UnitTrait::Ranged, UnitTrait::Caster];
+
+    /// Traits of each class: Mages are both ranged and casters
+    pub fn of(tile_type: TileType) -> &'static [UnitTrait] {
+        match tile_type {
+            TileType::Red | TileType::Blue | TileType::Yellow => &[UnitTrait::Melee],
+            TileType::Green => &[UnitTrait::Ranged],
+            TileType::Purple => &[UnitTrait::Ranged, UnitTrait::Caster],
+        }
+    }
+
+    /// Units with this trait needed before its bonus kicks in
+    pub fn threshold(&self) -> usize {
+        match self {
+            UnitTrait::Melee => 4,
+            UnitTrait::Ranged => 3,
+            UnitTrait::Caster => 2,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            UnitTrait::Melee => "Melee",
+            UnitTrait::Ranged => "Ranged",
+            UnitTrait::Caster => "Caster",
+        }
+    }
+
+    /// Team-wide bonus shown in the HUD
+    pub fn bonus_description(&self) -> &'static str {
+        match self {
+            UnitTrait::Melee => "+5 defense",
+            UnitTrait::Ranged => "+15% attack speed",
+            UnitTrait::Caster => "+20% mana regen",
+        }
+    }
+
+    fn apply(&self, stats: &mut UnitStats) {
+        match self {
+            UnitTrait::Melee => stats.defense += TRAIT_MELEE_DEFENSE,
+            UnitTrait::Ranged => stats.attack_speed *= TRAIT_RANGED_ATTACK_SPEED,
+            UnitTrait::Caster => stats.mana_regen *= TRAIT_CASTER_MANA_REGEN,
+        }
+    }
+
+    fn revert(&self, stats: &mut UnitStats) {
+        match self {
+            UnitTrait::Melee => stats.defense -= TRAIT_MELEE_DEFENSE,
+            UnitTrait::Ranged => stats.attack_speed /= TRAIT_RANGED_ATTACK_SPEED,
+            UnitTrait::Caster => stats.mana_regen /= TRAIT_CASTER_MANA_REGEN,
+        }
+    }
+}
+
+const TRAIT_MELEE_DEFENSE: f32 = 5.0;
+const TRAIT_RANGED_ATTACK_SPEED: f32 = 1.15;
+const TRAIT_CASTER_MANA_REGEN: f32 = 1.2;
+
+/// Trait bonuses currently folded into a unit's stats, so each is applied once
+/// and taken back off when the trait drops below its threshold
+#[derive(Component, Default, Debug)]
+pub struct TraitBonuses(pub Vec<UnitTrait>);
+
 #[derive(Resource, Default)]
 pub struct ActiveSynergies {
     pub bonuses: HashMap<TileType, SynergyLevel>,
+    /// Levels as of the previous update, to spot transitions
+    pub previous: HashMap<TileType, SynergyLevel>,
+    /// Player units per trait
+    pub traits: HashMap<UnitTrait, usize>,
 }
 
 impl ActiveSynergies {
     pub fn get_level(&self, tile_type: TileType) -> SynergyLevel {
         self.bonuses.get(&tile_type).copied().unwrap_or(SynergyLevel::None)
     }
+
+    pub fn previous_level(&self, tile_type: TileType) -> SynergyLevel {
+        self.previous.get(&tile_type).copied().unwrap_or(SynergyLevel::None)
+    }
+
+    pub fn trait_count(&self, unit_trait: UnitTrait) -> usize {
+        self.traits.get(&unit_trait).copied().unwrap_or(0)
+    }
+
+    /// Traits at or above their threshold, in `UnitTrait::ALL` order
+    pub fn active_traits(&self) -> Vec<UnitTrait> {
+        UnitTrait::ALL
+            .into_iter()
+            .filter(|unit_trait| self.trait_count(*unit_trait) >= unit_trait.threshold())
+            .collect()
+    }
+
+    /// Colors that reached Gold in the latest update, in tile order
+    pub fn newly_gold(&self) -> Vec<TileType> {
+        let mut reached: Vec<TileType> = self
+            .bonuses
+            .iter()
+            .filter(|(tile_type, level)| {
+                **level == SynergyLevel::Gold && self.previous_level(**tile_type) != SynergyLevel::Gold
+            })
+            .map(|(tile_type, _)| *tile_type)
+            .collect();
+        reached.sort_by_key(|tile_type| *tile_type as u8);
+        reached
+    }
+
+    /// Colors that climbed to a higher tier in the latest update, with their new
+    /// level, in tile order. Holding or dropping a tier doesn't count.
+    pub fn upgraded(&self) -> Vec<(TileType, SynergyLevel)> {
+        let mut upgraded: Vec<(TileType, SynergyLevel)> = self
+            .bonuses
+            .iter()
+            .filter(|(tile_type, level)| **level > self.previous_level(**tile_type))
+            .map(|(tile_type, level)| (*tile_type, *level))
+            .collect();
+        upgraded.sort_by_key(|(tile_type, _)| *tile_type as u8);
+        upgraded
+    }
+}
+
+/// A color's synergy just reached Gold; fires once per upward transition
+#[derive(Event, Debug)]
+pub struct SynergyActivationEvent {
+    pub tile_type: TileType,
+}
+
+/// A color's synergy reached a higher tier; announced with a floating banner
+#[derive(Event, Debug)]
+pub struct SynergyLevelUpEvent {
+    pub tile_type: TileType,
+    pub level: SynergyLevel,
 }
 
 pub fn update_synergies(
+    mut commands: Commands,
     mut synergies: ResMut<ActiveSynergies>,
-    units: Query<(&UnitType, &Team), With<Unit>>,
+    census: Res<UnitCensus>,
+    config: Res<SynergyConfig>,
 ) {
-    let mut counts: HashMap<TileType, usize> = HashMap::new();
-
-    for (unit_type, team) in units.iter() {
-        if *team == Team::Player {
-            *counts.entry(unit_type.0).or_insert(0) += 1;
+    synergies.previous = std::mem::take(&mut synergies.bonuses);
+    synergies.traits.clear();
+    for (tile_type, count) in census.types_for(Team::Player) {
+        let level = config.level(tile_type, count);
+        if level != SynergyLevel::None {
+            synergies.bonuses.insert(tile_type, level);
+        }
+        for unit_trait in UnitTrait::of(tile_type) {
+            *synergies.traits.entry(*unit_trait).or_insert(0) += count;
         }
     }
 
-    synergies.bonuses.clear();
-    for (tile_type, count) in counts {
-        let level = SynergyLevel::from_count(count);
-        if level != SynergyLevel::None {
-            synergies.bonuses.insert(tile_type, level);
+    for (tile_type, level) in synergies.upgraded() {
+        commands.trigger(SynergyLevelUpEvent { tile_type, level });
+    }
+    for tile_type in synergies.newly_gold() {
+        commands.trigger(SynergyActivationEvent { tile_type });
+    }
+}
+
+type SynergyTargetQuery<'w, 's> = Query<
+    'w,
+    's,
+    (Entity, Option<&'static UnitType>, &'static mut UnitStats, &'static Team, Option<&'static mut Shield>),
+    With<Unit>,
+>;
+
+/// One-time Gold effects: Red enrages the whole team, Blue shields it, Purple drops
+/// a free Meteor scaled by the strongest mage's ability power
+pub fn handle_synergy_activation(
+    trigger: Trigger<SynergyActivationEvent>,
+    mut commands: Commands,
+    mut units: SynergyTargetQuery,
+    mut battle_stats: ResMut<BattleStats>,
+) {
+    match trigger.event().tile_type {
+        TileType::Red => {
+            for (entity, _, _, team, _) in units.iter() {
+                if *team == Team::Player {
+                    commands.entity(entity).insert(RageBuff::new());
+                }
+            }
+        }
+        TileType::Purple => {
+            let ability_power = units
+                .iter()
+                .filter(|(_, unit_type, _, team, _)| {
+                    **team == Team::Player && unit_type.is_some_and(|u| u.0 == TileType::Purple)
+                })
+                .map(|(_, _, stats, ..)| stats.ability_power)
+                .fold(0.0, f32::max);
+            let damage = MeteorAbility::damage(ability_power);
+            for (entity, _, mut stats, team, mut shield) in units.iter_mut() {
+                if *team == Team::Enemy {
+                    take_shielded_damage(&mut stats, shield.as_deref_mut(), damage, DamageType::Magical);
+                    if shield.is_some_and(|shield| shield.is_depleted()) {
+                        commands.entity(entity).remove::<Shield>();
+                    }
+                    battle_stats.record_ally_damage(TileType::Purple, damage);
+                }
+            }
         }
+        TileType::Blue => {
+            for (entity, _, _, team, _) in units.iter() {
+                if *team == Team::Player {
+                    commands.entity(entity).insert(Shield::new(Shield::GOLD_BLUE_AMOUNT));
+                }
+            }
+        }
+        TileType::Green | TileType::Yellow => {}
     }
 }
 
+type SynergyUnitQuery<'w, 's> = Query<
+    'w,
+    's,
+    (Entity, &'static UnitType, &'static mut UnitStats, &'static Team, Option<&'static mut TraitBonuses>),
+    With<Unit>,
+>;
+
 pub fn apply_synergy_bonuses(
+    mut commands: Commands,
     synergies: Res<ActiveSynergies>,
-    mut units: Query<(&UnitType, &mut UnitStats, &Team), With<Unit>>,
+    mut units: SynergyUnitQuery,
 ) {
-    for (unit_type, mut stats, team) in units.iter_mut() {
+    let active_traits = synergies.active_traits();
+    for (entity, unit_type, mut stats, team, trait_bonuses) in units.iter_mut() {
         if *team != Team::Player {
             continue;
         }
 
+        let applied = trait_bonuses.as_deref().map_or(&[][..], |bonuses| &bonuses.0[..]);
+        if applied != active_traits.as_slice() {
+            for unit_trait in applied.iter().filter(|t| !active_traits.contains(t)) {
+                unit_trait.revert(&mut stats);
+            }
+            for unit_trait in active_traits.iter().filter(|t| !applied.contains(t)) {
+                unit_trait.apply(&mut stats);
+            }
+            match trait_bonuses {
+                Some(mut bonuses) => bonuses.0 = active_traits.clone(),
+                None => {
+                    commands.entity(entity).insert(TraitBonuses(active_traits.clone()));
+                }
+            }
+        }
+
         let level = synergies.get_level(unit_type.0);
         if level == SynergyLevel::None {
             continue;
@@ -135,3 +406,267 @@ pub fn apply_synergy_bonuses(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::census::update_unit_census;
+
+    fn synergies(previous: &[(TileType, SynergyLevel)], current: &[(TileType, SynergyLevel)]) -> ActiveSynergies {
+        ActiveSynergies {
+            bonuses: current.iter().copied().collect(),
+            previous: previous.iter().copied().collect(),
+            ..default()
+        }
+    }
+
+    #[test]
+    fn test_default_config_matches_from_count() {
+        let config = SynergyConfig::default();
+        let levels: Vec<SynergyLevel> = (0..=7).map(|count| config.level(TileType::Red, count)).collect();
+        assert_eq!(
+            levels,
+            vec![
+                SynergyLevel::None,
+                SynergyLevel::None,
+                SynergyLevel::Bronze,
+                SynergyLevel::Bronze,
+                SynergyLevel::Silver,
+                SynergyLevel::Silver,
+                SynergyLevel::Gold,
+                SynergyLevel::Gold,
+            ]
+        );
+        for (count, level) in levels.into_iter().enumerate() {
+            assert_eq!(SynergyLevel::from_count(count), level);
+        }
+    }
+
+    #[test]
+    fn test_custom_thresholds_per_type() {
+        let low = SynergyThresholds { bronze: 1, silver: 2, gold: 3 };
+        let config = SynergyConfig::default().with_thresholds(TileType::Purple, low);
+
+        assert_eq!(config.level(TileType::Purple, 3), SynergyLevel::Gold);
+        assert_eq!(config.level(TileType::Purple, 1), SynergyLevel::Bronze);
+        assert_eq!(config.level(TileType::Red, 3), SynergyLevel::Bronze, "other colors keep the defaults");
+
+        let config = SynergyConfig { default: low, ..default() };
+        assert_eq!(config.level(TileType::Red, 3), SynergyLevel::Gold);
+    }
+
+    #[test]
+    fn test_none_to_gold_activates() {
+        let active = synergies(&[], &[(TileType::Red, SynergyLevel::Gold)]);
+        assert_eq!(active.newly_gold(), vec![TileType::Red]);
+
+        let active = synergies(
+            &[(TileType::Purple, SynergyLevel::Silver)],
+            &[(TileType::Purple, SynergyLevel::Gold), (TileType::Blue, SynergyLevel::Gold)],
+        );
+        assert_eq!(active.newly_gold(), vec![TileType::Blue, TileType::Purple]);
+    }
+
+    #[test]
+    fn test_gold_to_gold_does_not_activate() {
+        let active = synergies(&[(TileType::Red, SynergyLevel::Gold)], &[(TileType::Red, SynergyLevel::Gold)]);
+        assert!(active.newly_gold().is_empty());
+
+        let active = synergies(&[(TileType::Red, SynergyLevel::Gold)], &[(TileType::Red, SynergyLevel::Silver)]);
+        assert!(active.newly_gold().is_empty(), "dropping a level is not an activation");
+    }
+
+    #[test]
+    fn test_upgraded_only_reports_tier_increases() {
+        let active = synergies(
+            &[(TileType::Blue, SynergyLevel::Bronze), (TileType::Green, SynergyLevel::Silver), (TileType::Red, SynergyLevel::Gold)],
+            &[
+                (TileType::Purple, SynergyLevel::Bronze),
+                (TileType::Blue, SynergyLevel::Silver),
+                (TileType::Green, SynergyLevel::Silver),
+                (TileType::Red, SynergyLevel::Silver),
+            ],
+        );
+        assert_eq!(
+            active.upgraded(),
+            vec![(TileType::Blue, SynergyLevel::Silver), (TileType::Purple, SynergyLevel::Bronze)],
+            "held and dropped tiers are not upgrades"
+        );
+    }
+
+    #[derive(Resource, Default)]
+    struct Activations(Vec<TileType>);
+
+    #[test]
+    fn test_activation_fires_once_per_upward_transition() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .init_resource::<UnitCensus>()
+            .init_resource::<ActiveSynergies>()
+            .init_resource::<SynergyConfig>()
+            .init_resource::<Activations>()
+            .add_observer(|trigger: Trigger<SynergyActivationEvent>, mut seen: ResMut<Activations>| {
+                seen.0.push(trigger.event().tile_type);
+            })
+            .add_systems(Update, (update_unit_census, update_synergies).chain());
+
+        let units: Vec<Entity> = (0..6)
+            .map(|_| app.world_mut().spawn((Unit, UnitType(TileType::Red), Team::Player)).id())
+            .collect();
+        app.update();
+        app.update();
+        assert_eq!(app.world().resource::<Activations>().0, vec![TileType::Red], "not every frame");
+
+        // Falling back to Silver and climbing again is a new activation
+        app.world_mut().despawn(units[0]);
+        app.update();
+        app.world_mut().spawn((Unit, UnitType(TileType::Red), Team::Player));
+        app.update();
+        assert_eq!(app.world().resource::<Activations>().0, vec![TileType::Red, TileType::Red]);
+    }
+
+    #[derive(Resource, Default)]
+    struct LevelUps(Vec<(TileType, SynergyLevel)>);
+
+    #[test]
+    fn test_level_up_fires_once_per_new_tier() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .init_resource::<UnitCensus>()
+            .init_resource::<ActiveSynergies>()
+            .init_resource::<SynergyConfig>()
+            .init_resource::<LevelUps>()
+            .add_observer(|trigger: Trigger<SynergyLevelUpEvent>, mut seen: ResMut<LevelUps>| {
+                seen.0.push((trigger.event().tile_type, trigger.event().level));
+            })
+            .add_systems(Update, (update_unit_census, update_synergies).chain());
+
+        let mut units: Vec<Entity> = (0..2)
+            .map(|_| app.world_mut().spawn((Unit, UnitType(TileType::Green), Team::Player)).id())
+            .collect();
+        app.update();
+        app.update();
+        units.extend((0..2).map(|_| app.world_mut().spawn((Unit, UnitType(TileType::Green), Team::Player)).id()));
+        app.update();
+        app.world_mut().despawn(units[0]);
+        app.update();
+
+        assert_eq!(
+            app.world().resource::<LevelUps>().0,
+            vec![(TileType::Green, SynergyLevel::Bronze), (TileType::Green, SynergyLevel::Silver)],
+            "once per climb, nothing for holding or dropping"
+        );
+    }
+
+    #[test]
+    fn test_update_synergies_reads_config() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .init_resource::<UnitCensus>()
+            .init_resource::<ActiveSynergies>()
+            .insert_resource(
+                SynergyConfig::default().with_thresholds(TileType::Green, SynergyThresholds { bronze: 2, silver: 3, gold: 4 }),
+            )
+            .add_systems(Update, (update_unit_census, update_synergies).chain());
+        for tile_type in [TileType::Green, TileType::Red] {
+            for _ in 0..4 {
+                app.world_mut().spawn((Unit, UnitType(tile_type), Team::Player));
+            }
+        }
+
+        app.update();
+
+        let synergies = app.world().resource::<ActiveSynergies>();
+        assert_eq!(synergies.get_level(TileType::Green), SynergyLevel::Gold);
+        assert_eq!(synergies.get_level(TileType::Red), SynergyLevel::Silver);
+    }
+
+    #[test]
+    fn test_trait_counts_span_colors() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .init_resource::<UnitCensus>()
+            .init_resource::<ActiveSynergies>()
+            .init_resource::<SynergyConfig>()
+            .add_systems(Update, (update_unit_census, update_synergies).chain());
+        for tile_type in [TileType::Green, TileType::Green, TileType::Purple, TileType::Red] {
+            app.world_mut().spawn((Unit, UnitType(tile_type), Team::Player));
+        }
+        app.world_mut().spawn((Unit, UnitType(TileType::Green), Team::Enemy));
+
+        app.update();
+
+        let synergies = app.world().resource::<ActiveSynergies>();
+        assert_eq!(synergies.trait_count(UnitTrait::Ranged), 3, "rangers and mages, player side only");
+        assert_eq!(synergies.trait_count(UnitTrait::Caster), 1);
+        assert_eq!(synergies.trait_count(UnitTrait::Melee), 1);
+        assert_eq!(synergies.active_traits(), vec![UnitTrait::Ranged]);
+    }
+
+    #[test]
+    fn test_trait_bonus_applies_once_and_reverts() {
+        let mut app = App::new();
+        let traits = |ranged: usize| ActiveSynergies {
+            traits: [(UnitTrait::Ranged, ranged)].into_iter().collect(),
+            ..default()
+        };
+        app.add_plugins(MinimalPlugins)
+            .insert_resource(traits(3))
+            .add_systems(Update, apply_synergy_bonuses);
+        let base = UnitStats::for_type(TileType::Red, 1);
+        let ally = app.world_mut().spawn((Unit, UnitType(TileType::Red), base.clone(), Team::Player)).id();
+        let enemy = app.world_mut().spawn((Unit, UnitType(TileType::Red), base.clone(), Team::Enemy)).id();
+        let attack_speed = |app: &App, entity| app.world().get::<UnitStats>(entity).unwrap().attack_speed;
+
+        app.update();
+        app.update();
+        app.update();
+        assert!((attack_speed(&app, ally) - base.attack_speed * 1.15).abs() < 1e-4, "not compounded per frame");
+        assert_eq!(attack_speed(&app, enemy), base.attack_speed);
+
+        app.insert_resource(traits(2));
+        app.update();
+        assert!((attack_speed(&app, ally) - base.attack_speed).abs() < 1e-4, "dropping below the threshold removes it");
+    }
+
+    #[test]
+    fn test_gold_red_enrages_player_team_only() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .init_resource::<BattleStats>()
+            .add_observer(handle_synergy_activation);
+        let ally = app
+            .world_mut()
+            .spawn((Unit, UnitType(TileType::Blue), UnitStats::for_type(TileType::Blue, 1), Team::Player))
+            .id();
+        let enemy = app
+            .world_mut()
+            .spawn((Unit, UnitType(TileType::Red), UnitStats::for_type(TileType::Red, 1), Team::Enemy))
+            .id();
+        app.world_mut().flush();
+
+        app.world_mut().trigger(SynergyActivationEvent { tile_type: TileType::Red });
+        app.world_mut().flush();
+
+        assert!(app.world().get::<RageBuff>(ally).is_some());
+        assert!(app.world().get::<RageBuff>(enemy).is_none());
+    }
+
+    #[test]
+    fn test_gold_blue_shields_player_team() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .init_resource::<BattleStats>()
+            .add_observer(handle_synergy_activation);
+        let ally = app
+            .world_mut()
+            .spawn((Unit, UnitType(TileType::Red), UnitStats::for_type(TileType::Red, 1), Team::Player))
+            .id();
+        app.world_mut().flush();
+
+        app.world_mut().trigger(SynergyActivationEvent { tile_type: TileType::Blue });
+        app.world_mut().flush();
+
+        assert_eq!(app.world().get::<Shield>(ally).map(|s| s.amount), Some(Shield::GOLD_BLUE_AMOUNT));
+    }
+}
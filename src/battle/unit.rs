@@ -1,13 +1,50 @@
 use crate::prelude::*;
 // TileType is now imported via prelude
+use super::wave::Boss;
 
 const HEALTH_BAR_WIDTH: f32 = 30.0;
 const HEALTH_BAR_HEIGHT: f32 = 4.0;
 const HEALTH_BAR_OFFSET_Y: f32 = 25.0;
+const MANA_BAR_HEIGHT: f32 = 2.0;
+const MANA_BAR_COLOR: Color = Color::srgb(0.2, 0.4, 1.0);
+const MANA_BAR_FULL_COLOR: Color = Color::srgb(0.7, 0.85, 1.0);
+/// Flashes per second while the mana bar is full
+const MANA_BAR_FLASH_RATE: f32 = 4.0;
+const SHIELD_BAR_COLOR: Color = Color::srgba(0.85, 0.95, 1.0, 0.85);
+/// Boss health bars are stretched horizontally and framed in gold above the larger sprite
+const BOSS_HEALTH_BAR_SCALE: Vec3 = Vec3::new(2.5, 1.5, 1.0);
+const BOSS_HEALTH_BAR_OFFSET_Y: f32 = 45.0;
+const BOSS_HEALTH_BAR_FRAME_COLOR: Color = Color::srgb(0.85, 0.65, 0.1);
+const CAST_RING_INNER_RADIUS: f32 = 24.0;
+const CAST_RING_OUTER_RADIUS: f32 = 28.0;
+const CAST_RING_COLOR: Color = Color::srgba(0.7, 0.85, 1.0, 0.9);
+/// Pulses per second while the cast is charged
+const CAST_RING_PULSE_RATE: f32 = 3.0;
+const CAST_RING_PULSE_SCALE: f32 = 0.12;
+/// Seconds the ring takes to burst outward and fade once the cast goes off
+pub const CAST_RING_FADE_SECS: f32 = 0.35;
 
 #[derive(Component)]
 pub struct Unit;
 
+/// Which resistance an attack is checked against: `defense` (physical) or `magic_resist` (magical)
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DamageType {
+    #[default]
+    Physical,
+    Magical,
+}
+
+impl DamageType {
+    /// Basic-attack damage type by unit archetype: the Mage hits with magic, everyone else physically
+    pub fn for_unit(unit_type: TileType) -> Self {
+        match unit_type {
+            TileType::Purple => Self::Magical,
+            _ => Self::Physical,
+        }
+    }
+}
+
 #[derive(Component, Clone)]
 pub struct UnitStats {
     pub health: f32,
@@ -19,6 +56,7 @@ pub struct UnitStats {
     pub max_mana: f32,
     pub move_speed: f32,
     pub defense: f32,
+    pub magic_resist: f32,
     pub crit_chance: f32,
     pub ability_power: f32,
     pub mana_regen: f32,
@@ -36,6 +74,7 @@ impl Default for UnitStats {
             max_mana: 100.0,
             move_speed: 1.0,
             defense: 0.0,
+            magic_resist: 0.0,
             crit_chance: 0.0,
             ability_power: 0.0,
             mana_regen: 1.0,
@@ -67,6 +106,7 @@ impl UnitStats {
                 attack: 8.0,
                 attack_speed: 0.8,
                 attack_range: 1,
+                defense: 4.0,
                 ..default()
             },
             TileType::Green => Self {
@@ -92,6 +132,8 @@ impl UnitStats {
                 attack_speed: 1.0,
                 attack_range: 2,
                 max_mana: 80.0,
+                ability_power: 10.0,
+                magic_resist: 4.0,
                 ..default()
             },
         };
@@ -100,6 +142,7 @@ impl UnitStats {
             health: base.health * multiplier,
             max_health: base.max_health * multiplier,
             attack: base.attack * multiplier,
+            ability_power: base.ability_power * multiplier,
             ..base
         }
     }
@@ -108,20 +151,47 @@ impl UnitStats {
         self.health <= 0.0
     }
 
+    /// Resistance that applies to the given damage type
+    pub fn resistance(&self, damage_type: DamageType) -> f32 {
+        match damage_type {
+            DamageType::Physical => self.defense,
+            DamageType::Magical => self.magic_resist,
+        }
+    }
+
+    /// Physical damage with flat defense reduction
     pub fn take_damage(&mut self, amount: f32) {
-        let reduced = (amount - self.defense).max(1.0);
+        self.take_typed_damage(amount, DamageType::Physical);
+    }
+
+    /// Flat reduction by the matching resistance, at least 1 damage
+    pub fn take_typed_damage(&mut self, amount: f32, damage_type: DamageType) {
+        let reduced = (amount - self.resistance(damage_type)).max(1.0);
         self.health = (self.health - reduced).max(0.0);
     }
 
-    /// Take damage with percentage-based defense reduction
-    /// Defense is converted to percentage: defense 50 = 50% reduction (capped at 80%)
-    pub fn take_calculated_damage(&mut self, amount: f32) {
-        let reduction = (self.defense / 100.0).min(0.8);
+    /// Damage that ignores defense and the 1-damage floor; used for per-frame
+    /// damage-over-time ticks, which would otherwise scale with frame rate
+    pub fn take_true_damage(&mut self, amount: f32) {
+        self.health = (self.health - amount.max(0.0)).max(0.0);
+    }
+
+    /// Take damage with percentage-based reduction from the matching resistance
+    /// Resistance is converted to percentage: 50 = 50% reduction (capped at 80%)
+    pub fn take_calculated_damage(&mut self, amount: f32, damage_type: DamageType) {
+        let reduction = (self.resistance(damage_type) / 100.0).min(0.8);
         let reduced = amount * (1.0 - reduction);
         let final_damage = reduced.max(1.0);
         self.health = (self.health - final_damage).max(0.0);
     }
 
+    /// Restore up to `amount` HP (capped at max) and return how much was actually healed
+    pub fn heal(&mut self, amount: f32) -> f32 {
+        let before = self.health;
+        self.health = (self.health + amount.max(0.0)).min(self.max_health);
+        self.health - before
+    }
+
     pub fn gain_mana(&mut self, amount: f32) {
         self.mana = (self.mana + amount).min(self.max_mana);
     }
@@ -137,7 +207,7 @@ pub struct StarRank(pub u8);
 #[derive(Component, Clone, Copy)]
 pub struct UnitType(pub TileType);
 
-#[derive(Component, Clone, Copy, PartialEq, Eq)]
+#[derive(Component, Clone, Copy, PartialEq, Eq, Hash, Debug)]
 pub enum Team {
     Player,
     Enemy,
@@ -146,9 +216,67 @@ pub enum Team {
 #[derive(Component)]
 pub struct Target(pub Option<Entity>);
 
+/// How a unit picks among enemies already in attack range.
+/// Out of range it always heads for the nearest one.
+#[derive(Component, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TargetingMode {
+    #[default]
+    Nearest,
+    LowestHealth,
+    HighestAttack,
+}
+
+impl TargetingMode {
+    /// Next mode in the WaveBreak cycle
+    pub fn next(self) -> Self {
+        match self {
+            TargetingMode::Nearest => TargetingMode::LowestHealth,
+            TargetingMode::LowestHealth => TargetingMode::HighestAttack,
+            TargetingMode::HighestAttack => TargetingMode::Nearest,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            TargetingMode::Nearest => "Nearest",
+            TargetingMode::LowestHealth => "Lowest HP",
+            TargetingMode::HighestAttack => "Highest ATK",
+        }
+    }
+}
+
+/// Seconds until the unit may attack again
 #[derive(Component)]
 pub struct AttackCooldown(pub f32);
 
+impl AttackCooldown {
+    /// One full attack interval; fresh units start here so their first shot isn't instant
+    pub fn for_stats(stats: &UnitStats) -> Self {
+        Self(1.0 / stats.attack_speed)
+    }
+}
+
+/// Most recent unit to damage this one; credited with the kill in `death_system`
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LastHitBy(pub Entity);
+
+/// Out-of-combat tracking for player units. Any drop below `last_health` counts as
+/// being hit, whatever the damage source.
+#[derive(Component, Clone, Copy, Debug)]
+pub struct CombatActivity {
+    /// Scaled seconds since the unit last took damage or had a target
+    pub out_of_combat: f32,
+    pub last_health: f32,
+    /// Regenerated health not yet shown in a heal popup
+    pub unshown_heal: f32,
+}
+
+impl CombatActivity {
+    pub fn new(health: f32) -> Self {
+        Self { out_of_combat: 0.0, last_health: health, unshown_heal: 0.0 }
+    }
+}
+
 // ============================================================
 // Ability Buff Components
 // ============================================================
@@ -270,18 +398,118 @@ impl Default for StealthBuff {
     }
 }
 
+// ============================================================
+// Debuff Components
+// ============================================================
+
+/// Poison damage-over-time: `dps` damage per second until `remaining` runs out.
+/// Applied by the Yellow (Assassin) ability to the enemy it is targeting.
+#[derive(Component, Clone)]
+pub struct PoisonDebuff {
+    pub dps: f32,
+    pub remaining: f32,
+}
+
+impl PoisonDebuff {
+    /// Assassin poison: 8 damage/s for 4 seconds
+    pub const ASSASSIN_DPS: f32 = 8.0;
+    pub const ASSASSIN_DURATION: f32 = 4.0;
+
+    pub fn new(dps: f32, duration: f32) -> Self {
+        Self { dps, remaining: duration }
+    }
+
+    pub fn assassin() -> Self {
+        Self::new(Self::ASSASSIN_DPS, Self::ASSASSIN_DURATION)
+    }
+
+    /// Advance by `delta` seconds and return the damage dealt in that slice
+    /// (never more than what is left of the poison)
+    pub fn tick(&mut self, delta: f32) -> f32 {
+        let elapsed = delta.min(self.remaining).max(0.0);
+        self.remaining -= elapsed;
+        self.dps * elapsed
+    }
+
+    pub fn is_expired(&self) -> bool {
+        self.remaining <= 0.0
+    }
+}
+
+/// Temporary HP that soaks incoming hits before `UnitStats::health`
+#[derive(Component, Clone, Copy, Debug)]
+pub struct Shield {
+    pub amount: f32,
+}
+
+impl Shield {
+    /// Granted to the whole team when the Blue (Tank) synergy reaches Gold
+    pub const GOLD_BLUE_AMOUNT: f32 = 40.0;
+
+    pub fn new(amount: f32) -> Self {
+        Self { amount }
+    }
+
+    /// Soak up to `damage` and return what spills over onto health
+    pub fn absorb(&mut self, damage: f32) -> f32 {
+        let soaked = damage.max(0.0).min(self.amount);
+        self.amount -= soaked;
+        damage.max(0.0) - soaked
+    }
+
+    pub fn is_depleted(&self) -> bool {
+        self.amount <= 0.0
+    }
+}
+
+/// Apply a hit through the target's shield (if any); only the spill-over reaches
+/// `take_typed_damage`, so a fully soaked hit costs no health at all
+pub fn take_shielded_damage(
+    stats: &mut UnitStats,
+    shield: Option<&mut Shield>,
+    amount: f32,
+    damage_type: DamageType,
+) {
+    let spill = match shield {
+        Some(shield) => shield.absorb(amount),
+        None => amount,
+    };
+    if spill > 0.0 {
+        stats.take_typed_damage(spill, damage_type);
+    }
+}
+
+/// `take_shielded_damage` for hits that use `take_calculated_damage`'s percentage
+/// defense instead (bombs)
+pub fn take_shielded_calculated_damage(
+    stats: &mut UnitStats,
+    shield: Option<&mut Shield>,
+    amount: f32,
+    damage_type: DamageType,
+) {
+    let spill = shield.map_or(amount, |shield| shield.absorb(amount));
+    if spill > 0.0 {
+        stats.take_calculated_damage(spill, damage_type);
+    }
+}
+
 /// Purple (Mage) Meteor ability helper
+///
+/// Damage per enemy = `DAMAGE + ability_power * AP_RATIO`.
+/// Only Meteor scales with ability power; the other abilities keep flat effects.
 pub struct MeteorAbility;
 
 impl MeteorAbility {
     pub const DAMAGE: f32 = 15.0;
+    /// Extra damage per point of caster ability power
+    pub const AP_RATIO: f32 = 0.5;
 
-    pub fn damage() -> f32 {
-        Self::DAMAGE
+    pub fn damage(ability_power: f32) -> f32 {
+        Self::DAMAGE + ability_power.max(0.0) * Self::AP_RATIO
     }
 
-    pub fn calculate_damages(enemy_count: usize) -> Vec<f32> {
-        vec![Self::DAMAGE; enemy_count]
+    pub fn calculate_damages(enemy_count: usize, ability_power: f32) -> Vec<f32> {
+        vec![Self::damage(ability_power); enemy_count]
     }
 }
 
@@ -291,24 +519,39 @@ pub struct HealthBar;
 #[derive(Component)]
 pub struct HealthBarBackground;
 
+#[derive(Component)]
+pub struct ManaBar;
+
+/// White overlay on the health bar sized to the unit's `Shield`
+#[derive(Component)]
+pub struct ShieldBar;
+
+#[derive(Component)]
+pub struct ManaBarBackground;
+
 pub fn spawn_health_bars(
     mut commands: Commands,
-    units: Query<(Entity, &UnitStats), (With<Unit>, Without<Children>)>,
+    units: Query<(Entity, &UnitStats, Has<Boss>), (With<Unit>, Without<Children>)>,
 ) {
-    for (entity, stats) in units.iter() {
+    for (entity, stats, is_boss) in units.iter() {
         // 死亡済みユニットはスキップ
         if stats.is_dead() {
             continue;
         }
+        let (offset_y, scale, frame_color) = if is_boss {
+            (BOSS_HEALTH_BAR_OFFSET_Y, BOSS_HEALTH_BAR_SCALE, BOSS_HEALTH_BAR_FRAME_COLOR)
+        } else {
+            (HEALTH_BAR_OFFSET_Y, Vec3::ONE, Color::srgb(0.2, 0.2, 0.2))
+        };
         commands.entity(entity).with_children(|parent| {
             parent.spawn((
                 HealthBarBackground,
                 Sprite {
-                    color: Color::srgb(0.2, 0.2, 0.2),
+                    color: frame_color,
                     custom_size: Some(Vec2::new(HEALTH_BAR_WIDTH, HEALTH_BAR_HEIGHT)),
                     ..default()
                 },
-                Transform::from_translation(Vec3::new(0.0, HEALTH_BAR_OFFSET_Y, 0.1)),
+                Transform::from_translation(Vec3::new(0.0, offset_y, 0.1)).with_scale(scale),
             ));
             parent.spawn((
                 HealthBar,
@@ -317,8 +560,38 @@ pub fn spawn_health_bars(
                     custom_size: Some(Vec2::new(HEALTH_BAR_WIDTH, HEALTH_BAR_HEIGHT)),
                     ..default()
                 },
-                Transform::from_translation(Vec3::new(0.0, HEALTH_BAR_OFFSET_Y, 0.2)),
+                Transform::from_translation(Vec3::new(0.0, offset_y, 0.2)).with_scale(scale),
+            ));
+            parent.spawn((
+                ShieldBar,
+                Sprite {
+                    color: SHIELD_BAR_COLOR,
+                    custom_size: Some(Vec2::new(0.0, HEALTH_BAR_HEIGHT)),
+                    ..default()
+                },
+                Transform::from_translation(Vec3::new(0.0, offset_y, 0.3)).with_scale(scale),
             ));
+            // Only casters get a mana bar, tucked just below the health bar
+            if has_mana_bar(stats) {
+                parent.spawn((
+                    ManaBarBackground,
+                    Sprite {
+                        color: Color::srgb(0.2, 0.2, 0.2),
+                        custom_size: Some(Vec2::new(HEALTH_BAR_WIDTH, MANA_BAR_HEIGHT)),
+                        ..default()
+                    },
+                    Transform::from_translation(Vec3::new(0.0, offset_y - HEALTH_BAR_HEIGHT * scale.y, 0.1)).with_scale(scale),
+                ));
+                parent.spawn((
+                    ManaBar,
+                    Sprite {
+                        color: MANA_BAR_COLOR,
+                        custom_size: Some(Vec2::new(0.0, MANA_BAR_HEIGHT)),
+                        ..default()
+                    },
+                    Transform::from_translation(Vec3::new(0.0, offset_y - HEALTH_BAR_HEIGHT * scale.y, 0.2)).with_scale(scale),
+                ));
+            }
         });
     }
 }
@@ -341,6 +614,143 @@ pub fn update_health_bars(
     }
 }
 
+pub fn update_shield_bars(
+    units: Query<(&Children, &UnitStats, Option<&Shield>), With<Unit>>,
+    mut shield_bars: Query<&mut Sprite, With<ShieldBar>>,
+) {
+    for (children, stats, shield) in units.iter() {
+        let ratio = shield_ratio(stats, shield);
+        for &child in children.iter() {
+            if let Ok(mut sprite) = shield_bars.get_mut(child) {
+                sprite.custom_size = Some(Vec2::new(HEALTH_BAR_WIDTH * ratio, HEALTH_BAR_HEIGHT));
+            }
+        }
+    }
+}
+
+/// Shield size relative to max health, capped at a full bar
+pub fn shield_ratio(stats: &UnitStats, shield: Option<&Shield>) -> f32 {
+    match shield {
+        Some(shield) if stats.max_health > 0.0 => (shield.amount / stats.max_health).clamp(0.0, 1.0),
+        _ => 0.0,
+    }
+}
+
+pub fn update_mana_bars(
+    time: Res<Time>,
+    units: Query<(&Children, &UnitStats), With<Unit>>,
+    mut mana_bars: Query<(&mut Sprite, &mut Visibility), With<ManaBar>>,
+    mut backgrounds: Query<&mut Visibility, (With<ManaBarBackground>, Without<ManaBar>)>,
+) {
+    for (children, stats) in units.iter() {
+        let visibility = if has_mana_bar(stats) { Visibility::Inherited } else { Visibility::Hidden };
+        let ratio = mana_ratio(stats);
+        for &child in children.iter() {
+            if let Ok(mut background) = backgrounds.get_mut(child) {
+                *background = visibility;
+            }
+            if let Ok((mut sprite, mut bar_visibility)) = mana_bars.get_mut(child) {
+                *bar_visibility = visibility;
+                sprite.custom_size = Some(Vec2::new(HEALTH_BAR_WIDTH * ratio, MANA_BAR_HEIGHT));
+                sprite.color = mana_bar_color(ratio >= 1.0, time.elapsed_secs());
+            }
+        }
+    }
+}
+
+/// Units that can't accumulate mana (max_mana 0) get no mana bar
+pub fn has_mana_bar(stats: &UnitStats) -> bool {
+    stats.max_mana > 0.0
+}
+
+/// Fill ratio of the mana bar, 0.0 for units without mana
+pub fn mana_ratio(stats: &UnitStats) -> f32 {
+    if !has_mana_bar(stats) {
+        return 0.0;
+    }
+    (stats.mana / stats.max_mana).clamp(0.0, 1.0)
+}
+
+/// Unit's mana is full: its ability goes off as soon as it is cast (or, with manual
+/// cast on, as soon as the player presses its key)
+#[derive(Component)]
+pub struct CastReady;
+
+/// Pulsing ring under a `CastReady` unit; after the cast it bursts outward and fades
+#[derive(Component)]
+pub struct CastRing {
+    pub fade: Option<Timer>,
+}
+
+type CastReadyQuery<'w, 's> =
+    Query<'w, 's, (Entity, &'static UnitStats, Has<CastReady>, Option<&'static Children>), With<Unit>>;
+
+/// Keep `CastReady` and its ring in step with each unit's mana
+pub fn update_cast_ready(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    units: CastReadyQuery,
+    mut rings: Query<&mut CastRing>,
+) {
+    for (entity, stats, ready, children) in units.iter() {
+        if stats.can_cast() && !stats.is_dead() && !ready {
+            commands.entity(entity).insert(CastReady).with_children(|parent| {
+                parent.spawn((
+                    CastRing { fade: None },
+                    Mesh2d(meshes.add(Annulus::new(CAST_RING_INNER_RADIUS, CAST_RING_OUTER_RADIUS))),
+                    MeshMaterial2d(materials.add(ColorMaterial::from_color(CAST_RING_COLOR))),
+                    // Just under the unit's own mesh
+                    Transform::from_xyz(0.0, 0.0, -0.1),
+                ));
+            });
+        } else if !stats.can_cast() && ready {
+            commands.entity(entity).remove::<CastReady>();
+            for &child in children.into_iter().flatten() {
+                if let Ok(mut ring) = rings.get_mut(child) {
+                    ring.fade.get_or_insert_with(|| Timer::from_seconds(CAST_RING_FADE_SECS, TimerMode::Once));
+                }
+            }
+        }
+    }
+}
+
+/// Pulse charged rings; grow and fade spent ones, then despawn them
+pub fn animate_cast_rings(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    mut rings: Query<(Entity, &mut CastRing, &mut Transform, &MeshMaterial2d<ColorMaterial>)>,
+) {
+    for (entity, mut ring, mut transform, material) in rings.iter_mut() {
+        let Some(fade) = ring.fade.as_mut() else {
+            let pulse = (time.elapsed_secs() * CAST_RING_PULSE_RATE * std::f32::consts::TAU).sin();
+            transform.scale = Vec3::splat(1.0 + CAST_RING_PULSE_SCALE * pulse);
+            continue;
+        };
+
+        fade.tick(time.delta());
+        if fade.finished() {
+            commands.entity(entity).despawn_recursive();
+            continue;
+        }
+        let progress = fade.fraction();
+        transform.scale = Vec3::splat(1.0 + progress);
+        if let Some(material) = materials.get_mut(&material.0) {
+            material.color = CAST_RING_COLOR.with_alpha(CAST_RING_COLOR.alpha() * (1.0 - progress));
+        }
+    }
+}
+
+/// Plain blue while filling; alternates with a pale flash once ready to cast
+fn mana_bar_color(full: bool, elapsed: f32) -> Color {
+    if full && (elapsed * MANA_BAR_FLASH_RATE).fract() < 0.5 {
+        MANA_BAR_FULL_COLOR
+    } else {
+        MANA_BAR_COLOR
+    }
+}
+
 fn health_ratio_to_color(ratio: f32) -> Color {
     if ratio > 0.5 {
         Color::srgb(0.2, 0.9, 0.2)
@@ -440,23 +850,132 @@ mod tests {
         assert!(!buff.makes_untargetable());
     }
 
+    #[test]
+    fn test_heal_returns_amount_restored_up_to_max() {
+        let mut stats = UnitStats { health: 90.0, ..UnitStats::default() };
+        assert_eq!(stats.heal(25.0), 10.0);
+        assert_eq!(stats.health, 100.0);
+        assert_eq!(stats.heal(25.0), 0.0);
+    }
+
+    // Mana Bar Tests
+    #[test]
+    fn test_mana_ratio() {
+        let mut mage = UnitStats::for_type(TileType::Purple, 1);
+        assert_eq!(mana_ratio(&mage), 0.0);
+        mage.mana = 20.0;
+        assert!((mana_ratio(&mage) - 0.25).abs() < 0.001);
+        mage.mana = mage.max_mana + 10.0;
+        assert_eq!(mana_ratio(&mage), 1.0, "clamped when overfilled");
+
+        let no_mana = UnitStats { max_mana: 0.0, ..default() };
+        assert_eq!(mana_ratio(&no_mana), 0.0, "no mana pool means an empty bar, not NaN");
+    }
+
+    #[test]
+    fn test_mana_bar_flashes_only_when_full() {
+        assert_eq!(mana_bar_color(false, 0.0), MANA_BAR_COLOR);
+        assert_eq!(mana_bar_color(false, 0.2), MANA_BAR_COLOR);
+        assert_eq!(mana_bar_color(true, 0.0), MANA_BAR_FULL_COLOR);
+        assert_eq!(mana_bar_color(true, 0.2), MANA_BAR_COLOR);
+    }
+
+    #[test]
+    fn test_only_casters_get_a_mana_bar() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .add_systems(Update, spawn_health_bars);
+        let mage = app.world_mut()
+            .spawn((Unit, UnitStats::for_type(TileType::Purple, 1)))
+            .id();
+        let non_caster = app.world_mut()
+            .spawn((Unit, UnitStats { max_mana: 0.0, ..default() }))
+            .id();
+        app.update();
+
+        let count_mana_bars = |app: &mut App, unit: Entity| {
+            let world = app.world_mut();
+            let children: Vec<Entity> = world.get::<Children>(unit).unwrap().iter().copied().collect();
+            children.iter().filter(|&&child| world.get::<ManaBar>(child).is_some()).count()
+        };
+        assert_eq!(count_mana_bars(&mut app, mage), 1);
+        assert_eq!(count_mana_bars(&mut app, non_caster), 0);
+    }
+
+    // Damage Type Tests
+    #[test]
+    fn test_typed_damage_uses_matching_flat_resistance() {
+        let mut tank = UnitStats::for_type(TileType::Blue, 1);
+        let mut mage = UnitStats::for_type(TileType::Purple, 1);
+
+        tank.take_typed_damage(10.0, DamageType::Physical);
+        mage.take_typed_damage(10.0, DamageType::Physical);
+        assert_eq!(tank.max_health - tank.health, 6.0, "tank armor blunts physical hits");
+        assert_eq!(mage.max_health - mage.health, 10.0);
+
+        tank.health = tank.max_health;
+        mage.health = mage.max_health;
+        tank.take_typed_damage(10.0, DamageType::Magical);
+        mage.take_typed_damage(10.0, DamageType::Magical);
+        assert_eq!(tank.max_health - tank.health, 10.0);
+        assert_eq!(mage.max_health - mage.health, 6.0, "mage resist blunts magic");
+    }
+
+    // Poison Debuff Tests
+    #[test]
+    fn test_poison_tick_deals_dps_times_delta() {
+        let mut poison = PoisonDebuff::new(10.0, 2.0);
+        assert_eq!(poison.tick(0.5), 5.0);
+        assert_eq!(poison.remaining, 1.5);
+    }
+
+    #[test]
+    fn test_poison_last_tick_is_capped_at_remaining() {
+        let mut poison = PoisonDebuff::new(10.0, 1.0);
+        poison.tick(0.75);
+        assert_eq!(poison.tick(1.0), 2.5);
+        assert!(poison.is_expired());
+        assert_eq!(poison.tick(1.0), 0.0);
+    }
+
     // Purple (Mage) Meteor Tests
     #[test]
     fn test_meteor_damage_amount() {
-        let meteor_damage = MeteorAbility::damage();
+        let meteor_damage = MeteorAbility::damage(0.0);
         assert_eq!(meteor_damage, 15.0);
     }
 
     #[test]
     fn test_meteor_hits_all_enemies() {
         let enemy_count = 5;
-        let damages = MeteorAbility::calculate_damages(enemy_count);
+        let damages = MeteorAbility::calculate_damages(enemy_count, 0.0);
         assert_eq!(damages.len(), enemy_count);
         for damage in damages {
             assert_eq!(damage, 15.0);
         }
     }
 
+    #[test]
+    fn test_meteor_damage_scales_with_ability_power() {
+        let low = MeteorAbility::damage(10.0);
+        let high = MeteorAbility::damage(30.0);
+        assert!(high > low);
+        // DAMAGE + ability_power * AP_RATIO
+        assert_eq!(low, 15.0 + 10.0 * 0.5);
+        assert_eq!(high, 15.0 + 30.0 * 0.5);
+        assert_eq!(MeteorAbility::calculate_damages(3, 30.0), vec![high; 3]);
+    }
+
+    #[test]
+    fn test_mage_base_ability_power_scales_with_star_rank() {
+        let one_star = UnitStats::for_type(TileType::Purple, 1);
+        let two_star = UnitStats::for_type(TileType::Purple, 2);
+        assert_eq!(one_star.ability_power, 10.0);
+        assert!(two_star.ability_power > one_star.ability_power);
+        // Non-mage units have no ability power
+        assert_eq!(UnitStats::for_type(TileType::Red, 2).ability_power, 0.0);
+    }
+
     // Mana Tests
     #[test]
     fn test_mana_consumed_on_cast() {
@@ -466,4 +985,98 @@ mod tests {
         stats.mana = 0.0;
         assert!(!stats.can_cast());
     }
+
+    // Shield Tests
+    #[test]
+    fn test_shield_soaks_hit_without_touching_health() {
+        let mut stats = UnitStats::for_type(TileType::Red, 1);
+        let health = stats.health;
+        let mut shield = Shield::new(30.0);
+
+        take_shielded_damage(&mut stats, Some(&mut shield), 20.0, DamageType::Physical);
+
+        assert_eq!(stats.health, health);
+        assert_eq!(shield.amount, 10.0);
+        assert!(!shield.is_depleted());
+    }
+
+    #[test]
+    fn test_damage_spills_from_shield_into_health() {
+        let mut stats = UnitStats::for_type(TileType::Red, 1);
+        stats.defense = 0.0;
+        let health = stats.health;
+        let mut shield = Shield::new(15.0);
+
+        take_shielded_damage(&mut stats, Some(&mut shield), 40.0, DamageType::Physical);
+
+        assert!(shield.is_depleted());
+        assert_eq!(stats.health, health - 25.0, "only the overflow reaches health");
+
+        // Resistance still applies to the spill-over
+        stats.defense = 5.0;
+        let mut shield = Shield::new(10.0);
+        take_shielded_damage(&mut stats, Some(&mut shield), 30.0, DamageType::Physical);
+        assert_eq!(stats.health, health - 25.0 - 15.0);
+    }
+
+    #[test]
+    fn test_unshielded_damage_matches_typed_damage() {
+        let mut shielded = UnitStats::for_type(TileType::Blue, 1);
+        let mut plain = shielded.clone();
+        take_shielded_damage(&mut shielded, None, 22.0, DamageType::Magical);
+        plain.take_typed_damage(22.0, DamageType::Magical);
+        assert_eq!(shielded.health, plain.health);
+    }
+
+    #[test]
+    fn test_shield_ratio_caps_at_full_bar() {
+        let stats = UnitStats::for_type(TileType::Red, 1);
+        assert_eq!(shield_ratio(&stats, None), 0.0);
+        assert_eq!(shield_ratio(&stats, Some(&Shield::new(stats.max_health / 2.0))), 0.5);
+        assert_eq!(shield_ratio(&stats, Some(&Shield::new(stats.max_health * 3.0))), 1.0);
+    }
+
+    fn setup_cast_ring_app() -> App {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .insert_resource(bevy::time::TimeUpdateStrategy::ManualDuration(std::time::Duration::from_millis(100)))
+            .init_resource::<Assets<Mesh>>()
+            .init_resource::<Assets<ColorMaterial>>()
+            .add_systems(Update, (update_cast_ready, animate_cast_rings).chain());
+        app
+    }
+
+    fn ring_count(app: &mut App) -> usize {
+        let world = app.world_mut();
+        world.query::<&CastRing>().iter(world).count()
+    }
+
+    #[test]
+    fn test_cast_ready_follows_mana() {
+        let mut app = setup_cast_ring_app();
+        let stats = UnitStats { mana: 50.0, ..UnitStats::for_type(TileType::Purple, 1) };
+        let mage = app.world_mut().spawn((Unit, stats, Team::Player)).id();
+
+        app.update();
+        assert!(app.world().get::<CastReady>(mage).is_none());
+        assert_eq!(ring_count(&mut app), 0);
+
+        let mut stats = app.world_mut().get_mut::<UnitStats>(mage).unwrap();
+        stats.mana = stats.max_mana;
+        app.update();
+        assert!(app.world().get::<CastReady>(mage).is_some(), "full mana marks the unit");
+        assert_eq!(ring_count(&mut app), 1);
+
+        // The cast spends the mana
+        app.world_mut().get_mut::<UnitStats>(mage).unwrap().mana = 0.0;
+        app.update();
+        assert!(app.world().get::<CastReady>(mage).is_none());
+        assert_eq!(ring_count(&mut app), 1, "the ring fades out rather than vanishing");
+
+        for _ in 0..5 {
+            app.update();
+        }
+        assert_eq!(ring_count(&mut app), 0);
+        assert!(app.world().get::<Children>(mage).is_none_or(|children| children.is_empty()));
+    }
 }
@@ -1,17 +1,216 @@
 use crate::prelude::*;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet, VecDeque};
 // TileType, PuzzleBoard, GridPosition, Obstacle are now imported via prelude
+use crate::bridge::ObstacleSpawnEvent;
 use super::{
-    Unit, UnitStats, UnitType, StarRank, Team, BattleGrid, HexPosition,
-    Target, AttackCooldown,
+    Unit, UnitStats, UnitType, StarRank, Team, BattleGrid, HexPosition, DamageType, DamagePopupEvent,
+    Target, AttackCooldown, UnitCensus, Shield, take_shielded_calculated_damage,
 };
 
-#[derive(Resource)]
+/// Seconds a spawn hex is telegraphed before the enemy appears on it
+pub const SPAWN_TELEGRAPH_DURATION: f32 = 0.5;
+/// Pulses the telegraph marker makes over its lifetime
+const SPAWN_TELEGRAPH_PULSES: f32 = 2.0;
+const SPAWN_TELEGRAPH_COLOR: Color = Color::srgba(1.0, 0.25, 0.2, 0.45);
+const SPAWN_TELEGRAPH_SIZE: f32 = 50.0;
+
+/// Most enemies allowed on the field at once; summoners stop adding minions at this cap
+pub const MAX_CONCURRENT_ENEMIES: usize = 12;
+/// First wave that can roll a summoner
+pub const SUMMONER_MIN_WAVE: u32 = 3;
+/// Chance that a spawned enemy (from `SUMMONER_MIN_WAVE` on) is a summoner
+pub const SUMMONER_CHANCE: f32 = 0.15;
+/// Seconds between minions
+pub const SUMMON_INTERVAL: f32 = 4.0;
+/// Minions are 1-star units with this fraction of normal health and attack
+const MINION_STAT_SCALE: f32 = 0.5;
+
+/// Enemy that periodically spawns minions next to itself until it dies
+#[derive(Component)]
+pub struct Summoner {
+    pub timer: Timer,
+}
+
+impl Default for Summoner {
+    fn default() -> Self {
+        Self {
+            timer: Timer::from_seconds(SUMMON_INTERVAL, TimerMode::Repeating),
+        }
+    }
+}
+
+/// Marks an enemy spawned by a summoner rather than by the wave
+#[derive(Component)]
+pub struct Minion;
+
+/// Every `BOSS_WAVE_INTERVAL`th wave is a single boss instead of a group
+pub const BOSS_WAVE_INTERVAL: u32 = 5;
+/// Boss health relative to a 2-star unit of the same type
+pub const BOSS_HEALTH_MULTIPLIER: f32 = 8.0;
+const BOSS_ATTACK_MULTIPLIER: f32 = 1.5;
+const BOSS_SIZE: f32 = 70.0;
+/// Seconds between the obstacles a boss drops on the puzzle board
+pub const BOSS_OBSTACLE_INTERVAL: f32 = 6.0;
+
+/// Boss enemy; drops an obstacle on the puzzle board every `BOSS_OBSTACLE_INTERVAL`
+#[derive(Component)]
+pub struct Boss {
+    pub obstacle_timer: Timer,
+}
+
+impl Default for Boss {
+    fn default() -> Self {
+        Self {
+            obstacle_timer: Timer::from_seconds(BOSS_OBSTACLE_INTERVAL, TimerMode::Repeating),
+        }
+    }
+}
+
+/// First wave that can roll a modifier
+pub const MODIFIER_MIN_WAVE: u32 = 3;
+/// Chance that a regular wave (from `MODIFIER_MIN_WAVE` on) rolls a modifier
+pub const MODIFIER_CHANCE: f32 = 0.3;
+const ARMORED_DEFENSE_BONUS: f32 = 20.0;
+const SWIFT_ATTACK_SPEED_MULTIPLIER: f32 = 1.3;
+/// Turns until a death bomb from an Explosive wave goes off
+const DEATH_BOMB_COUNTDOWN: u8 = 3;
+
+/// Twist applied to every enemy a wave spawns
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WaveModifier {
+    /// +20 defense
+    Armored,
+    /// +30% attack speed
+    Swift,
+    /// Leaves a bomb on the puzzle board when killed
+    Explosive,
+}
+
+impl WaveModifier {
+    pub const ALL: [WaveModifier; 3] = [WaveModifier::Armored, WaveModifier::Swift, WaveModifier::Explosive];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            WaveModifier::Armored => "Armored",
+            WaveModifier::Swift => "Swift",
+            WaveModifier::Explosive => "Explosive",
+        }
+    }
+
+    /// Stat changes for an enemy spawned under this modifier
+    pub fn apply(&self, stats: UnitStats) -> UnitStats {
+        match self {
+            WaveModifier::Armored => UnitStats { defense: stats.defense + ARMORED_DEFENSE_BONUS, ..stats },
+            WaveModifier::Swift => UnitStats { attack_speed: stats.attack_speed * SWIFT_ATTACK_SPEED_MULTIPLIER, ..stats },
+            WaveModifier::Explosive => stats,
+        }
+    }
+}
+
+/// First wave that can roll Runner and Caster enemies
+pub const ARCHETYPE_MIN_WAVE: u32 = 2;
+/// Roll weight of each specialist archetype on `ARCHETYPE_MIN_WAVE`; grows per wave
+const SPECIALIST_BASE_WEIGHT: u32 = 10;
+const SPECIALIST_WEIGHT_PER_WAVE: u32 = 3;
+/// Specialists stop getting more common here, leaving half of all enemies Standard
+const SPECIALIST_MAX_WEIGHT: u32 = 25;
+const ARCHETYPE_TOTAL_WEIGHT: u32 = 100;
+const RUNNER_MOVE_SPEED: f32 = 2.0;
+const RUNNER_HEALTH_MULTIPLIER: f32 = 0.7;
+pub const CASTER_ATTACK_RANGE: i32 = 3;
+const CASTER_ATTACK_MULTIPLIER: f32 = 0.8;
+
+/// Enemy role rolled at spawn, independent of its tile color
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EnemyArchetype {
+    /// Fights whatever is closest
+    Standard,
+    /// Fast and frail; rushes the base
+    Runner,
+    /// Fires projectiles from range
+    Caster,
+}
+
+impl EnemyArchetype {
+    pub const ALL: [EnemyArchetype; 3] = [EnemyArchetype::Standard, EnemyArchetype::Runner, EnemyArchetype::Caster];
+
+    /// Roll weights for `wave`, in `ALL` order; each sums to `ARCHETYPE_TOTAL_WEIGHT`
+    pub fn weights(wave: u32) -> [u32; 3] {
+        if wave < ARCHETYPE_MIN_WAVE {
+            return [ARCHETYPE_TOTAL_WEIGHT, 0, 0];
+        }
+        let specialist = (SPECIALIST_BASE_WEIGHT + SPECIALIST_WEIGHT_PER_WAVE * (wave - ARCHETYPE_MIN_WAVE))
+            .min(SPECIALIST_MAX_WEIGHT);
+        [ARCHETYPE_TOTAL_WEIGHT - 2 * specialist, specialist, specialist]
+    }
+
+    pub fn roll(wave: u32, rng: &mut impl Rng) -> EnemyArchetype {
+        let mut roll = rng.gen_range(0..ARCHETYPE_TOTAL_WEIGHT);
+        for (archetype, weight) in Self::ALL.into_iter().zip(Self::weights(wave)) {
+            if roll < weight {
+                return archetype;
+            }
+            roll -= weight;
+        }
+        EnemyArchetype::Standard
+    }
+
+    pub fn is_ranged(&self) -> bool {
+        *self == EnemyArchetype::Caster
+    }
+
+    /// What an enemy of this archetype moves toward
+    pub fn goal(&self) -> EnemyGoal {
+        match self {
+            EnemyArchetype::Runner => EnemyGoal::RushBase,
+            EnemyArchetype::Standard | EnemyArchetype::Caster => EnemyGoal::Fight,
+        }
+    }
+
+    /// Stat changes for an enemy of this archetype
+    pub fn apply(&self, stats: UnitStats) -> UnitStats {
+        match self {
+            EnemyArchetype::Standard => stats,
+            EnemyArchetype::Runner => UnitStats {
+                health: stats.health * RUNNER_HEALTH_MULTIPLIER,
+                max_health: stats.max_health * RUNNER_HEALTH_MULTIPLIER,
+                move_speed: RUNNER_MOVE_SPEED,
+                ..stats
+            },
+            EnemyArchetype::Caster => UnitStats {
+                attack: stats.attack * CASTER_ATTACK_MULTIPLIER,
+                attack_range: stats.attack_range.max(CASTER_ATTACK_RANGE),
+                ..stats
+            },
+        }
+    }
+}
+
+/// Movement goal of an enemy; units without one fight
+#[derive(Component, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum EnemyGoal {
+    /// Close in on its combat target
+    #[default]
+    Fight,
+    /// Path to the base row past everything, attacking only when walled off
+    RushBase,
+}
+
+/// Enemy from an Explosive wave; `death_system` drops a bomb on the board when it dies
+#[derive(Component)]
+pub struct ExplosiveOnDeath;
+
+#[derive(Resource, Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct WaveManager {
     pub current_wave: u32,
     pub enemies_remaining: u32,
     pub wave_timer: f32,
     pub spawn_delay: f32,
     pub wave_active: bool,
+    /// Modifier rolled for the current wave, if any
+    pub modifier: Option<WaveModifier>,
 }
 
 impl Default for WaveManager {
@@ -22,33 +221,53 @@ impl Default for WaveManager {
             wave_timer: 3.0,
             spawn_delay: 0.0,
             wave_active: false,
+            modifier: None,
         }
     }
 }
 
 impl WaveManager {
-    pub fn start_wave(&mut self, wave_number: u32) {
+    pub fn start_wave(&mut self, wave_number: u32, difficulty: Difficulty) {
         self.current_wave = wave_number;
         self.wave_active = true;
         self.spawn_delay = 0.5;
-        self.enemies_remaining = self.enemies_for_wave(wave_number);
+        self.enemies_remaining = self.enemies_for_wave(wave_number, difficulty);
+        self.modifier = None;
+    }
+
+    /// Roll the current wave's modifier. Early waves and boss waves never get one.
+    pub fn roll_modifier(&mut self, rng: &mut impl Rng) {
+        self.modifier = Self::modifier_for_wave(self.current_wave, rng);
+    }
+
+    pub fn modifier_for_wave(wave: u32, rng: &mut impl Rng) -> Option<WaveModifier> {
+        if wave < MODIFIER_MIN_WAVE || Self::is_boss_wave(wave) || rng.gen::<f32>() >= MODIFIER_CHANCE {
+            return None;
+        }
+        Some(WaveModifier::ALL[rng.gen_range(0..WaveModifier::ALL.len())])
     }
 
-    pub fn enemies_for_wave(&self, wave: u32) -> u32 {
-        (3 + wave * 2).min(12)
+    pub fn is_boss_wave(wave: u32) -> bool {
+        wave > 0 && wave.is_multiple_of(BOSS_WAVE_INTERVAL)
     }
 
-    pub fn enemy_star_rank(&self, wave: u32) -> u8 {
+    pub fn enemies_for_wave(&self, wave: u32, difficulty: Difficulty) -> u32 {
+        if Self::is_boss_wave(wave) {
+            return 1;
+        }
+        difficulty.enemies_for_wave(wave)
+    }
+
+    pub fn enemy_star_rank(&self, wave: u32, rng: &mut impl Rng) -> u8 {
         match wave {
             0..=2 => 1,
-            3..=5 => if rand::random::<f32>() < 0.3 { 2 } else { 1 },
-            _ => if rand::random::<f32>() < 0.5 { 2 } else { 1 },
+            3..=5 => if rng.gen::<f32>() < 0.3 { 2 } else { 1 },
+            _ => if rng.gen::<f32>() < 0.5 { 2 } else { 1 },
         }
     }
 
-    pub fn random_enemy_type() -> TileType {
-        use rand::Rng;
-        match rand::thread_rng().gen_range(0..5) {
+    pub fn random_enemy_type(rng: &mut impl Rng) -> TileType {
+        match rng.gen_range(0..5) {
             0 => TileType::Red,
             1 => TileType::Blue,
             2 => TileType::Green,
@@ -58,29 +277,55 @@ impl WaveManager {
     }
 }
 
+/// Fired when a new wave begins spawning
+#[derive(Event)]
+pub struct WaveStartEvent {
+    pub wave_number: u32,
+}
+
+/// Pulsing marker on a hex an enemy is about to spawn into. The hex is reserved on
+/// the grid under the marker's entity until the enemy materializes.
+#[derive(Component)]
+pub struct SpawnTelegraph {
+    pub pos: HexPosition,
+    pub timer: Timer,
+}
+
+impl SpawnTelegraph {
+    pub fn new(pos: HexPosition) -> Self {
+        Self {
+            pos,
+            timer: Timer::from_seconds(SPAWN_TELEGRAPH_DURATION, TimerMode::Once),
+        }
+    }
+}
+
+/// Start waves and, on each spawn beat, reserve a hex and telegraph the next enemy.
+/// `enemies_remaining` only drops when an enemy materializes, so pending telegraphs
+/// are counted here to avoid reserving more hexes than there are enemies left.
 pub fn wave_spawner_system(
-    time: Res<Time>,
+    time: ScaledTime,
     mut wave_manager: ResMut<WaveManager>,
     mut commands: Commands,
     mut grid: ResMut<BattleGrid>,
-    mut meshes: ResMut<Assets<Mesh>>,
-    mut materials: ResMut<Assets<ColorMaterial>>,
-    enemy_units: Query<Entity, (With<Unit>, With<Team>)>,
+    telegraphs: Query<(), With<SpawnTelegraph>>,
     current_phase: Res<State<PhaseState>>,
+    mut rng: ResMut<GameRng>,
+    difficulty: Res<Difficulty>,
 ) {
     // WaveBreak中はWave処理を停止（配置時間を確保）
     if *current_phase.get() == PhaseState::WaveBreak {
         return;
     }
 
-    let _enemy_count = enemy_units.iter().count();
-
     if !wave_manager.wave_active {
         wave_manager.wave_timer -= time.delta_secs();
         if wave_manager.wave_timer <= 0.0 {
             let next_wave = wave_manager.current_wave + 1;
-            wave_manager.start_wave(next_wave);
+            wave_manager.start_wave(next_wave, *difficulty);
+            wave_manager.roll_modifier(&mut *rng);
             wave_manager.wave_timer = 10.0;
+            commands.trigger(WaveStartEvent { wave_number: next_wave });
         }
         return;
     }
@@ -91,19 +336,72 @@ pub fn wave_spawner_system(
     }
 
     wave_manager.spawn_delay -= time.delta_secs();
-    if wave_manager.spawn_delay > 0.0 {
+    if wave_manager.spawn_delay > 0.0 || wave_manager.enemies_remaining as usize <= telegraphs.iter().count() {
         return;
     }
 
     if let Some(pos) = find_enemy_spawn_position(&grid) {
-        let unit_type = WaveManager::random_enemy_type();
-        let star_rank = wave_manager.enemy_star_rank(wave_manager.current_wave);
-        spawn_enemy_unit(&mut commands, &mut grid, unit_type, star_rank, pos, &mut meshes, &mut materials);
-        wave_manager.enemies_remaining -= 1;
+        let telegraph = commands
+            .spawn((
+                SpawnTelegraph::new(pos),
+                Sprite {
+                    color: SPAWN_TELEGRAPH_COLOR,
+                    custom_size: Some(Vec2::splat(SPAWN_TELEGRAPH_SIZE)),
+                    ..default()
+                },
+                Transform::from_translation(grid.axial_to_pixel(&pos).extend(0.3)),
+            ))
+            .id();
+        grid.place_unit(pos, telegraph);
         wave_manager.spawn_delay = 0.8;
     }
 }
 
+/// Pulse each telegraph and, once its timer runs out, swap it for the enemy it announced
+pub fn materialize_spawn_telegraphs(
+    time: ScaledTime,
+    mut wave_manager: ResMut<WaveManager>,
+    mut commands: Commands,
+    mut grid: ResMut<BattleGrid>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    mut telegraphs: Query<(Entity, &mut SpawnTelegraph, &mut Transform)>,
+    mut rng: ResMut<GameRng>,
+    difficulty: Res<Difficulty>,
+) {
+    for (telegraph, mut spawn, mut transform) in telegraphs.iter_mut() {
+        spawn.timer.tick(time.delta());
+        let pulse = (spawn.timer.fraction() * SPAWN_TELEGRAPH_PULSES * std::f32::consts::TAU).sin();
+        transform.scale = Vec3::splat(1.0 + 0.15 * pulse);
+        if !spawn.timer.finished() {
+            continue;
+        }
+
+        commands.entity(telegraph).despawn();
+        grid.remove_unit(&spawn.pos);
+        wave_manager.enemies_remaining = wave_manager.enemies_remaining.saturating_sub(1);
+        let pos = spawn.pos;
+
+        if WaveManager::is_boss_wave(wave_manager.current_wave) {
+            let boss = spawn_boss_unit(&mut commands, &mut grid, wave_manager.current_wave, pos, &mut meshes, &mut materials);
+            commands.entity(boss).insert(scale_enemy_stats(boss_stats(wave_manager.current_wave), *difficulty));
+            continue;
+        }
+        let unit_type = WaveManager::random_enemy_type(&mut *rng);
+        let star_rank = wave_manager.enemy_star_rank(wave_manager.current_wave, &mut *rng);
+        let entity = spawn_enemy_unit(&mut commands, &mut grid, unit_type, star_rank, pos, &mut meshes, &mut materials);
+        let archetype = EnemyArchetype::roll(wave_manager.current_wave, &mut *rng);
+        let stats = archetype.apply(scale_enemy_stats(UnitStats::for_type(unit_type, star_rank), *difficulty));
+        commands.entity(entity).insert((wave_modified_stats(stats, wave_manager.modifier), archetype, archetype.goal()));
+        if wave_manager.modifier == Some(WaveModifier::Explosive) {
+            commands.entity(entity).insert(ExplosiveOnDeath);
+        }
+        if wave_manager.current_wave >= SUMMONER_MIN_WAVE && rng.gen::<f32>() < SUMMONER_CHANCE {
+            commands.entity(entity).insert(Summoner::default());
+        }
+    }
+}
+
 fn find_enemy_spawn_position(grid: &BattleGrid) -> Option<HexPosition> {
     for r in 1..=BATTLE_GRID_ROWS / 2 {
         for q in -BATTLE_GRID_COLS / 2..=BATTLE_GRID_COLS / 2 {
@@ -116,6 +414,154 @@ fn find_enemy_spawn_position(grid: &BattleGrid) -> Option<HexPosition> {
     None
 }
 
+/// Free hex next to `center`, checked in neighbor order
+fn find_spawn_position_near(grid: &BattleGrid, center: &HexPosition) -> Option<HexPosition> {
+    center
+        .neighbors()
+        .into_iter()
+        .find(|pos| grid.is_valid_position(pos) && !grid.is_occupied(pos))
+}
+
+/// Enemy health and attack adjusted for the chosen difficulty
+pub fn scale_enemy_stats(stats: UnitStats, difficulty: Difficulty) -> UnitStats {
+    let health_scale = difficulty.enemy_health_multiplier();
+    UnitStats {
+        health: stats.health * health_scale,
+        max_health: stats.max_health * health_scale,
+        attack: stats.attack * difficulty.enemy_attack_multiplier(),
+        ..stats
+    }
+}
+
+/// Enemy stats with the wave's modifier, if any, layered on top
+pub fn wave_modified_stats(stats: UnitStats, modifier: Option<WaveModifier>) -> UnitStats {
+    match modifier {
+        Some(modifier) => modifier.apply(stats),
+        None => stats,
+    }
+}
+
+/// Bomb left on a random board cell by a dying Explosive-wave enemy
+pub fn spawn_death_bomb(commands: &mut Commands, rng: &mut GameRng, board_size: usize) {
+    let x = rng.gen_range(0..board_size);
+    let y = rng.gen_range(0..board_size);
+    commands.trigger(ObstacleSpawnEvent {
+        position: (x, y),
+        obstacle_type: ObstacleType::Bomb,
+        countdown: Some(DEATH_BOMB_COUNTDOWN),
+    });
+}
+
+/// Weakened stats for a summoned minion
+pub fn minion_stats(unit_type: TileType) -> UnitStats {
+    let base = UnitStats::for_type(unit_type, 1);
+    UnitStats {
+        health: base.health * MINION_STAT_SCALE,
+        max_health: base.max_health * MINION_STAT_SCALE,
+        attack: base.attack * MINION_STAT_SCALE,
+        ..base
+    }
+}
+
+/// Each living summoner spawns a minion beside itself every `SUMMON_INTERVAL`,
+/// skipping the beat when boxed in or when the field is at the enemy cap.
+/// Minions are ordinary enemies, so they count toward the wave-clear check.
+pub fn summoner_system(
+    mut commands: Commands,
+    time: ScaledTime,
+    census: Res<UnitCensus>,
+    difficulty: Res<Difficulty>,
+    mut grid: ResMut<BattleGrid>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    mut summoners: Query<(&HexPosition, &UnitStats, &mut Summoner), With<Unit>>,
+) {
+    let mut enemy_count = census.count(Team::Enemy);
+
+    for (pos, stats, mut summoner) in summoners.iter_mut() {
+        if stats.is_dead() {
+            continue;
+        }
+        summoner.timer.tick(time.delta());
+        if !summoner.timer.just_finished() || enemy_count >= MAX_CONCURRENT_ENEMIES {
+            continue;
+        }
+        let Some(spawn_pos) = find_spawn_position_near(&grid, pos) else { continue };
+
+        let entity = spawn_enemy_unit(&mut commands, &mut grid, TileType::Red, 1, spawn_pos, &mut meshes, &mut materials);
+        commands.entity(entity).insert((scale_enemy_stats(minion_stats(TileType::Red), *difficulty), Minion));
+        enemy_count += 1;
+    }
+}
+
+/// Stats for the boss of `wave`: a 2-star Red brute with far more health,
+/// growing with each boss wave
+pub fn boss_stats(wave: u32) -> UnitStats {
+    let base = UnitStats::for_type(TileType::Red, 2);
+    let health_scale = BOSS_HEALTH_MULTIPLIER * (wave / BOSS_WAVE_INTERVAL).max(1) as f32;
+    UnitStats {
+        health: base.health * health_scale,
+        max_health: base.max_health * health_scale,
+        attack: base.attack * BOSS_ATTACK_MULTIPLIER,
+        ..base
+    }
+}
+
+/// Spawn the boss of a boss wave: an oversized enemy with boosted stats
+pub fn spawn_boss_unit(
+    commands: &mut Commands,
+    grid: &mut ResMut<BattleGrid>,
+    wave: u32,
+    pos: HexPosition,
+    meshes: &mut ResMut<Assets<Mesh>>,
+    materials: &mut ResMut<Assets<ColorMaterial>>,
+) -> Entity {
+    let entity = spawn_enemy_unit(commands, grid, TileType::Red, 2, pos, meshes, materials);
+    commands.entity(entity).insert((
+        boss_stats(wave),
+        Mesh2d(meshes.add(enemy_triangle(BOSS_SIZE))),
+        Boss::default(),
+    ));
+    entity
+}
+
+/// Living bosses drop an obstacle on a random board cell on every timer beat,
+/// independent of the per-attack obstacle chance
+pub fn boss_obstacle_system(
+    mut commands: Commands,
+    time: ScaledTime,
+    board_config: Res<BoardConfig>,
+    mut rng: ResMut<GameRng>,
+    mut bosses: Query<(&UnitStats, &mut Boss), With<Unit>>,
+) {
+    for (stats, mut boss) in bosses.iter_mut() {
+        if stats.is_dead() {
+            continue;
+        }
+        boss.obstacle_timer.tick(time.delta());
+        if !boss.obstacle_timer.just_finished() {
+            continue;
+        }
+        let x = rng.gen_range(0..board_config.size);
+        let y = rng.gen_range(0..board_config.size);
+        commands.trigger(ObstacleSpawnEvent {
+            position: (x, y),
+            obstacle_type: ObstacleType::Stone,
+            countdown: None,
+        });
+    }
+}
+
+/// Enemy units: downward triangle (▼)
+fn enemy_triangle(size: f32) -> Triangle2d {
+    let half = size / 2.0;
+    Triangle2d::new(
+        Vec2::new(0.0, -half),     // bottom
+        Vec2::new(half, half),     // top-right
+        Vec2::new(-half, half),    // top-left
+    )
+}
+
 fn spawn_enemy_unit(
     commands: &mut Commands,
     grid: &mut ResMut<BattleGrid>,
@@ -124,21 +570,15 @@ fn spawn_enemy_unit(
     pos: HexPosition,
     meshes: &mut ResMut<Assets<Mesh>>,
     materials: &mut ResMut<Assets<ColorMaterial>>,
-) {
+) -> Entity {
     let stats = UnitStats::for_type(unit_type, star_rank);
+    let cooldown = AttackCooldown::for_stats(&stats);
     let world_pos = grid.axial_to_pixel(&pos);
     let size = 30.0 + (star_rank as f32 * 5.0);
-    let half = size / 2.0;
 
     let mut color = unit_type.color();
     color = color.darker(0.3);
-
-    // Enemy units: downward triangle (▼)
-    let triangle = Triangle2d::new(
-        Vec2::new(0.0, -half),     // bottom
-        Vec2::new(half, half),     // top-right
-        Vec2::new(-half, half),    // top-left
-    );
+    let triangle = enemy_triangle(size);
 
     let entity = commands
         .spawn((
@@ -149,7 +589,7 @@ fn spawn_enemy_unit(
             pos,
             Team::Enemy,
             Target(None),
-            AttackCooldown(0.0),
+            cooldown,
             Mesh2d(meshes.add(triangle)),
             MeshMaterial2d(materials.add(ColorMaterial::from_color(color))),
             Transform::from_translation(world_pos.extend(1.0)),
@@ -157,6 +597,7 @@ fn spawn_enemy_unit(
         .id();
 
     grid.place_unit(pos, entity);
+    entity
 }
 
 #[derive(Event)]
@@ -175,6 +616,15 @@ pub struct BombExplosionEffect {
 /// Interval between bomb countdown decrements (in seconds)
 /// Increase this value to slow down bomb countdown
 pub const BOMB_COUNTDOWN_INTERVAL: f32 = 1.5;
+/// Bomb damage on wave 1
+pub const BOMB_BASE_DAMAGE: u32 = 10;
+/// Extra bomb damage for each wave after the first
+pub const BOMB_DAMAGE_PER_WAVE: u32 = 2;
+
+/// Damage a bomb deals when it goes off during `wave`
+pub fn bomb_damage_for_wave(wave: u32) -> u32 {
+    BOMB_BASE_DAMAGE + BOMB_DAMAGE_PER_WAVE * wave.saturating_sub(1)
+}
 
 /// Resource to track bomb countdown timing
 #[derive(Resource)]
@@ -188,10 +638,38 @@ impl Default for BombCountdownTimer {
     }
 }
 
+/// Orthogonally adjacent bombs set each other off: flood out from the bombs that
+/// ran out and return every cell that goes up, each exactly once
+pub fn bomb_chain(
+    detonating: &[(usize, usize)],
+    bombs: &HashSet<(usize, usize)>,
+) -> Vec<(usize, usize)> {
+    let mut visited: HashSet<(usize, usize)> = detonating.iter().copied().collect();
+    let mut queue: VecDeque<(usize, usize)> = detonating.iter().copied().collect();
+    let mut chain = Vec::new();
+
+    while let Some((x, y)) = queue.pop_front() {
+        chain.push((x, y));
+        let neighbors = [
+            x.checked_sub(1).map(|x| (x, y)),
+            Some((x + 1, y)),
+            y.checked_sub(1).map(|y| (x, y)),
+            Some((x, y + 1)),
+        ];
+        for neighbor in neighbors.into_iter().flatten() {
+            if bombs.contains(&neighbor) && visited.insert(neighbor) {
+                queue.push_back(neighbor);
+            }
+        }
+    }
+    chain
+}
+
 pub fn bomb_countdown_system(
     mut commands: Commands,
-    time: Res<Time>,
+    time: ScaledTime,
     mut countdown_timer: ResMut<BombCountdownTimer>,
+    wave_manager: Res<WaveManager>,
     mut board: ResMut<PuzzleBoard>,
     mut obstacles: Query<(Entity, &GridPosition, &mut Obstacle)>,
 ) {
@@ -202,46 +680,56 @@ pub fn bomb_countdown_system(
     }
     countdown_timer.timer = 0.0;
 
+    let mut bombs: HashMap<(usize, usize), Entity> = HashMap::new();
+    let mut detonating = Vec::new();
     for (entity, pos, mut obstacle) in obstacles.iter_mut() {
-        if obstacle.is_bomb() {
-            if let Some(ref mut countdown) = obstacle.countdown {
-                if *countdown > 0 {
-                    *countdown -= 1;
-                } else {
-                    // Spawn explosion effect at bomb position
-                    let world_pos = board.grid_to_world(pos.x, pos.y);
-                    commands.spawn((
-                        BombExplosionEffect {
-                            timer: 0.0,
-                            duration: 0.5,
-                        },
-                        Sprite {
-                            color: Color::srgba(1.0, 0.5, 0.0, 1.0),
-                            custom_size: Some(Vec2::splat(TILE_SIZE)),
-                            ..default()
-                        },
-                        Transform::from_translation(world_pos.extend(1.0)),
-                    ));
-
-                    // Trigger damage event
-                    commands.trigger(BombDamageEvent {
-                        position: (pos.x, pos.y),
-                        damage: 10,
-                    });
-                    // Clear the obstacle from the board
-                    board.clear_obstacle(pos.x, pos.y);
-                    // Despawn the bomb entity entirely
-                    commands.entity(entity).despawn_recursive();
-                }
+        if !obstacle.is_bomb() {
+            continue;
+        }
+        bombs.insert((pos.x, pos.y), entity);
+        if let Some(ref mut countdown) = obstacle.countdown {
+            if *countdown > 0 {
+                *countdown -= 1;
+            } else {
+                detonating.push((pos.x, pos.y));
             }
         }
     }
+
+    let damage = bomb_damage_for_wave(wave_manager.current_wave);
+    let bomb_cells: HashSet<(usize, usize)> = bombs.keys().copied().collect();
+    for (x, y) in bomb_chain(&detonating, &bomb_cells) {
+        // Spawn explosion effect at bomb position
+        let world_pos = board.grid_to_world(x, y);
+        commands.spawn((
+            BombExplosionEffect {
+                timer: 0.0,
+                duration: 0.5,
+            },
+            Sprite {
+                color: Color::srgba(1.0, 0.5, 0.0, 1.0),
+                custom_size: Some(Vec2::splat(TILE_SIZE)),
+                ..default()
+            },
+            Transform::from_translation(world_pos.extend(1.0)),
+        ));
+
+        // Trigger damage event; every bomb in a chain deals its own damage
+        commands.trigger(BombDamageEvent {
+            position: (x, y),
+            damage,
+        });
+        // Clear the bomb from the board; ice/stone under it stays
+        board.clear_bomb(x, y);
+        // Despawn the bomb entity entirely
+        commands.entity(bombs[&(x, y)]).despawn_recursive();
+    }
 }
 
 /// Animates and removes bomb explosion effects
 pub fn animate_bomb_explosion(
     mut commands: Commands,
-    time: Res<Time>,
+    time: ScaledTime,
     mut effects: Query<(Entity, &mut BombExplosionEffect, &mut Transform, &mut Sprite)>,
 ) {
     for (entity, mut effect, mut transform, mut sprite) in effects.iter_mut() {
@@ -259,17 +747,50 @@ pub fn animate_bomb_explosion(
     }
 }
 
+/// Unit a bomb lands on. The puzzle board has no spatial link to the battlefield,
+/// so the blast goes to the living player unit with the least HP left (ties go to
+/// the lowest entity, to stay deterministic) instead of hitting the whole team.
+pub fn bomb_target<'a>(units: impl IntoIterator<Item = (Entity, &'a UnitStats, &'a Team)>) -> Option<Entity> {
+    units
+        .into_iter()
+        .filter(|(_, stats, team)| **team == Team::Player && !stats.is_dead())
+        .min_by(|a, b| a.1.health.total_cmp(&b.1.health).then(a.0.cmp(&b.0)))
+        .map(|(entity, _, _)| entity)
+}
+
+type BombTargetQuery<'w, 's> = Query<
+    'w,
+    's,
+    (Entity, &'static mut UnitStats, &'static Team, &'static HexPosition, Option<&'static mut Shield>),
+    With<Unit>,
+>;
+
+/// Bombs are environmental: the hit is not credited to anyone in `BattleStats`
 pub fn handle_bomb_damage(
     trigger: Trigger<BombDamageEvent>,
-    mut player_units: Query<&mut UnitStats, (With<Unit>, With<Team>)>,
+    mut commands: Commands,
+    grid: Res<BattleGrid>,
+    mut units: BombTargetQuery,
 ) {
     let event = trigger.event();
-    let damage = event.damage as f32;
+    let Some(target) = bomb_target(units.iter().map(|(entity, stats, team, ..)| (entity, stats, team))) else {
+        return;
+    };
+    let Ok((_, mut stats, _, pos, mut shield)) = units.get_mut(target) else { return };
 
-    // Apply damage to player units (simplified: damage all friendly units)
-    for mut stats in player_units.iter_mut() {
-        stats.health = (stats.health - damage).max(0.0);
+    // A shield, then defense, soaks the blast
+    let before = stats.health;
+    take_shielded_calculated_damage(&mut stats, shield.as_deref_mut(), event.damage as f32, DamageType::Physical);
+    if shield.is_some_and(|shield| shield.is_depleted()) {
+        commands.entity(target).remove::<Shield>();
     }
+    commands.trigger(DamagePopupEvent {
+        position: grid.axial_to_pixel(pos).extend(0.0),
+        damage: (before - stats.health).round() as i32,
+        is_critical: false,
+        is_poison: false,
+        is_execute: false,
+    });
 }
 
 // ============================================================
@@ -365,6 +886,103 @@ mod tests {
         assert_eq!(enemy_count, 0, "Should be 0 when no enemies remain");
     }
 
+    // ============================================================
+    // Summoner Tests
+    // ============================================================
+
+    // Below the 250ms virtual-time delta cap
+    const STEP: f32 = 0.2;
+
+    fn setup_summoner_app() -> App {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .insert_resource(bevy::time::TimeUpdateStrategy::ManualDuration(
+                std::time::Duration::from_secs_f32(STEP),
+            ))
+            .insert_resource(BattleGrid::new())
+            .init_resource::<UnitCensus>()
+            .init_resource::<Difficulty>()
+            .init_resource::<TimeScale>()
+            .init_resource::<GameSpeed>()
+            .init_resource::<Assets<Mesh>>()
+            .init_resource::<Assets<ColorMaterial>>()
+            .add_systems(Update, summoner_system);
+        app
+    }
+
+    fn spawn_summoner(app: &mut App, pos: HexPosition) -> Entity {
+        let entity = app
+            .world_mut()
+            .spawn((Unit, UnitType(TileType::Purple), pos, UnitStats::for_type(TileType::Purple, 1), Team::Enemy, Summoner::default()))
+            .id();
+        app.world_mut().resource_mut::<BattleGrid>().place_unit(pos, entity);
+        entity
+    }
+
+    fn minion_count(app: &mut App) -> usize {
+        let world = app.world_mut();
+        world.query_filtered::<(), With<Minion>>().iter(world).count()
+    }
+
+    /// Advance by `seconds` of game time
+    fn run_for(app: &mut App, seconds: f32) {
+        for _ in 0..(seconds / STEP).round() as usize {
+            app.update();
+        }
+    }
+
+    #[test]
+    fn test_summoner_spawns_minion_on_interval() {
+        let mut app = setup_summoner_app();
+        let center = HexPosition::new(0, 1);
+        spawn_summoner(&mut app, center);
+        // First update only primes the clock
+        app.update();
+
+        run_for(&mut app, SUMMON_INTERVAL - STEP);
+        assert_eq!(minion_count(&mut app), 0);
+
+        run_for(&mut app, STEP);
+        assert_eq!(minion_count(&mut app), 1);
+
+        let world = app.world_mut();
+        let (pos, stats) = world.query_filtered::<(&HexPosition, &UnitStats), With<Minion>>().single(world);
+        let (pos, max_health) = (*pos, stats.max_health);
+        assert_eq!(pos.distance(&center), 1, "minion appears next to the summoner");
+        assert!(max_health < UnitStats::for_type(TileType::Red, 1).max_health);
+        assert!(app.world().resource::<BattleGrid>().is_occupied(&pos));
+    }
+
+    #[test]
+    fn test_summoner_stops_when_removed() {
+        let mut app = setup_summoner_app();
+        let summoner = spawn_summoner(&mut app, HexPosition::new(0, 1));
+        app.update();
+        run_for(&mut app, SUMMON_INTERVAL);
+        assert_eq!(minion_count(&mut app), 1);
+
+        app.world_mut().entity_mut(summoner).despawn();
+        run_for(&mut app, SUMMON_INTERVAL * 3.0);
+
+        assert_eq!(minion_count(&mut app), 1);
+    }
+
+    #[test]
+    fn test_summoner_respects_enemy_cap() {
+        let mut app = setup_summoner_app();
+        app.add_systems(Update, super::super::census::update_unit_census.before(summoner_system));
+        spawn_summoner(&mut app, HexPosition::new(0, 1));
+        // Fill the field up to the cap (the summoner itself counts)
+        for _ in 1..MAX_CONCURRENT_ENEMIES {
+            app.world_mut().spawn((Unit, UnitType(TileType::Red), Team::Enemy));
+        }
+        app.update();
+
+        run_for(&mut app, SUMMON_INTERVAL * 2.0);
+
+        assert_eq!(minion_count(&mut app), 0);
+    }
+
     // ============================================================
     // WaveManager Tests
     // ============================================================
@@ -372,18 +990,550 @@ mod tests {
     #[test]
     fn test_wave_manager_enemies_for_wave() {
         let wm = WaveManager::default();
-        assert_eq!(wm.enemies_for_wave(0), 3);
-        assert_eq!(wm.enemies_for_wave(1), 5);
-        assert_eq!(wm.enemies_for_wave(5), 12);
-        assert_eq!(wm.enemies_for_wave(10), 12); // Max capped at 12
+        assert_eq!(wm.enemies_for_wave(0, Difficulty::Normal), 3);
+        assert_eq!(wm.enemies_for_wave(1, Difficulty::Normal), 5);
+        assert_eq!(wm.enemies_for_wave(6, Difficulty::Normal), 12);
+        assert_eq!(wm.enemies_for_wave(11, Difficulty::Normal), 12); // Max capped at 12
+    }
+
+    // ============================================================
+    // Boss Wave Tests
+    // ============================================================
+
+    #[test]
+    fn test_boss_wave_every_fifth_wave() {
+        assert!(!WaveManager::is_boss_wave(0));
+        assert!(!WaveManager::is_boss_wave(4));
+        assert!(WaveManager::is_boss_wave(5));
+        assert!(!WaveManager::is_boss_wave(6));
+        assert!(WaveManager::is_boss_wave(10));
+    }
+
+    #[test]
+    fn test_boss_wave_spawns_single_enemy() {
+        let wm = WaveManager::default();
+        assert_eq!(wm.enemies_for_wave(5, Difficulty::Normal), 1);
+        assert_eq!(wm.enemies_for_wave(10, Difficulty::Hard), 1, "difficulty doesn't add bosses");
+
+        let mut wm = WaveManager::default();
+        wm.start_wave(5, Difficulty::Normal);
+        assert_eq!(wm.enemies_remaining, 1);
+    }
+
+    #[test]
+    fn test_boss_has_much_more_health() {
+        let normal = UnitStats::for_type(TileType::Red, 2);
+        let boss = boss_stats(5);
+        assert_eq!(boss.max_health, normal.max_health * BOSS_HEALTH_MULTIPLIER);
+        assert_eq!(boss.health, boss.max_health);
+        assert!(boss_stats(10).max_health > boss.max_health, "later bosses are tougher");
+    }
+
+    const SPAWN_STEP: f32 = 0.25;
+
+    /// Run until the first telegraph (reserved on the first, zero-delta frame) materializes
+    fn spawn_first(app: &mut App) {
+        for _ in 0..=(SPAWN_TELEGRAPH_DURATION / SPAWN_STEP) as usize {
+            app.update();
+        }
+    }
+
+    fn setup_boss_wave_app() -> App {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .add_plugins(bevy::state::app::StatesPlugin)
+            .init_state::<PhaseState>()
+            .insert_resource(BattleGrid::new())
+            .init_resource::<WaveBreakTimer>()
+            .init_resource::<GameRng>()
+            .init_resource::<TimeScale>()
+            .init_resource::<GameSpeed>()
+            .init_resource::<Difficulty>()
+            .init_resource::<Assets<Mesh>>()
+            .init_resource::<Assets<ColorMaterial>>()
+            .insert_resource(bevy::time::TimeUpdateStrategy::ManualDuration(std::time::Duration::from_secs_f32(SPAWN_STEP)))
+            .add_systems(
+                Update,
+                (wave_spawner_system, materialize_spawn_telegraphs, check_wave_complete_system).chain(),
+            );
+        let mut wm = WaveManager::default();
+        wm.start_wave(5, Difficulty::Normal);
+        wm.spawn_delay = 0.0;
+        app.insert_resource(wm);
+        app
+    }
+
+    #[test]
+    fn test_boss_wave_completes_when_boss_dies() {
+        let mut app = setup_boss_wave_app();
+        spawn_first(&mut app);
+        app.update();
+
+        let world = app.world_mut();
+        let bosses: Vec<Entity> = world.query_filtered::<Entity, With<Boss>>().iter(world).collect();
+        assert_eq!(bosses.len(), 1, "boss wave spawns exactly one boss");
+        assert_eq!(world.query_filtered::<(), (With<Unit>, With<Team>)>().iter(world).count(), 1);
+        assert_eq!(*app.world().resource::<State<PhaseState>>().get(), PhaseState::Idle);
+
+        app.world_mut().entity_mut(bosses[0]).despawn();
+        app.update();
+        app.update();
+
+        assert_eq!(*app.world().resource::<State<PhaseState>>().get(), PhaseState::WaveBreak);
     }
 
     #[test]
     fn test_wave_manager_start_wave() {
         let mut wm = WaveManager::default();
-        wm.start_wave(1);
+        wm.start_wave(1, Difficulty::Normal);
         assert_eq!(wm.current_wave, 1);
         assert!(wm.wave_active);
         assert_eq!(wm.enemies_remaining, 5); // 3 + 1*2 = 5
     }
+
+    #[test]
+    fn test_start_wave_enemy_count_per_difficulty() {
+        let mut wm = WaveManager::default();
+        wm.start_wave(2, Difficulty::Easy);
+        assert_eq!(wm.enemies_remaining, 4);
+        wm.start_wave(2, Difficulty::Normal);
+        assert_eq!(wm.enemies_remaining, 7);
+        wm.start_wave(2, Difficulty::Hard);
+        assert_eq!(wm.enemies_remaining, 8);
+    }
+
+    #[test]
+    fn test_enemy_stats_scale_with_difficulty() {
+        let base = UnitStats::for_type(TileType::Blue, 1);
+        for difficulty in [Difficulty::Easy, Difficulty::Normal, Difficulty::Hard] {
+            let scaled = scale_enemy_stats(base.clone(), difficulty);
+            assert_eq!(scaled.max_health, base.max_health * difficulty.enemy_health_multiplier());
+            assert_eq!(scaled.health, scaled.max_health);
+            assert_eq!(scaled.attack, base.attack * difficulty.enemy_attack_multiplier());
+            assert_eq!(scaled.defense, base.defense, "only health and attack scale");
+        }
+    }
+
+    #[test]
+    fn test_spawned_enemies_use_difficulty_stats() {
+        let mut app = setup_boss_wave_app();
+        app.insert_resource(Difficulty::Hard);
+        let mut wm = WaveManager::default();
+        wm.start_wave(1, Difficulty::Hard);
+        wm.spawn_delay = 0.0;
+        app.insert_resource(wm);
+        spawn_first(&mut app);
+
+        let world = app.world_mut();
+        let (unit_type, rank, stats) = world
+            .query::<(&UnitType, &StarRank, &UnitStats)>()
+            .single(world);
+        let normal = UnitStats::for_type(unit_type.0, rank.0);
+        assert_eq!(stats.max_health, normal.max_health * Difficulty::Hard.enemy_health_multiplier());
+        assert_eq!(stats.attack, normal.attack * Difficulty::Hard.enemy_attack_multiplier());
+    }
+
+    fn telegraphs(app: &mut App) -> Vec<(Entity, HexPosition)> {
+        let world = app.world_mut();
+        world.query::<(Entity, &SpawnTelegraph)>().iter(world).map(|(e, t)| (e, t.pos)).collect()
+    }
+
+    fn enemy_count(app: &mut App) -> usize {
+        let world = app.world_mut();
+        world.query_filtered::<(), With<Unit>>().iter(world).count()
+    }
+
+    fn setup_spawn_app() -> App {
+        let mut app = setup_boss_wave_app();
+        let mut wm = WaveManager::default();
+        wm.start_wave(1, Difficulty::Normal);
+        wm.spawn_delay = 0.0;
+        app.insert_resource(wm);
+        app
+    }
+
+    #[test]
+    fn test_enemy_appears_after_telegraph() {
+        let mut app = setup_spawn_app();
+        let remaining = app.world().resource::<WaveManager>().enemies_remaining;
+
+        app.update();
+        let pending = telegraphs(&mut app);
+        assert_eq!(pending.len(), 1, "the first beat only telegraphs");
+        assert_eq!(enemy_count(&mut app), 0);
+        let (marker, pos) = pending[0];
+        assert_eq!(app.world().resource::<BattleGrid>().units.get(&pos), Some(&marker), "hex is reserved");
+        assert_eq!(app.world().resource::<WaveManager>().enemies_remaining, remaining);
+
+        app.update();
+        assert_eq!(enemy_count(&mut app), 0, "still telegraphing after {SPAWN_STEP}s");
+
+        app.update();
+        assert!(telegraphs(&mut app).is_empty());
+        let world = app.world_mut();
+        let (enemy, enemy_pos) = world.query_filtered::<(Entity, &HexPosition), With<Unit>>().single(world);
+        assert_eq!(*enemy_pos, pos, "the enemy materializes on the telegraphed hex");
+        assert_eq!(app.world().resource::<BattleGrid>().units.get(&pos), Some(&enemy));
+        assert_eq!(app.world().resource::<WaveManager>().enemies_remaining, remaining - 1);
+    }
+
+    #[test]
+    fn test_reserved_hex_not_reused() {
+        let mut app = setup_spawn_app();
+        app.update();
+        let first = telegraphs(&mut app)[0].1;
+        assert_ne!(find_enemy_spawn_position(app.world().resource::<BattleGrid>()), Some(first));
+
+        app.world_mut().resource_mut::<WaveManager>().spawn_delay = 0.0;
+        app.update();
+
+        let pending = telegraphs(&mut app);
+        assert_eq!(pending.len(), 2);
+        assert_ne!(pending[0].1, pending[1].1, "each telegraph reserves its own hex");
+    }
+
+    #[test]
+    fn test_no_more_telegraphs_than_enemies_left() {
+        let mut app = setup_spawn_app();
+        app.world_mut().resource_mut::<WaveManager>().enemies_remaining = 1;
+        app.update();
+
+        app.world_mut().resource_mut::<WaveManager>().spawn_delay = 0.0;
+        app.update();
+
+        assert_eq!(telegraphs(&mut app).len() + enemy_count(&mut app), 1);
+    }
+
+    // ============================================================
+    // Wave Modifier Tests
+    // ============================================================
+
+    #[test]
+    fn test_modifier_never_rolled_early_or_on_boss_waves() {
+        let mut rng = GameRng::from_seed(7);
+        for _ in 0..200 {
+            assert_eq!(WaveManager::modifier_for_wave(1, &mut rng), None);
+            assert_eq!(WaveManager::modifier_for_wave(MODIFIER_MIN_WAVE - 1, &mut rng), None);
+            assert_eq!(WaveManager::modifier_for_wave(BOSS_WAVE_INTERVAL * 2, &mut rng), None);
+        }
+    }
+
+    #[test]
+    fn test_modifier_selection_covers_all_and_is_occasional() {
+        let mut rng = GameRng::from_seed(7);
+        let rolls: Vec<_> = (0..500).map(|_| WaveManager::modifier_for_wave(6, &mut rng)).collect();
+
+        let unmodified = rolls.iter().filter(|m| m.is_none()).count();
+        assert!(unmodified > 250, "most waves play unmodified, got {unmodified}/500");
+        for modifier in WaveModifier::ALL {
+            assert!(rolls.contains(&Some(modifier)), "{modifier:?} is never rolled");
+        }
+    }
+
+    #[test]
+    fn test_start_wave_clears_previous_modifier() {
+        let mut wm = WaveManager { modifier: Some(WaveModifier::Swift), ..default() };
+        wm.start_wave(4, Difficulty::Normal);
+        assert_eq!(wm.modifier, None);
+    }
+
+    #[test]
+    fn test_modifier_stat_changes() {
+        let base = UnitStats::for_type(TileType::Blue, 1);
+
+        let armored = wave_modified_stats(base.clone(), Some(WaveModifier::Armored));
+        assert_eq!(armored.defense, base.defense + 20.0);
+        assert_eq!(armored.attack_speed, base.attack_speed);
+
+        let swift = wave_modified_stats(base.clone(), Some(WaveModifier::Swift));
+        assert!((swift.attack_speed - base.attack_speed * 1.3).abs() < 1e-5);
+        assert_eq!(swift.defense, base.defense);
+
+        let plain = wave_modified_stats(base.clone(), None);
+        assert_eq!(plain.defense, base.defense);
+    }
+
+    fn spawn_first_enemy_with(modifier: WaveModifier) -> (App, Entity) {
+        let mut app = setup_boss_wave_app();
+        {
+            let mut wm = app.world_mut().resource_mut::<WaveManager>();
+            wm.start_wave(4, Difficulty::Normal);
+            wm.spawn_delay = 0.0;
+            wm.modifier = Some(modifier);
+        }
+        spawn_first(&mut app);
+        let world = app.world_mut();
+        let enemies: Vec<Entity> = world.query_filtered::<Entity, With<Unit>>().iter(world).collect();
+        assert_eq!(enemies.len(), 1);
+        (app, enemies[0])
+    }
+
+    #[test]
+    fn test_armored_wave_spawns_enemies_with_extra_defense() {
+        let (app, enemy) = spawn_first_enemy_with(WaveModifier::Armored);
+
+        let world = app.world();
+        let unit_type = world.get::<UnitType>(enemy).unwrap().0;
+        let star_rank = world.get::<StarRank>(enemy).unwrap().0;
+        let base = scale_enemy_stats(UnitStats::for_type(unit_type, star_rank), Difficulty::Normal);
+        let stats = world.get::<UnitStats>(enemy).unwrap();
+        assert_eq!(stats.defense, base.defense + 20.0);
+        assert!(world.get::<ExplosiveOnDeath>(enemy).is_none());
+    }
+
+    #[test]
+    fn test_explosive_wave_marks_enemies() {
+        let (app, enemy) = spawn_first_enemy_with(WaveModifier::Explosive);
+        assert!(app.world().get::<ExplosiveOnDeath>(enemy).is_some());
+    }
+
+    // ============================================================
+    // Enemy Archetype Tests
+    // ============================================================
+
+    #[test]
+    fn test_archetype_weights_stay_in_bounds() {
+        for wave in 0..ARCHETYPE_MIN_WAVE {
+            assert_eq!(EnemyArchetype::weights(wave), [100, 0, 0], "wave {wave} is all Standard");
+        }
+        for wave in ARCHETYPE_MIN_WAVE..40 {
+            let [standard, runner, caster] = EnemyArchetype::weights(wave);
+            assert_eq!(standard + runner + caster, 100);
+            assert!(runner > 0 && caster > 0, "wave {wave} never rolls a specialist");
+            assert!(standard >= 50, "wave {wave}: Standard dropped to {standard}");
+        }
+        assert!(EnemyArchetype::weights(8)[1] > EnemyArchetype::weights(2)[1], "specialists grow with waves");
+    }
+
+    #[test]
+    fn test_archetype_roll_distribution_matches_weights() {
+        let mut rng = GameRng::from_seed(11);
+        for wave in [2, 6, 20] {
+            let rolls = 2000;
+            let mut counts = [0u32; 3];
+            for _ in 0..rolls {
+                let rolled = EnemyArchetype::roll(wave, &mut rng);
+                counts[EnemyArchetype::ALL.iter().position(|a| *a == rolled).unwrap()] += 1;
+            }
+            for (count, weight) in counts.into_iter().zip(EnemyArchetype::weights(wave)) {
+                let expected = rolls * weight / 100;
+                assert!(count.abs_diff(expected) < rolls / 20, "wave {wave}: {counts:?} too far from weights");
+            }
+        }
+        assert!((0..200).all(|_| EnemyArchetype::roll(1, &mut rng) == EnemyArchetype::Standard));
+    }
+
+    #[test]
+    fn test_archetype_stat_changes() {
+        let base = UnitStats::for_type(TileType::Red, 1);
+
+        let runner = EnemyArchetype::Runner.apply(base.clone());
+        assert!(runner.move_speed > base.move_speed);
+        assert!(runner.max_health < base.max_health);
+        assert_eq!(runner.health, runner.max_health);
+
+        let caster = EnemyArchetype::Caster.apply(base.clone());
+        assert_eq!(caster.attack_range, CASTER_ATTACK_RANGE);
+        assert!(EnemyArchetype::Caster.is_ranged());
+        assert!(!EnemyArchetype::Runner.is_ranged());
+        assert_eq!(EnemyArchetype::Runner.goal(), EnemyGoal::RushBase);
+        assert_eq!(EnemyArchetype::Caster.goal(), EnemyGoal::Fight);
+
+        assert_eq!(EnemyArchetype::Standard.apply(base.clone()).max_health, base.max_health);
+    }
+
+    #[test]
+    fn test_spawned_enemies_carry_archetype() {
+        let (app, enemy) = spawn_first_enemy_with(WaveModifier::Swift);
+        let archetype = app.world().get::<EnemyArchetype>(enemy).copied().unwrap();
+        assert_eq!(app.world().get::<EnemyGoal>(enemy).copied(), Some(archetype.goal()));
+    }
+
+    #[test]
+    fn test_bomb_countdown_runs_on_scaled_time() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .insert_resource(bevy::time::TimeUpdateStrategy::ManualDuration(
+                std::time::Duration::from_secs_f32(STEP),
+            ))
+            .insert_resource(PuzzleBoard::new(4))
+            .init_resource::<BombCountdownTimer>()
+            .init_resource::<WaveManager>()
+            .insert_resource(TimeScale { scale: 0.5, ..default() })
+            .init_resource::<GameSpeed>()
+            .add_systems(Update, bomb_countdown_system);
+
+        for _ in 0..4 {
+            app.update();
+        }
+
+        let elapsed = app.world().resource::<Time>().elapsed_secs();
+        let timer = app.world().resource::<BombCountdownTimer>().timer;
+        assert!(elapsed * 0.5 < BOMB_COUNTDOWN_INTERVAL, "test stays within one interval");
+        assert!((timer - elapsed * 0.5).abs() < 1e-4, "countdown at {timer}s after {elapsed}s of half-speed play");
+    }
+
+    #[test]
+    fn test_bomb_countdown_runs_twice_as_fast_at_2x_speed() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .insert_resource(bevy::time::TimeUpdateStrategy::ManualDuration(
+                std::time::Duration::from_secs_f32(STEP),
+            ))
+            .insert_resource(PuzzleBoard::new(4))
+            .init_resource::<BombCountdownTimer>()
+            .init_resource::<WaveManager>()
+            .init_resource::<TimeScale>()
+            .insert_resource(GameSpeed(2.0))
+            .add_systems(Update, bomb_countdown_system);
+
+        for _ in 0..3 {
+            app.update();
+        }
+
+        let elapsed = app.world().resource::<Time>().elapsed_secs();
+        let timer = app.world().resource::<BombCountdownTimer>().timer;
+        assert!(elapsed * 2.0 < BOMB_COUNTDOWN_INTERVAL, "test stays within one interval");
+        assert!((timer - elapsed * 2.0).abs() < 1e-4, "countdown at {timer}s after {elapsed}s of 2x play");
+    }
+
+    #[test]
+    fn test_bomb_chain_floods_adjacent_bombs_once() {
+        let bombs: HashSet<(usize, usize)> = [(0, 0), (1, 0), (1, 1), (3, 3), (2, 2)].into_iter().collect();
+
+        let chain = bomb_chain(&[(0, 0)], &bombs);
+
+        assert_eq!(chain, vec![(0, 0), (1, 0), (1, 1)], "diagonal and distant bombs stay put");
+        assert!(bomb_chain(&[], &bombs).is_empty());
+    }
+
+    #[derive(Resource, Default)]
+    struct BombHits(Vec<(usize, usize)>);
+
+    #[test]
+    fn test_line_of_three_bombs_detonates_from_one_trigger() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .insert_resource(bevy::time::TimeUpdateStrategy::ManualDuration(
+                std::time::Duration::from_secs_f32(BOMB_COUNTDOWN_INTERVAL),
+            ))
+            .insert_resource(PuzzleBoard::new(8))
+            .insert_resource(BombCountdownTimer { timer: BOMB_COUNTDOWN_INTERVAL })
+            .init_resource::<WaveManager>()
+            .init_resource::<TimeScale>()
+            .init_resource::<GameSpeed>()
+            .init_resource::<BombHits>()
+            .add_observer(|trigger: Trigger<BombDamageEvent>, mut hits: ResMut<BombHits>| {
+                hits.0.push(trigger.event().position);
+            })
+            .add_systems(Update, bomb_countdown_system);
+        let mut spawn_bomb = |x: usize, countdown: u8| {
+            let world = app.world_mut();
+            world.resource_mut::<PuzzleBoard>().set_obstacle(x, 4, Some(ObstacleType::Bomb));
+            world.spawn((Obstacle::bomb(countdown), GridPosition::new(x, 4))).id()
+        };
+        let bombs = [spawn_bomb(2, 0), spawn_bomb(3, 5), spawn_bomb(4, 5)];
+        let far_bomb = spawn_bomb(6, 5);
+
+        app.update();
+
+        let mut hits = app.world().resource::<BombHits>().0.clone();
+        hits.sort();
+        assert_eq!(hits, vec![(2, 4), (3, 4), (4, 4)], "each bomb in the chain deals its own damage");
+        for bomb in bombs {
+            assert!(app.world().get_entity(bomb).is_err());
+        }
+        let board = app.world().resource::<PuzzleBoard>();
+        assert!(!board.has_bomb(2, 4) && !board.has_bomb(3, 4) && !board.has_bomb(4, 4));
+        assert!(board.has_bomb(6, 4), "a gap stops the chain");
+        assert_eq!(app.world().get::<Obstacle>(far_bomb).unwrap().countdown, Some(4));
+    }
+
+    #[test]
+    fn test_bomb_damage_scales_with_wave() {
+        assert_eq!(bomb_damage_for_wave(0), BOMB_BASE_DAMAGE, "bombs before the first wave still hurt");
+        assert_eq!(bomb_damage_for_wave(1), BOMB_BASE_DAMAGE);
+        assert_eq!(bomb_damage_for_wave(6), BOMB_BASE_DAMAGE + 5 * BOMB_DAMAGE_PER_WAVE);
+    }
+
+    #[test]
+    fn test_bomb_targets_lowest_hp_living_player_unit() {
+        let unit = |health: f32| UnitStats { health, ..default() };
+        let (healthy, wounded, dead, enemy) = (unit(90.0), unit(30.0), unit(0.0), unit(5.0));
+        let e = |index| Entity::from_raw(index);
+
+        let units = [
+            (e(1), &healthy, &Team::Player),
+            (e(2), &wounded, &Team::Player),
+            (e(3), &dead, &Team::Player),
+            (e(4), &enemy, &Team::Enemy),
+        ];
+        assert_eq!(bomb_target(units), Some(e(2)), "dead units and enemies are skipped");
+
+        let tied = [(e(7), &wounded, &Team::Player), (e(5), &wounded, &Team::Player)];
+        assert_eq!(bomb_target(tied), Some(e(5)), "ties go to the lowest entity");
+        assert_eq!(bomb_target([(e(4), &enemy, &Team::Enemy)]), None);
+    }
+
+    #[derive(Resource, Default)]
+    struct BombPopups(Vec<i32>);
+
+    #[test]
+    fn test_bomb_hits_only_its_target_and_defense_reduces_it() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .insert_resource(BattleGrid::new())
+            .init_resource::<BombPopups>()
+            .add_observer(handle_bomb_damage)
+            .add_observer(|trigger: Trigger<DamagePopupEvent>, mut popups: ResMut<BombPopups>| {
+                popups.0.push(trigger.event().damage);
+            });
+        let full = app
+            .world_mut()
+            .spawn((Unit, Team::Player, HexPosition::new(0, -1), UnitStats::default()))
+            .id();
+        let armored = app
+            .world_mut()
+            .spawn((Unit, Team::Player, HexPosition::new(1, -1), UnitStats { health: 60.0, defense: 50.0, ..default() }))
+            .id();
+        app.world_mut().flush();
+
+        app.world_mut().trigger(BombDamageEvent { position: (0, 0), damage: 20 });
+        app.world_mut().flush();
+
+        assert_eq!(app.world().get::<UnitStats>(armored).unwrap().health, 50.0, "50 defense halves the blast");
+        assert_eq!(app.world().get::<UnitStats>(full).unwrap().health, 100.0, "the rest of the team is spared");
+        assert_eq!(app.world().resource::<BombPopups>().0, vec![10]);
+    }
+
+    #[test]
+    fn test_shield_soaks_bomb_before_defense() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .insert_resource(BattleGrid::new())
+            .init_resource::<BombPopups>()
+            .add_observer(handle_bomb_damage)
+            .add_observer(|trigger: Trigger<DamagePopupEvent>, mut popups: ResMut<BombPopups>| {
+                popups.0.push(trigger.event().damage);
+            });
+        let shielded = app
+            .world_mut()
+            .spawn((
+                Unit,
+                Team::Player,
+                HexPosition::new(0, -1),
+                UnitStats { health: 60.0, defense: 50.0, ..default() },
+                Shield::new(4.0),
+            ))
+            .id();
+        app.world_mut().flush();
+
+        app.world_mut().trigger(BombDamageEvent { position: (0, 0), damage: 20 });
+        app.world_mut().flush();
+
+        // 4 soaked, the other 16 halved by defense
+        assert_eq!(app.world().get::<UnitStats>(shielded).unwrap().health, 52.0);
+        assert!(app.world().get::<Shield>(shielded).is_none(), "the spent shield is removed");
+        assert_eq!(app.world().resource::<BombPopups>().0, vec![8]);
+    }
 }
@@ -0,0 +1,108 @@
+//! Burst attack
+//!
+//! Clearing `BURST_CLEAR_THRESHOLD` tiles within one cascade (the swap and every chain
+//! step after it) makes the whole team attack at once. At most one burst per cascade.
+
+use crate::prelude::*;
+use super::MatchEvent;
+
+/// Tiles one cascade must clear to trigger a burst
+pub const BURST_CLEAR_THRESHOLD: usize = 20;
+
+/// Every living player unit with a target skips its remaining cooldown once
+#[derive(Event, Debug)]
+pub struct BurstAttackEvent {
+    /// Tiles cleared so far in the cascade that triggered it
+    pub cleared: usize,
+}
+
+/// Tiles cleared in the current cascade and whether it has already paid out its burst
+#[derive(Resource, Default, Debug)]
+pub struct CascadeClearCount {
+    pub cleared: usize,
+    pub burst_fired: bool,
+}
+
+pub fn count_cascade_clears(
+    trigger: Trigger<MatchEvent>,
+    mut commands: Commands,
+    mut clears: ResMut<CascadeClearCount>,
+) {
+    clears.cleared += trigger.event().count;
+    if clears.cleared >= BURST_CLEAR_THRESHOLD && !clears.burst_fired {
+        clears.burst_fired = true;
+        commands.trigger(BurstAttackEvent { cleared: clears.cleared });
+    }
+}
+
+/// A cascade is over once the board settles back to Idle
+pub fn reset_cascade_clears(mut clears: ResMut<CascadeClearCount>) {
+    *clears = CascadeClearCount::default();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::ecs::system::RunSystemOnce;
+
+    #[derive(Resource, Default)]
+    struct Bursts(Vec<usize>);
+
+    fn setup_burst_app() -> App {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .init_resource::<CascadeClearCount>()
+            .init_resource::<Bursts>()
+            .add_observer(count_cascade_clears)
+            .add_observer(|trigger: Trigger<BurstAttackEvent>, mut bursts: ResMut<Bursts>| {
+                bursts.0.push(trigger.event().cleared);
+            });
+        // Register the observers before triggering directly on the world
+        app.world_mut().flush();
+        app
+    }
+
+    fn clear_tiles(app: &mut App, count: usize) {
+        app.world_mut().trigger(MatchEvent {
+            tile_type: TileType::Red,
+            count,
+            positions: Vec::new(),
+        });
+        // Apply the burst the observer queued
+        app.world_mut().flush();
+    }
+
+    fn bursts(app: &App) -> &[usize] {
+        &app.world().resource::<Bursts>().0
+    }
+
+    #[test]
+    fn test_crossing_threshold_emits_one_burst() {
+        let mut app = setup_burst_app();
+
+        clear_tiles(&mut app, 12);
+        clear_tiles(&mut app, 6);
+        assert!(bursts(&app).is_empty(), "18 tiles is below the threshold");
+
+        clear_tiles(&mut app, 5);
+        assert_eq!(bursts(&app), [23]);
+
+        // The rest of the cascade can't fire another
+        clear_tiles(&mut app, 30);
+        assert_eq!(bursts(&app).len(), 1);
+    }
+
+    #[test]
+    fn test_next_cascade_starts_from_zero() {
+        let mut app = setup_burst_app();
+        clear_tiles(&mut app, BURST_CLEAR_THRESHOLD);
+        assert_eq!(bursts(&app).len(), 1);
+
+        app.world_mut().run_system_once(reset_cascade_clears).unwrap();
+        clear_tiles(&mut app, BURST_CLEAR_THRESHOLD - 1);
+        assert_eq!(bursts(&app).len(), 1, "leftover count from the last cascade doesn't carry over");
+
+        clear_tiles(&mut app, 1);
+        assert_eq!(bursts(&app).len(), 2);
+    }
+}
@@ -0,0 +1,127 @@
+//! Pre-game unit draft
+//!
+//! With the draft turned on, pressing Start on the title screen rolls a
+//! `DraftOffer` of a few units instead of starting right away. Picking one fires
+//! `DraftPickEvent`, which places that unit on the battlefield and starts the run.
+
+use crate::prelude::*;
+use rand::Rng;
+use crate::battle::{BattleGrid, Team};
+use super::spawn_unit_at;
+
+/// Units offered per draft
+pub const DRAFT_CHOICES: usize = 3;
+/// Chance each offered unit comes at ★2 instead of ★1
+pub const DRAFT_STAR2_CHANCE: f64 = 0.2;
+
+/// Title-screen toggle for the draft; off keeps the original empty-battlefield start
+#[derive(Resource, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct DraftSettings {
+    pub enabled: bool,
+}
+
+/// Units on offer in the current draft, as `(unit type, star rank)`. Only present
+/// while the player is picking.
+#[derive(Resource, Clone, Debug, PartialEq, Eq)]
+pub struct DraftOffer {
+    pub choices: Vec<(TileType, u8)>,
+}
+
+impl DraftOffer {
+    pub fn roll(rng: &mut impl Rng) -> Self {
+        let choices = (0..DRAFT_CHOICES)
+            .map(|_| {
+                let star_rank = if rng.gen_bool(DRAFT_STAR2_CHANCE) { 2 } else { 1 };
+                (TileType::random(rng), star_rank)
+            })
+            .collect();
+        Self { choices }
+    }
+}
+
+/// The player took choice `index` of the `DraftOffer`
+#[derive(Event, Debug)]
+pub struct DraftPickEvent {
+    pub index: usize,
+}
+
+/// Place the picked unit, close the draft and start the run
+pub fn handle_draft_pick(
+    trigger: Trigger<DraftPickEvent>,
+    mut commands: Commands,
+    offer: Option<Res<DraftOffer>>,
+    mut grid: ResMut<BattleGrid>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    let Some((unit_type, star_rank)) = offer.and_then(|offer| offer.choices.get(trigger.event().index).copied()) else {
+        return;
+    };
+    if let Some(pos) = grid.find_empty_position() {
+        spawn_unit_at(&mut commands, &mut grid, unit_type, star_rank, pos, Team::Player, &mut meshes, &mut materials);
+    }
+    commands.remove_resource::<DraftOffer>();
+    next_state.set(GameState::Playing);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::state::app::StatesPlugin;
+    use crate::battle::{StarRank, UnitType};
+
+    fn setup_draft_app(choices: Vec<(TileType, u8)>) -> App {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .add_plugins(StatesPlugin)
+            .init_state::<GameState>()
+            .init_resource::<Assets<Mesh>>()
+            .init_resource::<Assets<ColorMaterial>>()
+            .insert_resource(BattleGrid::new())
+            .insert_resource(DraftOffer { choices })
+            .add_observer(handle_draft_pick);
+        app.world_mut().flush();
+        app
+    }
+
+    #[test]
+    fn test_roll_offers_draft_choices() {
+        let offer = DraftOffer::roll(&mut GameRng::from_seed(3));
+        assert_eq!(offer.choices.len(), DRAFT_CHOICES);
+        assert!(offer.choices.iter().all(|&(_, star_rank)| star_rank == 1 || star_rank == 2));
+        assert_eq!(offer, DraftOffer::roll(&mut GameRng::from_seed(3)), "same seed, same offer");
+    }
+
+    #[test]
+    fn test_pick_spawns_chosen_unit_into_battle_grid() {
+        let mut app = setup_draft_app(vec![(TileType::Red, 1), (TileType::Purple, 2), (TileType::Green, 1)]);
+
+        app.world_mut().trigger(DraftPickEvent { index: 1 });
+        app.world_mut().flush();
+        app.update();
+
+        let grid = app.world().resource::<BattleGrid>();
+        assert_eq!(grid.units.len(), 1);
+        let unit = *grid.units.values().next().unwrap();
+        assert_eq!(app.world().get::<UnitType>(unit).unwrap().0, TileType::Purple);
+        assert_eq!(app.world().get::<StarRank>(unit).unwrap().0, 2);
+        assert_eq!(app.world().get::<Team>(unit), Some(&Team::Player));
+
+        assert!(!app.world().contains_resource::<DraftOffer>(), "the draft closes after a pick");
+        assert_eq!(*app.world().resource::<State<GameState>>().get(), GameState::Playing);
+    }
+
+    #[test]
+    fn test_out_of_range_pick_is_ignored() {
+        let mut app = setup_draft_app(vec![(TileType::Red, 1)]);
+
+        app.world_mut().trigger(DraftPickEvent { index: 5 });
+        app.world_mut().flush();
+        app.update();
+
+        assert!(app.world().resource::<BattleGrid>().units.is_empty());
+        assert!(app.world().contains_resource::<DraftOffer>());
+        assert_eq!(*app.world().resource::<State<GameState>>().get(), GameState::default(), "the state is left alone");
+    }
+}
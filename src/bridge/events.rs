@@ -1,10 +1,11 @@
 use crate::prelude::*;
 use crate::puzzle::{TileType, ObstacleType};
 use crate::battle::{
-    Unit, UnitStats, UnitType, StarRank, Team, BattleGrid, HexPosition,
-    Target, AttackCooldown,
+    Unit, DamageType, UnitStats, UnitType, StarRank, Team, BattleGrid, HexPosition,
+    Target, AttackCooldown, HealPopupEvent, UnitExperience,
 };
 use crate::state::SlowMoEvent;
+use super::SummonRules;
 
 #[derive(Event)]
 pub struct MatchEvent {
@@ -24,6 +25,25 @@ pub struct ManaSupplyEvent {
     pub amount: f32,
 }
 
+/// Mana for every tile in a match
+pub const COLORED_MANA_PER_TILE: f32 = 4.0;
+
+/// Mana from a match, for player units of the matched color only
+#[derive(Event, Debug)]
+pub struct ColoredManaEvent {
+    pub tile_type: TileType,
+    pub amount: f32,
+}
+
+impl ColoredManaEvent {
+    pub fn for_match(tile_type: TileType, count: usize) -> Self {
+        Self {
+            tile_type,
+            amount: COLORED_MANA_PER_TILE * count as f32,
+        }
+    }
+}
+
 #[derive(Event)]
 pub struct ObstacleSpawnEvent {
     pub position: (usize, usize),
@@ -43,35 +63,22 @@ pub struct SkillOrbEvent {
     pub orb_type: SkillOrbType,
 }
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum SkillOrbType {
     Buff,
     Meteor,
     Heal,
 }
 
-pub fn match_to_summon(trigger: Trigger<MatchEvent>, mut commands: Commands) {
+pub fn match_to_summon(trigger: Trigger<MatchEvent>, mut commands: Commands, rules: Res<SummonRules>) {
     let event = trigger.event();
 
-    let star_rank = if event.count >= 5 {
-        2
-    } else {
-        1
-    };
-
     commands.trigger(UnitSummonEvent {
-        unit_type: event.tile_type,
-        star_rank,
+        unit_type: rules.unit_for(event.tile_type),
+        star_rank: rules.star_rank_for(event.count),
     });
 
-    if event.count >= 4 {
-        let orb_type = match event.tile_type {
-            TileType::Red => SkillOrbType::Meteor,
-            TileType::Blue => SkillOrbType::Buff,
-            TileType::Green => SkillOrbType::Heal,
-            TileType::Yellow => SkillOrbType::Buff,
-            TileType::Purple => SkillOrbType::Meteor,
-        };
+    if let Some(orb_type) = rules.orb_for(event.tile_type, event.count) {
         commands.trigger(SkillOrbEvent { orb_type });
     }
 }
@@ -126,7 +133,8 @@ pub fn summon_unit(
     }
 }
 
-fn spawn_unit_at(
+/// Spawn a fresh unit with its type's base stats and register it on the grid
+pub fn spawn_unit_at(
     commands: &mut Commands,
     grid: &mut ResMut<BattleGrid>,
     unit_type: TileType,
@@ -135,8 +143,9 @@ fn spawn_unit_at(
     team: Team,
     meshes: &mut ResMut<Assets<Mesh>>,
     materials: &mut ResMut<Assets<ColorMaterial>>,
-) {
+) -> Entity {
     let stats = UnitStats::for_type(unit_type, star_rank);
+    let cooldown = AttackCooldown::for_stats(&stats);
     let world_pos = grid.axial_to_pixel(&pos);
 
     let size = 30.0 + (star_rank as f32 * 5.0);
@@ -158,7 +167,8 @@ fn spawn_unit_at(
             pos,
             team,
             Target(None),
-            AttackCooldown(0.0),
+            cooldown,
+            UnitExperience::default(),
             Mesh2d(meshes.add(triangle)),
             MeshMaterial2d(materials.add(ColorMaterial::from_color(unit_type.color()))),
             Transform::from_translation(world_pos.extend(1.0)),
@@ -166,34 +176,42 @@ fn spawn_unit_at(
         .id();
 
     grid.place_unit(pos, entity);
+    entity
 }
 
 pub fn handle_skill_orb(
     trigger: Trigger<SkillOrbEvent>,
-    mut units: Query<(&mut UnitStats, &Team), With<Unit>>,
+    mut commands: Commands,
+    mut units: Query<(&mut UnitStats, &Team, Option<&Transform>), With<Unit>>,
 ) {
     let event = trigger.event();
 
     match event.orb_type {
         SkillOrbType::Buff => {
-            for (mut stats, team) in units.iter_mut() {
+            for (mut stats, team, _) in units.iter_mut() {
                 if *team == Team::Player {
                     stats.attack *= 1.2;
                 }
             }
         }
         SkillOrbType::Heal => {
-            for (mut stats, team) in units.iter_mut() {
+            for (mut stats, team, transform) in units.iter_mut() {
                 if *team == Team::Player {
                     let heal = stats.max_health * 0.3;
-                    stats.health = (stats.health + heal).min(stats.max_health);
+                    let healed = stats.heal(heal);
+                    if let (true, Some(transform)) = (healed > 0.0, transform) {
+                        commands.trigger(HealPopupEvent {
+                            position: transform.translation,
+                            amount: healed.round() as i32,
+                        });
+                    }
                 }
             }
         }
         SkillOrbType::Meteor => {
-            for (mut stats, team) in units.iter_mut() {
+            for (mut stats, team, _) in units.iter_mut() {
                 if *team == Team::Enemy {
-                    stats.take_damage(50.0);
+                    stats.take_typed_damage(50.0, DamageType::Magical);
                 }
             }
         }
@@ -212,3 +230,44 @@ pub fn handle_mana_supply(
         }
     }
 }
+
+pub fn handle_colored_mana(
+    trigger: Trigger<ColoredManaEvent>,
+    mut units: Query<(&mut UnitStats, &UnitType, &Team), With<Unit>>,
+) {
+    let event = trigger.event();
+
+    for (mut stats, unit_type, team) in units.iter_mut() {
+        if *team == Team::Player && unit_type.0 == event.tile_type {
+            stats.gain_mana(event.amount);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_colored_mana_only_charges_matching_player_units() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins).add_observer(handle_colored_mana);
+        let mut spawn = |tile_type, team| {
+            app.world_mut()
+                .spawn((Unit, UnitType(tile_type), UnitStats::for_type(tile_type, 1), team))
+                .id()
+        };
+        let mage = spawn(TileType::Purple, Team::Player);
+        let warrior = spawn(TileType::Red, Team::Player);
+        let enemy_mage = spawn(TileType::Purple, Team::Enemy);
+        app.world_mut().flush();
+
+        app.world_mut().trigger(ColoredManaEvent::for_match(TileType::Purple, 3));
+        app.world_mut().flush();
+
+        let mana = |entity| app.world().get::<UnitStats>(entity).unwrap().mana;
+        assert_eq!(mana(mage), COLORED_MANA_PER_TILE * 3.0);
+        assert_eq!(mana(warrior), 0.0);
+        assert_eq!(mana(enemy_mage), 0.0);
+    }
+}
@@ -1,16 +1,34 @@
 mod events;
+mod score;
+mod burst;
+mod summon_rules;
+mod draft;
 
 use crate::prelude::*;
 
 pub use events::*;
+pub use score::{match_score, wave_clear_bonus};
+pub use burst::{BurstAttackEvent, CascadeClearCount, BURST_CLEAR_THRESHOLD};
+pub use summon_rules::SummonRules;
+pub use draft::{DraftSettings, DraftOffer, DraftPickEvent, DRAFT_CHOICES};
 
 pub struct BridgePlugin;
 
 impl Plugin for BridgePlugin {
     fn build(&self, app: &mut App) {
-        app.add_observer(events::match_to_summon)
+        app.init_resource::<CascadeClearCount>()
+            .init_resource::<SummonRules>()
+            .init_resource::<DraftSettings>()
+            .add_observer(events::match_to_summon)
             .add_observer(events::summon_unit)
             .add_observer(events::handle_skill_orb)
-            .add_observer(events::handle_mana_supply);
+            .add_observer(events::handle_mana_supply)
+            .add_observer(events::handle_colored_mana)
+            .add_observer(draft::handle_draft_pick)
+            .add_observer(score::score_match)
+            .add_observer(score::score_wave_clear)
+            .add_observer(score::record_game_stats)
+            .add_observer(burst::count_cascade_clears)
+            .add_systems(OnEnter(PhaseState::Idle), burst::reset_cascade_clears);
     }
 }
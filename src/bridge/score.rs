@@ -0,0 +1,148 @@
+//! Scoring
+//!
+//! Matches score by size and chain depth; clearing a wave adds a flat bonus.
+//! The final score goes into the lifetime stats when the run ends.
+
+use crate::prelude::*;
+use crate::battle::{WaveCompleteEvent, GameOverEvent, PersistentStats};
+use crate::ui::{Score, NewHighScore};
+use super::MatchEvent;
+
+/// Points per matched tile before the combo multiplier
+pub const POINTS_PER_TILE: u32 = 10;
+/// Wave clear bonus, multiplied by the wave number
+pub const WAVE_CLEAR_BONUS: u32 = 100;
+
+/// Points for a match of `count` tiles at chain step `combo` (1 = the swap itself,
+/// 2 = first cascade, ...). A combo of 0 scores like 1.
+pub fn match_score(count: usize, combo: u32) -> u32 {
+    count as u32 * POINTS_PER_TILE * combo.max(1)
+}
+
+pub fn wave_clear_bonus(wave_number: u32) -> u32 {
+    WAVE_CLEAR_BONUS * wave_number
+}
+
+/// Award match points. `MatchEvent` fires before `start_cascade` counts the step,
+/// so the match being scored is step `current + 1`.
+pub fn score_match(
+    trigger: Trigger<MatchEvent>,
+    combo: Res<ComboCounter>,
+    mut score: ResMut<Score>,
+) {
+    let event = trigger.event();
+    score.0 += match_score(event.count, combo.current + 1);
+}
+
+pub fn score_wave_clear(trigger: Trigger<WaveCompleteEvent>, mut score: ResMut<Score>) {
+    score.0 += wave_clear_bonus(trigger.event().wave_number);
+}
+
+/// Record the finished run, with its final score, in the lifetime stats and flag
+/// a beaten high score for the game-over screen
+pub fn record_game_stats(
+    trigger: Trigger<GameOverEvent>,
+    score: Option<Res<Score>>,
+    new_high_score: Option<ResMut<NewHighScore>>,
+    mut stats: ResMut<PersistentStats>,
+) {
+    let event = trigger.event();
+    let beaten = stats.record_game(event.waves_survived, score.map_or(0, |score| score.0));
+    if let Some(mut new_high_score) = new_high_score {
+        new_high_score.0 = beaten;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_match_score_formula() {
+        assert_eq!(match_score(3, 1), 30);
+        assert_eq!(match_score(4, 1), 40);
+        assert_eq!(match_score(3, 2), 60);
+        assert_eq!(match_score(5, 3), 150);
+        assert_eq!(match_score(3, 0), 30, "combo 0 counts as a single step");
+    }
+
+    #[test]
+    fn test_wave_clear_bonus_scales_with_wave() {
+        assert_eq!(wave_clear_bonus(1), 100);
+        assert_eq!(wave_clear_bonus(5), 500);
+    }
+
+    fn setup_score_app() -> App {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .init_resource::<Score>()
+            .init_resource::<ComboCounter>()
+            .add_observer(score_match)
+            .add_observer(score_wave_clear);
+        // Register the observers before triggering directly on the world
+        app.world_mut().flush();
+        app
+    }
+
+    #[test]
+    fn test_match_events_accumulate_with_chain_depth() {
+        let mut app = setup_score_app();
+        let trigger_match = |app: &mut App, count: usize| {
+            app.world_mut().trigger(MatchEvent {
+                tile_type: TileType::Red,
+                count,
+                positions: Vec::new(),
+            });
+        };
+
+        // Initial swap match: step 1
+        trigger_match(&mut app, 3);
+        assert_eq!(app.world().resource::<Score>().0, 30);
+
+        // After start_cascade counted that step, the next match is step 2
+        app.world_mut().resource_mut::<ComboCounter>().increment();
+        trigger_match(&mut app, 4);
+        assert_eq!(app.world().resource::<Score>().0, 30 + 80);
+    }
+
+    #[test]
+    fn test_wave_complete_adds_bonus() {
+        let mut app = setup_score_app();
+        app.world_mut().trigger(WaveCompleteEvent { wave_number: 3 });
+        assert_eq!(app.world().resource::<Score>().0, 300);
+    }
+
+    #[test]
+    fn test_game_over_updates_persistent_stats() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .insert_resource(Score(800))
+            .insert_resource(PersistentStats { best_wave: 2, high_score: 1000, games_played: 4 })
+            .add_observer(record_game_stats);
+        app.world_mut().flush();
+
+        app.world_mut().trigger(GameOverEvent { victory: false, waves_survived: 3 });
+
+        let stats = app.world().resource::<PersistentStats>();
+        assert_eq!(*stats, PersistentStats { best_wave: 3, high_score: 1000, games_played: 5 });
+    }
+
+    #[test]
+    fn test_game_over_flags_beaten_high_score() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .insert_resource(Score(1500))
+            .init_resource::<NewHighScore>()
+            .insert_resource(PersistentStats { best_wave: 2, high_score: 1000, games_played: 4 })
+            .add_observer(record_game_stats);
+        app.world_mut().flush();
+
+        app.world_mut().trigger(GameOverEvent { victory: false, waves_survived: 1 });
+        assert!(app.world().resource::<NewHighScore>().0);
+        assert_eq!(app.world().resource::<PersistentStats>().high_score, 1500);
+
+        // The next run falls short, clearing the flag
+        app.world_mut().trigger(GameOverEvent { victory: false, waves_survived: 1 });
+        assert!(!app.world().resource::<NewHighScore>().0);
+    }
+}
@@ -0,0 +1,147 @@
+//! Summon rule table
+//!
+//! Data that `match_to_summon` reads to turn a match into a unit summon and, for big
+//! enough matches, a skill orb. The defaults reproduce the original hardcoded rules:
+//! each color summons its own unit, 5+ matches summon at ★2, and 4+ matches drop the
+//! color's orb.
+
+use std::collections::HashMap;
+use crate::prelude::*;
+use super::SkillOrbType;
+
+const ALL_TILE_TYPES: [TileType; 5] = [TileType::Red, TileType::Blue, TileType::Green, TileType::Yellow, TileType::Purple];
+
+#[derive(Resource, Clone, Debug)]
+pub struct SummonRules {
+    /// Unit summoned by each matched color; colors left out summon their own unit
+    pub units: HashMap<TileType, TileType>,
+    /// `(minimum match size, star rank)`; the highest rank reached wins, ★1 otherwise
+    pub star_thresholds: Vec<(usize, u8)>,
+    /// Smallest match that also drops a skill orb
+    pub orb_min_count: usize,
+    /// Orb dropped by each matched color; colors left out drop none
+    pub orbs: HashMap<TileType, SkillOrbType>,
+}
+
+impl Default for SummonRules {
+    fn default() -> Self {
+        Self {
+            units: ALL_TILE_TYPES.into_iter().map(|tile_type| (tile_type, tile_type)).collect(),
+            star_thresholds: vec![(5, 2)],
+            orb_min_count: 4,
+            orbs: HashMap::from([
+                (TileType::Red, SkillOrbType::Meteor),
+                (TileType::Blue, SkillOrbType::Buff),
+                (TileType::Green, SkillOrbType::Heal),
+                (TileType::Yellow, SkillOrbType::Buff),
+                (TileType::Purple, SkillOrbType::Meteor),
+            ]),
+        }
+    }
+}
+
+impl SummonRules {
+    pub fn unit_for(&self, tile_type: TileType) -> TileType {
+        self.units.get(&tile_type).copied().unwrap_or(tile_type)
+    }
+
+    pub fn star_rank_for(&self, count: usize) -> u8 {
+        self.star_thresholds
+            .iter()
+            .filter(|(min_count, _)| count >= *min_count)
+            .map(|(_, star_rank)| *star_rank)
+            .max()
+            .unwrap_or(1)
+    }
+
+    pub fn orb_for(&self, tile_type: TileType, count: usize) -> Option<SkillOrbType> {
+        if count < self.orb_min_count {
+            return None;
+        }
+        self.orbs.get(&tile_type).copied()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::{match_to_summon, MatchEvent, UnitSummonEvent, SkillOrbEvent};
+
+    #[derive(Resource, Default)]
+    struct Summoned {
+        units: Vec<(TileType, u8)>,
+        orbs: Vec<SkillOrbType>,
+    }
+
+    fn setup_summon_app(rules: SummonRules) -> App {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .insert_resource(rules)
+            .init_resource::<Summoned>()
+            .add_observer(match_to_summon)
+            .add_observer(|trigger: Trigger<UnitSummonEvent>, mut summoned: ResMut<Summoned>| {
+                summoned.units.push((trigger.event().unit_type, trigger.event().star_rank));
+            })
+            .add_observer(|trigger: Trigger<SkillOrbEvent>, mut summoned: ResMut<Summoned>| {
+                summoned.orbs.push(trigger.event().orb_type);
+            });
+        app.world_mut().flush();
+        app
+    }
+
+    fn match_tiles(app: &mut App, tile_type: TileType, count: usize) -> &Summoned {
+        app.world_mut().trigger(MatchEvent { tile_type, count, positions: Vec::new() });
+        app.world_mut().flush();
+        app.world().resource::<Summoned>()
+    }
+
+    #[test]
+    fn test_default_rules_match_original_mapping() {
+        let rules = SummonRules::default();
+        for tile_type in ALL_TILE_TYPES {
+            assert_eq!(rules.unit_for(tile_type), tile_type);
+        }
+        assert_eq!(rules.star_rank_for(3), 1);
+        assert_eq!(rules.star_rank_for(4), 1);
+        assert_eq!(rules.star_rank_for(5), 2);
+        assert_eq!(rules.orb_for(TileType::Green, 3), None);
+        assert_eq!(rules.orb_for(TileType::Green, 4), Some(SkillOrbType::Heal));
+        assert_eq!(rules.orb_for(TileType::Red, 6), Some(SkillOrbType::Meteor));
+    }
+
+    #[test]
+    fn test_default_rules_drive_summon_and_orb() {
+        let mut app = setup_summon_app(SummonRules::default());
+
+        let summoned = match_tiles(&mut app, TileType::Green, 4);
+
+        assert_eq!(summoned.units, vec![(TileType::Green, 1)]);
+        assert_eq!(summoned.orbs, vec![SkillOrbType::Heal]);
+    }
+
+    #[test]
+    fn test_custom_rules_change_summoned_unit_and_orb() {
+        let mut rules = SummonRules::default();
+        rules.units.insert(TileType::Green, TileType::Purple);
+        rules.orbs.insert(TileType::Green, SkillOrbType::Meteor);
+        rules.star_thresholds.push((4, 3));
+        let mut app = setup_summon_app(rules);
+
+        let summoned = match_tiles(&mut app, TileType::Green, 4);
+
+        assert_eq!(summoned.units, vec![(TileType::Purple, 3)]);
+        assert_eq!(summoned.orbs, vec![SkillOrbType::Meteor]);
+    }
+
+    #[test]
+    fn test_color_without_orb_drops_none() {
+        let mut rules = SummonRules::default();
+        rules.orbs.remove(&TileType::Blue);
+        let mut app = setup_summon_app(rules);
+
+        let summoned = match_tiles(&mut app, TileType::Blue, 5);
+
+        assert_eq!(summoned.units, vec![(TileType::Blue, 2)]);
+        assert!(summoned.orbs.is_empty());
+    }
+}
@@ -0,0 +1,253 @@
+//! Daily challenge
+//!
+//! With daily mode on, every run that day is seeded from the current UTC date, so
+//! all players get the same boards and waves. Best scores are kept per day in
+//! `DAILY_SCORES_FILE` and the day's best is shown on the game-over screen.
+
+use std::collections::HashMap;
+use std::path::Path;
+use bevy::prelude::*;
+use bevy::utils::SystemTime;
+use serde::{Deserialize, Serialize};
+use crate::rng::GameRng;
+use crate::ui::Score;
+
+/// Environment variable that turns on daily mode, e.g. `PUZZLE_TACTICS_DAILY=1`
+pub const DAILY_ENV_VAR: &str = "PUZZLE_TACTICS_DAILY";
+/// Per-day best scores, next to `stats.json`
+pub const DAILY_SCORES_FILE: &str = "daily_scores.json";
+
+const SECS_PER_DAY: u64 = 24 * 60 * 60;
+
+/// Whole UTC days between the Unix epoch and `now`
+pub fn days_since_epoch(now: SystemTime) -> u64 {
+    now.duration_since(SystemTime::UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs() / SECS_PER_DAY)
+        .unwrap_or(0)
+}
+
+/// Today's day number (UTC)
+pub fn today() -> u64 {
+    days_since_epoch(SystemTime::now())
+}
+
+/// Seed for a given day. SplitMix64 scrambles consecutive days into unrelated seeds.
+pub fn daily_seed(day: u64) -> u64 {
+    let mut z = day.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+/// Which day's challenge this session is playing, if any
+#[derive(Resource, Default, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DailyChallenge {
+    day: Option<u64>,
+}
+
+impl DailyChallenge {
+    pub fn for_day(day: u64) -> Self {
+        Self { day: Some(day) }
+    }
+
+    /// Today's challenge if `PUZZLE_TACTICS_DAILY` is set to anything but `0`/empty
+    pub fn from_env() -> Self {
+        let enabled = std::env::var(DAILY_ENV_VAR)
+            .is_ok_and(|value| !matches!(value.trim(), "" | "0"));
+        if enabled { Self::for_day(today()) } else { Self::default() }
+    }
+
+    pub fn day(&self) -> Option<u64> {
+        self.day
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.day.is_some()
+    }
+
+    pub fn seed(&self) -> Option<u64> {
+        self.day.map(daily_seed)
+    }
+}
+
+/// Best score per daily challenge day
+#[derive(Resource, Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+pub struct DailyHighScores(HashMap<u64, u32>);
+
+impl DailyHighScores {
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("DailyHighScores always serializes")
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
+    /// Read scores from disk. No file starts empty; a corrupt file is reported and
+    /// reset rather than blocking the game.
+    pub fn load(path: &Path) -> Self {
+        let Ok(json) = std::fs::read_to_string(path) else {
+            return Self::default();
+        };
+        Self::from_json(&json).unwrap_or_else(|err| {
+            warn!("Resetting malformed {}: {}", path.display(), err);
+            Self::default()
+        })
+    }
+
+    pub fn save(&self, path: &Path) -> std::io::Result<()> {
+        std::fs::write(path, self.to_json())
+    }
+
+    pub fn best(&self, day: u64) -> Option<u32> {
+        self.0.get(&day).copied()
+    }
+
+    /// Keep `score` if it beats the day's best; returns true on a new record
+    pub fn record(&mut self, day: u64, score: u32) -> bool {
+        match self.0.get(&day) {
+            Some(&best) if best >= score => false,
+            _ => {
+                self.0.insert(day, score);
+                true
+            }
+        }
+    }
+}
+
+/// Restart the RNG from the day's seed so every daily run replays the same sequence
+pub fn reseed_daily_run(mut commands: Commands, daily: Res<DailyChallenge>) {
+    if let Some(seed) = daily.seed() {
+        commands.insert_resource(GameRng::from_seed(seed));
+    }
+}
+
+pub fn record_daily_score(
+    daily: Res<DailyChallenge>,
+    score: Option<Res<Score>>,
+    mut high_scores: ResMut<DailyHighScores>,
+) {
+    let (Some(day), Some(score)) = (daily.day(), score) else { return };
+    // Only a new record counts as a change, so `save_daily_high_scores` writes just then
+    if high_scores.bypass_change_detection().record(day, score.0) {
+        high_scores.set_changed();
+        info!("New daily best for day {}: {}", day, score.0);
+    }
+}
+
+/// Write scores back to disk after a new daily record
+pub fn save_daily_high_scores(high_scores: Res<DailyHighScores>) {
+    if !high_scores.is_changed() || high_scores.is_added() {
+        return;
+    }
+    if let Err(err) = high_scores.save(Path::new(DAILY_SCORES_FILE)) {
+        warn!("Failed to save {}: {}", DAILY_SCORES_FILE, err);
+    }
+}
+
+/// Game-over line with the day's best, in daily mode only
+pub fn daily_best_line(daily: &DailyChallenge, high_scores: &DailyHighScores) -> Option<String> {
+    let day = daily.day()?;
+    Some(format!("Daily Best: {}", high_scores.best(day).unwrap_or(0)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::utils::Duration;
+
+    fn at_day(day: u64, extra_secs: u64) -> SystemTime {
+        SystemTime::UNIX_EPOCH + Duration::from_secs(day * SECS_PER_DAY + extra_secs)
+    }
+
+    #[test]
+    fn test_days_since_epoch_ignores_time_of_day() {
+        assert_eq!(days_since_epoch(at_day(20_000, 0)), 20_000);
+        assert_eq!(days_since_epoch(at_day(20_000, SECS_PER_DAY - 1)), 20_000);
+        assert_eq!(days_since_epoch(at_day(20_001, 0)), 20_001);
+    }
+
+    #[test]
+    fn test_daily_seed_is_deterministic() {
+        assert_eq!(daily_seed(20_000), daily_seed(20_000));
+        assert_ne!(daily_seed(20_000), daily_seed(20_001));
+    }
+
+    #[test]
+    fn test_same_date_shares_seed() {
+        let morning = DailyChallenge::for_day(days_since_epoch(at_day(20_000, 60)));
+        let evening = DailyChallenge::for_day(days_since_epoch(at_day(20_000, SECS_PER_DAY - 60)));
+        assert_eq!(morning.seed(), evening.seed());
+
+        let a = GameRng::from_seed(morning.seed().unwrap());
+        let b = GameRng::from_seed(evening.seed().unwrap());
+        assert_eq!(a.seed(), b.seed());
+    }
+
+    #[test]
+    fn test_disabled_daily_has_no_seed() {
+        assert!(!DailyChallenge::default().is_enabled());
+        assert_eq!(DailyChallenge::default().seed(), None);
+    }
+
+    #[test]
+    fn test_high_score_kept_per_day() {
+        let mut scores = DailyHighScores::default();
+        assert!(scores.record(1, 500));
+        assert!(!scores.record(1, 300));
+        assert!(scores.record(1, 800));
+        assert!(scores.record(2, 100));
+
+        assert_eq!(scores.best(1), Some(800));
+        assert_eq!(scores.best(2), Some(100));
+        assert_eq!(scores.best(3), None);
+    }
+
+    #[test]
+    fn test_high_scores_survive_save_and_load() {
+        let path = std::env::temp_dir().join(format!("puzzle_tactics_daily_{}.json", std::process::id()));
+        let mut scores = DailyHighScores::load(&path);
+        assert_eq!(scores, DailyHighScores::default(), "no file yet");
+        scores.record(20_000, 700);
+        scores.record(20_001, 300);
+        scores.save(&path).unwrap();
+
+        let reloaded = DailyHighScores::load(&path);
+        std::fs::remove_file(&path).ok();
+        assert_eq!(reloaded, scores);
+        assert_eq!(reloaded.best(20_000), Some(700));
+    }
+
+    #[test]
+    fn test_record_marks_changed_only_on_new_best() {
+        let mut app = App::new();
+        app.insert_resource(DailyChallenge::for_day(3))
+            .insert_resource(Score(500))
+            .init_resource::<DailyHighScores>()
+            .add_systems(Update, record_daily_score);
+        app.update();
+        let last_change = app.world().resource_ref::<DailyHighScores>().last_changed();
+
+        app.world_mut().resource_mut::<Score>().0 = 200;
+        app.update();
+        assert_eq!(app.world().resource_ref::<DailyHighScores>().last_changed(), last_change, "no record, nothing to save");
+        assert_eq!(app.world().resource::<DailyHighScores>().best(3), Some(500));
+
+        assert_eq!(daily_best_line(&DailyChallenge::for_day(3), app.world().resource::<DailyHighScores>()).as_deref(), Some("Daily Best: 500"));
+        assert_eq!(daily_best_line(&DailyChallenge::default(), app.world().resource::<DailyHighScores>()), None);
+    }
+
+    #[test]
+    fn test_reseed_only_in_daily_mode() {
+        let mut app = App::new();
+        app.insert_resource(GameRng::from_seed(42))
+            .insert_resource(DailyChallenge::default())
+            .add_systems(Update, reseed_daily_run);
+        app.update();
+        assert_eq!(app.world().resource::<GameRng>().seed(), 42, "non-daily play is untouched");
+
+        app.insert_resource(DailyChallenge::for_day(7));
+        app.update();
+        assert_eq!(app.world().resource::<GameRng>().seed(), daily_seed(7));
+    }
+}
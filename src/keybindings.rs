@@ -0,0 +1,169 @@
+use crate::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Where key bindings are persisted, relative to the working directory
+pub const KEYBINDINGS_FILE: &str = "keybindings.json";
+
+pub struct KeyBindingsPlugin;
+
+impl Plugin for KeyBindingsPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(KeyBindings::load(Path::new(KEYBINDINGS_FILE)))
+            .add_systems(Update, save_key_bindings);
+    }
+}
+
+/// Keys for the rebindable actions. Input systems read these instead of
+/// hard-coding `KeyCode`s, so a rebind takes effect on the next frame.
+#[derive(Resource, Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct KeyBindings {
+    /// Opens and closes the pause menu
+    pub pause: KeyCode,
+    /// Any of these casts the selected unit's ability while `ManualCast` is on
+    pub manual_cast: [KeyCode; 5],
+    /// Steps through `GAME_SPEEDS`
+    pub toggle_speed: KeyCode,
+    /// Rerolls the puzzle board during a wave break
+    pub reroll: KeyCode,
+    /// Shows and hides the range rings under player units
+    pub range_rings: KeyCode,
+    /// Sells the selected unit during a wave break
+    pub sell: KeyCode,
+    /// Cycles the selected unit's `TargetingMode`
+    pub targeting_mode: KeyCode,
+    /// Spends a full `MatchEnergy` meter on a color clear
+    pub match_energy: KeyCode,
+    /// Writes the run to the save file during a wave break
+    pub save: KeyCode,
+    /// Replaces the run with the save file on a settled board
+    pub load: KeyCode,
+    /// Shows and hides the hex coordinate debug overlay
+    pub hex_debug: KeyCode,
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        Self {
+            pause: KeyCode::Escape,
+            manual_cast: [
+                KeyCode::Digit1,
+                KeyCode::Digit2,
+                KeyCode::Digit3,
+                KeyCode::Digit4,
+                KeyCode::Digit5,
+            ],
+            toggle_speed: KeyCode::KeyF,
+            reroll: KeyCode::KeyR,
+            range_rings: KeyCode::KeyV,
+            sell: KeyCode::KeyS,
+            targeting_mode: KeyCode::KeyT,
+            match_energy: KeyCode::Space,
+            save: KeyCode::F5,
+            load: KeyCode::F9,
+            hex_debug: KeyCode::F3,
+        }
+    }
+}
+
+impl KeyBindings {
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("KeyBindings always serializes")
+    }
+
+    /// Parse bindings; missing actions keep their default keys
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
+    /// Read bindings from disk; a missing or unreadable file gives the defaults
+    pub fn load(path: &Path) -> Self {
+        let Ok(json) = std::fs::read_to_string(path) else {
+            return Self::default();
+        };
+        Self::from_json(&json).unwrap_or_else(|err| {
+            warn!("Ignoring malformed {}: {}", path.display(), err);
+            Self::default()
+        })
+    }
+
+    pub fn save(&self, path: &Path) -> std::io::Result<()> {
+        std::fs::write(path, self.to_json())
+    }
+}
+
+/// Write bindings back to disk whenever they are rebound
+fn save_key_bindings(bindings: Res<KeyBindings>) {
+    if !bindings.is_changed() || bindings.is_added() {
+        return;
+    }
+    if let Err(err) = bindings.save(Path::new(KEYBINDINGS_FILE)) {
+        warn!("Failed to save {}: {}", KEYBINDINGS_FILE, err);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_key_bindings_json_roundtrip() {
+        let bindings = KeyBindings {
+            pause: KeyCode::KeyP,
+            toggle_speed: KeyCode::Tab,
+            ..default()
+        };
+        assert_eq!(KeyBindings::from_json(&bindings.to_json()).unwrap(), bindings);
+    }
+
+    #[test]
+    fn test_missing_actions_keep_default_keys() {
+        let bindings = KeyBindings::from_json(r#"{ "pause": "KeyP" }"#).unwrap();
+        assert_eq!(bindings.pause, KeyCode::KeyP);
+        assert_eq!(bindings.reroll, KeyBindings::default().reroll);
+        assert_eq!(bindings.manual_cast, KeyBindings::default().manual_cast);
+        assert_eq!(bindings.match_energy, KeyBindings::default().match_energy);
+        assert_eq!(bindings.save, KeyBindings::default().save);
+    }
+
+    #[test]
+    fn test_default_keys_are_all_distinct() {
+        let bindings = KeyBindings::default();
+        let mut keys = vec![
+            bindings.pause,
+            bindings.toggle_speed,
+            bindings.reroll,
+            bindings.range_rings,
+            bindings.sell,
+            bindings.targeting_mode,
+            bindings.match_energy,
+            bindings.save,
+            bindings.load,
+            bindings.hex_debug,
+        ];
+        keys.extend(bindings.manual_cast);
+        let count = keys.len();
+        keys.sort_by_key(|key| format!("{key:?}"));
+        keys.dedup();
+        assert_eq!(keys.len(), count, "no two actions share a default key");
+    }
+
+    #[test]
+    fn test_key_bindings_file_round_trip() {
+        let path = std::env::temp_dir().join(format!("puzzle_tactics_keybindings_{}.json", std::process::id()));
+        let rebound = KeyBindings { reroll: KeyCode::KeyG, ..default() };
+        rebound.save(&path).unwrap();
+        assert_eq!(KeyBindings::load(&path), rebound);
+
+        std::fs::write(&path, "not json").unwrap();
+        assert_eq!(KeyBindings::load(&path), KeyBindings::default(), "malformed file resets to defaults");
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_key_bindings_load_missing_file_gives_defaults() {
+        let missing = std::env::temp_dir().join("puzzle_tactics_keybindings_does_not_exist.json");
+        assert_eq!(KeyBindings::load(&missing), KeyBindings::default());
+    }
+}
@@ -0,0 +1,273 @@
+//! Screen layout
+//!
+//! Splits the window between the battle grid and the puzzle board and derives both
+//! origins from it. Vertical stacks the battle above the puzzle (the default 800x1100
+//! window); Horizontal puts the battle on the left and the puzzle on the right.
+//! Cursor math (`world_to_grid`, `pixel_to_axial`) reads those origins, so it follows
+//! every re-layout without further changes.
+
+use bevy::window::PrimaryWindow;
+use crate::prelude::*;
+use crate::battle::{BattleGrid, HexPosition};
+
+/// Space kept between the puzzle board and the window edge
+const PUZZLE_MARGIN: f32 = 20.0;
+
+/// How the battle and puzzle areas are arranged on screen
+#[derive(Resource, Default, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum LayoutOrientation {
+    /// Battle on the left, puzzle on the right (wide windows)
+    Horizontal,
+    /// Battle on top, puzzle below (tall windows)
+    #[default]
+    Vertical,
+}
+
+/// World-space regions and origins for one window size. The 2D camera sits at the
+/// world origin at one unit per pixel, so the window spans `±size / 2`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Layout {
+    pub battle: Rect,
+    pub puzzle: Rect,
+    pub battle_origin: Vec2,
+    pub puzzle_origin: Vec2,
+}
+
+/// Width and height covered by a square board of `size` tiles
+pub fn board_extent(size: usize) -> f32 {
+    size as f32 * (TILE_SIZE + TILE_GAP) - TILE_GAP
+}
+
+impl Layout {
+    pub fn compute(orientation: LayoutOrientation, window: Vec2, board_size: usize) -> Self {
+        let half = window / 2.0;
+        let extent = board_extent(board_size);
+
+        let (battle, puzzle) = match orientation {
+            LayoutOrientation::Vertical => {
+                let split = -half.y + extent + PUZZLE_MARGIN * 2.0;
+                (
+                    Rect::new(-half.x, split, half.x, half.y),
+                    Rect::new(-half.x, -half.y, half.x, split),
+                )
+            }
+            LayoutOrientation::Horizontal => (
+                Rect::new(-half.x, -half.y, 0.0, half.y),
+                Rect::new(0.0, -half.y, half.x, half.y),
+            ),
+        };
+
+        // Board origin is the center of tile (0, 0), its bottom-left cell
+        let bottom_left = match orientation {
+            // Hug the bottom edge so the battle keeps as much height as possible
+            LayoutOrientation::Vertical => Vec2::new(puzzle.center().x - extent / 2.0, puzzle.min.y + PUZZLE_MARGIN),
+            LayoutOrientation::Horizontal => puzzle.center() - Vec2::splat(extent / 2.0),
+        };
+
+        Self {
+            battle,
+            puzzle,
+            // Hex (0, 0) is the middle of the battle grid
+            battle_origin: battle.center(),
+            puzzle_origin: bottom_left + Vec2::splat(TILE_SIZE / 2.0),
+        }
+    }
+}
+
+/// Primary window size, or the default window size when there is none (tests)
+fn window_size(windows: &Query<&Window, With<PrimaryWindow>>) -> Vec2 {
+    windows
+        .get_single()
+        .map(|window| window.size())
+        .unwrap_or(Vec2::new(WINDOW_WIDTH, WINDOW_HEIGHT))
+}
+
+/// Root puzzle sprites (tiles, ice and stone overlays)
+type BoardSpriteQuery<'w, 's> =
+    Query<'w, 's, &'static mut Transform, (With<GridPosition>, Without<HexPosition>, Without<Parent>)>;
+/// Root battle sprites on the hex grid
+type BattleSpriteQuery<'w, 's> =
+    Query<'w, 's, &'static mut Transform, (With<HexPosition>, Without<GridPosition>, Without<Parent>)>;
+
+/// Keep both origins in line with the orientation and window size. Runs every frame
+/// so resizes and freshly reset boards/grids are picked up; sprites already on screen
+/// are shifted by the same offset so in-flight animations keep their relative motion.
+/// Children (bombs on their tiles) are positioned relative to their parent and left alone.
+pub fn apply_layout(
+    orientation: Res<LayoutOrientation>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    board: Option<ResMut<PuzzleBoard>>,
+    grid: Option<ResMut<BattleGrid>>,
+    mut board_sprites: BoardSpriteQuery,
+    mut battle_sprites: BattleSpriteQuery,
+) {
+    let board_size = board.as_ref().map_or(PUZZLE_BOARD_SIZE, |board| board.size);
+    let layout = Layout::compute(*orientation, window_size(&windows), board_size);
+
+    if let Some(mut board) = board {
+        let offset = layout.puzzle_origin - board.origin;
+        if offset != Vec2::ZERO {
+            board.origin = layout.puzzle_origin;
+            for mut transform in board_sprites.iter_mut() {
+                transform.translation += offset.extend(0.0);
+            }
+        }
+    }
+
+    if let Some(mut grid) = grid {
+        let offset = layout.battle_origin - grid.origin;
+        if offset != Vec2::ZERO {
+            grid.origin = layout.battle_origin;
+            for mut transform in battle_sprites.iter_mut() {
+                transform.translation += offset.extend(0.0);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TALL: Vec2 = Vec2::new(WINDOW_WIDTH, WINDOW_HEIGHT);
+    const WIDE: Vec2 = Vec2::new(1600.0, 900.0);
+
+    fn window_rect(window: Vec2) -> Rect {
+        Rect::from_center_size(Vec2::ZERO, window)
+    }
+
+    fn contains(outer: Rect, inner: Rect) -> bool {
+        outer.contains(inner.min) && outer.contains(inner.max)
+    }
+
+    #[test]
+    fn test_vertical_layout_stacks_battle_above_puzzle() {
+        let layout = Layout::compute(LayoutOrientation::Vertical, TALL, PUZZLE_BOARD_SIZE);
+
+        assert!(layout.battle.intersect(layout.puzzle).is_empty(), "regions don't overlap");
+        assert_eq!(layout.battle.min.y, layout.puzzle.max.y, "battle sits directly on top");
+        assert_eq!(layout.battle.width(), TALL.x);
+        assert_eq!(layout.puzzle.width(), TALL.x);
+        assert_eq!(layout.battle.height() + layout.puzzle.height(), TALL.y);
+        assert!(contains(window_rect(TALL), layout.battle));
+        assert!(contains(window_rect(TALL), layout.puzzle));
+    }
+
+    #[test]
+    fn test_horizontal_layout_places_regions_side_by_side() {
+        let layout = Layout::compute(LayoutOrientation::Horizontal, WIDE, PUZZLE_BOARD_SIZE);
+
+        assert!(layout.battle.intersect(layout.puzzle).is_empty());
+        assert_eq!(layout.battle.max.x, layout.puzzle.min.x, "battle on the left");
+        assert_eq!(layout.battle.height(), WIDE.y);
+    }
+
+    #[test]
+    fn test_board_and_grid_fit_their_regions() {
+        for (orientation, window) in [(LayoutOrientation::Vertical, TALL), (LayoutOrientation::Horizontal, WIDE)] {
+            let layout = Layout::compute(orientation, window, PUZZLE_BOARD_SIZE);
+
+            let mut board = PuzzleBoard::new(PUZZLE_BOARD_SIZE);
+            board.origin = layout.puzzle_origin;
+            let last = PUZZLE_BOARD_SIZE - 1;
+            for (x, y) in [(0, 0), (last, last)] {
+                assert!(layout.puzzle.contains(board.grid_to_world(x, y)), "{:?}: tile ({}, {})", orientation, x, y);
+            }
+
+            let mut grid = BattleGrid::new();
+            grid.origin = layout.battle_origin;
+            for hex in grid.valid_positions() {
+                assert!(layout.battle.contains(grid.axial_to_pixel(&hex)), "{:?}: hex {:?}", orientation, hex);
+            }
+        }
+    }
+
+    #[test]
+    fn test_cursor_math_follows_layout() {
+        for (orientation, window) in [(LayoutOrientation::Vertical, TALL), (LayoutOrientation::Horizontal, WIDE)] {
+            let layout = Layout::compute(orientation, window, PUZZLE_BOARD_SIZE);
+            let mut board = PuzzleBoard::new(PUZZLE_BOARD_SIZE);
+            board.origin = layout.puzzle_origin;
+            let mut grid = BattleGrid::new();
+            grid.origin = layout.battle_origin;
+
+            let near_tile = board.grid_to_world(3, 5) + Vec2::new(10.0, -10.0);
+            assert_eq!(board.world_to_grid(near_tile), Some((3, 5)));
+            assert_eq!(board.world_to_grid(layout.battle.center()), None, "battle area isn't a board cell");
+
+            let hex = HexPosition::new(1, -1);
+            assert_eq!(grid.pixel_to_axial(grid.axial_to_pixel(&hex) + Vec2::new(5.0, 4.0)), hex);
+        }
+    }
+
+    fn setup_layout_app(orientation: LayoutOrientation) -> App {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .insert_resource(orientation)
+            .insert_resource(PuzzleBoard::new(PUZZLE_BOARD_SIZE))
+            .insert_resource(BattleGrid::new())
+            .add_systems(Update, apply_layout);
+        app
+    }
+
+    #[test]
+    fn test_apply_layout_moves_origins_and_sprites() {
+        let mut app = setup_layout_app(LayoutOrientation::Horizontal);
+        let board = app.world().resource::<PuzzleBoard>();
+        let tile_pos = board.grid_to_world(2, 2);
+        let tile = app
+            .world_mut()
+            .spawn((GridPosition::new(2, 2), Transform::from_translation(tile_pos.extend(0.1))))
+            .id();
+
+        app.update();
+
+        let expected = Layout::compute(LayoutOrientation::Horizontal, TALL, PUZZLE_BOARD_SIZE);
+        let board = app.world().resource::<PuzzleBoard>();
+        assert_eq!(board.origin, expected.puzzle_origin);
+        assert_eq!(app.world().resource::<BattleGrid>().origin, expected.battle_origin);
+        let translation = app.world().get::<Transform>(tile).unwrap().translation;
+        assert_eq!(translation.truncate(), board.grid_to_world(2, 2));
+        assert_eq!(translation.z, 0.1);
+    }
+
+    #[test]
+    fn test_relayout_leaves_bomb_on_its_tile() {
+        let mut app = setup_layout_app(LayoutOrientation::Vertical);
+        let board = app.world().resource::<PuzzleBoard>();
+        let tile_pos = board.grid_to_world(2, 2);
+        let tile = app
+            .world_mut()
+            .spawn((GridPosition::new(2, 2), Transform::from_translation(tile_pos.extend(0.1))))
+            .id();
+        let bomb = app
+            .world_mut()
+            .spawn((Obstacle::bomb(3), GridPosition::new(2, 2), Transform::from_translation(Vec3::new(0.0, 0.0, 0.5))))
+            .set_parent(tile)
+            .id();
+
+        app.update();
+        app.insert_resource(LayoutOrientation::Horizontal);
+        app.update();
+
+        let board = app.world().resource::<PuzzleBoard>();
+        assert_eq!(app.world().get::<Transform>(tile).unwrap().translation.truncate(), board.grid_to_world(2, 2));
+        assert_eq!(
+            app.world().get::<Transform>(bomb).unwrap().translation,
+            Vec3::new(0.0, 0.0, 0.5),
+            "bomb keeps its tile-local offset"
+        );
+    }
+
+    #[test]
+    fn test_orientation_change_relayouts() {
+        let mut app = setup_layout_app(LayoutOrientation::Vertical);
+        app.update();
+        let vertical = app.world().resource::<BattleGrid>().origin;
+
+        app.insert_resource(LayoutOrientation::Horizontal);
+        app.update();
+
+        assert_ne!(app.world().resource::<BattleGrid>().origin, vertical);
+    }
+}
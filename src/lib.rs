@@ -1,25 +1,77 @@
 pub mod camera;
 mod prelude;
-mod state;
+pub mod state;
+mod session;
+pub mod rng;
+pub mod daily;
+pub mod layout;
+pub mod metrics;
+pub mod save;
 
 pub mod puzzle;
 pub mod battle;
 pub mod bridge;
 pub mod ui;
 pub mod audio;
+pub mod keybindings;
 
 use prelude::*;
 use camera::setup_cameras;
 
-pub struct GamePlugin;
+#[derive(Default)]
+pub struct GamePlugin {
+    /// Fixed RNG seed for reproducible runs; falls back to `PUZZLE_TACTICS_SEED`, then random
+    pub seed: Option<u64>,
+    /// Seed every run from today's date (daily challenge); also enabled by `PUZZLE_TACTICS_DAILY`
+    pub daily: bool,
+    /// Battle/puzzle arrangement; vertical (battle on top) by default
+    pub layout: layout::LayoutOrientation,
+}
 
 impl Plugin for GamePlugin {
     fn build(&self, app: &mut App) {
-        app.init_state::<GameState>()
+        let daily = if self.daily {
+            daily::DailyChallenge::for_day(daily::today())
+        } else {
+            daily::DailyChallenge::from_env()
+        };
+        // An explicit seed wins over the daily seed, which wins over the env/random seed
+        let rng = match self.seed.or(daily.seed()) {
+            Some(seed) => rng::GameRng::from_seed(seed),
+            None => rng::GameRng::from_env(),
+        };
+        info!("GameRng seed: {}", rng.seed());
+
+        app.insert_resource(rng)
+            .insert_resource(daily)
+            .insert_resource(daily::DailyHighScores::load(std::path::Path::new(daily::DAILY_SCORES_FILE)))
+            .init_state::<GameState>()
             .init_state::<PhaseState>()
             .init_resource::<TimeScale>()
-            .add_systems(Startup, (setup_cameras, start_game))
-            .add_systems(Update, update_timescale)
+            .init_resource::<GameSpeed>()
+            .init_resource::<GameMode>()
+            .init_resource::<Difficulty>()
+            .insert_resource(self.layout)
+            .add_systems(Startup, setup_cameras)
+            .add_systems(
+                OnEnter(GameState::Loading),
+                (session::reset_game(), daily::reseed_daily_run, session::enter_title).chain(),
+            )
+            // Retry from the game-over screen
+            .add_systems(
+                OnExit(GameState::GameOver),
+                (session::reset_game(), daily::reseed_daily_run).chain(),
+            )
+            .add_systems(OnEnter(GameState::GameOver), daily::record_daily_score)
+            .add_systems(Update, daily::save_daily_high_scores)
+            .add_systems(
+                Update,
+                (
+                    update_timescale.run_if(not(simulation_paused)),
+                    cycle_game_speed.run_if(in_state(GameState::Playing)),
+                    layout::apply_layout,
+                ),
+            )
             .add_observer(handle_slowmo_event)
             .add_plugins((
                 puzzle::PuzzlePlugin,
@@ -28,6 +80,9 @@ impl Plugin for GamePlugin {
                 bridge::BridgePlugin,
                 ui::UIPlugin,
                 audio::AudioPlugin,
+                keybindings::KeyBindingsPlugin,
+                metrics::MetricsPlugin,
+                save::SavePlugin,
             ));
     }
 }
@@ -37,12 +92,83 @@ fn update_timescale(time: Res<Time>, mut timescale: ResMut<TimeScale>) {
     timescale.update(time.delta_secs());
 }
 
-/// Observer to handle slow motion events
-fn handle_slowmo_event(trigger: Trigger<SlowMoEvent>, mut timescale: ResMut<TimeScale>) {
+/// The `toggle_speed` binding steps through `GAME_SPEEDS`
+fn cycle_game_speed(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    bindings: Res<KeyBindings>,
+    mut speed: ResMut<GameSpeed>,
+) {
+    if keyboard.just_pressed(bindings.toggle_speed) {
+        *speed = speed.next();
+    }
+}
+
+/// Observer to handle slow motion events; a hit landing as the pause opens must not
+/// queue a slow-mo that then plays out behind the menu
+fn handle_slowmo_event(
+    trigger: Trigger<SlowMoEvent>,
+    state: Option<Res<State<GameState>>>,
+    mut timescale: ResMut<TimeScale>,
+) {
+    if simulation_paused(state) {
+        return;
+    }
     let event = trigger.event();
     timescale.trigger_slowmo(event.scale, event.duration);
 }
 
-fn start_game(mut next_state: ResMut<NextState<GameState>>) {
-    next_state.set(GameState::Playing);
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::state::app::StatesPlugin;
+    use bevy::time::TimeUpdateStrategy;
+    use std::time::Duration;
+    use battle::{DamagePopup, WaveManager};
+
+    #[test]
+    fn test_pause_freezes_wave_timer_popups_and_timescale() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, StatesPlugin))
+            .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(100)))
+            .init_state::<GameState>()
+            .init_state::<PhaseState>()
+            .init_resource::<TimeScale>()
+            .init_resource::<GameSpeed>()
+            .init_resource::<GameRng>()
+            .init_resource::<GameMode>()
+            .init_resource::<Difficulty>()
+            .init_resource::<Assets<Mesh>>()
+            .init_resource::<Assets<ColorMaterial>>()
+            .init_resource::<ButtonInput<KeyCode>>()
+            .init_resource::<ButtonInput<MouseButton>>()
+            .init_resource::<KeyBindings>()
+            .add_systems(Update, update_timescale.run_if(not(simulation_paused)))
+            .add_observer(handle_slowmo_event)
+            .add_plugins(battle::BattlePlugin);
+        app.world_mut().resource_mut::<NextState<GameState>>().set(GameState::Paused);
+        app.update();
+
+        let popup = app
+            .world_mut()
+            .spawn(DamagePopup {
+                timer: Timer::from_seconds(1.0, TimerMode::Once),
+                start_pos: Vec3::ZERO,
+            })
+            .id();
+        app.world_mut().resource_mut::<TimeScale>().trigger_slowmo(0.3, 0.5);
+        app.world_mut().trigger(SlowMoEvent { scale: 0.1, duration: 2.0 });
+        let wave_timer = app.world().resource::<WaveManager>().wave_timer;
+
+        for _ in 0..10 {
+            app.update();
+        }
+
+        let world = app.world();
+        assert_eq!(world.resource::<WaveManager>().wave_timer, wave_timer);
+        assert_eq!(world.get::<DamagePopup>(popup).unwrap().timer.elapsed(), Duration::ZERO);
+        let timescale = world.resource::<TimeScale>();
+        assert!(timescale.active, "slow-mo waits for the pause to end");
+        assert_eq!(timescale.scale, 0.3, "no new slow-mo is queued while paused");
+        assert_eq!(timescale.duration.elapsed(), Duration::ZERO);
+    }
 }
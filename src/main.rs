@@ -7,11 +7,11 @@ fn main() {
             primary_window: Some(Window {
                 title: "Puzzle Tactics".into(),
                 resolution: (800.0, 1100.0).into(),
-                resizable: false,
+                resizable: true,
                 ..default()
             }),
             ..default()
         }))
-        .add_plugins(GamePlugin)
+        .add_plugins(GamePlugin::default())
         .run();
 }
@@ -0,0 +1,184 @@
+//! Telemetry hook
+//!
+//! An embedder (e.g. a hosted build) installs a `MetricsSink` through the `Metrics`
+//! resource to forward key game events to its own backend. Without one the observers
+//! return on a single `Option` check, so the default costs nothing per frame.
+
+use crate::prelude::*;
+use crate::battle::{WaveStartEvent, WaveCompleteEvent, GameOverEvent};
+use crate::bridge::MatchEvent;
+use crate::ui::Score;
+
+/// Callbacks for key game events. Every method defaults to a no-op, so an
+/// implementation only overrides what it forwards.
+pub trait MetricsSink: Send + Sync + 'static {
+    fn wave_started(&self, _wave: u32) {}
+    fn wave_completed(&self, _wave: u32) {}
+    fn game_over(&self, _victory: bool, _waves_survived: u32, _score: u32) {}
+    fn match_made(&self, _tile_type: TileType, _count: usize) {}
+}
+
+/// Installed telemetry sink; `Metrics::default()` has none
+#[derive(Resource, Default)]
+pub struct Metrics {
+    sink: Option<Box<dyn MetricsSink>>,
+}
+
+impl Metrics {
+    pub fn new(sink: impl MetricsSink) -> Self {
+        Self { sink: Some(Box::new(sink)) }
+    }
+
+    pub fn sink(&self) -> Option<&dyn MetricsSink> {
+        self.sink.as_deref()
+    }
+}
+
+pub struct MetricsPlugin;
+
+impl Plugin for MetricsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Metrics>()
+            .add_observer(report_wave_started)
+            .add_observer(report_wave_completed)
+            .add_observer(report_game_over)
+            .add_observer(report_match);
+    }
+}
+
+fn report_wave_started(trigger: Trigger<WaveStartEvent>, metrics: Res<Metrics>) {
+    if let Some(sink) = metrics.sink() {
+        sink.wave_started(trigger.event().wave_number);
+    }
+}
+
+fn report_wave_completed(trigger: Trigger<WaveCompleteEvent>, metrics: Res<Metrics>) {
+    if let Some(sink) = metrics.sink() {
+        sink.wave_completed(trigger.event().wave_number);
+    }
+}
+
+fn report_game_over(trigger: Trigger<GameOverEvent>, metrics: Res<Metrics>, score: Option<Res<Score>>) {
+    if let Some(sink) = metrics.sink() {
+        let event = trigger.event();
+        sink.game_over(event.victory, event.waves_survived, score.map_or(0, |score| score.0));
+    }
+}
+
+fn report_match(trigger: Trigger<MatchEvent>, metrics: Res<Metrics>) {
+    if let Some(sink) = metrics.sink() {
+        let event = trigger.event();
+        sink.match_made(event.tile_type, event.count);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+    use bevy::state::app::StatesPlugin;
+    use crate::battle::{GameResult, BaseHealth, UnitCensus, WaveManager};
+
+    #[derive(Debug, PartialEq)]
+    enum Call {
+        WaveStarted(u32),
+        WaveCompleted(u32),
+        GameOver(bool, u32, u32),
+        Match(TileType, usize),
+    }
+
+    #[derive(Clone, Default)]
+    struct Recorder(Arc<Mutex<Vec<Call>>>);
+
+    impl MetricsSink for Recorder {
+        fn wave_started(&self, wave: u32) {
+            self.0.lock().unwrap().push(Call::WaveStarted(wave));
+        }
+        fn wave_completed(&self, wave: u32) {
+            self.0.lock().unwrap().push(Call::WaveCompleted(wave));
+        }
+        fn game_over(&self, victory: bool, waves_survived: u32, score: u32) {
+            self.0.lock().unwrap().push(Call::GameOver(victory, waves_survived, score));
+        }
+        fn match_made(&self, tile_type: TileType, count: usize) {
+            self.0.lock().unwrap().push(Call::Match(tile_type, count));
+        }
+    }
+
+    /// Only overrides one callback; the rest stay no-ops
+    struct WavesOnly(Arc<Mutex<Vec<u32>>>);
+
+    impl MetricsSink for WavesOnly {
+        fn wave_completed(&self, wave: u32) {
+            self.0.lock().unwrap().push(wave);
+        }
+    }
+
+    fn setup_metrics_app(metrics: Metrics) -> App {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, StatesPlugin, MetricsPlugin))
+            .insert_resource(metrics)
+            .insert_resource(Score(1500));
+        app.world_mut().flush();
+        app
+    }
+
+    #[test]
+    fn test_sink_receives_wave_completion_from_game_result() {
+        let recorder = Recorder::default();
+        let mut app = setup_metrics_app(Metrics::new(recorder.clone()));
+        app.init_state::<GameState>()
+            .init_resource::<UnitCensus>()
+            .init_resource::<GameMode>()
+            .init_resource::<GameResult>()
+            .init_resource::<BaseHealth>()
+            .init_resource::<TimeScale>()
+            .init_resource::<GameSpeed>()
+            .add_systems(Update, crate::battle::check_game_result);
+        // Wave 2 fully spawned and cleared
+        let mut wave_manager = WaveManager::default();
+        wave_manager.start_wave(2, Difficulty::Normal);
+        wave_manager.enemies_remaining = 0;
+        wave_manager.wave_active = false;
+        app.insert_resource(wave_manager);
+
+        app.world_mut().trigger(WaveStartEvent { wave_number: 2 });
+        app.world_mut().trigger(MatchEvent { tile_type: TileType::Blue, count: 4, positions: Vec::new() });
+        for _ in 0..3 {
+            app.update();
+        }
+        app.world_mut().trigger(GameOverEvent { victory: false, waves_survived: 2 });
+
+        assert_eq!(
+            *recorder.0.lock().unwrap(),
+            vec![
+                Call::WaveStarted(2),
+                Call::Match(TileType::Blue, 4),
+                Call::WaveCompleted(2),
+                Call::GameOver(false, 2, 1500),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_partial_sink_only_sees_overridden_callbacks() {
+        let waves = Arc::new(Mutex::new(Vec::new()));
+        let mut app = setup_metrics_app(Metrics::new(WavesOnly(waves.clone())));
+
+        app.world_mut().trigger(WaveStartEvent { wave_number: 1 });
+        app.world_mut().trigger(WaveCompleteEvent { wave_number: 1 });
+        app.world_mut().trigger(GameOverEvent { victory: true, waves_survived: 1 });
+
+        assert_eq!(*waves.lock().unwrap(), vec![1]);
+    }
+
+    #[test]
+    fn test_default_metrics_has_no_sink() {
+        let mut app = setup_metrics_app(Metrics::default());
+        assert!(app.world().resource::<Metrics>().sink().is_none());
+
+        // Observers still run, they just have nothing to report to
+        app.world_mut().trigger(WaveCompleteEvent { wave_number: 1 });
+        app.world_mut().trigger(MatchEvent { tile_type: TileType::Red, count: 3, positions: Vec::new() });
+    }
+}
@@ -1,14 +1,17 @@
 pub use bevy::prelude::*;
 pub use bevy::math::primitives::Triangle2d;
 pub use bevy::sprite::ColorMaterial;
-pub use crate::state::{GameState, PhaseState, ComboCounter, TimeScale, SlowMoEvent, WaveBreakTimer};
+pub use crate::state::{GameState, GameMode, Difficulty, PhaseState, ComboCounter, Gold, TimeScale, GameSpeed, ScaledTime, SlowMoEvent, WaveBreakTimer, simulation_paused};
+pub use crate::rng::GameRng;
+pub use crate::keybindings::KeyBindings;
 
 // Shared types from puzzle module (re-exported for battle module to avoid direct dependency)
-pub use crate::puzzle::{TileType, ObstacleType, GridPosition, Obstacle, PuzzleBoard};
+pub use crate::puzzle::{TileType, ObstacleType, GridPosition, Obstacle, PuzzleBoard, BoardConfig};
 
 pub const WINDOW_WIDTH: f32 = 800.0;
 pub const WINDOW_HEIGHT: f32 = 1100.0;
 
+/// Default puzzle board size; override at runtime via `BoardConfig`
 pub const PUZZLE_BOARD_SIZE: usize = 8;
 pub const TILE_SIZE: f32 = 64.0;
 pub const TILE_GAP: f32 = 4.0;
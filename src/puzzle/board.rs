@@ -1,37 +1,69 @@
 use crate::prelude::*;
 use super::tile::{Tile, TileType, GridPosition, ObstacleType};
+use super::preview::TilePreview;
+use super::match_detector::{TileGrid, empty_tile_grid};
+
+/// Runtime puzzle board dimensions (board is always square)
+#[derive(Resource, Clone, Copy, Debug)]
+pub struct BoardConfig {
+    pub size: usize,
+}
+
+impl Default for BoardConfig {
+    fn default() -> Self {
+        Self { size: PUZZLE_BOARD_SIZE }
+    }
+}
 
 #[derive(Resource)]
 pub struct PuzzleBoard {
-    pub grid: [[Option<Entity>; PUZZLE_BOARD_SIZE]; PUZZLE_BOARD_SIZE],
-    pub obstacles: [[Option<ObstacleType>; PUZZLE_BOARD_SIZE]; PUZZLE_BOARD_SIZE],
+    pub size: usize,
+    pub grid: Vec<Vec<Option<Entity>>>,
+    /// Ice and stone, anchored to the cell
+    pub obstacles: Vec<Vec<Option<ObstacleType>>>,
+    /// Bombs ride on tiles, so they get their own layer and can share a cell with ice/stone
+    pub bombs: Vec<Vec<bool>>,
     pub tile_size: f32,
     pub origin: Vec2,
 }
 
 impl Default for PuzzleBoard {
     fn default() -> Self {
+        Self::new(PUZZLE_BOARD_SIZE)
+    }
+}
+
+impl PuzzleBoard {
+    pub fn new(size: usize) -> Self {
         Self {
-            grid: [[None; PUZZLE_BOARD_SIZE]; PUZZLE_BOARD_SIZE],
-            obstacles: [[None; PUZZLE_BOARD_SIZE]; PUZZLE_BOARD_SIZE],
+            size,
+            grid: vec![vec![None; size]; size],
+            obstacles: vec![vec![None; size]; size],
+            bombs: vec![vec![false; size]; size],
             tile_size: TILE_SIZE,
             origin: Vec2::new(
-                -((PUZZLE_BOARD_SIZE as f32 * (TILE_SIZE + TILE_GAP)) / 2.0) + (TILE_SIZE / 2.0),
+                -((size as f32 * (TILE_SIZE + TILE_GAP)) / 2.0) + (TILE_SIZE / 2.0),
                 -WINDOW_HEIGHT / 2.0 + TILE_SIZE / 2.0 + 20.0,
             ),
         }
     }
-}
 
-pub const CORE_POSITIONS: [(usize, usize); 4] = [(3, 3), (3, 4), (4, 3), (4, 4)];
+    pub fn from_config(config: &BoardConfig) -> Self {
+        Self::new(config.size)
+    }
 
-impl PuzzleBoard {
-    pub fn is_core_position(x: usize, y: usize) -> bool {
-        CORE_POSITIONS.contains(&(x, y))
+    /// The 2×2 crystal core block at the center of the board
+    pub fn core_positions(&self) -> [(usize, usize); 4] {
+        let c = (self.size / 2).max(1);
+        [(c - 1, c - 1), (c - 1, c), (c, c - 1), (c, c)]
     }
 
-    pub fn is_adjacent_to_core(x: usize, y: usize) -> bool {
-        for (cx, cy) in CORE_POSITIONS {
+    pub fn is_core_position(&self, x: usize, y: usize) -> bool {
+        self.core_positions().contains(&(x, y))
+    }
+
+    pub fn is_adjacent_to_core(&self, x: usize, y: usize) -> bool {
+        for (cx, cy) in self.core_positions() {
             let dx = (x as i32 - cx as i32).abs();
             let dy = (y as i32 - cy as i32).abs();
             if (dx == 1 && dy == 0) || (dx == 0 && dy == 1) {
@@ -41,6 +73,10 @@ impl PuzzleBoard {
         false
     }
 
+    pub fn in_bounds(&self, x: usize, y: usize) -> bool {
+        x < self.size && y < self.size
+    }
+
     pub fn grid_to_world(&self, x: usize, y: usize) -> Vec2 {
         Vec2::new(
             self.origin.x + x as f32 * (self.tile_size + TILE_GAP),
@@ -53,7 +89,7 @@ impl PuzzleBoard {
         let x = (local.x / (self.tile_size + TILE_GAP)).floor() as i32;
         let y = (local.y / (self.tile_size + TILE_GAP)).floor() as i32;
 
-        if x >= 0 && x < PUZZLE_BOARD_SIZE as i32 && y >= 0 && y < PUZZLE_BOARD_SIZE as i32 {
+        if x >= 0 && x < self.size as i32 && y >= 0 && y < self.size as i32 {
             Some((x as usize, y as usize))
         } else {
             None
@@ -65,7 +101,7 @@ impl PuzzleBoard {
     }
 
     pub fn set(&mut self, x: usize, y: usize, entity: Option<Entity>) {
-        if y < PUZZLE_BOARD_SIZE && x < PUZZLE_BOARD_SIZE {
+        if self.in_bounds(x, y) {
             self.grid[y][x] = entity;
         }
     }
@@ -80,15 +116,77 @@ impl PuzzleBoard {
         let temp_obstacle = self.obstacles[a.1][a.0];
         self.obstacles[a.1][a.0] = self.obstacles[b.1][b.0];
         self.obstacles[b.1][b.0] = temp_obstacle;
+        let temp_bomb = self.bombs[a.1][a.0];
+        self.bombs[a.1][a.0] = self.bombs[b.1][b.0];
+        self.bombs[b.1][b.0] = temp_bomb;
+    }
+
+    /// Build a board from a known color layout (`layout[y][x]`) and spawn its tile entities.
+    /// `None` cells are left empty. Intended for tests and scripted puzzles.
+    pub fn from_layout(commands: &mut Commands, layout: &[Vec<Option<TileType>>]) -> Self {
+        let size = layout.len();
+        assert!(
+            layout.iter().all(|row| row.len() == size),
+            "puzzle layout must be square"
+        );
+
+        let mut board = Self::new(size);
+        for (y, row) in layout.iter().enumerate() {
+            for (x, cell) in row.iter().enumerate() {
+                if let Some(tile_type) = cell {
+                    let entity = spawn_tile(commands, &board, *tile_type, x, y);
+                    board.set(x, y, Some(entity));
+                }
+            }
+        }
+        board
+    }
+
+    /// Snapshot of tile colors (`grid[y][x]`), resolving each entity through `tile_type_of`,
+    /// e.g. `|e| tiles.get(e).ok().copied()` in a system or `|e| world.get(e).copied()` in tests
+    pub fn tile_type_grid(&self, tile_type_of: impl Fn(Entity) -> Option<TileType>) -> TileGrid {
+        let mut grid = empty_tile_grid(self.size);
+        for (y, row) in self.grid.iter().enumerate() {
+            for (x, cell) in row.iter().enumerate() {
+                grid[y][x] = cell.and_then(&tile_type_of);
+            }
+        }
+        grid
+    }
+
+    /// Move a tile entity into an empty cell (used by gravity).
+    ///
+    /// Bombs are tile children, so their board entry travels with the tile,
+    /// even onto a cell holding ice or stone.
+    /// Ice and stone are features of the board cell and stay where they are.
+    pub fn move_tile(&mut self, from: (usize, usize), to: (usize, usize)) {
+        let entity = self.grid[from.1][from.0].take();
+        self.grid[to.1][to.0] = entity;
+
+        if self.has_bomb(from.0, from.1) {
+            self.clear_bomb(from.0, from.1);
+            self.set_obstacle(to.0, to.1, Some(ObstacleType::Bomb));
+        }
     }
 
+    /// Ice or stone at the cell, otherwise a bomb if one sits there
     pub fn get_obstacle(&self, x: usize, y: usize) -> Option<ObstacleType> {
-        self.obstacles.get(y).and_then(|row| row.get(x)).copied().flatten()
+        let cell = self.obstacles.get(y).and_then(|row| row.get(x)).copied().flatten();
+        cell.or_else(|| self.has_bomb(x, y).then_some(ObstacleType::Bomb))
     }
 
+    /// Bombs go to the bomb layer, ice/stone to the cell; `None` clears both
     pub fn set_obstacle(&mut self, x: usize, y: usize, obstacle: Option<ObstacleType>) {
-        if y < PUZZLE_BOARD_SIZE && x < PUZZLE_BOARD_SIZE {
-            self.obstacles[y][x] = obstacle;
+        if !self.in_bounds(x, y) {
+            return;
+        }
+        match obstacle {
+            Some(ObstacleType::Bomb) => self.bombs[y][x] = true,
+            Some(cell) => self.obstacles[y][x] = Some(cell),
+            None => {
+                self.obstacles[y][x] = None;
+                self.bombs[y][x] = false;
+            }
         }
     }
 
@@ -97,40 +195,192 @@ impl PuzzleBoard {
     }
 
     pub fn has_bomb(&self, x: usize, y: usize) -> bool {
-        self.get_obstacle(x, y) == Some(ObstacleType::Bomb)
+        self.bombs.get(y).and_then(|row| row.get(x)).copied().unwrap_or(false)
+    }
+
+    pub fn has_stone(&self, x: usize, y: usize) -> bool {
+        self.get_obstacle(x, y) == Some(ObstacleType::Stone)
+    }
+
+    /// Ice and stone pin the tile in place
+    pub fn is_swap_blocked(&self, x: usize, y: usize) -> bool {
+        self.has_ice(x, y) || self.has_stone(x, y)
     }
 
+    /// Remove everything at the cell: ice/stone and any bomb
     pub fn clear_obstacle(&mut self, x: usize, y: usize) {
         self.set_obstacle(x, y, None);
     }
+
+    /// Melt ice or break stone, leaving a bomb on the tile in place
+    pub fn clear_cell_obstacle(&mut self, x: usize, y: usize) {
+        if self.in_bounds(x, y) {
+            self.obstacles[y][x] = None;
+        }
+    }
+
+    /// Defuse or detonate a bomb, leaving ice/stone under it in place
+    pub fn clear_bomb(&mut self, x: usize, y: usize) {
+        if self.in_bounds(x, y) {
+            self.bombs[y][x] = false;
+        }
+    }
+}
+
+/// Spawn a tile entity at its board cell (does not register it in `board.grid`)
+pub fn spawn_tile(
+    commands: &mut Commands,
+    board: &PuzzleBoard,
+    tile_type: TileType,
+    x: usize,
+    y: usize,
+) -> Entity {
+    let pos = board.grid_to_world(x, y);
+    commands
+        .spawn((
+            Tile,
+            tile_type,
+            GridPosition::new(x, y),
+            Sprite {
+                color: tile_type.color(),
+                custom_size: Some(Vec2::splat(TILE_SIZE)),
+                ..default()
+            },
+            Transform::from_translation(pos.extend(0.1)),
+            Visibility::default(),
+        ))
+        .id()
 }
 
-pub fn setup_puzzle_board(mut commands: Commands) {
-    let mut board = PuzzleBoard::default();
-
-    for y in 0..PUZZLE_BOARD_SIZE {
-        for x in 0..PUZZLE_BOARD_SIZE {
-            let tile_type = TileType::random();
-            let pos = board.grid_to_world(x, y);
-
-            let entity = commands
-                .spawn((
-                    Tile,
-                    tile_type,
-                    GridPosition::new(x, y),
-                    Sprite {
-                        color: tile_type.color(),
-                        custom_size: Some(Vec2::splat(TILE_SIZE)),
-                        ..default()
-                    },
-                    Transform::from_translation(pos.extend(0.1)),
-                    Visibility::default(),
-                ))
-                .id();
+pub fn setup_puzzle_board(
+    mut commands: Commands,
+    config: Res<BoardConfig>,
+    mut rng: ResMut<GameRng>,
+) {
+    let mut board = PuzzleBoard::from_config(&config);
 
+    for y in 0..board.size {
+        for x in 0..board.size {
+            let tile_type = TileType::random(&mut *rng);
+            let entity = spawn_tile(&mut commands, &board, tile_type, x, y);
             board.set(x, y, Some(entity));
         }
     }
 
     commands.insert_resource(board);
+    commands.insert_resource(TilePreview::new(&mut *rng));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_board_config_default_size() {
+        assert_eq!(BoardConfig::default().size, PUZZLE_BOARD_SIZE);
+    }
+
+    #[test]
+    fn test_board_allocates_runtime_size() {
+        let board = PuzzleBoard::new(6);
+        assert_eq!(board.grid.len(), 6);
+        assert!(board.grid.iter().all(|row| row.len() == 6));
+        assert_eq!(board.obstacles.len(), 6);
+    }
+
+    #[test]
+    fn test_core_positions_default_board() {
+        let board = PuzzleBoard::default();
+        assert_eq!(board.core_positions(), [(3, 3), (3, 4), (4, 3), (4, 4)]);
+    }
+
+    #[test]
+    fn test_core_positions_centered_on_6x6() {
+        let board = PuzzleBoard::new(6);
+        assert_eq!(board.core_positions(), [(2, 2), (2, 3), (3, 2), (3, 3)]);
+        assert!(board.is_adjacent_to_core(1, 2));
+        assert!(!board.is_adjacent_to_core(0, 0));
+    }
+
+    #[test]
+    fn test_world_to_grid_respects_runtime_size() {
+        let board = PuzzleBoard::new(6);
+        let last = board.grid_to_world(5, 5);
+        assert_eq!(board.world_to_grid(last), Some((5, 5)));
+
+        let outside = board.grid_to_world(5, 5) + Vec2::new(TILE_SIZE + TILE_GAP, 0.0);
+        assert_eq!(board.world_to_grid(outside), None);
+    }
+
+    #[test]
+    fn test_set_ignores_out_of_bounds() {
+        let mut board = PuzzleBoard::new(6);
+        board.set_obstacle(7, 7, Some(ObstacleType::Ice));
+        assert!(board.get_obstacle(7, 7).is_none());
+    }
+
+    #[test]
+    fn test_move_tile_carries_bomb_but_not_ice() {
+        let mut board = PuzzleBoard::new(4);
+        let tile = Entity::from_raw(1);
+        let frozen = Entity::from_raw(2);
+        board.set(0, 3, Some(tile));
+        board.set_obstacle(0, 3, Some(ObstacleType::Bomb));
+        board.set(1, 2, Some(frozen));
+        board.set_obstacle(1, 2, Some(ObstacleType::Ice));
+
+        board.move_tile((0, 3), (0, 0));
+        board.move_tile((1, 2), (1, 0));
+
+        assert_eq!(board.get(0, 0), Some(tile));
+        assert_eq!(board.get(0, 3), None);
+        assert!(board.has_bomb(0, 0));
+        assert!(!board.has_bomb(0, 3));
+        assert_eq!(board.get(1, 0), Some(frozen));
+        assert!(board.has_ice(1, 2));
+        assert!(!board.has_ice(1, 0));
+    }
+
+    #[test]
+    fn test_bomb_keeps_its_entry_when_falling_onto_ice() {
+        let mut board = PuzzleBoard::new(4);
+        board.set(2, 3, Some(Entity::from_raw(1)));
+        board.set_obstacle(2, 3, Some(ObstacleType::Bomb));
+        board.set_obstacle(2, 0, Some(ObstacleType::Ice));
+
+        board.move_tile((2, 3), (2, 0));
+
+        assert!(board.has_bomb(2, 0), "bomb still tracked on the icy cell");
+        assert!(board.has_ice(2, 0));
+        assert!(!board.has_bomb(2, 3));
+
+        board.clear_cell_obstacle(2, 0);
+        assert!(!board.has_ice(2, 0));
+        assert!(board.has_bomb(2, 0), "melting ice leaves the bomb");
+        board.set_obstacle(2, 0, Some(ObstacleType::Ice));
+        board.clear_bomb(2, 0);
+        assert!(board.has_ice(2, 0), "defusing leaves the ice");
+    }
+
+    #[test]
+    fn test_from_layout_spawns_tiles_and_snapshots_back() {
+        let layout = vec![
+            vec![Some(TileType::Red), None, Some(TileType::Blue)],
+            vec![Some(TileType::Green), Some(TileType::Green), None],
+            vec![None, Some(TileType::Purple), Some(TileType::Yellow)],
+        ];
+        let mut world = World::new();
+        let mut commands = world.commands();
+        let board = PuzzleBoard::from_layout(&mut commands, &layout);
+        world.flush();
+
+        assert_eq!(board.size, 3);
+        assert_eq!(board.get(1, 0), None);
+        let tile = board.get(2, 0).unwrap();
+        let pos = world.get::<GridPosition>(tile).unwrap();
+        assert_eq!((pos.x, pos.y), (2, 0));
+
+        let snapshot = board.tile_type_grid(|e| world.get::<TileType>(e).copied());
+        assert_eq!(snapshot, layout);
+    }
 }
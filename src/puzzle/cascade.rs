@@ -3,6 +3,40 @@ use crate::bridge::ManaSupplyEvent;
 use crate::battle::BattleStats;
 use super::{PuzzleBoard, Tile, TileType, GridPosition, Matched, TilePreview};
 use super::input::SwapAnimation;
+use super::board::spawn_tile;
+
+/// Which way tiles fall after a clear. Refills come in from the opposite edge.
+/// Board coordinates are unchanged, so match detection doesn't care.
+#[derive(Resource, Default, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum GravityDirection {
+    /// Toward y = 0 (bottom of the screen)
+    #[default]
+    Down,
+    Up,
+    /// Toward x = 0
+    Left,
+    Right,
+}
+
+impl GravityDirection {
+    /// Every column (Down/Up) or row (Left/Right) of a `size` board, each listed from
+    /// the cell tiles settle against to the edge new tiles come in from. Gravity and
+    /// spawning both walk these lines so they always agree on the direction.
+    pub fn lines(self, size: usize) -> Vec<Vec<(usize, usize)>> {
+        (0..size)
+            .map(|line| {
+                (0..size)
+                    .map(|i| match self {
+                        GravityDirection::Down => (line, i),
+                        GravityDirection::Up => (line, size - 1 - i),
+                        GravityDirection::Left => (i, line),
+                        GravityDirection::Right => (size - 1 - i, line),
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+}
 
 #[derive(Resource, Default)]
 pub struct CascadeState {
@@ -31,6 +65,7 @@ pub fn start_cascade(
 
 pub fn apply_gravity(
     mut cascade_state: ResMut<CascadeState>,
+    gravity: Res<GravityDirection>,
     mut board: ResMut<PuzzleBoard>,
     mut tiles: Query<(Entity, &mut GridPosition, &mut Transform), With<Tile>>,
     swap_anims: Query<Entity, With<SwapAnimation>>,
@@ -44,22 +79,24 @@ pub fn apply_gravity(
         return;
     }
 
-    for x in 0..PUZZLE_BOARD_SIZE {
-        let mut write_y = 0;
+    for line in gravity.lines(board.size) {
+        let mut write = 0;
 
-        for read_y in 0..PUZZLE_BOARD_SIZE {
-            if let Some(entity) = board.get(x, read_y) {
-                if read_y != write_y {
-                    board.set(x, write_y, Some(entity));
-                    board.set(x, read_y, None);
+        for read in 0..line.len() {
+            let (from, to) = (line[read], line[write]);
+            if let Some(entity) = board.get(from.0, from.1) {
+                if read != write {
+                    // Ice/stone stay on their cell, bomb data follows the tile
+                    board.move_tile(from, to);
 
                     if let Ok((_, mut pos, mut transform)) = tiles.get_mut(entity) {
-                        pos.y = write_y;
-                        let target = board.grid_to_world(x, write_y);
+                        pos.x = to.0;
+                        pos.y = to.1;
+                        let target = board.grid_to_world(to.0, to.1);
                         transform.translation = target.extend(0.1);
                     }
                 }
-                write_y += 1;
+                write += 1;
             }
         }
     }
@@ -72,33 +109,20 @@ pub fn spawn_new_tiles(
     mut commands: Commands,
     mut board: ResMut<PuzzleBoard>,
     mut cascade_state: ResMut<CascadeState>,
+    gravity: Res<GravityDirection>,
     mut tile_preview: ResMut<TilePreview>,
+    mut rng: ResMut<GameRng>,
 ) {
     if !cascade_state.pending_spawn {
         return;
     }
 
-    for x in 0..PUZZLE_BOARD_SIZE {
-        for y in 0..PUZZLE_BOARD_SIZE {
+    // Gravity left the gaps at the far end of each line; fill them stacking outward
+    for line in gravity.lines(board.size) {
+        for (x, y) in line {
             if board.get(x, y).is_none() {
-                let tile_type = tile_preview.consume_next();
-                let pos = board.grid_to_world(x, y);
-
-                let entity = commands
-                    .spawn((
-                        Tile,
-                        tile_type,
-                        GridPosition::new(x, y),
-                        Sprite {
-                            color: tile_type.color(),
-                            custom_size: Some(Vec2::splat(TILE_SIZE)),
-                            ..default()
-                        },
-                        Transform::from_translation(pos.extend(0.1)),
-                        Visibility::default(),
-                    ))
-                    .id();
-
+                let tile_type = tile_preview.consume_next(&mut *rng);
+                let entity = spawn_tile(&mut commands, &board, tile_type, x, y);
                 board.set(x, y, Some(entity));
             }
         }
@@ -141,7 +165,8 @@ fn calculate_mana_from_combo(combo_count: u32) -> f32 {
         return 0.0;
     }
 
-    let base_mana = 10.0;
+    // Team-wide baseline; matches also charge their own color via `ColoredManaEvent`
+    let base_mana = 5.0;
     let combo_bonus = match combo_count {
         1 => 1.0,
         2 => 1.5,
@@ -153,11 +178,199 @@ fn calculate_mana_from_combo(combo_count: u32) -> f32 {
     base_mana * combo_bonus
 }
 
-pub fn reset_combo_on_idle(
-    mut combo: ResMut<ComboCounter>,
-    phase: Res<State<PhaseState>>,
-) {
-    if *phase.get() == PhaseState::Idle && combo.current > 0 {
-        combo.reset();
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn setup_gravity_app(size: usize) -> App {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .insert_resource(PuzzleBoard::new(size))
+            .insert_resource(CascadeState {
+                has_matches: true,
+                pending_gravity: true,
+                pending_spawn: false,
+            })
+            .init_resource::<GravityDirection>()
+            .add_systems(Update, apply_gravity);
+        app
+    }
+
+    fn spawn_tile(app: &mut App, tile_type: TileType, x: usize, y: usize) -> Entity {
+        let entity = app
+            .world_mut()
+            .spawn((Tile, tile_type, GridPosition::new(x, y), Transform::default()))
+            .id();
+        app.world_mut().resource_mut::<PuzzleBoard>().set(x, y, Some(entity));
+        entity
+    }
+
+    #[test]
+    fn test_gravity_compacts_column_on_6x6_board() {
+        let mut app = setup_gravity_app(6);
+        let lower = spawn_tile(&mut app, TileType::Red, 2, 3);
+        let upper = spawn_tile(&mut app, TileType::Blue, 2, 5);
+
+        app.update();
+
+        let board = app.world().resource::<PuzzleBoard>();
+        assert_eq!(board.get(2, 0), Some(lower));
+        assert_eq!(board.get(2, 1), Some(upper));
+        assert_eq!(board.get(2, 5), None);
+        assert_eq!(app.world().get::<GridPosition>(upper).unwrap().y, 1);
+
+        let cascade = app.world().resource::<CascadeState>();
+        assert!(!cascade.pending_gravity);
+        assert!(cascade.pending_spawn);
+    }
+
+    #[test]
+    fn test_gravity_leaves_ice_on_its_cell() {
+        let mut app = setup_gravity_app(6);
+        // Frozen tile at (1,2) with an empty gap beneath it
+        let frozen = spawn_tile(&mut app, TileType::Green, 1, 2);
+        app.world_mut()
+            .resource_mut::<PuzzleBoard>()
+            .set_obstacle(1, 2, Some(ObstacleType::Ice));
+        let overlay = app
+            .world_mut()
+            .spawn((Obstacle::ice(), GridPosition::new(1, 2)))
+            .id();
+
+        app.update();
+
+        let board = app.world().resource::<PuzzleBoard>();
+        assert_eq!(board.get(1, 0), Some(frozen), "tile falls out from under the ice");
+        assert!(board.has_ice(1, 2), "ice stays at its board coordinate");
+        assert!(!board.has_ice(1, 0));
+        let overlay_pos = app.world().get::<GridPosition>(overlay).unwrap();
+        assert_eq!((overlay_pos.x, overlay_pos.y), (1, 2));
+    }
+
+    #[test]
+    fn test_gravity_moves_bomb_with_its_tile() {
+        let mut app = setup_gravity_app(6);
+        let tile = spawn_tile(&mut app, TileType::Red, 3, 4);
+        let bomb = app
+            .world_mut()
+            .spawn((Obstacle::bomb(3), GridPosition::new(3, 4)))
+            .set_parent(tile)
+            .id();
+        app.world_mut()
+            .resource_mut::<PuzzleBoard>()
+            .set_obstacle(3, 4, Some(ObstacleType::Bomb));
+
+        app.update();
+
+        let board = app.world().resource::<PuzzleBoard>();
+        assert_eq!(board.get(3, 0), Some(tile));
+        assert!(board.has_bomb(3, 0), "bomb data follows the falling tile");
+        assert!(!board.has_bomb(3, 4));
+        assert_eq!(app.world().get::<Parent>(bomb).map(|p| p.get()), Some(tile));
+    }
+
+    #[test]
+    fn test_bomb_does_not_overwrite_ice_below() {
+        let mut app = setup_gravity_app(6);
+        let tile = spawn_tile(&mut app, TileType::Red, 0, 2);
+        {
+            let mut board = app.world_mut().resource_mut::<PuzzleBoard>();
+            board.set_obstacle(0, 0, Some(ObstacleType::Ice));
+            board.set_obstacle(0, 2, Some(ObstacleType::Bomb));
+        }
+
+        app.update();
+
+        let board = app.world().resource::<PuzzleBoard>();
+        assert_eq!(board.get(0, 0), Some(tile));
+        assert!(board.has_ice(0, 0));
+        assert!(board.has_bomb(0, 0), "bomb stays tracked on the icy cell");
+        assert!(!board.has_bomb(0, 2));
+    }
+
+    #[test]
+    fn test_gravity_lines_run_from_settle_side_to_spawn_edge() {
+        assert_eq!(GravityDirection::Down.lines(3)[1], vec![(1, 0), (1, 1), (1, 2)]);
+        assert_eq!(GravityDirection::Up.lines(3)[1], vec![(1, 2), (1, 1), (1, 0)]);
+        assert_eq!(GravityDirection::Left.lines(3)[1], vec![(0, 1), (1, 1), (2, 1)]);
+        assert_eq!(GravityDirection::Right.lines(3)[1], vec![(2, 1), (1, 1), (0, 1)]);
+    }
+
+    #[test]
+    fn test_left_gravity_compacts_toward_x0_and_refills_from_the_right() {
+        let mut app = setup_gravity_app(6);
+        app.insert_resource(GravityDirection::Left)
+            .init_resource::<TilePreview>()
+            .insert_resource(GameRng::from_seed(7))
+            .add_systems(Update, spawn_new_tiles.after(apply_gravity));
+        let near = spawn_tile(&mut app, TileType::Red, 2, 4);
+        let far = spawn_tile(&mut app, TileType::Blue, 5, 4);
+        let other_row = spawn_tile(&mut app, TileType::Green, 3, 1);
+
+        app.update();
+
+        let board = app.world().resource::<PuzzleBoard>();
+        assert_eq!(board.get(0, 4), Some(near), "first tile in the row slides to x=0");
+        assert_eq!(board.get(1, 4), Some(far), "order along the row is kept");
+        assert_eq!(board.get(0, 1), Some(other_row));
+        assert_eq!(board.get(3, 1).map(|e| e == other_row), Some(false), "vacated cell refilled");
+        let pos = app.world().get::<GridPosition>(far).unwrap();
+        assert_eq!((pos.x, pos.y), (1, 4), "only x changes");
+
+        let board = app.world().resource::<PuzzleBoard>();
+        for x in 2..6 {
+            let refill = board.get(x, 4).expect("right side of the row is refilled");
+            assert!(refill != near && refill != far);
+        }
+        let cascade = app.world().resource::<CascadeState>();
+        assert!(!cascade.pending_gravity && !cascade.pending_spawn);
+    }
+
+    #[test]
+    fn test_spawned_tiles_follow_preview_order_past_queue_length() {
+        let mut rng = GameRng::from_seed(21);
+        let preview = TilePreview::new(&mut rng);
+        let shown = preview.peek_all();
+
+        // Same seed, same draws: the order refills should come out in
+        let mut expected_rng = GameRng::from_seed(21);
+        let mut expected_preview = TilePreview::new(&mut expected_rng);
+        let expected: Vec<TileType> = (0..5)
+            .map(|_| expected_preview.consume_next(&mut expected_rng))
+            .collect();
+        assert_eq!(expected[..shown.len()], shown[..], "first refills are the previewed tiles");
+
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .insert_resource(PuzzleBoard::new(6))
+            .insert_resource(CascadeState {
+                has_matches: true,
+                pending_gravity: false,
+                pending_spawn: true,
+            })
+            .init_resource::<GravityDirection>()
+            .insert_resource(preview)
+            .insert_resource(rng)
+            .add_systems(Update, spawn_new_tiles);
+        // Only column 2 above y = 0 is empty: five refills, more than the preview holds
+        for y in 0..6 {
+            for x in 0..6 {
+                if x != 2 || y == 0 {
+                    spawn_tile(&mut app, TileType::Red, x, y);
+                }
+            }
+        }
+
+        app.update();
+
+        let world = app.world();
+        let board = world.resource::<PuzzleBoard>();
+        let spawned: Vec<TileType> = (1..6)
+            .map(|y| *world.get::<TileType>(board.get(2, y).unwrap()).unwrap())
+            .collect();
+        assert_eq!(spawned, expected);
+        let preview = world.resource::<TilePreview>();
+        assert_eq!(preview.len(), super::super::preview::PREVIEW_SIZE, "queue refilled after the burst");
+        assert_eq!(preview.peek_all(), expected_preview.peek_all());
     }
 }
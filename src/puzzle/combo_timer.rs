@@ -0,0 +1,156 @@
+//! Combo window
+//!
+//! The combo carries over from one swap to the next as long as the player keeps
+//! matching. Every match refreshes a `COMBO_WINDOW` second timer; it only runs down
+//! while the board is idle, and once it empties the combo is dropped.
+
+use crate::prelude::*;
+use crate::bridge::MatchEvent;
+
+/// Seconds the player has after a match to land the next one
+pub const COMBO_WINDOW: f32 = 3.0;
+
+#[derive(Resource, Clone, Copy, Debug, Default)]
+pub struct ComboTimer {
+    /// Seconds left before the combo drops; 0 when no combo is running
+    pub remaining: f32,
+}
+
+impl ComboTimer {
+    pub fn refresh(&mut self) {
+        self.remaining = COMBO_WINDOW;
+    }
+
+    /// Run the window down; true on the tick it runs out
+    pub fn tick(&mut self, delta: f32) -> bool {
+        if !self.is_running() {
+            return false;
+        }
+        self.remaining = (self.remaining - delta).max(0.0);
+        !self.is_running()
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.remaining > 0.0
+    }
+
+    /// Time left in 0..=1 for the HUD bar
+    pub fn fraction(&self) -> f32 {
+        (self.remaining / COMBO_WINDOW).clamp(0.0, 1.0)
+    }
+}
+
+pub fn refresh_combo_timer(_trigger: Trigger<MatchEvent>, mut timer: ResMut<ComboTimer>) {
+    timer.refresh();
+}
+
+/// Drop the combo once the window runs out. The clock is held during a cascade, so
+/// only time the player spends on a settled board counts against them.
+pub fn decay_combo_timer(
+    time: Res<Time>,
+    phase: Res<State<PhaseState>>,
+    mut timer: ResMut<ComboTimer>,
+    mut combo: ResMut<ComboCounter>,
+) {
+    if *phase.get() != PhaseState::Idle {
+        return;
+    }
+    if timer.tick(time.delta_secs()) && combo.current > 0 {
+        combo.reset();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::state::app::StatesPlugin;
+    use bevy::time::TimeUpdateStrategy;
+    use std::time::Duration;
+
+    const STEP: f32 = 0.25;
+
+    fn setup_combo_app() -> App {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, StatesPlugin))
+            .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f32(STEP)))
+            .init_state::<PhaseState>()
+            .init_resource::<ComboTimer>()
+            .init_resource::<ComboCounter>()
+            .add_observer(refresh_combo_timer)
+            .add_systems(Update, decay_combo_timer);
+        app.world_mut().flush();
+        app
+    }
+
+    fn match_tiles(app: &mut App) {
+        app.world_mut().trigger(MatchEvent {
+            tile_type: TileType::Red,
+            count: 3,
+            positions: Vec::new(),
+        });
+        app.world_mut().resource_mut::<ComboCounter>().increment();
+    }
+
+    fn remaining(app: &App) -> f32 {
+        app.world().resource::<ComboTimer>().remaining
+    }
+
+    #[test]
+    fn test_match_refreshes_window() {
+        let mut app = setup_combo_app();
+        match_tiles(&mut app);
+        assert_eq!(remaining(&app), COMBO_WINDOW);
+
+        for _ in 0..3 {
+            app.update();
+        }
+        assert!(remaining(&app) < COMBO_WINDOW, "window runs down on an idle board");
+
+        match_tiles(&mut app);
+        assert_eq!(remaining(&app), COMBO_WINDOW, "the next match tops it back up");
+        assert_eq!(app.world().resource::<ComboCounter>().current, 2, "combo carries over");
+    }
+
+    #[test]
+    fn test_combo_resets_when_window_expires() {
+        let mut app = setup_combo_app();
+        match_tiles(&mut app);
+        match_tiles(&mut app);
+
+        for _ in 0..(COMBO_WINDOW / STEP) as usize + 2 {
+            app.update();
+        }
+
+        assert!(!app.world().resource::<ComboTimer>().is_running());
+        assert_eq!(app.world().resource::<ComboCounter>().current, 0);
+    }
+
+    #[test]
+    fn test_window_holds_during_cascade() {
+        let mut app = setup_combo_app();
+        app.world_mut()
+            .resource_mut::<NextState<PhaseState>>()
+            .set(PhaseState::Cascading);
+        app.update();
+        match_tiles(&mut app);
+
+        for _ in 0..(COMBO_WINDOW / STEP) as usize + 2 {
+            app.update();
+        }
+
+        assert_eq!(remaining(&app), COMBO_WINDOW);
+        assert_eq!(app.world().resource::<ComboCounter>().current, 1);
+    }
+
+    #[test]
+    fn test_fraction_tracks_remaining_time() {
+        let mut timer = ComboTimer::default();
+        assert_eq!(timer.fraction(), 0.0);
+        timer.refresh();
+        assert_eq!(timer.fraction(), 1.0);
+        assert!(!timer.tick(COMBO_WINDOW / 2.0));
+        assert!((timer.fraction() - 0.5).abs() < 1e-6);
+        assert!(timer.tick(COMBO_WINDOW), "expires on the tick that empties it");
+        assert!(!timer.tick(1.0), "an empty window doesn't expire again");
+    }
+}
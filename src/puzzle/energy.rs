@@ -0,0 +1,241 @@
+//! Match energy
+//!
+//! Every match charges a meter in proportion to the tiles it cleared. Once full, the
+//! player can press the `match_energy` binding on a settled board to clear every tile of one
+//! random color; the rest of the board cascades down as after a normal match.
+
+use rand::Rng;
+use crate::prelude::*;
+use crate::bridge::MatchEvent;
+use super::{Tile, Matched, PowerTile, LineClearEvent};
+
+/// Energy gained per tile cleared by a match
+pub const ENERGY_PER_TILE: f32 = 1.0;
+/// Energy needed for a color clear
+pub const MATCH_ENERGY_MAX: f32 = 45.0;
+
+#[derive(Resource, Clone, Copy, Debug)]
+pub struct MatchEnergy {
+    pub current: f32,
+    pub max: f32,
+}
+
+impl Default for MatchEnergy {
+    fn default() -> Self {
+        Self {
+            current: 0.0,
+            max: MATCH_ENERGY_MAX,
+        }
+    }
+}
+
+impl MatchEnergy {
+    /// Charge the meter; anything past `max` is lost
+    pub fn add(&mut self, amount: f32) {
+        self.current = (self.current + amount).min(self.max);
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.current >= self.max
+    }
+
+    /// Fill level in 0..=1 for the HUD bar
+    pub fn fraction(&self) -> f32 {
+        if self.max <= 0.0 {
+            return 0.0;
+        }
+        (self.current / self.max).clamp(0.0, 1.0)
+    }
+
+    pub fn reset(&mut self) {
+        self.current = 0.0;
+    }
+}
+
+/// Clear every tile of `tile_type` from the board
+#[derive(Event, Debug)]
+pub struct ColorClearEvent {
+    pub tile_type: TileType,
+}
+
+pub fn fill_match_energy(trigger: Trigger<MatchEvent>, mut energy: ResMut<MatchEnergy>) {
+    energy.add(trigger.event().count as f32 * ENERGY_PER_TILE);
+}
+
+/// Tiles a color clear removes: every tile of `tile_type` not shielded by stone
+pub fn color_clear_targets<'a>(
+    board: &PuzzleBoard,
+    tiles: impl IntoIterator<Item = (Entity, &'a GridPosition, &'a TileType)>,
+    tile_type: TileType,
+) -> Vec<Entity> {
+    tiles
+        .into_iter()
+        .filter(|(_, pos, t)| **t == tile_type && !board.has_stone(pos.x, pos.y))
+        .map(|(entity, ..)| entity)
+        .collect()
+}
+
+/// Random color among those a clear would actually remove, so a charge is never wasted
+fn pick_clear_color<'a>(
+    board: &PuzzleBoard,
+    tiles: impl IntoIterator<Item = (Entity, &'a GridPosition, &'a TileType)>,
+    rng: &mut impl Rng,
+) -> Option<TileType> {
+    let mut colors: Vec<TileType> = Vec::new();
+    for (_, pos, tile_type) in tiles {
+        if !board.has_stone(pos.x, pos.y) && !colors.contains(tile_type) {
+            colors.push(*tile_type);
+        }
+    }
+    if colors.is_empty() {
+        return None;
+    }
+    // Board scan order depends on entity order; sort so a seed always picks the same color
+    colors.sort_by_key(|t| *t as u8);
+    Some(colors[rng.gen_range(0..colors.len())])
+}
+
+/// Spend a full meter on a color clear. Runs only in `PhaseState::Idle`, on a settled
+/// board, so the clear can't race a cascade that is still refilling.
+pub fn activate_match_energy(
+    mut commands: Commands,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    bindings: Res<KeyBindings>,
+    board: Res<PuzzleBoard>,
+    mut energy: ResMut<MatchEnergy>,
+    mut rng: ResMut<GameRng>,
+    tiles: Query<(Entity, &GridPosition, &TileType), With<Tile>>,
+) {
+    if !keyboard.just_pressed(bindings.match_energy) || !energy.is_full() {
+        return;
+    }
+    let Some(tile_type) = pick_clear_color(&board, tiles.iter(), &mut *rng) else { return };
+
+    energy.reset();
+    commands.trigger(ColorClearEvent { tile_type });
+}
+
+/// Mark the cleared color as matched so it flows through `remove_matched_tiles`
+/// and the cascade like a normal match. Power tiles caught in it detonate.
+pub fn handle_color_clear(
+    trigger: Trigger<ColorClearEvent>,
+    mut commands: Commands,
+    board: Res<PuzzleBoard>,
+    tiles: Query<(Entity, &GridPosition, &TileType, Has<PowerTile>), (With<Tile>, Without<Matched>)>,
+) {
+    let targets = color_clear_targets(
+        &board,
+        tiles.iter().map(|(entity, pos, tile_type, _)| (entity, pos, tile_type)),
+        trigger.event().tile_type,
+    );
+
+    for entity in targets {
+        commands.entity(entity).insert(Matched);
+        if let Ok((_, pos, _, true)) = tiles.get(entity) {
+            commands.trigger(LineClearEvent { row: pos.y, col: pos.x });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_energy_fills_per_tile_and_clamps_at_max() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .init_resource::<MatchEnergy>()
+            .add_observer(fill_match_energy);
+        app.world_mut().flush();
+
+        let mut match_tiles = |count: usize| {
+            app.world_mut().trigger(MatchEvent {
+                tile_type: TileType::Red,
+                count,
+                positions: Vec::new(),
+            });
+            *app.world().resource::<MatchEnergy>()
+        };
+
+        let energy = match_tiles(3);
+        assert_eq!(energy.current, 3.0 * ENERGY_PER_TILE);
+        assert!(!energy.is_full());
+
+        let energy = match_tiles(100);
+        assert_eq!(energy.current, energy.max, "overflow is clamped");
+        assert!(energy.is_full());
+        assert_eq!(energy.fraction(), 1.0);
+    }
+
+    #[test]
+    fn test_reset_empties_meter() {
+        let mut energy = MatchEnergy::default();
+        energy.add(MATCH_ENERGY_MAX);
+        energy.reset();
+        assert_eq!(energy.current, 0.0);
+        assert!(!energy.is_full());
+    }
+
+    #[test]
+    fn test_color_clear_targets_skip_other_colors_and_stone() {
+        let mut board = PuzzleBoard::new(4);
+        board.set_obstacle(2, 0, Some(ObstacleType::Stone));
+        let (red, blue, stoned_red, other_red) =
+            (Entity::from_raw(1), Entity::from_raw(2), Entity::from_raw(3), Entity::from_raw(4));
+        let positions = [
+            GridPosition::new(0, 0),
+            GridPosition::new(1, 0),
+            GridPosition::new(2, 0),
+            GridPosition::new(3, 3),
+        ];
+        let types = [TileType::Red, TileType::Blue, TileType::Red, TileType::Red];
+        let tiles = [red, blue, stoned_red, other_red]
+            .into_iter()
+            .zip(positions.iter())
+            .zip(types.iter())
+            .map(|((e, p), t)| (e, p, t));
+
+        assert_eq!(color_clear_targets(&board, tiles.clone(), TileType::Red), vec![red, other_red]);
+        assert_eq!(color_clear_targets(&board, tiles, TileType::Green), Vec::<Entity>::new());
+    }
+
+    #[test]
+    fn test_clear_color_is_one_on_the_board() {
+        let mut board = PuzzleBoard::new(4);
+        board.set_obstacle(1, 0, Some(ObstacleType::Stone));
+        let positions = [GridPosition::new(0, 0), GridPosition::new(1, 0)];
+        let types = [TileType::Green, TileType::Purple];
+        let tiles = || {
+            [Entity::from_raw(1), Entity::from_raw(2)]
+                .into_iter()
+                .zip(positions.iter())
+                .zip(types.iter())
+                .map(|((e, p), t)| (e, p, t))
+        };
+        let mut rng = GameRng::from_seed(4);
+
+        // Purple sits under stone, so green is the only color a clear could take
+        for _ in 0..10 {
+            assert_eq!(pick_clear_color(&board, tiles(), &mut rng), Some(TileType::Green));
+        }
+        assert_eq!(pick_clear_color(&board, std::iter::empty(), &mut rng), None);
+    }
+
+    #[test]
+    fn test_color_clear_marks_tiles_matched() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .insert_resource(PuzzleBoard::new(4))
+            .add_observer(handle_color_clear);
+        let blue = app.world_mut().spawn((Tile, TileType::Blue, GridPosition::new(0, 0))).id();
+        let red = app.world_mut().spawn((Tile, TileType::Red, GridPosition::new(1, 0))).id();
+        app.world_mut().flush();
+
+        app.world_mut().trigger(ColorClearEvent { tile_type: TileType::Blue });
+        app.world_mut().flush();
+
+        assert!(app.world().get::<Matched>(blue).is_some());
+        assert!(app.world().get::<Matched>(red).is_none());
+    }
+}
@@ -0,0 +1,176 @@
+use crate::prelude::*;
+use super::input::{select_cell, BoardBusy, BufferedSwap, SelectableTiles, SelectedTile};
+
+/// Left stick deflection that counts as a direction
+const STICK_DEADZONE: f32 = 0.5;
+/// Seconds between cursor steps while the stick is held
+const STICK_REPEAT: f32 = 0.18;
+const CURSOR_COLOR: Color = Color::srgba(1.0, 1.0, 1.0, 0.6);
+
+/// Board cell under the gamepad cursor
+#[derive(Resource, Debug)]
+pub struct PuzzleCursor {
+    pub position: GridPosition,
+    /// Time left before a held stick moves the cursor again
+    pub stick_cooldown: f32,
+}
+
+impl Default for PuzzleCursor {
+    fn default() -> Self {
+        Self {
+            position: GridPosition::new(0, 0),
+            stick_cooldown: 0.0,
+        }
+    }
+}
+
+impl PuzzleCursor {
+    /// Move one cell by `(dx, dy)`, stopping at the board edges
+    pub fn step(&mut self, (dx, dy): (i32, i32), size: usize) {
+        let max = size.saturating_sub(1) as i32;
+        self.position.x = (self.position.x as i32 + dx).clamp(0, max) as usize;
+        self.position.y = (self.position.y as i32 + dy).clamp(0, max) as usize;
+    }
+
+    pub fn cell(&self) -> (usize, usize) {
+        (self.position.x, self.position.y)
+    }
+}
+
+/// Highlight frame drawn around the cursor cell
+#[derive(Component)]
+pub struct PuzzleCursorHighlight;
+
+/// One-cell step for an analog direction; the dominant axis wins
+fn stick_direction(stick: Vec2) -> Option<(i32, i32)> {
+    if stick.length() < STICK_DEADZONE {
+        return None;
+    }
+    if stick.x.abs() >= stick.y.abs() {
+        Some((stick.x.signum() as i32, 0))
+    } else {
+        Some((0, stick.y.signum() as i32))
+    }
+}
+
+/// Move the cursor with the D-pad (one step per press) or left stick (repeating while held),
+/// and select/swap the cell under it with the South face button
+pub fn gamepad_puzzle_input(
+    mut commands: Commands,
+    time: Res<Time>,
+    gamepads: Query<&Gamepad>,
+    board: Res<PuzzleBoard>,
+    mut cursor: ResMut<PuzzleCursor>,
+    mut selected: ResMut<SelectedTile>,
+    mut buffered: ResMut<BufferedSwap>,
+    busy: BoardBusy,
+    tiles: SelectableTiles,
+) {
+    cursor.stick_cooldown = (cursor.stick_cooldown - time.delta_secs()).max(0.0);
+
+    for gamepad in gamepads.iter() {
+        let dpad = [
+            (GamepadButton::DPadLeft, (-1, 0)),
+            (GamepadButton::DPadRight, (1, 0)),
+            (GamepadButton::DPadDown, (0, -1)),
+            (GamepadButton::DPadUp, (0, 1)),
+        ];
+        for (button, delta) in dpad {
+            if gamepad.just_pressed(button) {
+                cursor.step(delta, board.size);
+            }
+        }
+
+        match stick_direction(gamepad.left_stick()) {
+            Some(delta) if cursor.stick_cooldown <= 0.0 => {
+                cursor.step(delta, board.size);
+                cursor.stick_cooldown = STICK_REPEAT;
+            }
+            Some(_) => {}
+            // Releasing the stick lets the next push move immediately
+            None => cursor.stick_cooldown = 0.0,
+        }
+
+        if gamepad.just_pressed(GamepadButton::South) {
+            let cell = cursor.cell();
+            select_cell(&mut commands, &board, &mut selected, &mut buffered, busy.is_busy(), &tiles, cell);
+        }
+    }
+}
+
+/// Keep the cursor frame on its cell; it only shows while a gamepad is connected
+pub fn update_cursor_highlight(
+    mut commands: Commands,
+    gamepads: Query<(), With<Gamepad>>,
+    board: Res<PuzzleBoard>,
+    cursor: Res<PuzzleCursor>,
+    mut highlight: Query<(&mut Transform, &mut Visibility), With<PuzzleCursorHighlight>>,
+) {
+    let pos = board.grid_to_world(cursor.position.x, cursor.position.y);
+    let Ok((mut transform, mut visibility)) = highlight.get_single_mut() else {
+        if !gamepads.is_empty() {
+            // Sits just under the tile so only the rim around it shows
+            commands.spawn((
+                PuzzleCursorHighlight,
+                Sprite {
+                    color: CURSOR_COLOR,
+                    custom_size: Some(Vec2::splat(TILE_SIZE + TILE_GAP)),
+                    ..default()
+                },
+                Transform::from_translation(pos.extend(0.05)),
+                Visibility::Visible,
+            ));
+        }
+        return;
+    };
+
+    transform.translation = pos.extend(transform.translation.z);
+    *visibility = if gamepads.is_empty() { Visibility::Hidden } else { Visibility::Visible };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::input::is_adjacent;
+
+    #[test]
+    fn test_cursor_clamps_at_board_edges() {
+        let mut cursor = PuzzleCursor::default();
+        cursor.step((-1, 0), 6);
+        cursor.step((0, -1), 6);
+        assert_eq!(cursor.cell(), (0, 0));
+
+        for _ in 0..10 {
+            cursor.step((1, 0), 6);
+            cursor.step((0, 1), 6);
+        }
+        assert_eq!(cursor.cell(), (5, 5), "stops on the last row/column");
+    }
+
+    #[test]
+    fn test_stick_direction_picks_dominant_axis() {
+        assert_eq!(stick_direction(Vec2::new(0.2, 0.1)), None, "inside the deadzone");
+        assert_eq!(stick_direction(Vec2::new(0.9, 0.3)), Some((1, 0)));
+        assert_eq!(stick_direction(Vec2::new(-0.2, -0.8)), Some((0, -1)));
+    }
+
+    #[test]
+    fn test_cursor_steps_only_reach_adjacent_cells() {
+        let mut cursor = PuzzleCursor::default();
+        cursor.step((1, 0), 8);
+        cursor.step((0, 1), 8);
+        let from = cursor.cell();
+
+        for delta in [(1, 0), (-1, 0), (0, 1), (0, -1)] {
+            let mut moved = PuzzleCursor { position: GridPosition::new(from.0, from.1), stick_cooldown: 0.0 };
+            moved.step(delta, 8);
+            assert!(is_adjacent(from, moved.cell()));
+        }
+        assert!(!is_adjacent(from, (from.0 + 1, from.1 + 1)), "diagonal is not a swap");
+
+        // A clamped step stays put, which is never a valid swap partner
+        let mut corner = PuzzleCursor::default();
+        corner.step((-1, 0), 8);
+        assert!(!is_adjacent((0, 0), corner.cell()));
+    }
+}
@@ -0,0 +1,231 @@
+//! Idle move hint
+//!
+//! After the board has sat in `PhaseState::Idle` for `IdleHintTimer::delay` seconds
+//! without any input, the two tiles of a valid swap get a `HintHighlight` and pulse.
+//! Any key, click or gamepad press, or any change to the board, drops the hint and
+//! restarts the wait.
+
+use bevy::ecs::system::SystemParam;
+
+use crate::prelude::*;
+use super::{PuzzleBoard, Tile, GridPosition, TileType};
+use super::match_detector::{build_matchable_grid, find_valid_move_where};
+
+/// Default seconds of inactivity before a hint shows
+pub const HINT_IDLE_SECS: f32 = 5.0;
+const HINT_PULSE_SPEED: f32 = 4.0;
+const HINT_PULSE_SCALE: f32 = 0.08;
+
+#[derive(Resource, Debug)]
+pub struct IdleHintTimer {
+    /// Seconds idle so far
+    pub idle: f32,
+    /// Seconds idle before the hint shows
+    pub delay: f32,
+}
+
+impl Default for IdleHintTimer {
+    fn default() -> Self {
+        Self { idle: 0.0, delay: HINT_IDLE_SECS }
+    }
+}
+
+/// One of the two tiles of the hinted swap; `phase` drives its pulse
+#[derive(Component, Default)]
+pub struct HintHighlight {
+    pub phase: f32,
+}
+
+/// A valid swap on the current board, skipping ice- and stone-locked cells
+pub fn find_hint(
+    tiles: &[(Entity, &GridPosition, &TileType)],
+    board: &PuzzleBoard,
+) -> Option<((usize, usize), (usize, usize))> {
+    let grid = build_matchable_grid(tiles, board);
+    find_valid_move_where(&grid, |x, y| board.is_swap_blocked(x, y))
+}
+
+/// Keyboard, mouse and gamepad presses, any of which counts as activity
+#[derive(SystemParam)]
+pub struct AnyPress<'w, 's> {
+    keyboard: Res<'w, ButtonInput<KeyCode>>,
+    mouse: Res<'w, ButtonInput<MouseButton>>,
+    gamepads: Query<'w, 's, &'static Gamepad>,
+}
+
+impl AnyPress<'_, '_> {
+    pub fn just_pressed(&self) -> bool {
+        self.keyboard.get_just_pressed().next().is_some()
+            || self.mouse.get_just_pressed().next().is_some()
+            || self.gamepads.iter().any(|gamepad| gamepad.get_just_pressed().next().is_some())
+    }
+}
+
+type ChangedTileQuery<'w, 's> =
+    Query<'w, 's, (), (With<Tile>, Or<(Changed<TileType>, Changed<GridPosition>)>)>;
+
+/// Restart the wait and drop the hint on input, a board change or leaving Idle.
+/// Board changes are read off the tiles and obstacles themselves: `PuzzleBoard` is
+/// borrowed mutably by the match pass every frame, so its change tick always moves.
+pub fn reset_idle_hint(
+    mut commands: Commands,
+    phase: Res<State<PhaseState>>,
+    input: AnyPress,
+    changed_tiles: ChangedTileQuery,
+    new_obstacles: Query<(), Added<Obstacle>>,
+    mut timer: ResMut<IdleHintTimer>,
+    mut hinted: Query<(Entity, &mut Transform), With<HintHighlight>>,
+) {
+    let interrupted = *phase.get() != PhaseState::Idle
+        || input.just_pressed()
+        || !changed_tiles.is_empty()
+        || !new_obstacles.is_empty();
+    if !interrupted {
+        return;
+    }
+    timer.idle = 0.0;
+    for (entity, mut transform) in hinted.iter_mut() {
+        transform.scale = Vec3::ONE;
+        commands.entity(entity).remove::<HintHighlight>();
+    }
+}
+
+/// Count idle time and, once it passes the delay, highlight a valid swap
+pub fn show_idle_hint(
+    mut commands: Commands,
+    time: Res<Time>,
+    board: Res<PuzzleBoard>,
+    mut timer: ResMut<IdleHintTimer>,
+    tiles: Query<(Entity, &GridPosition, &TileType), With<Tile>>,
+    hinted: Query<(), With<HintHighlight>>,
+) {
+    timer.idle += time.delta_secs();
+    if timer.idle < timer.delay || !hinted.is_empty() {
+        return;
+    }
+
+    let tile_data: Vec<_> = tiles.iter().collect();
+    let Some((a, b)) = find_hint(&tile_data, &board) else { return };
+    for (x, y) in [a, b] {
+        if let Some(entity) = board.get(x, y) {
+            commands.entity(entity).insert(HintHighlight::default());
+        }
+    }
+}
+
+/// Gently grow and shrink the hinted tiles
+pub fn pulse_hint_tiles(
+    time: Res<Time>,
+    mut hinted: Query<(&mut HintHighlight, &mut Transform)>,
+) {
+    for (mut hint, mut transform) in hinted.iter_mut() {
+        hint.phase += time.delta_secs() * HINT_PULSE_SPEED;
+        transform.scale = Vec3::splat(1.0 + HINT_PULSE_SCALE * hint.phase.sin().abs());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::state::app::StatesPlugin;
+    use bevy::time::TimeUpdateStrategy;
+    use std::time::Duration;
+    use super::super::board::spawn_tile;
+
+    /// 4x4 board whose only valid swap is (0,1) <-> (1,1)
+    fn single_move_types() -> Vec<Vec<TileType>> {
+        let palette = [TileType::Red, TileType::Blue, TileType::Green, TileType::Yellow];
+        let mut types: Vec<Vec<TileType>> = (0..4)
+            .map(|y| (0..4).map(|x| palette[(x + 2 * y) % 4]).collect())
+            .collect();
+        types[1][0] = TileType::Blue;
+        types
+    }
+
+    fn setup_hint_app() -> (App, Vec<Vec<Entity>>) {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .add_plugins(StatesPlugin)
+            .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f32(0.25)))
+            .init_state::<PhaseState>()
+            .init_resource::<ButtonInput<KeyCode>>()
+            .init_resource::<ButtonInput<MouseButton>>()
+            .init_resource::<IdleHintTimer>()
+            .add_systems(Update, (reset_idle_hint, show_idle_hint).chain());
+
+        let mut board = PuzzleBoard::new(4);
+        let mut entities = vec![Vec::new(); 4];
+        let mut commands = app.world_mut().commands();
+        for (y, row) in single_move_types().into_iter().enumerate() {
+            for (x, tile_type) in row.into_iter().enumerate() {
+                let entity = spawn_tile(&mut commands, &board, tile_type, x, y);
+                board.set(x, y, Some(entity));
+                entities[y].push(entity);
+            }
+        }
+        app.insert_resource(board);
+        app.world_mut().flush();
+        (app, entities)
+    }
+
+    fn hinted(app: &mut App) -> Vec<Entity> {
+        let world = app.world_mut();
+        let mut hinted: Vec<Entity> = world.query_filtered::<Entity, With<HintHighlight>>().iter(world).collect();
+        hinted.sort();
+        hinted
+    }
+
+    #[test]
+    fn test_find_hint_on_board_with_one_valid_move() {
+        let (mut app, _) = setup_hint_app();
+        let world = app.world_mut();
+        let tiles: Vec<(Entity, GridPosition, TileType)> = world
+            .query::<(Entity, &GridPosition, &TileType)>()
+            .iter(world)
+            .map(|(entity, pos, tile_type)| (entity, *pos, *tile_type))
+            .collect();
+        let tile_data: Vec<_> = tiles.iter().map(|(entity, pos, tile_type)| (*entity, pos, tile_type)).collect();
+        let mut board = PuzzleBoard::new(4);
+
+        assert_eq!(find_hint(&tile_data, &board), Some(((0, 1), (1, 1))));
+
+        board.set_obstacle(1, 1, Some(ObstacleType::Ice));
+        assert_eq!(find_hint(&tile_data, &board), None, "an iced cell can't be part of the hint");
+    }
+
+    #[test]
+    fn test_untouched_board_resource_does_not_block_hint() {
+        let (mut app, _) = setup_hint_app();
+        app.add_systems(Update, |mut board: ResMut<PuzzleBoard>| board.set_changed());
+
+        for _ in 0..(HINT_IDLE_SECS / 0.25) as usize + 2 {
+            app.update();
+        }
+        assert_eq!(hinted(&mut app).len(), 2);
+    }
+
+    #[test]
+    fn test_hint_shows_after_idle_delay_and_clears_on_input() {
+        let (mut app, tiles) = setup_hint_app();
+        app.world_mut().resource_mut::<IdleHintTimer>().delay = 1.0;
+
+        // The first frames see the freshly spawned board as a change
+        for _ in 0..3 {
+            app.update();
+        }
+        assert!(hinted(&mut app).is_empty(), "still inside the idle delay");
+
+        for _ in 0..2 {
+            app.update();
+        }
+        let mut expected = vec![tiles[1][0], tiles[1][1]];
+        expected.sort();
+        assert_eq!(hinted(&mut app), expected);
+
+        app.world_mut().resource_mut::<ButtonInput<MouseButton>>().press(MouseButton::Left);
+        app.update();
+        assert!(hinted(&mut app).is_empty(), "any input cancels the hint");
+        let timer = app.world().resource::<IdleHintTimer>();
+        assert!(timer.idle < timer.delay, "the wait starts over");
+    }
+}
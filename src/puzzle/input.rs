@@ -1,20 +1,66 @@
+use bevy::ecs::system::SystemParam;
+
 use crate::prelude::*;
 use crate::camera::MainCamera;
-use super::{PuzzleBoard, Tile, GridPosition, Selected, TileType};
-use super::match_detector::would_match_after_swap;
+use super::{PuzzleBoard, Tile, GridPosition, Selected, TileType, Matched, PowerTile};
+use super::match_detector::{would_match_after_swap, build_matchable_grid, find_match_groups, LineClearEvent};
 
 const SWAP_DURATION: f32 = 0.2;
 
+/// Cell the player has picked as the first half of a swap
 #[derive(Resource, Default)]
 pub struct SelectedTile(pub Option<(usize, usize)>);
 
+/// Cells touched by the most recent player swap; a 4+ match there leaves its power tile on that cell
+#[derive(Resource, Default)]
+pub struct LastSwap(pub Option<[(usize, usize); 2]>);
+
+/// Swap completed while the board was busy; replayed on the next idle frame. Holds at
+/// most one swap: a newer one replaces it.
+#[derive(Resource, Default)]
+pub struct BufferedSwap(pub Option<((usize, usize), (usize, usize))>);
+
+/// Committed swap waiting for its slide to finish so it can be checked for a match
+#[derive(Resource, Default)]
+pub struct PendingSwapCheck {
+    pub swap: Option<((usize, usize), (usize, usize))>,
+    /// The next swap is the swap-back itself and must not be checked again
+    pub reverting: bool,
+}
+
+/// Tile slide between two board cells. Endpoints are stored as grid cells and
+/// converted to pixels every frame, so a board origin change mid-flight stays smooth.
 #[derive(Component)]
 pub struct SwapAnimation {
-    pub start_pos: Vec2,
-    pub end_pos: Vec2,
+    pub from: (usize, usize),
+    pub to: (usize, usize),
     pub timer: Timer,
 }
 
+impl SwapAnimation {
+    pub fn new(from: (usize, usize), to: (usize, usize)) -> Self {
+        Self {
+            from,
+            to,
+            timer: Timer::from_seconds(SWAP_DURATION, TimerMode::Once),
+        }
+    }
+
+    /// Current pixel start/end for the board's present layout
+    pub fn endpoints(&self, board: &PuzzleBoard) -> (Vec2, Vec2) {
+        (
+            board.grid_to_world(self.from.0, self.from.1),
+            board.grid_to_world(self.to.0, self.to.1),
+        )
+    }
+
+    /// Eased pixel position at the timer's current progress
+    pub fn current_position(&self, board: &PuzzleBoard) -> Vec2 {
+        let (start, end) = self.endpoints(board);
+        lerp_position(start, end, ease_out_cubic(self.timer.fraction()))
+    }
+}
+
 /// Ice tile shake feedback animation when player tries to interact with frozen tile
 #[derive(Component)]
 pub struct IceShakeAnimation {
@@ -44,8 +90,10 @@ pub fn handle_tile_click(
     windows: Query<&Window>,
     camera_q: Query<(&Camera, &GlobalTransform), With<MainCamera>>,
     board: Res<PuzzleBoard>,
-    mut selected: Local<Option<(usize, usize)>>,
-    tiles: Query<(Entity, &GridPosition, &TileType), With<Tile>>,
+    mut selected: ResMut<SelectedTile>,
+    mut buffered: ResMut<BufferedSwap>,
+    busy: BoardBusy,
+    tiles: SelectableTiles,
 ) {
     if !mouse.just_pressed(MouseButton::Left) {
         return;
@@ -58,9 +106,26 @@ pub fn handle_tile_click(
     let Ok(world_pos) = camera.viewport_to_world_2d(camera_transform, cursor_pos) else { return };
 
     let Some((x, y)) = board.world_to_grid(world_pos) else { return };
+    select_cell(&mut commands, &board, &mut selected, &mut buffered, busy.is_busy(), &tiles, (x, y));
+}
 
-    // Ice tiles cannot be moved - trigger shake feedback
-    if board.has_ice(x, y) {
+/// Tiles as seen by the selection logic: entity, cell, color and whether it is a power tile
+pub type SelectableTiles<'w, 's> =
+    Query<'w, 's, (Entity, &'static GridPosition, &'static TileType, Has<PowerTile>), With<Tile>>;
+
+/// Pick the cell at `(x, y)` as the first half of a swap, or complete a swap with the
+/// previously selected cell. Shared by mouse clicks and the gamepad cursor.
+pub fn select_cell(
+    commands: &mut Commands,
+    board: &PuzzleBoard,
+    selected: &mut SelectedTile,
+    buffered: &mut BufferedSwap,
+    board_busy: bool,
+    tiles: &SelectableTiles,
+    (x, y): (usize, usize),
+) {
+    // Ice and stone tiles cannot be moved - trigger shake feedback
+    if board.is_swap_blocked(x, y) {
         if let Some(entity) = board.get(x, y) {
             let tile_pos = board.grid_to_world(x, y);
             commands.entity(entity).insert(IceShakeAnimation {
@@ -72,24 +137,35 @@ pub fn handle_tile_click(
         return;
     }
 
-    for (entity, _, _) in tiles.iter() {
+    for (entity, ..) in tiles.iter() {
         commands.entity(entity).remove::<Selected>();
     }
 
-    if let Some(prev) = *selected {
-        // Cannot swap if either tile has ice
-        if board.has_ice(prev.0, prev.1) {
-            *selected = None;
+    if let Some(prev) = selected.0 {
+        // Clicking a selected power tile again activates it in place
+        if prev == (x, y) {
+            let power_tile = board
+                .get(x, y)
+                .filter(|e| tiles.get(*e).is_ok_and(|(.., is_power)| is_power));
+            if let Some(entity) = power_tile {
+                commands.entity(entity).insert(Matched);
+                commands.trigger(LineClearEvent { row: y, col: x });
+            }
+            selected.0 = None;
             return;
         }
-        if is_adjacent(prev, (x, y)) {
+        // Cannot swap if either tile has ice or stone
+        if board.is_swap_blocked(prev.0, prev.1) {
+            selected.0 = None;
+            return;
+        }
+        if is_adjacent(prev, (x, y)) && board_busy {
+            // Board is still resolving or sliding - hold the swap until it settles
+            buffered.0 = Some((prev, (x, y)));
+        } else if is_adjacent(prev, (x, y)) {
             // Build grid from current tiles for match prediction
-            let tile_data: Vec<_> = tiles.iter().collect();
-            let mut grid: [[Option<TileType>; PUZZLE_BOARD_SIZE]; PUZZLE_BOARD_SIZE] =
-                [[None; PUZZLE_BOARD_SIZE]; PUZZLE_BOARD_SIZE];
-            for (_, pos, tile_type) in &tile_data {
-                grid[pos.y][pos.x] = Some(**tile_type);
-            }
+            let tile_data: Vec<_> = tiles.iter().map(|(e, pos, tile_type, _)| (e, pos, tile_type)).collect();
+            let grid = build_matchable_grid(&tile_data, board);
 
             // Check if swap would create a match
             if would_match_after_swap(&grid, prev, (x, y)) {
@@ -99,16 +175,82 @@ pub fn handle_tile_click(
                 commands.trigger(InvalidSwapEvent { pos1: prev, pos2: (x, y) });
             }
         }
-        *selected = None;
+        selected.0 = None;
     } else {
-        *selected = Some((x, y));
+        selected.0 = Some((x, y));
         if let Some(entity) = board.get(x, y) {
             commands.entity(entity).insert(Selected);
         }
     }
 }
 
-fn is_adjacent(a: (usize, usize), b: (usize, usize)) -> bool {
+/// Drops the player's pending selection when a match kicks off a cascade,
+/// so no highlight survives on a tile that is about to be consumed or moved
+pub fn clear_selection_on_cascade(
+    mut commands: Commands,
+    mut selected_tile: ResMut<SelectedTile>,
+    matched: Query<(), With<Matched>>,
+    selected: Query<Entity, With<Selected>>,
+) {
+    if matched.is_empty() {
+        return;
+    }
+
+    selected_tile.0 = None;
+    for entity in selected.iter() {
+        commands.entity(entity).remove::<Selected>();
+    }
+}
+
+/// Whether the board can't take a new swap yet: matches are resolving, or a swap is
+/// still sliding or waiting for its match check
+#[derive(SystemParam)]
+pub struct BoardBusy<'w, 's> {
+    phase: Res<'w, State<PhaseState>>,
+    pending_check: Res<'w, PendingSwapCheck>,
+    swap_anims: Query<'w, 's, (), With<SwapAnimation>>,
+}
+
+impl BoardBusy<'_, '_> {
+    pub fn is_busy(&self) -> bool {
+        is_board_busy(self.phase.get())
+            || self.pending_check.swap.is_some()
+            || !self.swap_anims.is_empty()
+    }
+}
+
+/// Replays a swap buffered while the board was busy once it is idle again.
+/// The board may have changed underneath it, so it is re-validated and
+/// silently dropped if it no longer applies.
+pub fn execute_buffered_swap(
+    mut commands: Commands,
+    busy: BoardBusy,
+    board: Res<PuzzleBoard>,
+    mut buffered: ResMut<BufferedSwap>,
+    tiles: Query<(Entity, &GridPosition, &TileType), With<Tile>>,
+) {
+    if busy.is_busy() {
+        return;
+    }
+    let Some((from, to)) = buffered.0.take() else { return };
+
+    let movable = |(x, y): (usize, usize)| board.in_bounds(x, y) && !board.is_swap_blocked(x, y);
+    if !is_adjacent(from, to) || !movable(from) || !movable(to) {
+        return;
+    }
+
+    let tile_data: Vec<_> = tiles.iter().collect();
+    let grid = build_matchable_grid(&tile_data, &board);
+    if would_match_after_swap(&grid, from, to) {
+        commands.trigger(SwapTilesEvent { from, to });
+    }
+}
+
+fn is_board_busy(phase: &PhaseState) -> bool {
+    matches!(phase, PhaseState::Matching | PhaseState::Cascading)
+}
+
+pub(crate) fn is_adjacent(a: (usize, usize), b: (usize, usize)) -> bool {
     let dx = (a.0 as i32 - b.0 as i32).abs();
     let dy = (a.1 as i32 - b.1 as i32).abs();
     (dx == 1 && dy == 0) || (dx == 0 && dy == 1)
@@ -124,11 +266,19 @@ pub fn handle_tile_swap(
     trigger: Trigger<SwapTilesEvent>,
     mut commands: Commands,
     mut board: ResMut<PuzzleBoard>,
-    mut tiles: Query<(&mut GridPosition, &Transform), With<Tile>>,
+    mut last_swap: ResMut<LastSwap>,
+    mut pending_check: ResMut<PendingSwapCheck>,
+    mut tiles: Query<&mut GridPosition, With<Tile>>,
 ) {
     let event = trigger.event();
     let from = event.from;
     let to = event.to;
+    last_swap.0 = Some([from, to]);
+    if pending_check.reverting {
+        pending_check.reverting = false;
+    } else {
+        pending_check.swap = Some((from, to));
+    }
 
     let from_entity = board.get(from.0, from.1);
     let to_entity = board.get(to.0, to.1);
@@ -136,30 +286,18 @@ pub fn handle_tile_swap(
     board.swap(from, to);
 
     if let Some(entity) = from_entity {
-        if let Ok((mut pos, transform)) = tiles.get_mut(entity) {
-            let start_pos = transform.translation.truncate();
-            let end_pos = board.grid_to_world(to.0, to.1);
+        if let Ok(mut pos) = tiles.get_mut(entity) {
             pos.x = to.0;
             pos.y = to.1;
-            commands.entity(entity).insert(SwapAnimation {
-                start_pos,
-                end_pos,
-                timer: Timer::from_seconds(SWAP_DURATION, TimerMode::Once),
-            });
+            commands.entity(entity).insert(SwapAnimation::new(from, to));
         }
     }
 
     if let Some(entity) = to_entity {
-        if let Ok((mut pos, transform)) = tiles.get_mut(entity) {
-            let start_pos = transform.translation.truncate();
-            let end_pos = board.grid_to_world(from.0, from.1);
+        if let Ok(mut pos) = tiles.get_mut(entity) {
             pos.x = from.0;
             pos.y = from.1;
-            commands.entity(entity).insert(SwapAnimation {
-                start_pos,
-                end_pos,
-                timer: Timer::from_seconds(SWAP_DURATION, TimerMode::Once),
-            });
+            commands.entity(entity).insert(SwapAnimation::new(to, from));
         }
     }
 }
@@ -167,22 +305,55 @@ pub fn handle_tile_swap(
 pub fn animate_swap(
     mut commands: Commands,
     time: Res<Time>,
+    board: Res<PuzzleBoard>,
     mut query: Query<(Entity, &mut Transform, &mut SwapAnimation)>,
 ) {
     for (entity, mut transform, mut anim) in query.iter_mut() {
         anim.timer.tick(time.delta());
-        let progress = anim.timer.fraction();
-        let eased_progress = ease_out_cubic(progress);
-        let current_pos = anim.start_pos.lerp(anim.end_pos, eased_progress);
+        let current_pos = anim.current_position(&board);
         transform.translation = current_pos.extend(transform.translation.z);
 
         if anim.timer.finished() {
-            transform.translation = anim.end_pos.extend(transform.translation.z);
+            let (_, end_pos) = anim.endpoints(&board);
+            transform.translation = end_pos.extend(transform.translation.z);
             commands.entity(entity).remove::<SwapAnimation>();
         }
     }
 }
 
+/// Once a committed swap has finished sliding, swap it back with shake feedback if
+/// neither of its cells ended up in a match. Bombs ride along with `board.swap` both ways.
+pub fn pending_swap_check(
+    mut commands: Commands,
+    phase: Res<State<PhaseState>>,
+    board: Res<PuzzleBoard>,
+    mut pending_check: ResMut<PendingSwapCheck>,
+    swap_anims: Query<(), With<SwapAnimation>>,
+    tiles: Query<(Entity, &GridPosition, &TileType), With<Tile>>,
+) {
+    if !swap_anims.is_empty() {
+        return;
+    }
+    let Some((from, to)) = pending_check.swap.take() else { return };
+    // The swap matched and its tiles are already being cleared
+    if is_board_busy(phase.get()) {
+        return;
+    }
+
+    let tile_data: Vec<_> = tiles.iter().collect();
+    let grid = build_matchable_grid(&tile_data, &board);
+    let matched = find_match_groups(&grid)
+        .iter()
+        .any(|(_, run)| run.contains(&from) || run.contains(&to));
+    if matched {
+        return;
+    }
+
+    pending_check.reverting = true;
+    commands.trigger(SwapTilesEvent { from: to, to: from });
+    commands.trigger(InvalidSwapEvent { pos1: from, pos2: to });
+}
+
 /// Animate ice shake feedback when player tries to interact with frozen tile
 pub fn animate_ice_shake(
     mut commands: Commands,
@@ -277,6 +448,7 @@ pub fn ease_out_cubic(t: f32) -> f32 {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::puzzle::match_detector::{remove_matched_tiles, MatchedRuns, MegaMatchRule};
 
     #[test]
     fn test_swap_animation_lerp_at_start() {
@@ -356,4 +528,344 @@ mod tests {
             assert!(values[i] >= values[i - 1], "ease_out_cubic should be monotonically increasing");
         }
     }
+
+    #[test]
+    fn test_swap_animation_endpoints_follow_board_origin() {
+        let mut board = PuzzleBoard::new(6);
+        let anim = SwapAnimation::new((1, 2), (2, 2));
+        let (start, end) = anim.endpoints(&board);
+        assert_eq!(start, board.grid_to_world(1, 2));
+        assert_eq!(end, board.grid_to_world(2, 2));
+
+        board.origin += Vec2::new(40.0, -25.0);
+
+        let (moved_start, moved_end) = anim.endpoints(&board);
+        assert_eq!(moved_start, start + Vec2::new(40.0, -25.0));
+        assert_eq!(moved_end, end + Vec2::new(40.0, -25.0));
+    }
+
+    #[test]
+    fn test_in_flight_swap_lands_on_new_origin() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .insert_resource(PuzzleBoard::new(6))
+            .add_systems(Update, animate_swap);
+
+        let mut anim = SwapAnimation::new((0, 0), (1, 0));
+        anim.timer.set_elapsed(std::time::Duration::from_secs_f32(SWAP_DURATION * 0.5));
+        let entity = app.world_mut().spawn((Transform::default(), anim)).id();
+
+        // Board moves while the tile is halfway across
+        app.world_mut().resource_mut::<PuzzleBoard>().origin += Vec2::new(100.0, 0.0);
+        app.update();
+
+        let board = app.world().resource::<PuzzleBoard>();
+        let anim = app.world().get::<SwapAnimation>(entity).unwrap();
+        let translation = app.world().get::<Transform>(entity).unwrap().translation.truncate();
+        assert_eq!(translation, anim.current_position(board));
+        let (start, end) = anim.endpoints(board);
+        assert!(translation.x >= start.x && translation.x <= end.x);
+
+        // Finish the animation: it ends exactly on the new target cell
+        app.world_mut()
+            .get_mut::<SwapAnimation>(entity)
+            .unwrap()
+            .timer
+            .set_elapsed(std::time::Duration::from_secs_f32(SWAP_DURATION));
+        app.update();
+
+        let board = app.world().resource::<PuzzleBoard>();
+        let translation = app.world().get::<Transform>(entity).unwrap().translation.truncate();
+        assert_eq!(translation, board.grid_to_world(1, 0));
+        assert!(app.world().get::<SwapAnimation>(entity).is_none());
+    }
+
+    #[test]
+    fn test_cascade_start_clears_selection_from_consumed_tiles() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .insert_resource(PuzzleBoard::new(4))
+            .insert_resource(SelectedTile(Some((0, 0))))
+            .init_resource::<MegaMatchRule>()
+            .init_resource::<MatchedRuns>()
+            .add_systems(
+                Update,
+                (clear_selection_on_cascade, remove_matched_tiles).chain(),
+            );
+
+        let consumed = app
+            .world_mut()
+            .spawn((Tile, TileType::Red, GridPosition::new(0, 0), Matched, Selected))
+            .id();
+        let bystander = app
+            .world_mut()
+            .spawn((Tile, TileType::Blue, GridPosition::new(1, 0), Selected))
+            .id();
+        {
+            let mut board = app.world_mut().resource_mut::<PuzzleBoard>();
+            board.set(0, 0, Some(consumed));
+            board.set(1, 0, Some(bystander));
+        }
+
+        app.update();
+
+        let world = app.world_mut();
+        assert!(world.get_entity(consumed).is_err(), "matched tile is consumed");
+        assert!(world.get::<Selected>(bystander).is_none());
+        assert_eq!(world.query::<&Selected>().iter(world).count(), 0);
+        assert_eq!(world.resource::<SelectedTile>().0, None);
+    }
+
+    #[test]
+    fn test_selection_kept_without_matches() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .insert_resource(SelectedTile(Some((2, 1))))
+            .add_systems(Update, clear_selection_on_cascade);
+        let tile = app
+            .world_mut()
+            .spawn((Tile, TileType::Green, GridPosition::new(2, 1), Selected))
+            .id();
+
+        app.update();
+
+        assert!(app.world().get::<Selected>(tile).is_some());
+        assert_eq!(app.world().resource::<SelectedTile>().0, Some((2, 1)));
+    }
+
+    /// Cells "clicked" by `click_queued_cell`, one per frame
+    #[derive(Resource, Default)]
+    struct QueuedClicks(Vec<(usize, usize)>);
+
+    fn click_queued_cell(
+        mut commands: Commands,
+        board: Res<PuzzleBoard>,
+        mut clicks: ResMut<QueuedClicks>,
+        mut selected: ResMut<SelectedTile>,
+        mut buffered: ResMut<BufferedSwap>,
+        busy: BoardBusy,
+        tiles: SelectableTiles,
+    ) {
+        if clicks.0.is_empty() {
+            return;
+        }
+        let cell = clicks.0.remove(0);
+        select_cell(&mut commands, &board, &mut selected, &mut buffered, busy.is_busy(), &tiles, cell);
+    }
+
+    #[test]
+    fn test_non_matching_swap_rejected_without_touching_board() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, bevy::state::app::StatesPlugin))
+            .init_state::<PhaseState>()
+            .insert_resource(PuzzleBoard::new(4))
+            .init_resource::<SelectedTile>()
+            .init_resource::<BufferedSwap>()
+            .init_resource::<LastSwap>()
+            .init_resource::<PendingSwapCheck>()
+            .insert_resource(QueuedClicks(vec![(0, 0), (1, 0)]))
+            .add_observer(handle_tile_swap)
+            .add_observer(handle_invalid_swap)
+            .add_systems(Update, click_queued_cell);
+
+        // Swapping (0, 0) and (1, 0) gives Blue Red Green Yellow: no match
+        let mut entities = Vec::new();
+        for (x, tile_type) in [TileType::Red, TileType::Blue, TileType::Green, TileType::Yellow]
+            .into_iter()
+            .enumerate()
+        {
+            let entity = app.world_mut().spawn((Tile, tile_type, GridPosition::new(x, 0))).id();
+            app.world_mut().resource_mut::<PuzzleBoard>().set(x, 0, Some(entity));
+            entities.push(entity);
+        }
+        app.world_mut()
+            .resource_mut::<PuzzleBoard>()
+            .set_obstacle(1, 0, Some(ObstacleType::Bomb));
+
+        app.update();
+        app.update();
+
+        let board = app.world().resource::<PuzzleBoard>();
+        for (x, &entity) in entities.iter().enumerate() {
+            assert_eq!(board.get(x, 0), Some(entity));
+            assert_eq!(*app.world().get::<GridPosition>(entity).unwrap(), GridPosition::new(x, 0));
+        }
+        assert!(board.has_bomb(1, 0), "bomb stays with its unmoved tile");
+        assert!(!board.has_bomb(0, 0));
+        assert_eq!(app.world().resource::<LastSwap>().0, None, "no swap was committed");
+        assert!(app.world().get::<SwapAnimation>(entities[0]).is_none());
+        assert!(app.world().get::<InvalidSwapShakeAnimation>(entities[0]).is_some());
+        assert!(app.world().get::<InvalidSwapShakeAnimation>(entities[1]).is_some());
+        assert_eq!(app.world().resource::<SelectedTile>().0, None);
+    }
+
+    #[test]
+    fn test_no_match_swap_slides_back_with_its_bomb() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, bevy::state::app::StatesPlugin))
+            .insert_resource(bevy::time::TimeUpdateStrategy::ManualDuration(
+                std::time::Duration::from_secs_f32(0.1),
+            ))
+            .init_state::<PhaseState>()
+            .insert_resource(PuzzleBoard::new(4))
+            .init_resource::<LastSwap>()
+            .init_resource::<PendingSwapCheck>()
+            .add_observer(handle_tile_swap)
+            .add_observer(handle_invalid_swap)
+            .add_systems(Update, (animate_swap, pending_swap_check).chain());
+
+        let mut entities = Vec::new();
+        for (x, tile_type) in [TileType::Red, TileType::Blue, TileType::Green, TileType::Yellow]
+            .into_iter()
+            .enumerate()
+        {
+            let entity = app
+                .world_mut()
+                .spawn((Tile, tile_type, GridPosition::new(x, 0), Transform::default()))
+                .id();
+            app.world_mut().resource_mut::<PuzzleBoard>().set(x, 0, Some(entity));
+            entities.push(entity);
+        }
+        app.world_mut()
+            .resource_mut::<PuzzleBoard>()
+            .set_obstacle(0, 0, Some(ObstacleType::Bomb));
+
+        // Optimistic commit straight through the event, skipping input validation
+        app.world_mut().trigger(SwapTilesEvent { from: (0, 0), to: (1, 0) });
+        assert_eq!(*app.world().get::<GridPosition>(entities[0]).unwrap(), GridPosition::new(1, 0));
+        assert!(app.world().resource::<PuzzleBoard>().has_bomb(1, 0));
+
+        for _ in 0..10 {
+            app.update();
+        }
+
+        let board = app.world().resource::<PuzzleBoard>();
+        for (x, &entity) in entities.iter().enumerate() {
+            assert_eq!(board.get(x, 0), Some(entity));
+            assert_eq!(*app.world().get::<GridPosition>(entity).unwrap(), GridPosition::new(x, 0));
+        }
+        assert!(board.has_bomb(0, 0), "bomb reverts with its tile");
+        assert!(!board.has_bomb(1, 0));
+        assert!(app.world().get::<SwapAnimation>(entities[0]).is_none(), "swapped back only once");
+        let check = app.world().resource::<PendingSwapCheck>();
+        assert_eq!(check.swap, None);
+        assert!(!check.reverting);
+    }
+
+    #[derive(Resource, Default)]
+    struct FiredSwaps(Vec<((usize, usize), (usize, usize))>);
+
+    fn setup_buffered_swap_app() -> App {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, bevy::state::app::StatesPlugin))
+            .init_state::<PhaseState>()
+            .insert_resource(PuzzleBoard::new(4))
+            .init_resource::<BufferedSwap>()
+            .init_resource::<PendingSwapCheck>()
+            .init_resource::<FiredSwaps>()
+            .add_observer(|trigger: Trigger<SwapTilesEvent>, mut fired: ResMut<FiredSwaps>| {
+                fired.0.push((trigger.event().from, trigger.event().to));
+            })
+            .add_systems(Update, execute_buffered_swap);
+
+        // Swapping (0, 0) and (1, 0) lines up three reds
+        for (x, tile_type) in [TileType::Red, TileType::Blue, TileType::Red, TileType::Red]
+            .into_iter()
+            .enumerate()
+        {
+            let entity = app.world_mut().spawn((Tile, tile_type, GridPosition::new(x, 0))).id();
+            app.world_mut().resource_mut::<PuzzleBoard>().set(x, 0, Some(entity));
+        }
+
+        app.world_mut()
+            .resource_mut::<NextState<PhaseState>>()
+            .set(PhaseState::Cascading);
+        app.update();
+        app.world_mut().resource_mut::<BufferedSwap>().0 = Some(((0, 0), (1, 0)));
+        app
+    }
+
+    fn settle(app: &mut App) {
+        app.world_mut()
+            .resource_mut::<NextState<PhaseState>>()
+            .set(PhaseState::Idle);
+        app.update();
+    }
+
+    #[test]
+    fn test_buffered_swap_executes_on_next_idle_frame() {
+        let mut app = setup_buffered_swap_app();
+
+        app.update();
+        assert!(app.world().resource::<FiredSwaps>().0.is_empty(), "held while cascading");
+        assert!(app.world().resource::<BufferedSwap>().0.is_some());
+
+        settle(&mut app);
+        assert_eq!(app.world().resource::<FiredSwaps>().0, vec![((0, 0), (1, 0))]);
+        assert_eq!(app.world().resource::<BufferedSwap>().0, None);
+    }
+
+    #[test]
+    fn test_clicks_during_cascade_buffer_latest_swap_for_idle() {
+        let mut app = setup_buffered_swap_app();
+        app.world_mut().resource_mut::<BufferedSwap>().0 = None;
+        app.init_resource::<SelectedTile>()
+            .insert_resource(QueuedClicks(vec![(3, 0), (2, 0), (0, 0), (1, 0)]))
+            .add_systems(Update, click_queued_cell.before(execute_buffered_swap));
+
+        for _ in 0..4 {
+            app.update();
+        }
+        assert!(app.world().resource::<FiredSwaps>().0.is_empty(), "nothing swaps mid-cascade");
+        assert_eq!(app.world().resource::<BufferedSwap>().0, Some(((0, 0), (1, 0))), "only the latest swap is kept");
+
+        settle(&mut app);
+        assert_eq!(app.world().resource::<FiredSwaps>().0, vec![((0, 0), (1, 0))]);
+    }
+
+    #[test]
+    fn test_clicks_during_swap_slide_are_buffered() {
+        let mut app = setup_buffered_swap_app();
+        app.world_mut().resource_mut::<BufferedSwap>().0 = None;
+        settle(&mut app);
+        let sliding = app.world_mut().spawn(SwapAnimation::new((2, 3), (3, 3))).id();
+        app.init_resource::<SelectedTile>()
+            .insert_resource(QueuedClicks(vec![(0, 0), (1, 0)]))
+            .add_systems(Update, click_queued_cell.before(execute_buffered_swap));
+
+        app.update();
+        app.update();
+        assert!(app.world().resource::<FiredSwaps>().0.is_empty(), "held while the last swap slides");
+        assert!(app.world().resource::<BufferedSwap>().0.is_some());
+
+        app.world_mut().despawn(sliding);
+        app.update();
+        assert_eq!(app.world().resource::<FiredSwaps>().0, vec![((0, 0), (1, 0))]);
+    }
+
+    #[test]
+    fn test_buffered_swap_discarded_when_no_longer_valid() {
+        let mut app = setup_buffered_swap_app();
+
+        // The cascade refills (3, 0) with a different colour, breaking the match
+        let entity = app.world().resource::<PuzzleBoard>().get(3, 0).unwrap();
+        *app.world_mut().get_mut::<TileType>(entity).unwrap() = TileType::Green;
+
+        settle(&mut app);
+        assert!(app.world().resource::<FiredSwaps>().0.is_empty());
+        assert_eq!(app.world().resource::<BufferedSwap>().0, None, "stale swap is dropped");
+    }
+
+    #[test]
+    fn test_buffered_swap_discarded_when_tile_frozen() {
+        let mut app = setup_buffered_swap_app();
+
+        app.world_mut()
+            .resource_mut::<PuzzleBoard>()
+            .set_obstacle(1, 0, Some(ObstacleType::Ice));
+
+        settle(&mut app);
+        assert!(app.world().resource::<FiredSwaps>().0.is_empty());
+        assert_eq!(app.world().resource::<BufferedSwap>().0, None);
+    }
 }
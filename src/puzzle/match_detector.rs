@@ -1,37 +1,97 @@
 use crate::prelude::*;
-use super::{PuzzleBoard, Tile, TileType, GridPosition, Matched, IceMeltEvent, BombDefuseEvent};
-use crate::bridge::{MatchEvent, CoreAbilityEvent};
+use super::{PuzzleBoard, Tile, TileType, GridPosition, Matched, Frozen, PowerTile, PowerTileMarker, IceMeltEvent, BombDefuseEvent, StoneCrackEvent};
+use super::input::LastSwap;
+use crate::bridge::{MatchEvent, ColoredManaEvent, CoreAbilityEvent};
 use crate::audio::MatchSoundEvent;
 
-pub fn detect_matches(
-    mut commands: Commands,
-    _board: Res<PuzzleBoard>,
-    combo: Res<ComboCounter>,
-    tiles: Query<(Entity, &GridPosition, &TileType), (With<Tile>, Without<Matched>)>,
-) {
-    let mut matched_positions: Vec<(usize, usize)> = Vec::new();
-    let mut match_groups: Vec<(TileType, Vec<(usize, usize)>)> = Vec::new();
+/// Row-major snapshot of tile colors, indexed as `grid[y][x]`
+pub type TileGrid = Vec<Vec<Option<TileType>>>;
+
+/// Minimum run length that leaves a power tile behind
+pub const POWER_TILE_MATCH: usize = 4;
+
+/// Optional rule: 4+ matches sweep obstacles in a radius around the run, not just adjacent cells
+#[derive(Resource, Default, Clone, Copy, Debug)]
+pub struct MegaMatchRule {
+    pub enabled: bool,
+}
+
+/// Runs found by `detect_matches` this pass; drained by `remove_matched_tiles`
+#[derive(Resource, Default)]
+pub struct MatchedRuns(pub Vec<(TileType, Vec<(usize, usize)>)>);
+
+const POWER_MARKER_COLOR: Color = Color::srgba(1.0, 1.0, 1.0, 0.85);
+
+/// Fired when a power tile is matched or activated; clears its whole row and column
+#[derive(Event)]
+pub struct LineClearEvent {
+    pub row: usize,
+    pub col: usize,
+}
+
+/// All cells covered by a line clear centered on (col, row)
+pub fn line_clear_positions(size: usize, row: usize, col: usize) -> Vec<(usize, usize)> {
+    let mut positions: Vec<(usize, usize)> = (0..size).map(|x| (x, row)).collect();
+    positions.extend((0..size).filter(|&y| y != row).map(|y| (col, y)));
+    positions
+}
+
+/// Pick where a 4+ run leaves its power tile: the swapped cell if it is part of the run,
+/// otherwise the middle of the run
+pub fn power_tile_spot(run: &[(usize, usize)], last_swap: Option<[(usize, usize); 2]>) -> (usize, usize) {
+    last_swap
+        .and_then(|swap| swap.into_iter().find(|pos| run.contains(pos)))
+        .unwrap_or(run[run.len() / 2])
+}
 
-    let mut grid: [[Option<TileType>; PUZZLE_BOARD_SIZE]; PUZZLE_BOARD_SIZE] =
-        [[None; PUZZLE_BOARD_SIZE]; PUZZLE_BOARD_SIZE];
+/// Obstacle-clearing radius of a run under the mega-match rule (4 → 2, 5 → 3, ...),
+/// or `None` for runs too short to qualify
+pub fn mega_match_radius(run_len: usize) -> Option<usize> {
+    (run_len >= POWER_TILE_MATCH).then(|| run_len - 2)
+}
 
-    for (_, pos, tile_type) in tiles.iter() {
-        grid[pos.y][pos.x] = Some(*tile_type);
+/// Cells whose obstacles a mega-match clears: within Manhattan `radius` of each run's
+/// middle cell, inside the board and outside the core. Sorted and deduplicated.
+pub fn mega_match_region(board: &PuzzleBoard, groups: &[(TileType, Vec<(usize, usize)>)]) -> Vec<(usize, usize)> {
+    let mut region: Vec<(usize, usize)> = Vec::new();
+    for (_, run) in groups {
+        let Some(radius) = mega_match_radius(run.len()) else { continue };
+        let (cx, cy) = run[run.len() / 2];
+        for y in cy.saturating_sub(radius)..=(cy + radius).min(board.size - 1) {
+            for x in cx.saturating_sub(radius)..=(cx + radius).min(board.size - 1) {
+                if cx.abs_diff(x) + cy.abs_diff(y) <= radius && !board.is_core_position(x, y) {
+                    region.push((x, y));
+                }
+            }
+        }
     }
+    region.sort();
+    region.dedup();
+    region
+}
+
+/// Create an empty square tile grid of the given size
+pub fn empty_tile_grid(size: usize) -> TileGrid {
+    vec![vec![None; size]; size]
+}
 
-    for y in 0..PUZZLE_BOARD_SIZE {
+/// Find all horizontal and vertical runs of 3+ same-colored tiles
+pub fn find_match_groups(grid: &[Vec<Option<TileType>>]) -> Vec<(TileType, Vec<(usize, usize)>)> {
+    let size = grid.len();
+    let mut match_groups: Vec<(TileType, Vec<(usize, usize)>)> = Vec::new();
+
+    for y in 0..size {
         let mut x = 0;
-        while x < PUZZLE_BOARD_SIZE {
+        while x < size {
             if let Some(tile_type) = grid[y][x] {
                 let mut run = vec![(x, y)];
                 let mut nx = x + 1;
-                while nx < PUZZLE_BOARD_SIZE && grid[y][nx] == Some(tile_type) {
+                while nx < size && grid[y][nx] == Some(tile_type) {
                     run.push((nx, y));
                     nx += 1;
                 }
                 if run.len() >= 3 {
-                    matched_positions.extend(run.iter().copied());
-                    match_groups.push((tile_type, run.clone()));
+                    match_groups.push((tile_type, run));
                 }
                 x = nx;
             } else {
@@ -40,19 +100,18 @@ pub fn detect_matches(
         }
     }
 
-    for x in 0..PUZZLE_BOARD_SIZE {
+    for x in 0..size {
         let mut y = 0;
-        while y < PUZZLE_BOARD_SIZE {
+        while y < size {
             if let Some(tile_type) = grid[y][x] {
                 let mut run = vec![(x, y)];
                 let mut ny = y + 1;
-                while ny < PUZZLE_BOARD_SIZE && grid[ny][x] == Some(tile_type) {
+                while ny < size && grid[ny][x] == Some(tile_type) {
                     run.push((x, ny));
                     ny += 1;
                 }
                 if run.len() >= 3 {
-                    matched_positions.extend(run.iter().copied());
-                    match_groups.push((tile_type, run.clone()));
+                    match_groups.push((tile_type, run));
                 }
                 y = ny;
             } else {
@@ -61,12 +120,55 @@ pub fn detect_matches(
         }
     }
 
+    match_groups
+}
+
+pub fn detect_matches(
+    mut commands: Commands,
+    board: Res<PuzzleBoard>,
+    combo: Res<ComboCounter>,
+    mut last_swap: ResMut<LastSwap>,
+    mut runs: ResMut<MatchedRuns>,
+    tiles: Query<(Entity, &GridPosition, &TileType), (With<Tile>, Without<Matched>)>,
+    power_tiles: Query<(), With<PowerTile>>,
+) {
+    let tile_data: Vec<_> = tiles.iter().collect();
+    let grid = build_matchable_grid(&tile_data, &board);
+
+    let match_groups = find_match_groups(&grid);
+    let mut matched_positions: Vec<(usize, usize)> = match_groups
+        .iter()
+        .flat_map(|(_, run)| run.iter().copied())
+        .collect();
+
     matched_positions.sort();
     matched_positions.dedup();
 
+    // 4+ runs leave a power tile behind instead of clearing that cell.
+    // A run that already contains a power tile spends it rather than making a new one.
+    let is_power = |(x, y): (usize, usize)| board.get(x, y).is_some_and(|e| power_tiles.contains(e));
+    let new_power_spots: Vec<(usize, usize)> = match_groups
+        .iter()
+        .filter(|(_, run)| run.len() >= POWER_TILE_MATCH && !run.iter().any(|pos| is_power(*pos)))
+        .map(|(_, run)| power_tile_spot(run, last_swap.0))
+        .collect();
+    if !match_groups.is_empty() {
+        last_swap.0 = None;
+    }
+    runs.0.extend(match_groups.iter().filter(|(_, run)| run.len() >= POWER_TILE_MATCH).cloned());
+
     for (entity, pos, _) in tiles.iter() {
-        if matched_positions.contains(&(pos.x, pos.y)) {
+        let position = (pos.x, pos.y);
+        if new_power_spots.contains(&position) {
+            commands
+                .entity(entity)
+                .insert(PowerTile)
+                .with_children(spawn_power_marker);
+        } else if matched_positions.contains(&position) {
             commands.entity(entity).insert(Matched);
+            if power_tiles.contains(entity) {
+                commands.trigger(LineClearEvent { row: pos.y, col: pos.x });
+            }
         }
     }
 
@@ -80,13 +182,14 @@ pub fn detect_matches(
     for (tile_type, positions) in match_groups {
         let is_core_adjacent = positions
             .iter()
-            .any(|(x, y)| PuzzleBoard::is_adjacent_to_core(*x, *y) || PuzzleBoard::is_core_position(*x, *y));
+            .any(|(x, y)| board.is_adjacent_to_core(*x, *y) || board.is_core_position(*x, *y));
 
         commands.trigger(MatchEvent {
             tile_type,
             count: positions.len(),
             positions: positions.clone(),
         });
+        commands.trigger(ColoredManaEvent::for_match(tile_type, positions.len()));
 
         if is_core_adjacent {
             commands.trigger(CoreAbilityEvent {
@@ -98,15 +201,54 @@ pub fn detect_matches(
     }
 }
 
+fn spawn_power_marker(parent: &mut ChildBuilder) {
+    for size in [Vec2::new(TILE_SIZE * 0.8, 6.0), Vec2::new(6.0, TILE_SIZE * 0.8)] {
+        parent.spawn((
+            Sprite {
+                color: POWER_MARKER_COLOR,
+                custom_size: Some(size),
+                ..default()
+            },
+            Transform::from_xyz(0.0, 0.0, 0.15),
+            PowerTileMarker,
+        ));
+    }
+}
+
+/// Mark every tile in the cleared row and column as matched so it flows through
+/// `remove_matched_tiles` and the cascade like a normal match.
+/// Other power tiles caught in the blast chain their own line clear.
+pub fn handle_line_clear(
+    trigger: Trigger<LineClearEvent>,
+    mut commands: Commands,
+    board: Res<PuzzleBoard>,
+    tiles: Query<(Entity, &GridPosition, Has<PowerTile>), (With<Tile>, Without<Matched>)>,
+) {
+    let event = trigger.event();
+    let path = line_clear_positions(board.size, event.row, event.col);
+
+    for (entity, pos, is_power) in tiles.iter() {
+        let position = (pos.x, pos.y);
+        // Stones shield their tile; they take a crack from the adjacent clear instead
+        if !path.contains(&position) || board.has_stone(pos.x, pos.y) {
+            continue;
+        }
+        commands.entity(entity).insert(Matched);
+        if is_power {
+            commands.trigger(LineClearEvent { row: pos.y, col: pos.x });
+        }
+    }
+}
+
 /// Check if swapping two positions would result in a match (without modifying the board)
 /// Used to prevent invalid moves that don't create any matches
 pub fn would_match_after_swap(
-    grid: &[[Option<TileType>; PUZZLE_BOARD_SIZE]; PUZZLE_BOARD_SIZE],
+    grid: &[Vec<Option<TileType>>],
     pos1: (usize, usize),
     pos2: (usize, usize),
 ) -> bool {
     // Create a virtual copy of the grid
-    let mut virtual_grid = *grid;
+    let mut virtual_grid = grid.to_vec();
 
     // Perform virtual swap
     let temp = virtual_grid[pos1.1][pos1.0];
@@ -118,12 +260,52 @@ pub fn would_match_after_swap(
         || check_match_at_position(&virtual_grid, pos2.0, pos2.1)
 }
 
+/// True if at least one adjacent swap anywhere on the board would produce a match
+pub fn has_any_valid_move(grid: &[Vec<Option<TileType>>]) -> bool {
+    has_any_valid_move_where(grid, |_, _| false)
+}
+
+/// Like `has_any_valid_move`, but ignores swaps that touch a cell where `is_blocked` holds
+/// (ice/stone cells cannot be swapped)
+pub fn has_any_valid_move_where(
+    grid: &[Vec<Option<TileType>>],
+    is_blocked: impl Fn(usize, usize) -> bool,
+) -> bool {
+    find_valid_move_where(grid, is_blocked).is_some()
+}
+
+/// First swap (scanning row by row) that makes a match, skipping blocked cells
+pub fn find_valid_move_where(
+    grid: &[Vec<Option<TileType>>],
+    is_blocked: impl Fn(usize, usize) -> bool,
+) -> Option<((usize, usize), (usize, usize))> {
+    let size = grid.len();
+    for y in 0..size {
+        for x in 0..size {
+            if is_blocked(x, y) {
+                continue;
+            }
+            // Right and up neighbours cover every adjacent pair exactly once
+            for (nx, ny) in [(x + 1, y), (x, y + 1)] {
+                if nx >= size || ny >= size || is_blocked(nx, ny) {
+                    continue;
+                }
+                if would_match_after_swap(grid, (x, y), (nx, ny)) {
+                    return Some(((x, y), (nx, ny)));
+                }
+            }
+        }
+    }
+    None
+}
+
 /// Check if there's a match (3+ in a row) at the given position
 fn check_match_at_position(
-    grid: &[[Option<TileType>; PUZZLE_BOARD_SIZE]; PUZZLE_BOARD_SIZE],
+    grid: &[Vec<Option<TileType>>],
     x: usize,
     y: usize,
 ) -> bool {
+    let size = grid.len();
     let Some(tile_type) = grid[y][x] else {
         return false;
     };
@@ -138,7 +320,7 @@ fn check_match_at_position(
     }
     // Count right
     let mut nx = x + 1;
-    while nx < PUZZLE_BOARD_SIZE && grid[y][nx] == Some(tile_type) {
+    while nx < size && grid[y][nx] == Some(tile_type) {
         h_count += 1;
         nx += 1;
     }
@@ -156,7 +338,7 @@ fn check_match_at_position(
     }
     // Count up
     let mut ny = y + 1;
-    while ny < PUZZLE_BOARD_SIZE && grid[ny][x] == Some(tile_type) {
+    while ny < size && grid[ny][x] == Some(tile_type) {
         v_count += 1;
         ny += 1;
     }
@@ -166,65 +348,169 @@ fn check_match_at_position(
 /// Build a TileType grid from Query for use with would_match_after_swap
 pub fn build_tile_grid(
     tiles: &[(Entity, &GridPosition, &TileType)],
-) -> [[Option<TileType>; PUZZLE_BOARD_SIZE]; PUZZLE_BOARD_SIZE] {
-    let mut grid: [[Option<TileType>; PUZZLE_BOARD_SIZE]; PUZZLE_BOARD_SIZE] =
-        [[None; PUZZLE_BOARD_SIZE]; PUZZLE_BOARD_SIZE];
+    size: usize,
+) -> TileGrid {
+    let mut grid = empty_tile_grid(size);
     for (_, pos, tile_type) in tiles {
-        grid[pos.y][pos.x] = Some(**tile_type);
+        if pos.x < size && pos.y < size {
+            grid[pos.y][pos.x] = Some(**tile_type);
+        }
+    }
+    grid
+}
+
+/// Build a TileType grid with stone-covered cells masked out (stones cannot be matched)
+pub fn build_matchable_grid(
+    tiles: &[(Entity, &GridPosition, &TileType)],
+    board: &PuzzleBoard,
+) -> TileGrid {
+    let mut grid = build_tile_grid(tiles, board.size);
+    for (y, row) in grid.iter_mut().enumerate() {
+        for (x, cell) in row.iter_mut().enumerate() {
+            if board.has_stone(x, y) {
+                *cell = None;
+            }
+        }
     }
     grid
 }
 
+type MatchedTileQuery<'w, 's> = Query<
+    'w,
+    's,
+    (Entity, &'static GridPosition, &'static TileType, Option<&'static mut Frozen>, Option<&'static mut Sprite>),
+    With<Matched>,
+>;
+
 pub fn remove_matched_tiles(
     mut commands: Commands,
     mut board: ResMut<PuzzleBoard>,
-    matched: Query<(Entity, &GridPosition), With<Matched>>,
+    mega_match: Res<MegaMatchRule>,
+    mut runs: ResMut<MatchedRuns>,
+    mut matched: MatchedTileQuery,
 ) {
+    // Frozen tiles take the match as a thaw and stay on the board
+    for (entity, _, tile_type, frozen, sprite) in matched.iter_mut() {
+        let Some(mut frozen) = frozen else { continue };
+        commands.entity(entity).remove::<Matched>();
+        if frozen.thaws_remaining > 1 {
+            frozen.thaws_remaining -= 1;
+            continue;
+        }
+        commands.entity(entity).remove::<Frozen>();
+        if let Some(mut sprite) = sprite {
+            sprite.color = tile_type.color();
+        }
+    }
+
     let matched_positions: Vec<(usize, usize)> = matched
         .iter()
-        .map(|(_, pos)| (pos.x, pos.y))
+        .filter(|(_, _, _, frozen, _)| frozen.is_none())
+        .map(|(_, pos, ..)| (pos.x, pos.y))
         .collect();
 
+    let runs = std::mem::take(&mut runs.0);
+    let mega_region = if mega_match.enabled {
+        mega_match_region(&board, &runs)
+    } else {
+        Vec::new()
+    };
+
+    clear_obstacles(&mut commands, &mut board, &matched_positions, &mega_region);
+
+    // Despawn matched tiles (despawn_recursive removes child bombs too)
+    for (entity, pos, ..) in matched.iter().filter(|(_, _, _, frozen, _)| frozen.is_none()) {
+        board.set(pos.x, pos.y, None);
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+/// Melt, crack and defuse obstacles for one clearing pass. `cleared` holds every cell
+/// leaving the board this pass — normal matches and power-tile line clears alike, since
+/// detonations mark their cells `Matched` — and `region` the extra mega-match reach.
+fn clear_obstacles(
+    commands: &mut Commands,
+    board: &mut PuzzleBoard,
+    cleared: &[(usize, usize)],
+    region: &[(usize, usize)],
+) {
+    // Melt ice on the cleared tiles themselves (line clears sweep straight through frozen cells)
+    for &(x, y) in cleared {
+        if board.has_ice(x, y) {
+            board.clear_cell_obstacle(x, y);
+            commands.trigger(IceMeltEvent { position: (x, y) });
+        }
+    }
+
     // Clear ice obstacles adjacent to matched tiles
-    for (x, y) in &matched_positions {
+    for (x, y) in cleared {
         for (dx, dy) in [(-1i32, 0i32), (1, 0), (0, -1), (0, 1)] {
             let nx = *x as i32 + dx;
             let ny = *y as i32 + dy;
-            if nx >= 0 && ny >= 0 && (nx as usize) < PUZZLE_BOARD_SIZE && (ny as usize) < PUZZLE_BOARD_SIZE {
+            if nx >= 0 && ny >= 0 && board.in_bounds(nx as usize, ny as usize) {
                 if board.has_ice(nx as usize, ny as usize) {
-                    board.clear_obstacle(nx as usize, ny as usize);
+                    board.clear_cell_obstacle(nx as usize, ny as usize);
                     commands.trigger(IceMeltEvent { position: (nx as usize, ny as usize) });
                 }
             }
         }
     }
+    for &(x, y) in region {
+        if board.has_ice(x, y) {
+            board.clear_cell_obstacle(x, y);
+            commands.trigger(IceMeltEvent { position: (x, y) });
+        }
+    }
+
+    // Crack stones adjacent to matched tiles (each stone takes at most one hit per pass)
+    let mut cracked_stones: Vec<(usize, usize)> = Vec::new();
+    for (x, y) in cleared {
+        for (dx, dy) in [(-1i32, 0i32), (1, 0), (0, -1), (0, 1)] {
+            let nx = *x as i32 + dx;
+            let ny = *y as i32 + dy;
+            if nx >= 0 && ny >= 0 && board.has_stone(nx as usize, ny as usize) {
+                let position = (nx as usize, ny as usize);
+                if !cracked_stones.contains(&position) {
+                    cracked_stones.push(position);
+                }
+            }
+        }
+    }
+    for &position in region {
+        if board.has_stone(position.0, position.1) && !cracked_stones.contains(&position) {
+            cracked_stones.push(position);
+        }
+    }
+    for position in cracked_stones {
+        commands.trigger(StoneCrackEvent { position });
+    }
 
     // Defuse bombs on matched tiles (bomb is child, will be despawned with tile)
-    for &(x, y) in &matched_positions {
+    for &(x, y) in cleared {
         if board.has_bomb(x, y) {
             commands.trigger(BombDefuseEvent { position: (x, y) });
-            board.clear_obstacle(x, y);
+            board.clear_bomb(x, y);
         }
     }
 
     // Defuse bombs adjacent to matched tiles (similar to ice melt)
-    for (x, y) in &matched_positions {
+    for (x, y) in cleared {
         for (dx, dy) in [(-1i32, 0i32), (1, 0), (0, -1), (0, 1)] {
             let nx = *x as i32 + dx;
             let ny = *y as i32 + dy;
-            if nx >= 0 && ny >= 0 && (nx as usize) < PUZZLE_BOARD_SIZE && (ny as usize) < PUZZLE_BOARD_SIZE {
+            if nx >= 0 && ny >= 0 && board.in_bounds(nx as usize, ny as usize) {
                 if board.has_bomb(nx as usize, ny as usize) {
                     commands.trigger(BombDefuseEvent { position: (nx as usize, ny as usize) });
-                    board.clear_obstacle(nx as usize, ny as usize);
+                    board.clear_bomb(nx as usize, ny as usize);
                 }
             }
         }
     }
-
-    // Despawn matched tiles (despawn_recursive removes child bombs too)
-    for (entity, pos) in matched.iter() {
-        board.set(pos.x, pos.y, None);
-        commands.entity(entity).despawn_recursive();
+    for &(x, y) in region {
+        if board.has_bomb(x, y) {
+            commands.trigger(BombDefuseEvent { position: (x, y) });
+            board.clear_bomb(x, y);
+        }
     }
 }
 
@@ -232,8 +518,8 @@ pub fn remove_matched_tiles(
 mod tests {
     use super::*;
 
-    fn empty_grid() -> [[Option<TileType>; PUZZLE_BOARD_SIZE]; PUZZLE_BOARD_SIZE] {
-        [[None; PUZZLE_BOARD_SIZE]; PUZZLE_BOARD_SIZE]
+    fn empty_grid() -> TileGrid {
+        empty_tile_grid(PUZZLE_BOARD_SIZE)
     }
 
     #[test]
@@ -364,4 +650,496 @@ mod tests {
         assert!(!check_match_at_position(&grid, 1, 0));
         assert!(!check_match_at_position(&grid, 2, 0));
     }
+
+    #[test]
+    fn test_find_match_groups_on_6x6_board() {
+        let mut grid = empty_tile_grid(6);
+        // Horizontal run reaching the last column
+        grid[5][3] = Some(TileType::Blue);
+        grid[5][4] = Some(TileType::Blue);
+        grid[5][5] = Some(TileType::Blue);
+        // Vertical run in the first column
+        grid[0][0] = Some(TileType::Red);
+        grid[1][0] = Some(TileType::Red);
+        grid[2][0] = Some(TileType::Red);
+
+        let groups = find_match_groups(&grid);
+        assert_eq!(groups.len(), 2);
+        assert!(groups.contains(&(TileType::Blue, vec![(3, 5), (4, 5), (5, 5)])));
+        assert!(groups.contains(&(TileType::Red, vec![(0, 0), (0, 1), (0, 2)])));
+    }
+
+    #[test]
+    fn test_would_match_on_6x6_board_edge() {
+        let mut grid = empty_tile_grid(6);
+        grid[0][3] = Some(TileType::Green);
+        grid[0][4] = Some(TileType::Green);
+        grid[0][5] = Some(TileType::Red);
+        grid[1][5] = Some(TileType::Green);
+
+        assert!(would_match_after_swap(&grid, (5, 0), (5, 1)));
+    }
+
+    #[test]
+    fn test_build_matchable_grid_masks_stone() {
+        let mut board = PuzzleBoard::new(4);
+        board.set_obstacle(1, 0, Some(ObstacleType::Stone));
+        let positions = [GridPosition::new(0, 0), GridPosition::new(1, 0), GridPosition::new(2, 0)];
+        let tile_type = TileType::Red;
+        let tiles: Vec<_> = positions
+            .iter()
+            .enumerate()
+            .map(|(i, pos)| (Entity::from_raw(i as u32), pos, &tile_type))
+            .collect();
+
+        let grid = build_matchable_grid(&tiles, &board);
+        assert_eq!(grid[0][1], None, "Stone-covered tile must not be matchable");
+        assert!(find_match_groups(&grid).is_empty());
+    }
+
+    #[test]
+    fn test_match_adjacent_to_stone_fires_single_crack() {
+        #[derive(Resource, Default)]
+        struct CrackCount(u32);
+
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .init_resource::<CrackCount>()
+            .insert_resource(PuzzleBoard::default())
+            .init_resource::<MegaMatchRule>()
+            .init_resource::<MatchedRuns>()
+            .add_observer(|_trigger: Trigger<StoneCrackEvent>, mut count: ResMut<CrackCount>| {
+                count.0 += 1;
+            })
+            .add_systems(Update, remove_matched_tiles);
+
+        // Stone at (1,1), horizontal match directly above it on row 2
+        app.world_mut()
+            .resource_mut::<PuzzleBoard>()
+            .set_obstacle(1, 1, Some(ObstacleType::Stone));
+        for x in 0..3 {
+            app.world_mut().spawn((Tile, TileType::Red, GridPosition::new(x, 2), Matched));
+        }
+
+        app.update();
+
+        assert_eq!(app.world().resource::<CrackCount>().0, 1);
+    }
+
+    #[test]
+    fn test_frozen_tile_thaws_then_clears_on_second_match() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .insert_resource(PuzzleBoard::default())
+            .init_resource::<MegaMatchRule>()
+            .init_resource::<MatchedRuns>()
+            .add_systems(Update, remove_matched_tiles);
+        let spawn_run = |app: &mut App| -> Vec<Entity> {
+            (0..2)
+                .map(|x| {
+                    let entity = app.world_mut().spawn((Tile, TileType::Blue, GridPosition::new(x, 0), Matched)).id();
+                    app.world_mut().resource_mut::<PuzzleBoard>().set(x, 0, Some(entity));
+                    entity
+                })
+                .collect()
+        };
+        let frozen = app
+            .world_mut()
+            .spawn((
+                Tile,
+                TileType::Blue,
+                GridPosition::new(2, 0),
+                Matched,
+                Frozen::default(),
+                Sprite::from_color(Frozen::tint(TileType::Blue), Vec2::ONE),
+            ))
+            .id();
+        app.world_mut().resource_mut::<PuzzleBoard>().set(2, 0, Some(frozen));
+
+        let first_run = spawn_run(&mut app);
+        app.update();
+
+        assert!(first_run.iter().all(|entity| app.world().get_entity(*entity).is_err()));
+        let tile = app.world().entity(frozen);
+        assert!(!tile.contains::<Matched>() && !tile.contains::<Frozen>(), "the first match only thaws it");
+        assert_eq!(tile.get::<Sprite>().unwrap().color, TileType::Blue.color());
+        assert_eq!(app.world().resource::<PuzzleBoard>().get(2, 0), Some(frozen));
+
+        spawn_run(&mut app);
+        app.world_mut().entity_mut(frozen).insert(Matched);
+        app.update();
+
+        assert!(app.world().get_entity(frozen).is_err(), "the second match clears it");
+        assert_eq!(app.world().resource::<PuzzleBoard>().get(2, 0), None);
+    }
+
+    #[test]
+    fn test_extra_thaws_keep_the_tile_frozen() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .insert_resource(PuzzleBoard::default())
+            .init_resource::<MegaMatchRule>()
+            .init_resource::<MatchedRuns>()
+            .add_systems(Update, remove_matched_tiles);
+        let tile = app
+            .world_mut()
+            .spawn((Tile, TileType::Red, GridPosition::new(0, 0), Matched, Frozen { thaws_remaining: 2 }))
+            .id();
+
+        app.update();
+
+        assert_eq!(app.world().get::<Frozen>(tile), Some(&Frozen { thaws_remaining: 1 }));
+        assert!(app.world().get::<Matched>(tile).is_none());
+    }
+
+    fn setup_line_clear_app(size: usize) -> App {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .insert_resource(PuzzleBoard::new(size))
+            .init_resource::<ComboCounter>()
+            .init_resource::<LastSwap>()
+            .init_resource::<MegaMatchRule>()
+            .init_resource::<MatchedRuns>()
+            .add_observer(handle_line_clear)
+            .add_systems(Update, (detect_matches, remove_matched_tiles).chain());
+        app
+    }
+
+    /// Fill the board with a checkerboard-free pattern that has no 3-runs
+    fn fill_without_matches(app: &mut App, size: usize) -> Vec<Vec<Entity>> {
+        let palette = [TileType::Red, TileType::Blue, TileType::Green, TileType::Yellow];
+        (0..size)
+            .map(|y| {
+                (0..size)
+                    .map(|x| {
+                        let tile_type = palette[(x + 2 * y) % palette.len()];
+                        let entity = app
+                            .world_mut()
+                            .spawn((Tile, tile_type, GridPosition::new(x, y)))
+                            .id();
+                        app.world_mut().resource_mut::<PuzzleBoard>().set(x, y, Some(entity));
+                        entity
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_line_clear_positions_cover_row_and_column() {
+        let positions = line_clear_positions(8, 2, 5);
+        assert_eq!(positions.len(), 8 + 8 - 1);
+        for x in 0..8 {
+            assert!(positions.contains(&(x, 2)), "row cell ({x}, 2) missing");
+        }
+        for y in 0..8 {
+            assert!(positions.contains(&(5, y)), "column cell (5, {y}) missing");
+        }
+        assert!(!positions.contains(&(4, 3)));
+    }
+
+    #[test]
+    fn test_line_clear_positions_at_corner_of_6x6() {
+        let positions = line_clear_positions(6, 0, 5);
+        assert_eq!(positions.len(), 11);
+        assert!(positions.contains(&(0, 0)));
+        assert!(positions.contains(&(5, 5)));
+    }
+
+    #[test]
+    fn test_power_tile_spot_prefers_swapped_cell() {
+        let run = vec![(0, 0), (1, 0), (2, 0), (3, 0)];
+        assert_eq!(power_tile_spot(&run, Some([(1, 0), (1, 1)])), (1, 0));
+        assert_eq!(power_tile_spot(&run, Some([(7, 7), (6, 7)])), (2, 0));
+        assert_eq!(power_tile_spot(&run, None), (2, 0));
+    }
+
+    #[test]
+    fn test_four_match_leaves_power_tile_at_swap() {
+        let mut app = setup_line_clear_app(6);
+        let tiles = fill_without_matches(&mut app, 6);
+        for &entity in &tiles[0][..4] {
+            app.world_mut().entity_mut(entity).insert(TileType::Purple);
+        }
+        app.world_mut().resource_mut::<LastSwap>().0 = Some([(1, 0), (1, 1)]);
+
+        app.update();
+
+        assert!(app.world().get::<PowerTile>(tiles[0][1]).is_some());
+        assert!(app.world().get_entity(tiles[0][1]).is_ok());
+        for x in [0, 2, 3] {
+            assert!(app.world().get_entity(tiles[0][x]).is_err(), "tile ({x}, 0) should be cleared");
+        }
+        assert!(app.world().resource::<LastSwap>().0.is_none());
+    }
+
+    #[test]
+    fn test_activated_power_tile_clears_row_and_column() {
+        let size = 6;
+        let mut app = setup_line_clear_app(size);
+        let tiles = fill_without_matches(&mut app, size);
+        let (px, py) = (2, 3);
+        app.world_mut().entity_mut(tiles[py][px]).insert((PowerTile, Matched));
+        {
+            let mut board = app.world_mut().resource_mut::<PuzzleBoard>();
+            board.set_obstacle(4, py, Some(ObstacleType::Ice));
+            board.set_obstacle(px, 0, Some(ObstacleType::Bomb));
+        }
+        app.world_mut().commands().trigger(LineClearEvent { row: py, col: px });
+
+        app.update();
+
+        for (y, row) in tiles.iter().enumerate() {
+            for (x, entity) in row.iter().enumerate() {
+                let in_path = x == px || y == py;
+                assert_eq!(
+                    app.world().get_entity(*entity).is_err(),
+                    in_path,
+                    "tile ({x}, {y}) cleared state wrong"
+                );
+            }
+        }
+        let board = app.world().resource::<PuzzleBoard>();
+        assert!(!board.has_ice(4, py), "ice in the cleared row should melt");
+        assert!(!board.has_bomb(px, 0), "bomb in the cleared column should be defused");
+    }
+
+    #[derive(Resource, Default)]
+    struct ObstacleEvents {
+        melted: Vec<(usize, usize)>,
+        defused: Vec<(usize, usize)>,
+    }
+
+    fn record_obstacle_events(app: &mut App) {
+        app.init_resource::<ObstacleEvents>()
+            .add_observer(|trigger: Trigger<IceMeltEvent>, mut events: ResMut<ObstacleEvents>| {
+                events.melted.push(trigger.event().position);
+            })
+            .add_observer(|trigger: Trigger<BombDefuseEvent>, mut events: ResMut<ObstacleEvents>| {
+                events.defused.push(trigger.event().position);
+            });
+    }
+
+    #[test]
+    fn test_row_clear_melts_a_whole_line_of_ice() {
+        let size = 6;
+        let mut app = setup_line_clear_app(size);
+        record_obstacle_events(&mut app);
+        let tiles = fill_without_matches(&mut app, size);
+        let (px, py) = (0, 2);
+        app.world_mut().entity_mut(tiles[py][px]).insert((PowerTile, Matched));
+        {
+            let mut board = app.world_mut().resource_mut::<PuzzleBoard>();
+            for x in 1..size {
+                board.set_obstacle(x, py, Some(ObstacleType::Ice));
+            }
+        }
+        app.world_mut().commands().trigger(LineClearEvent { row: py, col: px });
+
+        app.update();
+
+        let board = app.world().resource::<PuzzleBoard>();
+        assert!((1..size).all(|x| !board.has_ice(x, py)), "every frozen cell in the row melts");
+        let mut melted = app.world().resource::<ObstacleEvents>().melted.clone();
+        melted.sort();
+        let expected: Vec<_> = (1..size).map(|x| (x, py)).collect();
+        assert_eq!(melted, expected, "exactly one melt event per frozen cell");
+    }
+
+    #[test]
+    fn test_row_clear_defuses_bombs_in_the_row() {
+        let size = 6;
+        let mut app = setup_line_clear_app(size);
+        record_obstacle_events(&mut app);
+        let tiles = fill_without_matches(&mut app, size);
+        let (px, py) = (3, 4);
+        app.world_mut().entity_mut(tiles[py][px]).insert((PowerTile, Matched));
+        {
+            let mut board = app.world_mut().resource_mut::<PuzzleBoard>();
+            board.set_obstacle(0, py, Some(ObstacleType::Bomb));
+            board.set_obstacle(5, py, Some(ObstacleType::Bomb));
+            // Two rows away from the blast: out of reach of both the line and adjacency
+            board.set_obstacle(0, 1, Some(ObstacleType::Bomb));
+        }
+        app.world_mut().commands().trigger(LineClearEvent { row: py, col: px });
+
+        app.update();
+
+        let board = app.world().resource::<PuzzleBoard>();
+        assert!(!board.has_bomb(0, py));
+        assert!(!board.has_bomb(5, py));
+        assert!(board.has_bomb(0, 1));
+        let mut defused = app.world().resource::<ObstacleEvents>().defused.clone();
+        defused.sort();
+        assert_eq!(defused, vec![(0, py), (5, py)]);
+    }
+
+    #[test]
+    fn test_row_clear_defuses_bomb_sitting_on_ice() {
+        let size = 6;
+        let mut app = setup_line_clear_app(size);
+        record_obstacle_events(&mut app);
+        let tiles = fill_without_matches(&mut app, size);
+        let (px, py) = (3, 4);
+        app.world_mut().entity_mut(tiles[py][px]).insert((PowerTile, Matched));
+        {
+            // A bomb that fell onto a frozen cell
+            let mut board = app.world_mut().resource_mut::<PuzzleBoard>();
+            board.set_obstacle(0, py, Some(ObstacleType::Ice));
+            board.set_obstacle(0, py, Some(ObstacleType::Bomb));
+        }
+        app.world_mut().commands().trigger(LineClearEvent { row: py, col: px });
+
+        app.update();
+
+        let board = app.world().resource::<PuzzleBoard>();
+        assert!(!board.has_ice(0, py));
+        assert!(!board.has_bomb(0, py), "bomb under ice is defused, not left ticking");
+        assert_eq!(app.world().resource::<ObstacleEvents>().defused, vec![(0, py)]);
+    }
+
+    #[test]
+    fn test_line_clear_chains_into_other_power_tiles() {
+        let size = 6;
+        let mut app = setup_line_clear_app(size);
+        let tiles = fill_without_matches(&mut app, size);
+        app.world_mut().entity_mut(tiles[0][0]).insert((PowerTile, Matched));
+        // Second power tile sits in row 0 and will fire its own column
+        app.world_mut().entity_mut(tiles[0][4]).insert(PowerTile);
+        app.world_mut().commands().trigger(LineClearEvent { row: 0, col: 0 });
+
+        app.update();
+
+        for (y, row) in tiles.iter().enumerate() {
+            assert!(app.world().get_entity(row[4]).is_err(), "chained column cell (4, {y}) should clear");
+        }
+        assert!(app.world().get_entity(tiles[3][3]).is_ok());
+    }
+
+    /// 4x4 arrangement where no single swap makes a run of three
+    fn deadlocked_4x4() -> TileGrid {
+        let palette = [TileType::Red, TileType::Blue, TileType::Green, TileType::Yellow];
+        (0..4)
+            .map(|y| (0..4).map(|x| Some(palette[(x + 2 * y) % 4])).collect())
+            .collect()
+    }
+
+    #[test]
+    fn test_has_any_valid_move_deadlocked_board() {
+        let grid = deadlocked_4x4();
+        assert!(find_match_groups(&grid).is_empty());
+        assert!(!has_any_valid_move(&grid));
+    }
+
+    #[test]
+    fn test_has_any_valid_move_single_move() {
+        // Blue at (0,1) lets (0,1)<->(1,1) line up Blue down column 1
+        let mut grid = deadlocked_4x4();
+        grid[1][0] = Some(TileType::Blue);
+        assert!(find_match_groups(&grid).is_empty());
+        assert!(has_any_valid_move(&grid));
+        assert!(would_match_after_swap(&grid, (0, 1), (1, 1)));
+    }
+
+    #[test]
+    fn test_has_any_valid_move_ignores_blocked_cells() {
+        let mut grid = deadlocked_4x4();
+        grid[1][0] = Some(TileType::Blue);
+        // The only move touches (1,1); freezing it leaves the board stuck
+        assert!(!has_any_valid_move_where(&grid, |x, y| (x, y) == (1, 1)));
+    }
+
+    #[derive(Resource, Default)]
+    struct MeltedIce(Vec<(usize, usize)>);
+
+    /// Default board with the mega-match rule on, a horizontal run of `len` reds on row 0
+    /// and ice on the given cells
+    fn setup_mega_match_app(len: usize, ice: &[(usize, usize)]) -> App {
+        let mut app = setup_line_clear_app(PUZZLE_BOARD_SIZE);
+        app.insert_resource(MegaMatchRule { enabled: true })
+            .init_resource::<MatchedRuns>()
+            .init_resource::<MeltedIce>()
+            .add_observer(|trigger: Trigger<IceMeltEvent>, mut melted: ResMut<MeltedIce>| {
+                melted.0.push(trigger.event().position);
+            });
+        for x in 0..len {
+            let entity = app
+                .world_mut()
+                .spawn((Tile, TileType::Red, GridPosition::new(x, 0)))
+                .id();
+            app.world_mut().resource_mut::<PuzzleBoard>().set(x, 0, Some(entity));
+        }
+        let mut board = app.world_mut().resource_mut::<PuzzleBoard>();
+        for &(x, y) in ice {
+            board.set_obstacle(x, y, Some(ObstacleType::Ice));
+        }
+        app
+    }
+
+    #[test]
+    fn test_mega_match_radius_by_run_length() {
+        assert_eq!(mega_match_radius(3), None);
+        assert_eq!(mega_match_radius(4), Some(2));
+        assert_eq!(mega_match_radius(5), Some(3));
+    }
+
+    #[test]
+    fn test_four_match_clears_ice_beyond_adjacent_cells() {
+        // Run x=0..3 centers on (2,0) with radius 2; (2,2) is two rows up, out of adjacency reach
+        let mut app = setup_mega_match_app(4, &[(2, 2), (1, 2)]);
+
+        app.update();
+
+        let board = app.world().resource::<PuzzleBoard>();
+        assert!(!board.has_ice(2, 2));
+        assert!(board.has_ice(1, 2), "(1,2) is 3 steps from the center");
+        assert!(app.world().resource::<MatchedRuns>().0.is_empty(), "runs are drained");
+    }
+
+    #[test]
+    fn test_three_match_only_clears_adjacent_ice() {
+        let mut app = setup_mega_match_app(3, &[(1, 1), (1, 2)]);
+
+        app.update();
+
+        let board = app.world().resource::<PuzzleBoard>();
+        assert!(!board.has_ice(1, 1));
+        assert!(board.has_ice(1, 2));
+    }
+
+    #[test]
+    fn test_mega_match_does_not_double_fire_melt() {
+        // (1,1) is both adjacent to the run and inside the radius
+        let mut app = setup_mega_match_app(4, &[(1, 1)]);
+
+        app.update();
+
+        assert_eq!(app.world().resource::<MeltedIce>().0, vec![(1, 1)]);
+    }
+
+    #[test]
+    fn test_mega_match_rule_disabled_keeps_adjacent_only() {
+        let mut app = setup_mega_match_app(4, &[(2, 2)]);
+        app.insert_resource(MegaMatchRule { enabled: false });
+
+        app.update();
+
+        assert!(app.world().resource::<PuzzleBoard>().has_ice(2, 2));
+    }
+
+    #[test]
+    fn test_mega_match_region_skips_core_and_stays_in_bounds() {
+        let board = PuzzleBoard::default();
+        // 5-run on row 2 centered on (3,2) reaches the core at (3,3)/(4,3) with radius 3
+        let run: Vec<_> = (1..6).map(|x| (x, 2)).collect();
+        let region = mega_match_region(&board, &[(TileType::Blue, run)]);
+        assert!(region.contains(&(3, 5)));
+        assert!(board.core_positions().iter().all(|pos| !region.contains(pos)));
+
+        // Corner run: every cell stays on the board
+        let corner: Vec<_> = (0..4).map(|y| (0, y)).collect();
+        let region = mega_match_region(&board, &[(TileType::Red, corner)]);
+        assert!(region.iter().all(|&(x, y)| board.in_bounds(x, y)));
+        assert!(region.contains(&(0, 0)) && region.contains(&(2, 2)));
+    }
 }
@@ -1,18 +1,34 @@
 mod board;
 mod tile;
 mod input;
+mod gamepad;
 mod match_detector;
 mod cascade;
 mod obstacle;
 mod preview;
+mod reshuffle;
+mod energy;
+mod reroll;
+mod combo_timer;
+mod symbols;
+mod hint;
 
 use crate::prelude::*;
 
-pub use board::PuzzleBoard;
-pub use tile::{Tile, TileType, GridPosition, Matched, Falling, Selected, Obstacle, ObstacleType};
-pub use cascade::CascadeState;
-pub use obstacle::{ObstaclePlugin, BombCountdownText, IceOverlay, IceMeltEvent, BombDefuseEvent};
+pub use board::{PuzzleBoard, BoardConfig, spawn_tile};
+pub use tile::{Tile, TileType, GridPosition, Matched, Frozen, Falling, Selected, Obstacle, ObstacleType, PowerTile, PowerTileMarker};
+pub use match_detector::{LineClearEvent, MatchedRuns, MegaMatchRule, TileGrid, has_any_valid_move, find_match_groups, detect_matches};
+pub use gamepad::{PuzzleCursor, PuzzleCursorHighlight};
+pub use input::{BufferedSwap, PendingSwapCheck, LastSwap, SelectedTile, SwapTilesEvent, handle_tile_swap};
+pub use reshuffle::BoardReshuffleEvent;
+pub use cascade::{CascadeState, GravityDirection};
+pub use obstacle::{ObstaclePlugin, BombCountdownText, BombDefuseEffect, IceOverlay, IceMeltEvent, BombDefuseEvent, StoneOverlay, StoneCrackEvent, IceSpreadEvent, IceSpreadConfig, IceSpreadTimer, ObstacleRecord, ObstacleSnapshot, restore_obstacles};
 pub use preview::TilePreview;
+pub use energy::{MatchEnergy, ColorClearEvent};
+pub use reroll::{RerollBoardEvent, REROLL_COST};
+pub use combo_timer::{ComboTimer, COMBO_WINDOW};
+pub use symbols::{ColorblindMode, TileSymbol, TileSymbolOverlay, symbol_for};
+pub use hint::{IdleHintTimer, HintHighlight, HINT_IDLE_SECS};
 
 const HIGHLIGHT_INTENSITY: f32 = 0.4;
 
@@ -20,27 +36,78 @@ pub struct PuzzlePlugin;
 
 impl Plugin for PuzzlePlugin {
     fn build(&self, app: &mut App) {
-        app.init_resource::<CascadeState>()
+        app.init_resource::<BoardConfig>()
+            .init_resource::<PuzzleBoard>()
+            .init_resource::<CascadeState>()
             .init_resource::<ComboCounter>()
             .init_resource::<preview::TilePreview>()
-            .add_systems(Startup, board::setup_puzzle_board)
+            .init_resource::<input::LastSwap>()
+            .init_resource::<input::BufferedSwap>()
+            .init_resource::<input::PendingSwapCheck>()
+            .init_resource::<input::SelectedTile>()
+            .init_resource::<gamepad::PuzzleCursor>()
+            .init_resource::<MegaMatchRule>()
+            .init_resource::<cascade::GravityDirection>()
+            .init_resource::<MatchedRuns>()
+            .init_resource::<energy::MatchEnergy>()
+            .init_resource::<combo_timer::ComboTimer>()
+            .init_resource::<Gold>()
+            .init_resource::<ColorblindMode>()
+            .init_resource::<IdleHintTimer>()
+            .add_systems(
+                OnTransition {
+                    exited: GameState::Title,
+                    entered: GameState::Playing,
+                },
+                (board::setup_puzzle_board, reshuffle::reshuffle_if_deadlocked).chain(),
+            )
+            .add_systems(
+                OnTransition {
+                    exited: GameState::GameOver,
+                    entered: GameState::Playing,
+                },
+                (board::setup_puzzle_board, reshuffle::reshuffle_if_deadlocked).chain(),
+            )
+            .add_systems(
+                OnEnter(PhaseState::Idle),
+                reshuffle::reshuffle_if_deadlocked.run_if(in_state(GameState::Playing)),
+            )
             .add_observer(input::handle_tile_swap)
             .add_observer(input::handle_invalid_swap)
+            .add_observer(match_detector::handle_line_clear)
+            .add_observer(energy::fill_match_energy)
+            .add_observer(combo_timer::refresh_combo_timer)
+            .add_observer(energy::handle_color_clear)
+            .add_observer(reroll::handle_reroll_board)
+            .add_systems(Update, symbols::sync_tile_symbols)
+            .add_systems(
+                Update,
+                (hint::reset_idle_hint, hint::show_idle_hint, hint::pulse_hint_tiles)
+                    .chain()
+                    .after(cascade::check_cascade_complete)
+                    .run_if(in_state(GameState::Playing)),
+            )
             .add_systems(
                 Update,
                 (
+                    input::execute_buffered_swap,
                     input::handle_tile_click,
+                    gamepad::gamepad_puzzle_input,
+                    gamepad::update_cursor_highlight,
+                    energy::activate_match_energy.run_if(in_state(PhaseState::Idle)),
                     input::animate_swap,
+                    input::pending_swap_check,
                     input::animate_ice_shake,
                     input::animate_invalid_swap_shake,
                     highlight_selected_tile,
                     match_detector::detect_matches,
                     cascade::start_cascade,
+                    input::clear_selection_on_cascade,
                     match_detector::remove_matched_tiles,
                     cascade::apply_gravity,
                     cascade::spawn_new_tiles,
                     cascade::check_cascade_complete,
-                    cascade::reset_combo_on_idle,
+                    combo_timer::decay_combo_timer,
                 )
                     .chain()
                     .run_if(in_state(GameState::Playing)),
@@ -48,12 +115,13 @@ impl Plugin for PuzzlePlugin {
     }
 }
 
-fn highlight_selected_tile(
-    mut tiles: Query<(&mut Sprite, &TileType, Option<&Selected>), With<Tile>>,
-) {
-    for (mut sprite, tile_type, selected) in tiles.iter_mut() {
-        let base_color = tile_type.color();
-        if selected.is_some() {
+type TileHighlightQuery<'w, 's> =
+    Query<'w, 's, (&'static mut Sprite, &'static TileType, Has<Selected>, Has<Frozen>), With<Tile>>;
+
+fn highlight_selected_tile(mut tiles: TileHighlightQuery) {
+    for (mut sprite, tile_type, selected, frozen) in tiles.iter_mut() {
+        let base_color = if frozen { Frozen::tint(*tile_type) } else { tile_type.color() };
+        if selected {
             sprite.color = base_color.lighter(HIGHLIGHT_INTENSITY);
         } else {
             sprite.color = base_color;
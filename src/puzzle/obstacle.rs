@@ -1,3 +1,5 @@
+use rand::Rng;
+use serde::{Deserialize, Serialize};
 use crate::prelude::*;
 use crate::bridge::ObstacleSpawnEvent;
 use super::board::PuzzleBoard;
@@ -23,6 +25,14 @@ impl Default for IceOverlay {
     }
 }
 
+/// Marker component for stone overlay sprites
+#[derive(Component)]
+pub struct StoneOverlay;
+
+/// Stone colors: intact and cracked (after the first adjacent match)
+const STONE_COLOR: Color = Color::srgb(0.5, 0.5, 0.55);
+const STONE_CRACKED_COLOR: Color = Color::srgb(0.35, 0.33, 0.32);
+
 /// Event to trigger ice melting animation
 #[derive(Event)]
 pub struct IceMeltEvent {
@@ -35,19 +45,144 @@ pub struct BombDefuseEvent {
     pub position: (usize, usize),
 }
 
+/// Event fired when an adjacent match hits a stone
+#[derive(Event)]
+pub struct StoneCrackEvent {
+    pub position: (usize, usize),
+}
+
+/// Event fired when ice grows from `from` onto the neighboring cell `to`
+#[derive(Event)]
+pub struct IceSpreadEvent {
+    pub from: (usize, usize),
+    pub to: (usize, usize),
+}
+
+/// Harder-variant rule: uncleared ice creeps onto neighboring cells.
+/// Off in the base game.
+#[derive(Resource, Clone, Copy, Debug)]
+pub struct IceSpreadConfig {
+    pub enabled: bool,
+    /// Seconds between spread checks
+    pub interval: f32,
+    /// Chance per ice block per check to spread
+    pub chance: f32,
+}
+
+impl Default for IceSpreadConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            interval: 5.0,
+            chance: 0.25,
+        }
+    }
+}
+
+/// Time accumulated toward the next ice spread check
+#[derive(Resource, Default)]
+pub struct IceSpreadTimer {
+    pub timer: f32,
+}
+
 /// Visual effect component for bomb defuse animation
 #[derive(Component)]
 pub struct BombDefuseEffect {
     pub timer: f32,
 }
 
+/// One obstacle as saved: where it is, what it is, and its progress
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ObstacleRecord {
+    pub position: (usize, usize),
+    pub obstacle_type: ObstacleType,
+    /// Turns left before a bomb goes off
+    pub countdown: Option<u8>,
+    /// Hits a stone has already taken
+    pub cracks: u8,
+}
+
+/// Every obstacle on the board, taken from the `Obstacle` entities rather than
+/// `PuzzleBoard.obstacles` because countdowns and cracks only live there.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ObstacleSnapshot {
+    pub obstacles: Vec<ObstacleRecord>,
+}
+
+impl ObstacleSnapshot {
+    pub fn capture<'a>(obstacles: impl IntoIterator<Item = (&'a Obstacle, &'a GridPosition)>) -> Self {
+        let mut obstacles: Vec<ObstacleRecord> = obstacles
+            .into_iter()
+            .map(|(obstacle, pos)| ObstacleRecord {
+                position: (pos.x, pos.y),
+                obstacle_type: obstacle.obstacle_type,
+                countdown: obstacle.countdown,
+                cracks: obstacle.cracks,
+            })
+            .collect();
+        // Query order isn't stable; keep saves deterministic
+        obstacles.sort_by_key(|record| (record.position.1, record.position.0, record.obstacle_type as u8));
+        Self { obstacles }
+    }
+}
+
+/// Replace the current obstacles with `snapshot`: despawns `existing` obstacle entities,
+/// rebuilds `PuzzleBoard.obstacles`, and re-spawns ice/stone overlays and bombs as
+/// children of the tiles now at their positions. Tiles must already be restored.
+pub fn restore_obstacles(
+    commands: &mut Commands,
+    board: &mut PuzzleBoard,
+    existing: impl IntoIterator<Item = Entity>,
+    snapshot: &ObstacleSnapshot,
+) {
+    for entity in existing {
+        commands.entity(entity).despawn_recursive();
+    }
+    for row in board.obstacles.iter_mut() {
+        row.fill(None);
+    }
+    for row in board.bombs.iter_mut() {
+        row.fill(false);
+    }
+
+    // Bombs need their tile; ice and stone belong to the cell
+    let (bombs, cells): (Vec<&ObstacleRecord>, Vec<_>) = snapshot
+        .obstacles
+        .iter()
+        .filter(|record| board.in_bounds(record.position.0, record.position.1))
+        .partition(|record| record.obstacle_type == ObstacleType::Bomb);
+
+    for record in cells {
+        let (x, y) = record.position;
+        board.set_obstacle(x, y, Some(record.obstacle_type));
+        match record.obstacle_type {
+            ObstacleType::Ice => spawn_ice(commands, board, x, y),
+            ObstacleType::Stone => spawn_stone(commands, board, x, y, record.cracks),
+            ObstacleType::Bomb => unreachable!("bombs are partitioned out"),
+        }
+    }
+
+    for record in bombs {
+        let (x, y) = record.position;
+        let Some(tile_entity) = board.get(x, y) else { continue };
+        board.set_obstacle(x, y, Some(ObstacleType::Bomb));
+        spawn_bomb(commands, tile_entity, record.countdown.unwrap_or(3), x, y);
+    }
+}
+
 pub struct ObstaclePlugin;
 
 impl Plugin for ObstaclePlugin {
     fn build(&self, app: &mut App) {
-        app.add_observer(handle_obstacle_spawn)
+        app.init_resource::<IceSpreadConfig>()
+            .init_resource::<IceSpreadTimer>()
+            .init_resource::<TimeScale>()
+            .init_resource::<GameSpeed>()
+            .add_observer(handle_obstacle_spawn)
             .add_observer(handle_ice_melt)
+            .add_observer(handle_ice_spread)
             .add_observer(handle_bomb_defuse)
+            .add_observer(handle_stone_crack)
             .add_systems(
                 Update,
                 (
@@ -57,6 +192,12 @@ impl Plugin for ObstaclePlugin {
                     bomb_defuse_animation_system,
                 )
                     .run_if(in_state(GameState::Playing)),
+            )
+            // Only on a settled board, so a cascade never lands tiles under fresh ice mid-fall
+            .add_systems(
+                Update,
+                spread_ice_system
+                    .run_if(in_state(GameState::Playing).and(in_state(PhaseState::Idle))),
             );
     }
 }
@@ -69,7 +210,7 @@ fn handle_obstacle_spawn(
     let event = trigger.event();
     let (x, y) = event.position;
 
-    if x >= PUZZLE_BOARD_SIZE || y >= PUZZLE_BOARD_SIZE {
+    if !board.in_bounds(x, y) {
         return;
     }
 
@@ -86,9 +227,51 @@ fn handle_obstacle_spawn(
                 spawn_bomb(&mut commands, tile_entity, event.countdown.unwrap_or(3), x, y);
             }
         }
+        ObstacleType::Stone => {
+            // Never stack stone on top of another obstacle
+            if board.get_obstacle(x, y).is_none() {
+                board.set_obstacle(x, y, Some(event.obstacle_type));
+                spawn_stone(&mut commands, &board, x, y, 0);
+            }
+        }
     }
 }
 
+/// Spawn a stone overlay that has already taken `cracks` hits
+fn spawn_stone(commands: &mut Commands, board: &PuzzleBoard, x: usize, y: usize, cracks: u8) {
+    let pos = board.grid_to_world(x, y);
+
+    let stone = commands.spawn((
+        Obstacle { cracks, ..Obstacle::stone() },
+        GridPosition::new(x, y),
+        StoneOverlay,
+        Sprite {
+            color: if cracks > 0 { STONE_CRACKED_COLOR } else { STONE_COLOR },
+            custom_size: Some(Vec2::splat(TILE_SIZE)),
+            ..default()
+        },
+        Transform::from_translation(pos.extend(0.5)),
+        Visibility::default(),
+    )).id();
+
+    if cracks > 0 {
+        spawn_crack_line(commands, stone);
+    }
+}
+
+/// Diagonal crack line across a cracked stone
+fn spawn_crack_line(commands: &mut Commands, stone: Entity) {
+    commands.entity(stone).with_child((
+        Sprite {
+            color: Color::srgb(0.1, 0.1, 0.1),
+            custom_size: Some(Vec2::new(TILE_SIZE * 0.9, 3.0)),
+            ..default()
+        },
+        Transform::from_translation(Vec3::new(0.0, 0.0, 0.1))
+            .with_rotation(Quat::from_rotation_z(std::f32::consts::FRAC_PI_4)),
+    ));
+}
+
 fn spawn_ice(commands: &mut Commands, board: &PuzzleBoard, x: usize, y: usize) {
     let pos = board.grid_to_world(x, y);
 
@@ -135,6 +318,68 @@ fn spawn_bomb(commands: &mut Commands, parent_tile: Entity, countdown: u8, x: us
     commands.entity(parent_tile).add_child(bomb_entity);
 }
 
+/// Random orthogonal neighbor of the ice at `from` that ice may spread onto:
+/// on the board, holding a tile, free of any obstacle, and never a core cell
+pub fn ice_spread_target(board: &PuzzleBoard, from: (usize, usize), rng: &mut impl Rng) -> Option<(usize, usize)> {
+    let (x, y) = (from.0 as i32, from.1 as i32);
+    let candidates: Vec<(usize, usize)> = [(x - 1, y), (x + 1, y), (x, y - 1), (x, y + 1)]
+        .into_iter()
+        .filter(|&(nx, ny)| nx >= 0 && ny >= 0)
+        .map(|(nx, ny)| (nx as usize, ny as usize))
+        .filter(|&(nx, ny)| {
+            board.in_bounds(nx, ny)
+                && board.get(nx, ny).is_some()
+                && board.get_obstacle(nx, ny).is_none()
+                && !board.is_core_position(nx, ny)
+        })
+        .collect();
+
+    if candidates.is_empty() {
+        return None;
+    }
+    Some(candidates[rng.gen_range(0..candidates.len())])
+}
+
+/// Every `IceSpreadConfig::interval`, each ice block rolls to freeze a neighbor.
+/// Only ice present at the start of the check can spread, so growth is one step per beat.
+pub fn spread_ice_system(
+    mut commands: Commands,
+    time: ScaledTime,
+    config: Res<IceSpreadConfig>,
+    mut spread_timer: ResMut<IceSpreadTimer>,
+    mut board: ResMut<PuzzleBoard>,
+    mut rng: ResMut<GameRng>,
+) {
+    if !config.enabled {
+        return;
+    }
+    spread_timer.timer += time.delta_secs();
+    if spread_timer.timer < config.interval {
+        return;
+    }
+    spread_timer.timer = 0.0;
+
+    let ice: Vec<(usize, usize)> = (0..board.size)
+        .flat_map(|y| (0..board.size).map(move |x| (x, y)))
+        .filter(|&(x, y)| board.has_ice(x, y))
+        .collect();
+
+    for from in ice {
+        if rng.gen::<f32>() >= config.chance {
+            continue;
+        }
+        let Some(to) = ice_spread_target(&board, from, &mut *rng) else { continue };
+        board.set_obstacle(to.0, to.1, Some(ObstacleType::Ice));
+        commands.trigger(IceSpreadEvent { from, to });
+    }
+}
+
+/// Ice spread visual: a fresh overlay on the newly frozen cell
+fn handle_ice_spread(trigger: Trigger<IceSpreadEvent>, mut commands: Commands, board: Res<PuzzleBoard>) {
+    let (x, y) = trigger.event().to;
+    spawn_ice(&mut commands, &board, x, y);
+}
+
 /// Sync bomb GridPosition with parent tile's GridPosition after swaps
 fn sync_bomb_position_with_parent(
     tiles: Query<(&GridPosition, &Children), With<super::tile::Tile>>,
@@ -196,7 +441,7 @@ fn ice_melt_animation_system(
             overlay.alpha -= MELT_SPEED * time.delta_secs();
 
             if overlay.alpha <= 0.0 {
-                board.clear_obstacle(pos.x, pos.y);
+                board.clear_cell_obstacle(pos.x, pos.y);
                 commands.entity(entity).despawn_recursive();
             } else {
                 sprite.color = Color::srgba(0.7, 0.9, 1.0, overlay.alpha);
@@ -210,13 +455,13 @@ fn handle_bomb_defuse(
     trigger: Trigger<BombDefuseEvent>,
     mut commands: Commands,
     board: Res<PuzzleBoard>,
-    obstacles: Query<(Entity, &GridPosition), With<Obstacle>>,
+    obstacles: Query<(Entity, &GridPosition, &Obstacle)>,
 ) {
     let (x, y) = trigger.event().position;
 
-    // Find and remove the bomb at this position
-    for (entity, pos) in obstacles.iter() {
-        if pos.x == x && pos.y == y {
+    // Find and remove the bomb at this position (ice or stone may share the cell)
+    for (entity, pos, obstacle) in obstacles.iter() {
+        if obstacle.is_bomb() && pos.x == x && pos.y == y {
             // Spawn defuse effect at world position
             let world_pos = board.grid_to_world(x, y);
             commands.spawn((
@@ -238,6 +483,31 @@ fn handle_bomb_defuse(
     }
 }
 
+/// Handle stone crack event - first hit cracks the stone, second hit removes it
+fn handle_stone_crack(
+    trigger: Trigger<StoneCrackEvent>,
+    mut commands: Commands,
+    mut board: ResMut<PuzzleBoard>,
+    mut stones: Query<(Entity, &GridPosition, &mut Obstacle, &mut Sprite), With<StoneOverlay>>,
+) {
+    let (x, y) = trigger.event().position;
+
+    for (entity, pos, mut obstacle, mut sprite) in stones.iter_mut() {
+        if pos.x != x || pos.y != y {
+            continue;
+        }
+
+        if obstacle.crack() {
+            board.clear_cell_obstacle(x, y);
+            commands.entity(entity).despawn_recursive();
+        } else {
+            sprite.color = STONE_CRACKED_COLOR;
+            spawn_crack_line(&mut commands, entity);
+        }
+        break;
+    }
+}
+
 /// Animate bomb defuse effect (green expanding circle that fades)
 fn bomb_defuse_animation_system(
     mut commands: Commands,
@@ -258,3 +528,275 @@ fn bomb_defuse_animation_system(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::puzzle::Tile;
+
+    fn setup_stone_app() -> (App, Entity) {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .insert_resource(PuzzleBoard::default())
+            .add_observer(handle_stone_crack);
+
+        app.world_mut()
+            .resource_mut::<PuzzleBoard>()
+            .set_obstacle(2, 2, Some(ObstacleType::Stone));
+        let stone = app
+            .world_mut()
+            .spawn((Obstacle::stone(), GridPosition::new(2, 2), StoneOverlay, Sprite::default()))
+            .id();
+        (app, stone)
+    }
+
+    #[test]
+    fn test_stone_crack_counts_hits() {
+        let mut stone = Obstacle::stone();
+        assert!(!stone.crack(), "First hit should only crack the stone");
+        assert!(stone.crack(), "Second hit should break the stone");
+    }
+
+    #[test]
+    fn test_stone_survives_first_adjacent_match() {
+        let (mut app, stone) = setup_stone_app();
+
+        app.world_mut().trigger(StoneCrackEvent { position: (2, 2) });
+        app.world_mut().flush();
+
+        assert!(app.world().resource::<PuzzleBoard>().has_stone(2, 2));
+        assert_eq!(app.world().get::<Obstacle>(stone).unwrap().cracks, 1);
+    }
+
+    #[test]
+    fn test_stone_breaks_on_second_adjacent_match() {
+        let (mut app, stone) = setup_stone_app();
+
+        app.world_mut().trigger(StoneCrackEvent { position: (2, 2) });
+        app.world_mut().flush();
+        app.world_mut().trigger(StoneCrackEvent { position: (2, 2) });
+        app.world_mut().flush();
+
+        assert!(!app.world().resource::<PuzzleBoard>().has_stone(2, 2));
+        assert!(app.world().get_entity(stone).is_err());
+    }
+
+    #[test]
+    fn test_stone_blocks_swaps() {
+        let mut board = PuzzleBoard::default();
+        board.set_obstacle(1, 1, Some(ObstacleType::Stone));
+        assert!(board.is_swap_blocked(1, 1));
+        assert!(!board.is_swap_blocked(0, 0));
+    }
+
+    /// Board with a tile entity in every cell and the spawn observer, like a live game
+    fn setup_snapshot_app() -> App {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .insert_resource(PuzzleBoard::default())
+            .add_observer(handle_obstacle_spawn);
+        let size = app.world().resource::<PuzzleBoard>().size;
+        for y in 0..size {
+            for x in 0..size {
+                let tile = app.world_mut().spawn((Tile, GridPosition::new(x, y))).id();
+                app.world_mut().resource_mut::<PuzzleBoard>().set(x, y, Some(tile));
+            }
+        }
+        app.world_mut().flush();
+        app
+    }
+
+    fn spawn_obstacle(app: &mut App, obstacle_type: ObstacleType, position: (usize, usize), countdown: Option<u8>) {
+        app.world_mut().trigger(ObstacleSpawnEvent { obstacle_type, position, countdown });
+        app.world_mut().flush();
+    }
+
+    fn capture(app: &mut App) -> ObstacleSnapshot {
+        let world = app.world_mut();
+        let mut query = world.query::<(&Obstacle, &GridPosition)>();
+        ObstacleSnapshot::capture(query.iter(world))
+    }
+
+    fn restore(app: &mut App, snapshot: &ObstacleSnapshot) {
+        let world = app.world_mut();
+        let existing: Vec<Entity> = world.query_filtered::<Entity, With<Obstacle>>().iter(world).collect();
+        world.resource_scope(|world, mut board: Mut<PuzzleBoard>| {
+            let mut commands = world.commands();
+            restore_obstacles(&mut commands, &mut board, existing, snapshot);
+        });
+        world.flush();
+    }
+
+    #[test]
+    fn test_snapshot_round_trip_restores_obstacles() {
+        let mut app = setup_snapshot_app();
+        spawn_obstacle(&mut app, ObstacleType::Ice, (0, 0), None);
+        spawn_obstacle(&mut app, ObstacleType::Stone, (2, 3), None);
+        spawn_obstacle(&mut app, ObstacleType::Bomb, (4, 1), Some(5));
+        app.world_mut().trigger(StoneCrackEvent { position: (2, 3) });
+        app.world_mut().flush();
+        // A few turns tick by before saving
+        let world = app.world_mut();
+        for mut obstacle in world.query::<&mut Obstacle>().iter_mut(world) {
+            if let Some(countdown) = obstacle.countdown.as_mut() {
+                *countdown = 2;
+            }
+        }
+
+        let snapshot = capture(&mut app);
+        let json = serde_json::to_string(&snapshot).unwrap();
+        let loaded: ObstacleSnapshot = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded, snapshot);
+
+        restore(&mut app, &loaded);
+
+        assert_eq!(capture(&mut app), snapshot, "same obstacles after restore");
+        let world = app.world_mut();
+        assert_eq!(world.query::<&Obstacle>().iter(world).count(), 3, "old entities were replaced, not duplicated");
+        let board = app.world().resource::<PuzzleBoard>();
+        assert!(board.has_ice(0, 0));
+        assert!(board.has_stone(2, 3));
+        assert!(board.has_bomb(4, 1));
+        assert_eq!(board.obstacles.iter().flatten().flatten().count(), 2);
+        assert_eq!(board.bombs.iter().flatten().filter(|&&bomb| bomb).count(), 1);
+    }
+
+    #[test]
+    fn test_restored_bomb_is_child_of_its_tile_with_countdown() {
+        let mut app = setup_snapshot_app();
+        let snapshot = ObstacleSnapshot {
+            obstacles: vec![ObstacleRecord {
+                position: (3, 4),
+                obstacle_type: ObstacleType::Bomb,
+                countdown: Some(2),
+                cracks: 0,
+            }],
+        };
+
+        restore(&mut app, &snapshot);
+
+        let tile = app.world().resource::<PuzzleBoard>().get(3, 4).unwrap();
+        let world = app.world_mut();
+        let (bomb, obstacle, parent, children) = world
+            .query::<(Entity, &Obstacle, &Parent, &Children)>()
+            .single(world);
+        assert_eq!(parent.get(), tile, "bomb rides on the tile at its position");
+        assert_eq!(obstacle.countdown, Some(2));
+        assert!(world.get::<Children>(tile).unwrap().contains(&bomb));
+        let text = world.get::<Text2d>(children[0]).unwrap();
+        assert_eq!(text.0, "2", "countdown label shows the restored value");
+    }
+
+    #[test]
+    fn test_bomb_over_ice_keeps_ice_board_entry() {
+        let mut app = setup_snapshot_app();
+        let snapshot = ObstacleSnapshot {
+            obstacles: vec![
+                ObstacleRecord { position: (1, 1), obstacle_type: ObstacleType::Bomb, countdown: Some(4), cracks: 0 },
+                ObstacleRecord { position: (1, 1), obstacle_type: ObstacleType::Ice, countdown: None, cracks: 0 },
+            ],
+        };
+
+        restore(&mut app, &snapshot);
+
+        let board = app.world().resource::<PuzzleBoard>();
+        assert!(board.has_ice(1, 1));
+        assert!(board.has_bomb(1, 1), "bomb keeps its own board entry");
+        let world = app.world_mut();
+        assert_eq!(world.query::<&Obstacle>().iter(world).count(), 2, "bomb entity still restored");
+    }
+
+    /// 5x5 board filled with tiles, ice at `ice`
+    fn spread_board(ice: (usize, usize)) -> PuzzleBoard {
+        let mut board = PuzzleBoard::new(5);
+        let mut world = World::new();
+        for y in 0..5 {
+            for x in 0..5 {
+                board.set(x, y, Some(world.spawn_empty().id()));
+            }
+        }
+        board.set_obstacle(ice.0, ice.1, Some(ObstacleType::Ice));
+        board
+    }
+
+    #[test]
+    fn test_ice_spread_target_is_free_orthogonal_neighbor() {
+        let mut board = spread_board((0, 4));
+        board.set_obstacle(1, 4, Some(ObstacleType::Stone));
+        let mut rng = GameRng::from_seed(3);
+
+        // (1, 4) is stone and (-1, 4)/(0, 5) are off the board: only (0, 3) is left
+        for _ in 0..20 {
+            assert_eq!(ice_spread_target(&board, (0, 4), &mut rng), Some((0, 3)));
+        }
+
+        board.set_obstacle(0, 3, Some(ObstacleType::Bomb));
+        assert_eq!(ice_spread_target(&board, (0, 4), &mut rng), None);
+    }
+
+    #[test]
+    fn test_ice_spread_target_skips_empty_cells() {
+        let mut board = spread_board((0, 0));
+        board.set(1, 0, None);
+        let mut rng = GameRng::from_seed(3);
+        for _ in 0..20 {
+            assert_eq!(ice_spread_target(&board, (0, 0), &mut rng), Some((0, 1)));
+        }
+    }
+
+    #[test]
+    fn test_ice_never_spreads_onto_core() {
+        // Core of a 5x5 board is (1..=2, 1..=2); ice at (1, 0) borders (1, 1)
+        let board = spread_board((1, 0));
+        assert!(board.is_core_position(1, 1));
+        let mut rng = GameRng::from_seed(11);
+        for _ in 0..100 {
+            let target = ice_spread_target(&board, (1, 0), &mut rng).unwrap();
+            assert!(!board.is_core_position(target.0, target.1));
+            assert!([(0, 0), (2, 0)].contains(&target));
+        }
+    }
+
+    fn setup_spread_app(enabled: bool) -> App {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .insert_resource(bevy::time::TimeUpdateStrategy::ManualDuration(
+                std::time::Duration::from_secs_f32(0.2),
+            ))
+            .insert_resource(spread_board((0, 0)))
+            .insert_resource(IceSpreadConfig { enabled, interval: 0.1, chance: 1.0 })
+            .init_resource::<IceSpreadTimer>()
+            .init_resource::<TimeScale>()
+            .init_resource::<GameSpeed>()
+            .insert_resource(GameRng::from_seed(5))
+            .add_observer(handle_ice_spread)
+            .add_systems(Update, spread_ice_system);
+        app
+    }
+
+    fn ice_cells(app: &App) -> usize {
+        let board = app.world().resource::<PuzzleBoard>();
+        (0..5).flat_map(|y| (0..5).map(move |x| (x, y))).filter(|&(x, y)| board.has_ice(x, y)).count()
+    }
+
+    #[test]
+    fn test_spread_ice_system_freezes_one_neighbor_per_beat() {
+        let mut app = setup_spread_app(true);
+        app.update();
+        app.update();
+
+        assert_eq!(ice_cells(&app), 2);
+        let world = app.world_mut();
+        assert_eq!(world.query::<&IceOverlay>().iter(world).count(), 1, "overlay for the new ice");
+    }
+
+    #[test]
+    fn test_spread_ice_disabled_by_config() {
+        assert!(!IceSpreadConfig::default().enabled, "base game keeps ice static");
+        let mut app = setup_spread_app(false);
+        for _ in 0..5 {
+            app.update();
+        }
+        assert_eq!(ice_cells(&app), 1);
+    }
+}
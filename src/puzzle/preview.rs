@@ -1,5 +1,6 @@
 use crate::prelude::*;
 use super::tile::TileType;
+use rand::Rng;
 use std::collections::VecDeque;
 
 pub const PREVIEW_SIZE: usize = 3;
@@ -11,18 +12,22 @@ pub struct TilePreview {
 
 impl Default for TilePreview {
     fn default() -> Self {
+        Self::new(&mut rand::thread_rng())
+    }
+}
+
+impl TilePreview {
+    pub fn new(rng: &mut impl Rng) -> Self {
         let mut preview = Self {
             queue: VecDeque::with_capacity(PREVIEW_SIZE),
         };
-        preview.fill_queue();
+        preview.fill_queue(rng);
         preview
     }
-}
 
-impl TilePreview {
-    fn fill_queue(&mut self) {
+    fn fill_queue(&mut self, rng: &mut impl Rng) {
         while self.queue.len() < PREVIEW_SIZE {
-            self.queue.push_back(TileType::random());
+            self.queue.push_back(TileType::random(rng));
         }
     }
 
@@ -30,9 +35,12 @@ impl TilePreview {
         self.queue.iter().copied().collect()
     }
 
-    pub fn consume_next(&mut self) -> TileType {
-        let tile = self.queue.pop_front().unwrap_or_else(TileType::random);
-        self.fill_queue();
+    pub fn consume_next(&mut self, rng: &mut impl Rng) -> TileType {
+        let tile = self
+            .queue
+            .pop_front()
+            .unwrap_or_else(|| TileType::random(rng));
+        self.fill_queue(rng);
         tile
     }
 
@@ -61,7 +69,7 @@ mod tests {
         let mut preview = TilePreview::default();
         let initial_first = preview.peek_all()[0];
 
-        let consumed = preview.consume_next();
+        let consumed = preview.consume_next(&mut rand::thread_rng());
         assert_eq!(consumed, initial_first);
         assert_eq!(preview.len(), PREVIEW_SIZE);
     }
@@ -89,7 +97,7 @@ mod tests {
         let mut preview = TilePreview::default();
 
         for _ in 0..10 {
-            preview.consume_next();
+            preview.consume_next(&mut rand::thread_rng());
             assert_eq!(preview.len(), PREVIEW_SIZE);
         }
     }
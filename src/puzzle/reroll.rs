@@ -0,0 +1,203 @@
+//! Board reroll
+//!
+//! During WaveBreak the player can pay `REROLL_COST` gold to deal fresh random colors
+//! to every tile without an obstacle. Rerolled boards pass the same check as a deadlock
+//! reshuffle: no ready-made matches and at least one valid move.
+
+use crate::prelude::*;
+use super::{PuzzleBoard, Tile, TileType, GridPosition};
+use super::match_detector::{TileGrid, empty_tile_grid};
+use super::reshuffle::{MAX_RESHUFFLE_ATTEMPTS, is_playable};
+
+/// Gold spent per reroll
+pub const REROLL_COST: u32 = 5;
+
+/// Request a paid reroll of the board's tile colors
+#[derive(Event, Debug)]
+pub struct RerollBoardEvent;
+
+/// Deal random colors to `cells` until the board is playable.
+/// Returns `false` (grid left mid-roll) if no attempt worked.
+pub fn reroll_grid(
+    grid: &mut TileGrid,
+    cells: &[(usize, usize)],
+    is_blocked: impl Fn(usize, usize) -> bool,
+    rng: &mut impl rand::Rng,
+) -> bool {
+    for _ in 0..MAX_RESHUFFLE_ATTEMPTS {
+        for &(x, y) in cells {
+            grid[y][x] = Some(TileType::random(rng));
+        }
+        if is_playable(grid, &is_blocked) {
+            return true;
+        }
+    }
+    false
+}
+
+/// Charge the reroll and recolor the board. Does nothing outside WaveBreak or when
+/// the player can't afford it; obstacle cells keep their tile and obstacle.
+pub fn handle_reroll_board(
+    _trigger: Trigger<RerollBoardEvent>,
+    phase: Res<State<PhaseState>>,
+    board: Res<PuzzleBoard>,
+    mut gold: ResMut<Gold>,
+    mut rng: ResMut<GameRng>,
+    mut tiles: Query<(&GridPosition, &mut TileType, &mut Sprite), With<Tile>>,
+) {
+    if *phase.get() != PhaseState::WaveBreak || gold.0 < REROLL_COST {
+        return;
+    }
+
+    // Stones can't be matched, so they don't count as colors on the board
+    let mut grid = empty_tile_grid(board.size);
+    for (pos, tile_type, _) in tiles.iter() {
+        if board.in_bounds(pos.x, pos.y) && !board.has_stone(pos.x, pos.y) {
+            grid[pos.y][pos.x] = Some(*tile_type);
+        }
+    }
+
+    let cells: Vec<(usize, usize)> = (0..board.size)
+        .flat_map(|y| (0..board.size).map(move |x| (x, y)))
+        .filter(|&(x, y)| grid[y][x].is_some() && board.get_obstacle(x, y).is_none())
+        .collect();
+
+    let is_blocked = |x: usize, y: usize| board.is_swap_blocked(x, y);
+    if !reroll_grid(&mut grid, &cells, is_blocked, &mut *rng) {
+        warn!("No reroll produced a playable board; keeping the current one");
+        return;
+    }
+    gold.spend(REROLL_COST);
+
+    for (pos, mut tile_type, mut sprite) in tiles.iter_mut() {
+        if !cells.contains(&(pos.x, pos.y)) {
+            continue;
+        }
+        if let Some(new_type) = grid[pos.y][pos.x] {
+            *tile_type = new_type;
+            sprite.color = new_type.color();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::state::app::StatesPlugin;
+    use super::super::match_detector::{find_match_groups, has_any_valid_move_where};
+
+    fn setup_reroll_app(gold: u32) -> App {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, StatesPlugin))
+            .insert_state(PhaseState::WaveBreak)
+            .insert_resource(PuzzleBoard::new(4))
+            .insert_resource(GameRng::from_seed(9))
+            .insert_resource(Gold(gold))
+            .add_observer(handle_reroll_board);
+
+        for y in 0..4 {
+            for x in 0..4 {
+                let tile_type = [TileType::Red, TileType::Blue][(x + y) % 2];
+                let entity = app
+                    .world_mut()
+                    .spawn((Tile, tile_type, GridPosition::new(x, y), Sprite::from_color(tile_type.color(), Vec2::ONE)))
+                    .id();
+                app.world_mut().resource_mut::<PuzzleBoard>().set(x, y, Some(entity));
+            }
+        }
+        app.update();
+        app
+    }
+
+    fn tile_types(app: &mut App) -> Vec<(GridPosition, TileType)> {
+        let world = app.world_mut();
+        let mut tiles: Vec<_> = world
+            .query::<(&GridPosition, &TileType)>()
+            .iter(world)
+            .map(|(pos, tile_type)| (*pos, *tile_type))
+            .collect();
+        tiles.sort_by_key(|(pos, _)| (pos.y, pos.x));
+        tiles
+    }
+
+    fn reroll(app: &mut App) {
+        app.world_mut().trigger(RerollBoardEvent);
+        app.world_mut().flush();
+    }
+
+    #[test]
+    fn test_no_reroll_when_broke() {
+        let mut app = setup_reroll_app(REROLL_COST - 1);
+        let before = tile_types(&mut app);
+
+        reroll(&mut app);
+
+        assert_eq!(tile_types(&mut app), before);
+        assert_eq!(*app.world().resource::<Gold>(), Gold(REROLL_COST - 1));
+    }
+
+    #[test]
+    fn test_reroll_charges_and_deals_playable_board() {
+        let mut app = setup_reroll_app(REROLL_COST + 2);
+        let before = tile_types(&mut app);
+
+        reroll(&mut app);
+
+        let after = tile_types(&mut app);
+        assert_ne!(after, before);
+        assert_eq!(*app.world().resource::<Gold>(), Gold(2));
+
+        let mut grid = empty_tile_grid(4);
+        for (pos, tile_type) in &after {
+            grid[pos.y][pos.x] = Some(*tile_type);
+        }
+        assert!(find_match_groups(&grid).is_empty());
+        assert!(has_any_valid_move_where(&grid, |_, _| false));
+
+        // Sprites follow the new colors
+        let world = app.world_mut();
+        for (tile_type, sprite) in world.query::<(&TileType, &Sprite)>().iter(world) {
+            assert_eq!(sprite.color, tile_type.color());
+        }
+    }
+
+    #[test]
+    fn test_reroll_preserves_obstacles() {
+        let mut app = setup_reroll_app(REROLL_COST * 3);
+        {
+            let mut board = app.world_mut().resource_mut::<PuzzleBoard>();
+            board.set_obstacle(0, 0, Some(ObstacleType::Ice));
+            board.set_obstacle(3, 3, Some(ObstacleType::Stone));
+            board.set_obstacle(1, 2, Some(ObstacleType::Bomb));
+        }
+        let before = tile_types(&mut app);
+
+        for _ in 0..3 {
+            reroll(&mut app);
+        }
+
+        let after = tile_types(&mut app);
+        for cell in [(0, 0), (3, 3), (1, 2)] {
+            let at = |tiles: &[(GridPosition, TileType)]| {
+                tiles.iter().find(|(pos, _)| (pos.x, pos.y) == cell).unwrap().1
+            };
+            assert_eq!(at(&after), at(&before), "obstacle cell {cell:?} kept its tile");
+        }
+        let board = app.world().resource::<PuzzleBoard>();
+        assert!(board.has_ice(0, 0) && board.has_stone(3, 3) && board.has_bomb(1, 2));
+        assert_eq!(*app.world().resource::<Gold>(), Gold(0));
+    }
+
+    #[test]
+    fn test_reroll_only_during_wave_break() {
+        let mut app = setup_reroll_app(REROLL_COST);
+        app.world_mut().resource_mut::<NextState<PhaseState>>().set(PhaseState::Idle);
+        app.update();
+        let before = tile_types(&mut app);
+
+        reroll(&mut app);
+
+        assert_eq!(tile_types(&mut app), before);
+        assert_eq!(*app.world().resource::<Gold>(), Gold(REROLL_COST));
+    }
+}
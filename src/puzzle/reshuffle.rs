@@ -0,0 +1,187 @@
+use crate::prelude::*;
+use rand::seq::SliceRandom;
+use super::{PuzzleBoard, Tile, TileType, GridPosition};
+use super::match_detector::{TileGrid, empty_tile_grid, find_match_groups, has_any_valid_move_where};
+
+/// Give up after this many shuffles (only reachable on boards that are mostly obstacles)
+pub const MAX_RESHUFFLE_ATTEMPTS: usize = 100;
+
+/// Fired after a deadlocked board had its tile colors reshuffled
+#[derive(Event)]
+pub struct BoardReshuffleEvent {
+    pub attempts: usize,
+}
+
+/// No ready-made matches and at least one swap that makes one
+pub(super) fn is_playable(grid: &TileGrid, is_blocked: impl Fn(usize, usize) -> bool) -> bool {
+    find_match_groups(grid).is_empty() && has_any_valid_move_where(grid, is_blocked)
+}
+
+/// Shuffle tile colors among `movable` cells until the board has a valid move and no
+/// ready-made matches. Returns the number of shuffles used, or `None` if none worked.
+pub fn reshuffle_grid(
+    grid: &mut TileGrid,
+    movable: &[(usize, usize)],
+    is_blocked: impl Fn(usize, usize) -> bool,
+    rng: &mut impl rand::Rng,
+) -> Option<usize> {
+    let mut colors: Vec<Option<TileType>> = movable.iter().map(|&(x, y)| grid[y][x]).collect();
+
+    for attempt in 1..=MAX_RESHUFFLE_ATTEMPTS {
+        colors.shuffle(rng);
+        for (&(x, y), color) in movable.iter().zip(&colors) {
+            grid[y][x] = *color;
+        }
+        if is_playable(grid, &is_blocked) {
+            return Some(attempt);
+        }
+    }
+    None
+}
+
+/// When the board settles, reshuffle tile colors in place if no swap can make a match.
+/// Ice and stone cells keep their tile; bombs ride along on their tile entity.
+pub fn reshuffle_if_deadlocked(
+    mut commands: Commands,
+    board: Res<PuzzleBoard>,
+    mut rng: ResMut<GameRng>,
+    mut tiles: Query<(&GridPosition, &mut TileType), With<Tile>>,
+) {
+    if tiles.is_empty() {
+        return;
+    }
+
+    // Stones can't be matched, so they don't count as colors on the board
+    let mut grid = empty_tile_grid(board.size);
+    for (pos, tile_type) in tiles.iter() {
+        if board.in_bounds(pos.x, pos.y) && !board.has_stone(pos.x, pos.y) {
+            grid[pos.y][pos.x] = Some(*tile_type);
+        }
+    }
+
+    let is_blocked = |x: usize, y: usize| board.is_swap_blocked(x, y);
+    if has_any_valid_move_where(&grid, is_blocked) {
+        return;
+    }
+
+    let movable: Vec<(usize, usize)> = (0..board.size)
+        .flat_map(|y| (0..board.size).map(move |x| (x, y)))
+        .filter(|&(x, y)| grid[y][x].is_some() && !is_blocked(x, y))
+        .collect();
+
+    let Some(attempts) = reshuffle_grid(&mut grid, &movable, is_blocked, &mut *rng) else {
+        warn!("Board deadlocked and no reshuffle produced a valid move");
+        return;
+    };
+
+    for (pos, mut tile_type) in tiles.iter_mut() {
+        if let Some(new_type) = grid.get(pos.y).and_then(|row| row.get(pos.x)).copied().flatten() {
+            if *tile_type != new_type {
+                *tile_type = new_type;
+            }
+        }
+    }
+
+    commands.trigger(BoardReshuffleEvent { attempts });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::match_detector::has_any_valid_move;
+    use std::collections::HashMap;
+
+    #[derive(Resource, Default)]
+    struct ReshuffleCount(u32);
+
+    fn deadlocked_type(x: usize, y: usize) -> TileType {
+        [TileType::Red, TileType::Blue, TileType::Green, TileType::Yellow][(x + 2 * y) % 4]
+    }
+
+    fn setup_reshuffle_app() -> App {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .insert_resource(PuzzleBoard::new(4))
+            .insert_resource(GameRng::from_seed(7))
+            .init_resource::<ReshuffleCount>()
+            .add_observer(|_trigger: Trigger<BoardReshuffleEvent>, mut count: ResMut<ReshuffleCount>| {
+                count.0 += 1;
+            })
+            .add_systems(Update, reshuffle_if_deadlocked);
+
+        for y in 0..4 {
+            for x in 0..4 {
+                let entity = app
+                    .world_mut()
+                    .spawn((Tile, deadlocked_type(x, y), GridPosition::new(x, y)))
+                    .id();
+                app.world_mut().resource_mut::<PuzzleBoard>().set(x, y, Some(entity));
+            }
+        }
+        app
+    }
+
+    fn current_grid(app: &mut App) -> TileGrid {
+        let mut grid = empty_tile_grid(4);
+        for (pos, tile_type) in app
+            .world_mut()
+            .query::<(&GridPosition, &TileType)>()
+            .iter(app.world())
+        {
+            grid[pos.y][pos.x] = Some(*tile_type);
+        }
+        grid
+    }
+
+    fn color_counts(grid: &TileGrid) -> HashMap<TileType, usize> {
+        let mut counts = HashMap::new();
+        for tile_type in grid.iter().flatten().flatten() {
+            *counts.entry(*tile_type).or_insert(0) += 1;
+        }
+        counts
+    }
+
+    #[test]
+    fn test_deadlocked_board_is_reshuffled() {
+        let mut app = setup_reshuffle_app();
+        let before = current_grid(&mut app);
+        assert!(!has_any_valid_move(&before));
+
+        app.update();
+
+        let after = current_grid(&mut app);
+        assert!(has_any_valid_move(&after));
+        assert!(find_match_groups(&after).is_empty());
+        assert_eq!(color_counts(&before), color_counts(&after), "reshuffle must keep the same colors");
+        assert_eq!(app.world().resource::<ReshuffleCount>().0, 1);
+    }
+
+    #[test]
+    fn test_reshuffle_keeps_ice_tiles_in_place() {
+        let mut app = setup_reshuffle_app();
+        app.world_mut()
+            .resource_mut::<PuzzleBoard>()
+            .set_obstacle(2, 2, Some(ObstacleType::Ice));
+
+        app.update();
+
+        let after = current_grid(&mut app);
+        assert_eq!(after[2][2], Some(deadlocked_type(2, 2)));
+        let board = app.world().resource::<PuzzleBoard>();
+        assert!(has_any_valid_move_where(&after, |x, y| board.is_swap_blocked(x, y)));
+    }
+
+    #[test]
+    fn test_playable_board_is_left_alone() {
+        let mut app = setup_reshuffle_app();
+        // Blue at (0,1) opens a move
+        let entity = app.world().resource::<PuzzleBoard>().get(0, 1).unwrap();
+        app.world_mut().entity_mut(entity).insert(TileType::Blue);
+        let before = current_grid(&mut app);
+
+        app.update();
+
+        assert_eq!(current_grid(&mut app), before);
+        assert_eq!(app.world().resource::<ReshuffleCount>().0, 0);
+    }
+}
@@ -0,0 +1,151 @@
+//! Colorblind tile symbols
+//!
+//! With `ColorblindMode` on, every tile carries a small shape child so tile types
+//! can be told apart without relying on color. The overlays follow the tiles'
+//! `TileType`, so freshly spawned, refilled and reshuffled tiles pick up the right
+//! shape, and toggling the mode adds or removes them on the tiles already on the board.
+
+use crate::prelude::*;
+use super::tile::Tile;
+
+const SYMBOL_SIZE: f32 = TILE_SIZE * 0.4;
+const SYMBOL_COLOR: Color = Color::srgba(0.0, 0.0, 0.0, 0.5);
+/// Above the tile sprite, below bomb overlays
+const SYMBOL_Z: f32 = 0.3;
+
+#[derive(Resource, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ColorblindMode {
+    pub enabled: bool,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum TileSymbol {
+    Circle,
+    Triangle,
+    Square,
+    Diamond,
+    Hexagon,
+}
+
+impl TileSymbol {
+    fn mesh(self) -> Mesh {
+        let half = SYMBOL_SIZE / 2.0;
+        match self {
+            TileSymbol::Circle => Circle::new(half).into(),
+            TileSymbol::Triangle => RegularPolygon::new(half, 3).into(),
+            TileSymbol::Square => Rectangle::from_length(SYMBOL_SIZE * 0.85).into(),
+            TileSymbol::Diamond => Rhombus::new(SYMBOL_SIZE * 0.8, SYMBOL_SIZE).into(),
+            TileSymbol::Hexagon => RegularPolygon::new(half, 6).into(),
+        }
+    }
+}
+
+/// Shape drawn on tiles of `tile_type`
+pub fn symbol_for(tile_type: TileType) -> TileSymbol {
+    match tile_type {
+        TileType::Red => TileSymbol::Circle,
+        TileType::Blue => TileSymbol::Square,
+        TileType::Green => TileSymbol::Triangle,
+        TileType::Yellow => TileSymbol::Diamond,
+        TileType::Purple => TileSymbol::Hexagon,
+    }
+}
+
+/// Shape child of a tile; records which symbol it draws
+#[derive(Component)]
+pub struct TileSymbolOverlay(pub TileSymbol);
+
+type SymbolTileQuery<'w, 's> =
+    Query<'w, 's, (Entity, Ref<'static, TileType>, Option<&'static Children>), With<Tile>>;
+
+/// Keep overlays in step with `ColorblindMode` and each tile's type
+pub fn sync_tile_symbols(
+    mut commands: Commands,
+    mode: Res<ColorblindMode>,
+    tiles: SymbolTileQuery,
+    overlays: Query<(Entity, &TileSymbolOverlay)>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    if !mode.enabled {
+        if mode.is_changed() {
+            for (overlay, _) in overlays.iter() {
+                commands.entity(overlay).despawn_recursive();
+            }
+        }
+        return;
+    }
+
+    for (tile, tile_type, children) in tiles.iter() {
+        if !mode.is_changed() && !tile_type.is_changed() {
+            continue;
+        }
+        let symbol = symbol_for(*tile_type);
+        let existing = children
+            .into_iter()
+            .flatten()
+            .find_map(|child| overlays.get(*child).ok());
+        match existing {
+            Some((_, overlay)) if overlay.0 == symbol => continue,
+            Some((stale, _)) => commands.entity(stale).despawn_recursive(),
+            None => {}
+        }
+        commands.entity(tile).with_children(|parent| {
+            parent.spawn((
+                TileSymbolOverlay(symbol),
+                Mesh2d(meshes.add(symbol.mesh())),
+                MeshMaterial2d(materials.add(ColorMaterial::from_color(SYMBOL_COLOR))),
+                Transform::from_xyz(0.0, 0.0, SYMBOL_Z),
+            ));
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    const ALL_TYPES: [TileType; 5] = [TileType::Red, TileType::Blue, TileType::Green, TileType::Yellow, TileType::Purple];
+
+    #[test]
+    fn test_each_tile_type_has_a_distinct_symbol() {
+        let symbols: HashSet<TileSymbol> = ALL_TYPES.into_iter().map(symbol_for).collect();
+        assert_eq!(symbols.len(), ALL_TYPES.len());
+    }
+
+    fn overlay_symbols(app: &mut App) -> Vec<TileSymbol> {
+        let world = app.world_mut();
+        world.query::<&TileSymbolOverlay>().iter(world).map(|overlay| overlay.0).collect()
+    }
+
+    #[test]
+    fn test_toggling_mode_adds_and_removes_overlays() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .init_resource::<ColorblindMode>()
+            .init_resource::<Assets<Mesh>>()
+            .init_resource::<Assets<ColorMaterial>>()
+            .add_systems(Update, sync_tile_symbols);
+        let tile = app.world_mut().spawn((Tile, TileType::Green)).id();
+        app.update();
+        assert!(overlay_symbols(&mut app).is_empty(), "off by default");
+
+        app.world_mut().resource_mut::<ColorblindMode>().enabled = true;
+        app.update();
+        assert_eq!(overlay_symbols(&mut app), vec![TileSymbol::Triangle]);
+
+        // A reshuffle changes the type in place; the shape follows it
+        *app.world_mut().get_mut::<TileType>(tile).unwrap() = TileType::Purple;
+        app.update();
+        assert_eq!(overlay_symbols(&mut app), vec![TileSymbol::Hexagon]);
+
+        app.world_mut().spawn((Tile, TileType::Red));
+        app.update();
+        assert_eq!(overlay_symbols(&mut app).len(), 2, "new tiles get a symbol too");
+
+        app.world_mut().resource_mut::<ColorblindMode>().enabled = false;
+        app.update();
+        assert!(overlay_symbols(&mut app).is_empty());
+    }
+}
@@ -1,9 +1,10 @@
+use serde::{Deserialize, Serialize};
 use crate::prelude::*;
 
 #[derive(Component)]
 pub struct Tile;
 
-#[derive(Component, Clone, Copy, PartialEq, Eq, Debug, Hash)]
+#[derive(Component, Clone, Copy, PartialEq, Eq, Debug, Hash, Serialize, Deserialize)]
 pub enum TileType {
     Red,
     Blue,
@@ -13,9 +14,8 @@ pub enum TileType {
 }
 
 impl TileType {
-    pub fn random() -> Self {
-        use rand::Rng;
-        match rand::thread_rng().gen_range(0..5) {
+    pub fn random(rng: &mut impl rand::Rng) -> Self {
+        match rng.gen_range(0..5) {
             0 => TileType::Red,
             1 => TileType::Blue,
             2 => TileType::Green,
@@ -50,6 +50,39 @@ impl GridPosition {
 #[derive(Component)]
 pub struct Matched;
 
+/// Pale blue the frozen tint pulls a tile's color toward
+const FROZEN_TINT: Color = Color::srgb(0.75, 0.9, 1.0);
+const FROZEN_TINT_STRENGTH: f32 = 0.6;
+
+/// Frost on a normal tile. Unlike the ice obstacle the tile still swaps and matches,
+/// but a match only thaws it; once `thaws_remaining` runs out the next match clears it.
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Frozen {
+    pub thaws_remaining: u8,
+}
+
+impl Default for Frozen {
+    /// Cleared by the second match
+    fn default() -> Self {
+        Self { thaws_remaining: 1 }
+    }
+}
+
+impl Frozen {
+    /// Sprite color of a frozen tile of `tile_type`
+    pub fn tint(tile_type: TileType) -> Color {
+        tile_type.color().mix(&FROZEN_TINT, FROZEN_TINT_STRENGTH)
+    }
+}
+
+/// Special tile left behind by a 4+ match; clears its row and column when matched or activated
+#[derive(Component)]
+pub struct PowerTile;
+
+/// Cross-shaped marker drawn on top of a power tile
+#[derive(Component)]
+pub struct PowerTileMarker;
+
 #[derive(Component)]
 pub struct Falling {
     pub target_y: f32,
@@ -58,23 +91,30 @@ pub struct Falling {
 #[derive(Component)]
 pub struct Selected;
 
-#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
 pub enum ObstacleType {
     Ice,
     Bomb,
+    Stone,
 }
 
 #[derive(Component)]
 pub struct Obstacle {
     pub obstacle_type: ObstacleType,
     pub countdown: Option<u8>,
+    /// Number of adjacent-match hits taken (stone only)
+    pub cracks: u8,
 }
 
 impl Obstacle {
+    /// Adjacent-match hits required to break a stone
+    pub const STONE_HITS: u8 = 2;
+
     pub fn ice() -> Self {
         Self {
             obstacle_type: ObstacleType::Ice,
             countdown: None,
+            cracks: 0,
         }
     }
 
@@ -82,9 +122,28 @@ impl Obstacle {
         Self {
             obstacle_type: ObstacleType::Bomb,
             countdown: Some(countdown),
+            cracks: 0,
         }
     }
 
+    pub fn stone() -> Self {
+        Self {
+            obstacle_type: ObstacleType::Stone,
+            countdown: None,
+            cracks: 0,
+        }
+    }
+
+    pub fn is_stone(&self) -> bool {
+        self.obstacle_type == ObstacleType::Stone
+    }
+
+    /// Apply one hit to a stone. Returns true when the stone breaks.
+    pub fn crack(&mut self) -> bool {
+        self.cracks = self.cracks.saturating_add(1);
+        self.cracks >= Self::STONE_HITS
+    }
+
     pub fn is_ice(&self) -> bool {
         self.obstacle_type == ObstacleType::Ice
     }
@@ -0,0 +1,642 @@
+//! Save and resume
+//!
+//! Pressing the save key (`KeyBindings::save`, F5 by default) during a wave break writes
+//! the run to `SAVE_FILE`; the field is empty and the board settled then, so nothing is
+//! caught halfway. Pressing the load key (`KeyBindings::load`, F9 by default) on a settled
+//! board throws away the current units, tiles and obstacles and rebuilds them from
+//! the file. Cooldowns, targets and buffs aren't saved and start fresh, and so does
+//! any swap in flight on the old board. Battle stats, synergies and the combo are
+//! reset as on a new game, and play resumes in the wave break the save was taken in.
+//! A save written by another `SAVE_VERSION`, or for a board size other than
+//! `BoardConfig`'s, is refused rather than half-loaded.
+
+use bevy::ecs::system::SystemParam;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use crate::prelude::*;
+use crate::battle::{
+    Unit, UnitType, StarRank, Team, UnitStats, UnitExperience, HexPosition, BattleGrid, Projectile, SpawnTelegraph, WaveManager, GameResult,
+    BaseHealth, BattleStats, ActiveSynergies, apply_level_ups,
+};
+use crate::bridge::spawn_unit_at;
+use crate::puzzle::{Tile, TileGrid, ObstacleSnapshot, restore_obstacles, spawn_tile, BufferedSwap, PendingSwapCheck, LastSwap, SelectedTile};
+use crate::ui::Score;
+
+/// Where the run is saved, relative to the working directory
+pub const SAVE_FILE: &str = "savegame.json";
+/// Bumped whenever `SaveGame` changes shape
+pub const SAVE_VERSION: u32 = 2;
+
+/// One player unit as saved
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SavedUnit {
+    pub unit_type: TileType,
+    pub star_rank: u8,
+    pub position: HexPosition,
+    pub health: f32,
+    pub xp: f32,
+    pub level: u32,
+}
+
+/// Everything needed to resume a run
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SaveGame {
+    pub version: u32,
+    pub wave_manager: WaveManager,
+    pub game_result: GameResult,
+    pub score: u32,
+    pub gold: u32,
+    pub base_health: BaseHealth,
+    pub units: Vec<SavedUnit>,
+    /// Tile colors, `tiles[y][x]`
+    pub tiles: TileGrid,
+    pub obstacles: ObstacleSnapshot,
+}
+
+/// Only the version, read first so an incompatible save is reported as such
+#[derive(Deserialize)]
+struct SaveHeader {
+    version: u32,
+}
+
+#[derive(Debug)]
+pub enum LoadError {
+    Io(std::io::Error),
+    Parse(serde_json::Error),
+    /// Written by a different `SAVE_VERSION`
+    Version(u32),
+    /// Board doesn't match `BoardConfig::size`, or isn't square
+    BoardSize { found: usize, expected: usize },
+}
+
+impl std::fmt::Display for LoadError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LoadError::Io(err) => write!(f, "{err}"),
+            LoadError::Parse(err) => write!(f, "malformed save: {err}"),
+            LoadError::Version(found) => write!(f, "save version {found}, expected {SAVE_VERSION}"),
+            LoadError::BoardSize { found, expected } => write!(f, "saved board size {found}, expected {expected}"),
+        }
+    }
+}
+
+impl SaveGame {
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("SaveGame always serializes")
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, LoadError> {
+        let header: SaveHeader = serde_json::from_str(json).map_err(LoadError::Parse)?;
+        if header.version != SAVE_VERSION {
+            return Err(LoadError::Version(header.version));
+        }
+        serde_json::from_str(json).map_err(LoadError::Parse)
+    }
+
+    /// A board of another size can't be laid out where every other system expects it
+    pub fn check_board_size(&self, expected: usize) -> Result<(), LoadError> {
+        let found = std::iter::once(self.tiles.len())
+            .chain(self.tiles.iter().map(Vec::len))
+            .find(|&len| len != expected);
+        match found {
+            Some(found) => Err(LoadError::BoardSize { found, expected }),
+            None => Ok(()),
+        }
+    }
+}
+
+pub fn save_game(path: &Path, save: &SaveGame) -> std::io::Result<()> {
+    std::fs::write(path, save.to_json())
+}
+
+pub fn load_game(path: &Path) -> Result<SaveGame, LoadError> {
+    let json = std::fs::read_to_string(path).map_err(LoadError::Io)?;
+    SaveGame::from_json(&json)
+}
+
+/// Save file the keys read and write; tests point it somewhere temporary
+#[derive(Resource, Clone, Debug)]
+pub struct SavePath(pub PathBuf);
+
+impl Default for SavePath {
+    fn default() -> Self {
+        Self(PathBuf::from(SAVE_FILE))
+    }
+}
+
+#[derive(Event)]
+pub struct SaveGameEvent;
+
+#[derive(Event)]
+pub struct LoadGameEvent;
+
+/// Run-wide resources a save captures and a load overwrites
+#[derive(SystemParam)]
+pub struct RunState<'w> {
+    wave_manager: ResMut<'w, WaveManager>,
+    game_result: ResMut<'w, GameResult>,
+    score: ResMut<'w, Score>,
+    gold: ResMut<'w, Gold>,
+    base_health: ResMut<'w, BaseHealth>,
+    grid: ResMut<'w, BattleGrid>,
+    board: ResMut<'w, PuzzleBoard>,
+    /// Not saved; a loaded board has to match it
+    board_config: Res<'w, BoardConfig>,
+}
+
+type SavedUnitQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        &'static UnitType,
+        &'static StarRank,
+        &'static HexPosition,
+        &'static UnitStats,
+        &'static Team,
+        Option<&'static UnitExperience>,
+    ),
+    With<Unit>,
+>;
+
+/// Entities a load replaces. Bombs are children of tiles and go with them.
+type GameEntities = Or<(With<Unit>, With<Projectile>, With<SpawnTelegraph>, With<Tile>, (With<Obstacle>, Without<Parent>))>;
+
+/// Everything of the old run a load throws away: its entities, and the state a save
+/// leaves out, reset as `session::reset_game` would
+#[derive(SystemParam)]
+pub struct OldRun<'w, 's> {
+    entities: Query<'w, 's, Entity, GameEntities>,
+    battle_stats: ResMut<'w, BattleStats>,
+    synergies: ResMut<'w, ActiveSynergies>,
+    combo: ResMut<'w, ComboCounter>,
+    wave_break_timer: ResMut<'w, WaveBreakTimer>,
+    next_phase: ResMut<'w, NextState<PhaseState>>,
+}
+
+impl OldRun<'_, '_> {
+    /// Despawn the old entities and start fresh stats, synergies and combo, back in
+    /// the wave break the save was taken in
+    fn clear(&mut self, commands: &mut Commands) {
+        for entity in self.entities.iter() {
+            commands.entity(entity).despawn_recursive();
+        }
+        self.battle_stats.reset();
+        self.synergies.bonuses.clear();
+        self.synergies.previous.clear();
+        self.combo.reset();
+        self.wave_break_timer.reset();
+        self.next_phase.set(PhaseState::WaveBreak);
+    }
+}
+
+/// Living player units, ordered by position so saves are deterministic
+fn capture_units(units: &SavedUnitQuery) -> Vec<SavedUnit> {
+    let mut saved: Vec<SavedUnit> = units
+        .iter()
+        .filter(|(.., stats, team, _)| **team == Team::Player && !stats.is_dead())
+        .map(|(unit_type, star_rank, pos, stats, _, experience)| {
+            let experience = experience.copied().unwrap_or_default();
+            SavedUnit {
+                unit_type: unit_type.0,
+                star_rank: star_rank.0,
+                position: *pos,
+                health: stats.health,
+                xp: experience.xp,
+                level: experience.level,
+            }
+        })
+        .collect();
+    saved.sort_by_key(|unit| (unit.position.r, unit.position.q));
+    saved
+}
+
+pub fn handle_save_game(
+    _trigger: Trigger<SaveGameEvent>,
+    path: Res<SavePath>,
+    run: RunState,
+    tiles: Query<&TileType, With<Tile>>,
+    obstacles: Query<(&Obstacle, &GridPosition)>,
+    units: SavedUnitQuery,
+) {
+    let save = SaveGame {
+        version: SAVE_VERSION,
+        wave_manager: run.wave_manager.clone(),
+        game_result: run.game_result.clone(),
+        score: run.score.0,
+        gold: run.gold.0,
+        base_health: *run.base_health,
+        units: capture_units(&units),
+        tiles: run.board.tile_type_grid(|entity| tiles.get(entity).ok().copied()),
+        obstacles: ObstacleSnapshot::capture(obstacles.iter()),
+    };
+
+    match save_game(&path.0, &save) {
+        Ok(()) => info!("Saved run to {}", path.0.display()),
+        Err(err) => warn!("Failed to save {}: {}", path.0.display(), err),
+    }
+}
+
+/// Replace the current run with the saved one. A missing, corrupt or incompatible
+/// save, including one for another board size, leaves the run untouched.
+pub fn handle_load_game(
+    _trigger: Trigger<LoadGameEvent>,
+    mut commands: Commands,
+    path: Res<SavePath>,
+    mut run: RunState,
+    mut old_run: OldRun,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    let loaded = load_game(&path.0).and_then(|save| save.check_board_size(run.board_config.size).map(|()| save));
+    let save = match loaded {
+        Ok(save) => save,
+        Err(err) => {
+            warn!("Not loading {}: {}", path.0.display(), err);
+            return;
+        }
+    };
+
+    old_run.clear(&mut commands);
+
+    *run.wave_manager = save.wave_manager;
+    *run.game_result = save.game_result;
+    run.score.0 = save.score;
+    run.gold.0 = save.gold;
+    *run.base_health = save.base_health;
+
+    run.grid.units.clear();
+    for saved in &save.units {
+        let entity = spawn_unit_at(
+            &mut commands,
+            &mut run.grid,
+            saved.unit_type,
+            saved.star_rank,
+            saved.position,
+            Team::Player,
+            &mut meshes,
+            &mut materials,
+        );
+        // Levels compound on top of the star rank's base stats, as they did in battle
+        let experience = UnitExperience { xp: saved.xp, level: saved.level.max(1) };
+        let mut stats = UnitStats::for_type(saved.unit_type, saved.star_rank);
+        apply_level_ups(&mut stats, experience.level - 1);
+        stats.health = saved.health.min(stats.max_health);
+        commands.entity(entity).insert((stats, experience));
+    }
+
+    let mut board = PuzzleBoard::new(save.tiles.len());
+    for (y, row) in save.tiles.iter().enumerate() {
+        for (x, tile_type) in row.iter().enumerate() {
+            if let Some(tile_type) = tile_type {
+                let entity = spawn_tile(&mut commands, &board, *tile_type, x, y);
+                board.set(x, y, Some(entity));
+            }
+        }
+    }
+    restore_obstacles(&mut commands, &mut board, std::iter::empty(), &save.obstacles);
+    *run.board = board;
+    // Swaps picked, buffered or awaiting their match check belong to the old board
+    commands.insert_resource(SelectedTile::default());
+    commands.insert_resource(BufferedSwap::default());
+    commands.insert_resource(PendingSwapCheck::default());
+    commands.insert_resource(LastSwap::default());
+
+    info!("Loaded run from {}", path.0.display());
+}
+
+/// Save only between waves; load on any settled board
+pub fn save_load_input_system(
+    mut commands: Commands,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    bindings: Res<KeyBindings>,
+    phase: Res<State<PhaseState>>,
+) {
+    let phase = phase.get();
+    if keyboard.just_pressed(bindings.save) && *phase == PhaseState::WaveBreak {
+        commands.trigger(SaveGameEvent);
+    }
+    if keyboard.just_pressed(bindings.load) && matches!(phase, PhaseState::Idle | PhaseState::WaveBreak) {
+        commands.trigger(LoadGameEvent);
+    }
+}
+
+pub struct SavePlugin;
+
+impl Plugin for SavePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SavePath>()
+            .add_observer(handle_save_game)
+            .add_observer(handle_load_game)
+            .add_systems(
+                Update,
+                save_load_input_system.run_if(in_state(GameState::Playing)),
+            );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::battle::{WaveModifier, SynergyLevel};
+    use crate::state::WAVE_BREAK_DURATION;
+    use crate::puzzle::ObstacleType;
+
+    fn temp_save_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("puzzle_tactics_save_{}_{}.json", name, std::process::id()))
+    }
+
+    fn setup_save_app(path: PathBuf) -> App {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, bevy::state::app::StatesPlugin))
+            .init_state::<PhaseState>()
+            .insert_resource(SavePath(path))
+            .init_resource::<WaveManager>()
+            .init_resource::<GameResult>()
+            .init_resource::<Score>()
+            .init_resource::<Gold>()
+            .init_resource::<BaseHealth>()
+            .init_resource::<BattleStats>()
+            .init_resource::<ActiveSynergies>()
+            .init_resource::<ComboCounter>()
+            .init_resource::<WaveBreakTimer>()
+            .insert_resource(BattleGrid::new())
+            .insert_resource(PuzzleBoard::new(3))
+            .insert_resource(BoardConfig { size: 3 })
+            .init_resource::<Assets<Mesh>>()
+            .init_resource::<Assets<ColorMaterial>>()
+            .add_observer(handle_save_game)
+            .add_observer(handle_load_game);
+        app.world_mut().flush();
+        app
+    }
+
+    /// Mid-run state: wave 4 between waves, a wounded unit, a full board with ice
+    fn build_run(app: &mut App) {
+        let world = app.world_mut();
+        *world.resource_mut::<WaveManager>() = WaveManager {
+            current_wave: 4,
+            wave_timer: 6.5,
+            modifier: Some(WaveModifier::Swift),
+            ..default()
+        };
+        world.resource_mut::<GameResult>().waves_completed = 4;
+        world.resource_mut::<Score>().0 = 1234;
+        world.resource_mut::<Gold>().0 = 17;
+        world.resource_mut::<BaseHealth>().take_damage(6.0);
+
+        let pos = HexPosition::new(1, -1);
+        let mut stats = UnitStats::for_type(TileType::Red, 2);
+        stats.health = 42.0;
+        let unit = world
+            .spawn((Unit, UnitType(TileType::Red), StarRank(2), pos, stats, Team::Player))
+            .id();
+        world.resource_mut::<BattleGrid>().place_unit(pos, unit);
+
+        let colors = [TileType::Red, TileType::Blue, TileType::Green, TileType::Yellow, TileType::Purple];
+        for y in 0..3 {
+            for x in 0..3 {
+                let tile = world.spawn((Tile, colors[(x + y * 3) % 5], GridPosition::new(x, y))).id();
+                world.resource_mut::<PuzzleBoard>().set(x, y, Some(tile));
+            }
+        }
+        world.resource_mut::<PuzzleBoard>().set_obstacle(2, 1, Some(ObstacleType::Ice));
+        world.spawn((Obstacle::ice(), GridPosition::new(2, 1)));
+    }
+
+    fn tile_grid(app: &App) -> TileGrid {
+        let world = app.world();
+        let board = world.resource::<PuzzleBoard>();
+        board.tile_type_grid(|entity| world.get::<TileType>(entity).copied())
+    }
+
+    fn units(app: &mut App) -> Vec<(TileType, u8, HexPosition, f32)> {
+        let world = app.world_mut();
+        world
+            .query_filtered::<(&UnitType, &StarRank, &HexPosition, &UnitStats), With<Unit>>()
+            .iter(world)
+            .map(|(unit_type, star, pos, stats)| (unit_type.0, star.0, *pos, stats.health))
+            .collect()
+    }
+
+    #[test]
+    fn test_save_and_load_round_trip() {
+        let path = temp_save_path("round_trip");
+        let mut app = setup_save_app(path.clone());
+        build_run(&mut app);
+        let wave_manager = app.world().resource::<WaveManager>().clone();
+        let game_result = app.world().resource::<GameResult>().clone();
+        let base_health = *app.world().resource::<BaseHealth>();
+        let tiles = tile_grid(&app);
+
+        app.world_mut().trigger(SaveGameEvent);
+        app.world_mut().flush();
+
+        // Keep playing a little, then load
+        let world = app.world_mut();
+        world.resource_mut::<WaveManager>().current_wave = 5;
+        world.resource_mut::<Score>().0 = 0;
+        world.resource_mut::<Gold>().0 = 0;
+        world.resource_mut::<BaseHealth>().take_damage(10.0);
+        world.trigger(LoadGameEvent);
+        world.flush();
+        let _ = std::fs::remove_file(&path);
+
+        let world = app.world();
+        assert_eq!(*world.resource::<WaveManager>(), wave_manager);
+        assert_eq!(*world.resource::<GameResult>(), game_result);
+        assert_eq!(world.resource::<Score>().0, 1234);
+        assert_eq!(world.resource::<Gold>().0, 17);
+        assert_eq!(*world.resource::<BaseHealth>(), base_health);
+
+        assert_eq!(units(&mut app), vec![(TileType::Red, 2, HexPosition::new(1, -1), 42.0)], "old unit replaced, not duplicated");
+        let unit = app.world().resource::<BattleGrid>().units.get(&HexPosition::new(1, -1)).copied();
+        assert!(unit.is_some_and(|unit| app.world().get::<Unit>(unit).is_some()), "grid points at the rebuilt unit");
+
+        assert_eq!(tile_grid(&app), tiles);
+        let world = app.world_mut();
+        assert_eq!(world.query::<&Tile>().iter(world).count(), 9);
+        assert!(world.resource::<PuzzleBoard>().has_ice(2, 1));
+        assert_eq!(world.query::<&Obstacle>().iter(world).count(), 1);
+    }
+
+    #[test]
+    fn test_save_and_load_keeps_unit_levels() {
+        let path = temp_save_path("levels");
+        let mut app = setup_save_app(path.clone());
+        build_run(&mut app);
+        let world = app.world_mut();
+        let unit = world.query_filtered::<Entity, With<Unit>>().single(world);
+        let mut stats = UnitStats::for_type(TileType::Red, 2);
+        apply_level_ups(&mut stats, 2);
+        stats.health = 42.0;
+        world.entity_mut(unit).insert((stats.clone(), UnitExperience { xp: 65.0, level: 3 }));
+
+        world.trigger(SaveGameEvent);
+        world.flush();
+        world.trigger(LoadGameEvent);
+        world.flush();
+        let _ = std::fs::remove_file(&path);
+
+        let world = app.world_mut();
+        let (experience, restored) = world
+            .query_filtered::<(&UnitExperience, &UnitStats), With<Unit>>()
+            .single(world);
+        assert_eq!(*experience, UnitExperience { xp: 65.0, level: 3 });
+        assert!((restored.attack - stats.attack).abs() < 1e-3, "level bonuses reapplied");
+        assert!((restored.max_health - stats.max_health).abs() < 1e-3);
+        assert_eq!(restored.health, 42.0);
+    }
+
+    #[test]
+    fn test_load_drops_swaps_from_the_old_board() {
+        let path = temp_save_path("pending_swap");
+        let mut app = setup_save_app(path.clone());
+        build_run(&mut app);
+        app.world_mut().trigger(SaveGameEvent);
+        app.world_mut().flush();
+
+        // F9 right after a swap: its match check and a buffered follow-up are still queued
+        let world = app.world_mut();
+        world.insert_resource(PendingSwapCheck { swap: Some(((0, 0), (1, 0))), reverting: false });
+        world.insert_resource(BufferedSwap(Some(((1, 1), (1, 2)))));
+        world.insert_resource(SelectedTile(Some((2, 2))));
+        world.insert_resource(LastSwap(Some([(0, 0), (1, 0)])));
+        world.trigger(LoadGameEvent);
+        world.flush();
+        let _ = std::fs::remove_file(&path);
+
+        let world = app.world();
+        let check = world.resource::<PendingSwapCheck>();
+        assert_eq!(check.swap, None, "no swap-back onto the restored board");
+        assert!(!check.reverting);
+        assert_eq!(world.resource::<BufferedSwap>().0, None);
+        assert_eq!(world.resource::<SelectedTile>().0, None);
+        assert_eq!(world.resource::<LastSwap>().0, None);
+    }
+
+    #[test]
+    fn test_load_resets_run_state_the_save_leaves_out() {
+        let path = temp_save_path("leftovers");
+        let mut app = setup_save_app(path.clone());
+        build_run(&mut app);
+        app.world_mut().trigger(SaveGameEvent);
+        app.world_mut().flush();
+
+        // Between waves in the old run: stats, a synergy and a combo have built up
+        let world = app.world_mut();
+        world.resource_mut::<NextState<PhaseState>>().set(PhaseState::Idle);
+        world.resource_mut::<BattleStats>().record_ally_kill(TileType::Red, 50.0);
+        world.resource_mut::<ActiveSynergies>().bonuses.insert(TileType::Red, SynergyLevel::Bronze);
+        world.resource_mut::<ComboCounter>().current = 4;
+        world.resource_mut::<WaveBreakTimer>().tick(20.0);
+        app.update();
+
+        app.world_mut().trigger(LoadGameEvent);
+        app.update();
+        let _ = std::fs::remove_file(&path);
+
+        let world = app.world();
+        assert_eq!(*world.resource::<State<PhaseState>>().get(), PhaseState::WaveBreak);
+        assert_eq!(world.resource::<BattleStats>().ally_kills(TileType::Red), 0);
+        assert!(world.resource::<ActiveSynergies>().bonuses.is_empty());
+        assert_eq!(world.resource::<ComboCounter>().current, 0);
+        assert_eq!(world.resource::<WaveBreakTimer>().remaining, WAVE_BREAK_DURATION);
+    }
+
+    #[derive(Resource, Default)]
+    struct Triggered {
+        saves: u32,
+        loads: u32,
+    }
+
+    #[test]
+    fn test_save_and_load_follow_key_bindings() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, bevy::state::app::StatesPlugin))
+            .init_state::<PhaseState>()
+            .init_resource::<ButtonInput<KeyCode>>()
+            .insert_resource(KeyBindings { save: KeyCode::KeyK, load: KeyCode::KeyL, ..default() })
+            .init_resource::<Triggered>()
+            .add_observer(|_: Trigger<SaveGameEvent>, mut triggered: ResMut<Triggered>| triggered.saves += 1)
+            .add_observer(|_: Trigger<LoadGameEvent>, mut triggered: ResMut<Triggered>| triggered.loads += 1)
+            .add_systems(Update, save_load_input_system);
+        app.world_mut().resource_mut::<NextState<PhaseState>>().set(PhaseState::WaveBreak);
+        app.update();
+
+        let mut keyboard = app.world_mut().resource_mut::<ButtonInput<KeyCode>>();
+        keyboard.press(KeyCode::F5);
+        keyboard.press(KeyCode::F9);
+        app.update();
+        assert_eq!(app.world().resource::<Triggered>().saves, 0, "default keys are rebound away");
+        assert_eq!(app.world().resource::<Triggered>().loads, 0);
+
+        let mut keyboard = app.world_mut().resource_mut::<ButtonInput<KeyCode>>();
+        keyboard.clear();
+        keyboard.press(KeyCode::KeyK);
+        keyboard.press(KeyCode::KeyL);
+        app.update();
+        assert_eq!(app.world().resource::<Triggered>().saves, 1);
+        assert_eq!(app.world().resource::<Triggered>().loads, 1);
+    }
+
+    #[test]
+    fn test_version_mismatch_is_refused() {
+        let path = temp_save_path("version");
+        let mut app = setup_save_app(path.clone());
+        build_run(&mut app);
+        app.world_mut().trigger(SaveGameEvent);
+        app.world_mut().flush();
+
+        let json = std::fs::read_to_string(&path).unwrap();
+        let future = json.replacen(&format!("\"version\": {SAVE_VERSION}"), "\"version\": 999", 1);
+        assert!(matches!(SaveGame::from_json(&future), Err(LoadError::Version(999))));
+        assert!(SaveGame::from_json(&json).is_ok());
+
+        std::fs::write(&path, future).unwrap();
+        app.world_mut().resource_mut::<Score>().0 = 5;
+        app.world_mut().trigger(LoadGameEvent);
+        app.world_mut().flush();
+        let _ = std::fs::remove_file(&path);
+
+        assert_eq!(app.world().resource::<Score>().0, 5, "run untouched");
+        assert_eq!(units(&mut app).len(), 1);
+    }
+
+    #[test]
+    fn test_save_for_another_board_size_is_refused() {
+        let path = temp_save_path("board_size");
+        let mut app = setup_save_app(path.clone());
+        build_run(&mut app);
+        app.world_mut().trigger(SaveGameEvent);
+        app.world_mut().flush();
+        let save = load_game(&path).unwrap();
+        assert!(save.check_board_size(3).is_ok());
+        assert!(matches!(save.check_board_size(4), Err(LoadError::BoardSize { found: 3, expected: 4 })));
+
+        let mut ragged = save.clone();
+        ragged.tiles[1].pop();
+        assert!(matches!(ragged.check_board_size(3), Err(LoadError::BoardSize { found: 2, expected: 3 })));
+
+        app.world_mut().insert_resource(BoardConfig { size: 4 });
+        app.world_mut().resource_mut::<Score>().0 = 5;
+        app.world_mut().trigger(LoadGameEvent);
+        app.world_mut().flush();
+        let _ = std::fs::remove_file(&path);
+
+        assert_eq!(app.world().resource::<Score>().0, 5, "run untouched");
+        assert_eq!(app.world().resource::<PuzzleBoard>().size, 3);
+        assert_eq!(tile_grid(&app).iter().flatten().flatten().count(), 9);
+    }
+
+    #[test]
+    fn test_missing_save_leaves_run_untouched() {
+        let mut app = setup_save_app(temp_save_path("missing"));
+        build_run(&mut app);
+
+        app.world_mut().trigger(LoadGameEvent);
+        app.world_mut().flush();
+
+        assert_eq!(app.world().resource::<WaveManager>().current_wave, 4);
+        assert_eq!(tile_grid(&app).iter().flatten().flatten().count(), 9);
+    }
+}
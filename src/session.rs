@@ -0,0 +1,287 @@
+//! Game session lifecycle
+//!
+//! Entering `GameState::Loading` (startup or "Quit to Title") tears down the
+//! previous run and then hands control to the title screen. "Retry" on the game-over
+//! screen runs the same teardown when leaving `GameState::GameOver` and goes straight
+//! back to `GameState::Playing`, where a fresh board is dealt.
+
+use bevy::ecs::schedule::SystemConfigs;
+use crate::prelude::*;
+use crate::battle::{
+    Unit, Projectile, BattleGrid, WaveManager, GameResult, BaseHealth, BattleStats, ActiveSynergies, UnitDrag, BombCountdownTimer,
+    DamagePopup, AttackLine, BombExplosionEffect, MovementHighlight, AttackRangeHighlight, SpawnTelegraph, DragGhost, UnitTooltip,
+};
+use crate::bridge::CascadeClearCount;
+use crate::puzzle::{Tile, MatchedRuns, IdleHintTimer, MatchEnergy, ComboTimer, BufferedSwap, PendingSwapCheck, PuzzleCursor, PuzzleCursorHighlight, IceSpreadTimer, CascadeState, LastSwap, SelectedTile, BombDefuseEffect};
+use crate::ui::{Score, HudRoot, GameOverScreen};
+
+/// Free-standing visuals that belong to a run: HUD, popups, effects, overlays
+type TransientEntities = Or<(
+    With<HudRoot>,
+    With<DamagePopup>,
+    With<AttackLine>,
+    With<BombExplosionEffect>,
+    With<BombDefuseEffect>,
+    With<PuzzleCursorHighlight>,
+    With<MovementHighlight>,
+    With<AttackRangeHighlight>,
+    With<SpawnTelegraph>,
+    With<DragGhost>,
+    With<UnitTooltip>,
+    With<GameOverScreen>,
+)>;
+
+/// Despawn all units, projectiles, tiles and obstacle overlays from the previous run
+pub fn despawn_game_entities(
+    mut commands: Commands,
+    units: Query<Entity, With<Unit>>,
+    projectiles: Query<Entity, With<Projectile>>,
+    tiles: Query<Entity, With<Tile>>,
+    // Bombs are children of tiles and go with them; only free-standing overlays here
+    obstacles: Query<Entity, (With<Obstacle>, Without<Parent>)>,
+) {
+    for entity in units.iter().chain(projectiles.iter()).chain(tiles.iter()).chain(obstacles.iter()) {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+/// Despawn HUD, popups and effects that only make sense inside a run
+pub fn despawn_transient_entities(mut commands: Commands, transient: Query<Entity, TransientEntities>) {
+    for entity in transient.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+/// Reset battle-side resources back to a fresh game
+pub fn reset_battle_resources(
+    mut grid: ResMut<BattleGrid>,
+    mut wave_manager: ResMut<WaveManager>,
+    mut game_result: ResMut<GameResult>,
+    mut base_health: ResMut<BaseHealth>,
+    mut gold: ResMut<Gold>,
+    mut battle_stats: ResMut<BattleStats>,
+    mut synergies: ResMut<ActiveSynergies>,
+    mut drag: ResMut<UnitDrag>,
+) {
+    grid.units.clear();
+    *wave_manager = WaveManager::default();
+    *game_result = GameResult::default();
+    *base_health = BaseHealth::default();
+    *gold = Gold::default();
+    battle_stats.reset();
+    synergies.bonuses.clear();
+    synergies.previous.clear();
+    drag.dragging = None;
+}
+
+/// Reset run-wide timers: slow motion, the wave break clock and the burst counter
+pub fn reset_session_resources(
+    mut time_scale: ResMut<TimeScale>,
+    mut wave_break_timer: ResMut<WaveBreakTimer>,
+    mut cascade_clears: ResMut<CascadeClearCount>,
+) {
+    *time_scale = TimeScale::default();
+    *wave_break_timer = WaveBreakTimer::default();
+    *cascade_clears = CascadeClearCount::default();
+}
+
+/// Reset puzzle-side resources and phase back to a fresh game
+pub fn reset_puzzle_resources(
+    mut commands: Commands,
+    board_config: Res<BoardConfig>,
+    mut combo: ResMut<ComboCounter>,
+    mut cascade_state: ResMut<CascadeState>,
+    mut last_swap: ResMut<LastSwap>,
+    mut selected_tile: ResMut<SelectedTile>,
+    mut next_phase: ResMut<NextState<PhaseState>>,
+) {
+    commands.insert_resource(PuzzleBoard::from_config(&board_config));
+    commands.insert_resource(Score::default());
+    commands.insert_resource(BufferedSwap::default());
+    commands.insert_resource(PendingSwapCheck::default());
+    commands.insert_resource(IceSpreadTimer::default());
+    commands.insert_resource(PuzzleCursor::default());
+    commands.insert_resource(MatchEnergy::default());
+    commands.insert_resource(ComboTimer::default());
+    commands.insert_resource(MatchedRuns::default());
+    commands.insert_resource(IdleHintTimer::default());
+    commands.insert_resource(BombCountdownTimer::default());
+    combo.reset();
+    *cascade_state = CascadeState::default();
+    last_swap.0 = None;
+    selected_tile.0 = None;
+    next_phase.set(PhaseState::Idle);
+}
+
+/// Full teardown of the previous run: entities first, then battle and puzzle state
+pub fn reset_game() -> SystemConfigs {
+    (
+        despawn_game_entities,
+        despawn_transient_entities,
+        reset_battle_resources,
+        reset_puzzle_resources,
+        reset_session_resources,
+    )
+        .chain()
+}
+
+/// Move on to the title screen once teardown is done
+pub fn enter_title(mut next_state: ResMut<NextState<GameState>>) {
+    next_state.set(GameState::Title);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::battle::HexPosition;
+    use bevy::state::app::StatesPlugin;
+
+    fn setup_session_app() -> App {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .add_plugins(StatesPlugin)
+            .init_state::<GameState>()
+            .init_state::<PhaseState>()
+            .init_resource::<BoardConfig>()
+            .init_resource::<BattleGrid>()
+            .init_resource::<WaveManager>()
+            .init_resource::<GameResult>()
+            .init_resource::<BaseHealth>()
+            .init_resource::<Gold>()
+            .init_resource::<BattleStats>()
+            .init_resource::<ComboCounter>()
+            .init_resource::<CascadeState>()
+            .init_resource::<LastSwap>()
+            .init_resource::<SelectedTile>()
+            .init_resource::<ActiveSynergies>()
+            .init_resource::<UnitDrag>()
+            .init_resource::<TimeScale>()
+            .init_resource::<WaveBreakTimer>()
+            .init_resource::<CascadeClearCount>()
+            .add_systems(OnEnter(GameState::Loading), (reset_game(), enter_title).chain())
+            .add_systems(OnExit(GameState::GameOver), reset_game());
+        app.update(); // Startup Loading -> Title
+        app
+    }
+
+    fn set_state(app: &mut App, state: GameState) {
+        app.world_mut().resource_mut::<NextState<GameState>>().set(state);
+        app.update();
+    }
+
+    #[test]
+    fn test_startup_lands_on_title() {
+        let app = setup_session_app();
+
+        let state = app.world().resource::<State<GameState>>();
+        assert_eq!(*state.get(), GameState::Title);
+    }
+
+    #[test]
+    fn test_quit_to_title_cleans_up_previous_run() {
+        let mut app = setup_session_app();
+
+        app.world_mut()
+            .resource_mut::<NextState<GameState>>()
+            .set(GameState::Playing);
+        app.update();
+
+        // Simulate a run in progress
+        let unit = app
+            .world_mut()
+            .spawn(Unit)
+            .id();
+        app.world_mut()
+            .resource_mut::<BattleGrid>()
+            .place_unit(HexPosition::new(0, 0), unit);
+        app.world_mut().resource_mut::<WaveManager>().current_wave = 5;
+        app.world_mut().resource_mut::<ComboCounter>().current = 3;
+
+        // Pause menu "Quit to Title" targets Loading
+        app.world_mut()
+            .resource_mut::<NextState<GameState>>()
+            .set(GameState::Loading);
+        app.update(); // Enter Loading, teardown runs
+        app.update(); // Loading -> Title
+
+        let state = app.world().resource::<State<GameState>>();
+        assert_eq!(*state.get(), GameState::Title);
+        assert!(app.world().get_entity(unit).is_err());
+        assert!(app.world().resource::<BattleGrid>().units.is_empty());
+        assert_eq!(app.world().resource::<WaveManager>().current_wave, 0);
+        assert_eq!(app.world().resource::<ComboCounter>().current, 0);
+    }
+
+    #[test]
+    fn test_retry_resets_wave_manager_and_game_result() {
+        let mut app = setup_session_app();
+        set_state(&mut app, GameState::Playing);
+
+        let unit = app.world_mut().spawn(Unit).id();
+        {
+            let mut wave_manager = app.world_mut().resource_mut::<WaveManager>();
+            wave_manager.current_wave = 7;
+            wave_manager.wave_active = true;
+        }
+        {
+            let mut game_result = app.world_mut().resource_mut::<GameResult>();
+            game_result.game_ended = true;
+            game_result.waves_completed = 6;
+            game_result.player_had_units = true;
+        }
+        app.world_mut().resource_mut::<BaseHealth>().take_damage(15.0);
+        app.world_mut().resource_mut::<Gold>().earn(12);
+        app.insert_resource(BombCountdownTimer { timer: 0.7 })
+            .insert_resource(MatchedRuns(vec![(TileType::Red, vec![(0, 0), (1, 0), (2, 0)])]))
+            .insert_resource(IdleHintTimer { idle: 3.0, ..default() });
+        set_state(&mut app, GameState::GameOver);
+
+        // Retry button
+        set_state(&mut app, GameState::Playing);
+
+        assert_eq!(*app.world().resource::<State<GameState>>().get(), GameState::Playing);
+        assert!(app.world().get_entity(unit).is_err());
+        let wave_manager = app.world().resource::<WaveManager>();
+        let fresh = WaveManager::default();
+        assert_eq!(wave_manager.current_wave, fresh.current_wave);
+        assert_eq!(wave_manager.enemies_remaining, fresh.enemies_remaining);
+        assert_eq!(wave_manager.wave_timer, fresh.wave_timer);
+        assert_eq!(wave_manager.wave_active, fresh.wave_active);
+        let game_result = app.world().resource::<GameResult>();
+        assert!(!game_result.game_ended);
+        assert!(!game_result.victory);
+        assert_eq!(game_result.waves_completed, 0);
+        assert!(!game_result.player_had_units);
+        assert_eq!(app.world().resource::<BaseHealth>().current, BaseHealth::default().max);
+        assert_eq!(*app.world().resource::<Gold>(), Gold(0));
+        assert_eq!(app.world().resource::<Score>().0, 0);
+        assert_eq!(app.world().resource::<BombCountdownTimer>().timer, 0.0, "bomb countdown starts over");
+        assert!(app.world().resource::<MatchedRuns>().0.is_empty());
+        assert_eq!(app.world().resource::<IdleHintTimer>().idle, 0.0);
+    }
+
+    #[test]
+    fn test_quit_to_title_despawns_tiles_hud_and_effects() {
+        let mut app = setup_session_app();
+        set_state(&mut app, GameState::Playing);
+
+        let world = app.world_mut();
+        let tile = world.spawn((Tile, GridPosition::new(0, 0))).id();
+        let bomb = world.spawn(Obstacle::bomb(3)).set_parent(tile).id();
+        let ice = world.spawn((Obstacle::ice(), GridPosition::new(1, 1))).id();
+        let hud = world.spawn((Node::default(), HudRoot)).with_child(Text::new("Score: 10")).id();
+        let popup = world.spawn(DamagePopup { timer: Timer::from_seconds(1.0, TimerMode::Once), start_pos: Vec3::ZERO }).id();
+        let line = world.spawn(AttackLine { timer: Timer::from_seconds(0.1, TimerMode::Once) }).id();
+        let camera = world.spawn(Camera2d).id();
+        world.resource_mut::<TimeScale>().scale = 0.3;
+
+        set_state(&mut app, GameState::Loading);
+        app.update(); // Loading -> Title
+
+        for entity in [tile, bomb, ice, hud, popup, line] {
+            assert!(app.world().get_entity(entity).is_err(), "{:?} survived the quit", entity);
+        }
+        assert!(app.world().get_entity(camera).is_ok(), "non-gameplay entities stay");
+        assert_eq!(app.world().resource::<TimeScale>().scale, 1.0);
+    }
+}
@@ -1,9 +1,11 @@
 use bevy::prelude::*;
+use bevy::ecs::system::SystemParam;
 
 #[derive(States, Default, Clone, Eq, PartialEq, Hash, Debug)]
 pub enum GameState {
     #[default]
     Loading,
+    Title,
     Playing,
     Paused,
     GameOver,
@@ -19,6 +21,97 @@ pub enum PhaseState {
     WaveBreak,
 }
 
+/// Game mode chosen on the title screen
+#[derive(Resource, Default, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum GameMode {
+    /// Survive 10 waves to win
+    #[default]
+    Standard,
+    /// Waves continue until defeat
+    Endless,
+}
+
+impl GameMode {
+    pub fn label(&self) -> &'static str {
+        match self {
+            GameMode::Standard => "Standard",
+            GameMode::Endless => "Endless",
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        match self {
+            GameMode::Standard => GameMode::Endless,
+            GameMode::Endless => GameMode::Standard,
+        }
+    }
+
+    pub fn has_victory_wave(&self) -> bool {
+        *self == GameMode::Standard
+    }
+}
+
+/// Difficulty chosen on the title screen; scales enemy stats, wave sizes and obstacle rates
+#[derive(Resource, Default, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Difficulty {
+    Easy,
+    #[default]
+    Normal,
+    Hard,
+}
+
+impl Difficulty {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Difficulty::Easy => "Easy",
+            Difficulty::Normal => "Normal",
+            Difficulty::Hard => "Hard",
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        match self {
+            Difficulty::Easy => Difficulty::Normal,
+            Difficulty::Normal => Difficulty::Hard,
+            Difficulty::Hard => Difficulty::Easy,
+        }
+    }
+
+    pub fn enemy_health_multiplier(&self) -> f32 {
+        match self {
+            Difficulty::Easy => 0.75,
+            Difficulty::Normal => 1.0,
+            Difficulty::Hard => 1.4,
+        }
+    }
+
+    pub fn enemy_attack_multiplier(&self) -> f32 {
+        match self {
+            Difficulty::Easy => 0.8,
+            Difficulty::Normal => 1.0,
+            Difficulty::Hard => 1.25,
+        }
+    }
+
+    /// Enemies in a regular wave: a base count plus a per-wave increase, capped
+    pub fn enemies_for_wave(&self, wave: u32) -> u32 {
+        match self {
+            Difficulty::Easy => (2 + wave).min(8),
+            Difficulty::Normal => (3 + wave * 2).min(12),
+            Difficulty::Hard => (4 + wave * 2).min(14),
+        }
+    }
+
+    /// Multiplier on the chance that an enemy attack drops an obstacle on the board
+    pub fn obstacle_chance_multiplier(&self) -> f32 {
+        match self {
+            Difficulty::Easy => 0.5,
+            Difficulty::Normal => 1.0,
+            Difficulty::Hard => 1.5,
+        }
+    }
+}
+
 #[derive(Resource, Default)]
 pub struct ComboCounter {
     pub current: u32,
@@ -39,6 +132,29 @@ impl ComboCounter {
     }
 }
 
+// ============================================================
+// Gold (Run Currency)
+// ============================================================
+
+/// Currency for the current run; kept across waves, reset on a new game
+#[derive(Resource, Default, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Gold(pub u32);
+
+impl Gold {
+    pub fn earn(&mut self, amount: u32) {
+        self.0 = self.0.saturating_add(amount);
+    }
+
+    /// Pay `amount` if affordable; gold is untouched otherwise
+    pub fn spend(&mut self, amount: u32) -> bool {
+        if self.0 < amount {
+            return false;
+        }
+        self.0 -= amount;
+        true
+    }
+}
+
 // ============================================================
 // Wave Break Timer (Unit Repositioning Phase)
 // ============================================================
@@ -129,6 +245,51 @@ impl TimeScale {
     }
 }
 
+/// Speeds the player can fast-forward through with the `toggle_speed` binding
+pub const GAME_SPEEDS: [f32; 3] = [1.0, 2.0, 3.0];
+
+/// Player-chosen fast-forward. Unlike `TimeScale` it never expires, and it carries
+/// over between runs.
+#[derive(Resource, Clone, Copy, Debug, PartialEq)]
+pub struct GameSpeed(pub f32);
+
+impl Default for GameSpeed {
+    fn default() -> Self {
+        Self(GAME_SPEEDS[0])
+    }
+}
+
+impl GameSpeed {
+    /// The next speed in `GAME_SPEEDS`, wrapping back to 1x
+    pub fn next(&self) -> Self {
+        let index = GAME_SPEEDS.iter().position(|speed| *speed == self.0).map_or(0, |i| i + 1);
+        Self(GAME_SPEEDS[index % GAME_SPEEDS.len()])
+    }
+
+    pub fn label(&self) -> String {
+        format!("{}x", self.0)
+    }
+}
+
+/// Frame delta with `TimeScale` and `GameSpeed` applied, so slow-mo and fast-forward
+/// compose. Gameplay timers tick on this; UI countdowns keep reading `Time` directly.
+#[derive(SystemParam)]
+pub struct ScaledTime<'w> {
+    time: Res<'w, Time>,
+    time_scale: Res<'w, TimeScale>,
+    game_speed: Res<'w, GameSpeed>,
+}
+
+impl ScaledTime<'_> {
+    pub fn delta_secs(&self) -> f32 {
+        self.time_scale.scaled_delta(self.time.delta_secs()) * self.game_speed.0
+    }
+
+    pub fn delta(&self) -> std::time::Duration {
+        std::time::Duration::from_secs_f32(self.delta_secs())
+    }
+}
+
 /// Event to trigger slow motion effect
 #[derive(Event)]
 pub struct SlowMoEvent {
@@ -136,14 +297,70 @@ pub struct SlowMoEvent {
     pub duration: f32,
 }
 
+/// Run condition: the pause menu is up, so nothing in the run may advance.
+/// Gameplay systems already stop outside `GameState::Playing`; this covers the
+/// ungated time-scale machinery.
+pub fn simulation_paused(state: Option<Res<State<GameState>>>) -> bool {
+    state.is_some_and(|state| *state.get() == GameState::Paused)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_gold_spend_only_when_affordable() {
+        let mut gold = Gold(5);
+        assert!(!gold.spend(6));
+        assert_eq!(gold, Gold(5));
+        assert!(gold.spend(5));
+        assert_eq!(gold, Gold(0));
+        gold.earn(3);
+        assert_eq!(gold, Gold(3));
+    }
+
     // ============================================================
     // WaveBreak State Tests
     // ============================================================
 
+    #[test]
+    fn test_game_mode_cycles() {
+        assert_eq!(GameMode::default(), GameMode::Standard);
+        assert_eq!(GameMode::Standard.next(), GameMode::Endless);
+        assert_eq!(GameMode::Endless.next(), GameMode::Standard);
+        assert!(!GameMode::Endless.has_victory_wave());
+    }
+
+    #[test]
+    fn test_difficulty_cycles() {
+        assert_eq!(Difficulty::default(), Difficulty::Normal);
+        assert_eq!(Difficulty::Normal.next(), Difficulty::Hard);
+        assert_eq!(Difficulty::Hard.next(), Difficulty::Easy);
+        assert_eq!(Difficulty::Easy.next(), Difficulty::Normal);
+    }
+
+    #[test]
+    fn test_difficulty_enemy_count_per_wave() {
+        assert_eq!(Difficulty::Easy.enemies_for_wave(1), 3);
+        assert_eq!(Difficulty::Normal.enemies_for_wave(1), 5);
+        assert_eq!(Difficulty::Hard.enemies_for_wave(1), 6);
+
+        assert_eq!(Difficulty::Easy.enemies_for_wave(20), 8);
+        assert_eq!(Difficulty::Normal.enemies_for_wave(20), 12);
+        assert_eq!(Difficulty::Hard.enemies_for_wave(20), 14);
+    }
+
+    #[test]
+    fn test_difficulty_multipliers_are_ordered() {
+        let [easy, normal, hard] = [Difficulty::Easy, Difficulty::Normal, Difficulty::Hard];
+        assert_eq!(normal.enemy_health_multiplier(), 1.0);
+        assert_eq!(normal.enemy_attack_multiplier(), 1.0);
+        assert_eq!(normal.obstacle_chance_multiplier(), 1.0);
+        assert!(easy.enemy_health_multiplier() < 1.0 && hard.enemy_health_multiplier() > 1.0);
+        assert!(easy.enemy_attack_multiplier() < 1.0 && hard.enemy_attack_multiplier() > 1.0);
+        assert!(easy.obstacle_chance_multiplier() < 1.0 && hard.obstacle_chance_multiplier() > 1.0);
+    }
+
     #[test]
     fn test_phase_state_has_wave_break_variant() {
         // WaveBreak should be a valid PhaseState variant
@@ -219,4 +436,14 @@ mod tests {
         let scaled = ts.scaled_delta(delta);
         assert!((scaled - 0.008).abs() < f32::EPSILON, "Scaled delta should be half");
     }
+
+    #[test]
+    fn test_game_speed_cycles_and_wraps() {
+        let speed = GameSpeed::default();
+        assert_eq!(speed, GameSpeed(1.0));
+        assert_eq!(speed.next(), GameSpeed(2.0));
+        assert_eq!(speed.next().next(), GameSpeed(3.0));
+        assert_eq!(speed.next().next().next(), GameSpeed(1.0));
+        assert_eq!(GameSpeed(3.0).label(), "3x");
+    }
 }
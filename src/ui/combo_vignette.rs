@@ -0,0 +1,131 @@
+//! Combo vignette
+//!
+//! Full-screen overlay that glows at the screen edges as the combo climbs:
+//! faint on a 2-chain, a strong flash on big cascades, fading out once the combo resets.
+
+use crate::prelude::*;
+use bevy::ui::FocusPolicy;
+
+/// Combo at which the vignette reaches full intensity
+const FULL_INTENSITY_COMBO: u32 = 8;
+/// Edge alpha at full intensity
+const MAX_VIGNETTE_ALPHA: f32 = 0.45;
+/// Edge alpha cap when reduced flashing is on
+const REDUCED_VIGNETTE_ALPHA: f32 = 0.1;
+/// Center fill is a fraction of the edge alpha so the board stays readable
+const FLASH_FILL_RATIO: f32 = 0.25;
+/// Intensity change per second while rising toward / fading from the target
+const RISE_RATE: f32 = 6.0;
+const FADE_RATE: f32 = 1.5;
+const VIGNETTE_BORDER: f32 = 48.0;
+const VIGNETTE_COLOR: (f32, f32, f32) = (1.0, 0.55, 0.15);
+
+/// Accessibility options
+#[derive(Resource, Default, Clone, Copy, Debug)]
+pub struct AccessibilitySettings {
+    /// Keep screen flashes faint regardless of combo size
+    pub reduced_flashing: bool,
+}
+
+/// Overlay node; `intensity` is the displayed value easing toward the combo target
+#[derive(Component, Default)]
+pub struct ComboVignette {
+    pub intensity: f32,
+}
+
+/// Target intensity (0..=1) for a combo count. Single matches show nothing;
+/// the curve is quadratic so small chains stay subtle and big cascades stand out.
+pub fn combo_vignette_intensity(combo: u32) -> f32 {
+    if combo < 2 {
+        return 0.0;
+    }
+    let t = ((combo - 1) as f32 / (FULL_INTENSITY_COMBO - 1) as f32).min(1.0);
+    t * t
+}
+
+/// Edge alpha for an intensity, clamped when reduced flashing is on
+pub fn vignette_alpha(intensity: f32, reduced_flashing: bool) -> f32 {
+    let max_alpha = if reduced_flashing { REDUCED_VIGNETTE_ALPHA } else { MAX_VIGNETTE_ALPHA };
+    intensity.clamp(0.0, 1.0) * max_alpha
+}
+
+/// Move `current` toward `target`: quickly when rising, slowly when fading
+pub fn ease_intensity(current: f32, target: f32, delta: f32) -> f32 {
+    if target > current {
+        (current + RISE_RATE * delta).min(target)
+    } else {
+        (current - FADE_RATE * delta).max(target)
+    }
+}
+
+pub fn spawn_combo_vignette(mut commands: Commands) {
+    commands.spawn((
+        Node {
+            position_type: PositionType::Absolute,
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            border: UiRect::all(Val::Px(VIGNETTE_BORDER)),
+            ..default()
+        },
+        BorderColor(Color::NONE),
+        BackgroundColor(Color::NONE),
+        // Purely visual: never swallow clicks meant for buttons or the board
+        FocusPolicy::Pass,
+        PickingBehavior::IGNORE,
+        ComboVignette::default(),
+    ));
+}
+
+pub fn update_combo_vignette(
+    time: Res<Time>,
+    combo: Res<ComboCounter>,
+    settings: Res<AccessibilitySettings>,
+    mut vignettes: Query<(&mut ComboVignette, &mut BorderColor, &mut BackgroundColor)>,
+) {
+    let target = combo_vignette_intensity(combo.current);
+    let (r, g, b) = VIGNETTE_COLOR;
+
+    for (mut vignette, mut border, mut background) in vignettes.iter_mut() {
+        vignette.intensity = ease_intensity(vignette.intensity, target, time.delta_secs());
+        let alpha = vignette_alpha(vignette.intensity, settings.reduced_flashing);
+        border.0 = Color::srgba(r, g, b, alpha);
+        background.0 = Color::srgba(r, g, b, alpha * FLASH_FILL_RATIO);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_no_vignette_below_two_combo() {
+        assert_eq!(combo_vignette_intensity(0), 0.0);
+        assert_eq!(combo_vignette_intensity(1), 0.0);
+        assert!(combo_vignette_intensity(2) > 0.0);
+    }
+
+    #[test]
+    fn test_intensity_grows_with_combo_and_peaks() {
+        let values: Vec<f32> = (2..=FULL_INTENSITY_COMBO).map(combo_vignette_intensity).collect();
+        assert!(values.windows(2).all(|w| w[1] > w[0]), "strictly increasing: {:?}", values);
+        assert!(combo_vignette_intensity(2) < 0.1, "subtle at low combos");
+        assert_eq!(combo_vignette_intensity(FULL_INTENSITY_COMBO), 1.0);
+        assert_eq!(combo_vignette_intensity(FULL_INTENSITY_COMBO + 10), 1.0);
+    }
+
+    #[test]
+    fn test_reduced_flashing_clamps_alpha() {
+        assert_eq!(vignette_alpha(1.0, false), MAX_VIGNETTE_ALPHA);
+        assert_eq!(vignette_alpha(1.0, true), REDUCED_VIGNETTE_ALPHA);
+        assert!(vignette_alpha(0.5, true) <= REDUCED_VIGNETTE_ALPHA);
+        assert_eq!(vignette_alpha(0.0, true), 0.0);
+    }
+
+    #[test]
+    fn test_ease_rises_fast_and_fades_slowly() {
+        assert_eq!(ease_intensity(0.0, 1.0, 1.0), 1.0);
+        let faded = ease_intensity(1.0, 0.0, 0.1);
+        assert!((faded - (1.0 - FADE_RATE * 0.1)).abs() < 1e-5);
+        assert_eq!(ease_intensity(0.1, 0.0, 1.0), 0.0, "never overshoots the target");
+    }
+}
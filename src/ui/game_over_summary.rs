@@ -1,36 +1,70 @@
 use crate::prelude::*;
 use crate::battle::{BattleStats, GameResult};
+use super::hud::GameOverScreen;
 
 #[derive(Component)]
 pub struct GameOverSummary;
 
+/// Summary rows for the stats that were actually recorded this run.
+/// Enemy and MVP rows are skipped while their record is still empty.
+fn summary_lines(battle_stats: &BattleStats) -> Vec<(String, Color)> {
+    let mut lines = Vec::new();
+
+    let enemy = &battle_stats.most_dangerous_enemy;
+    if enemy.unit_type.is_some() {
+        lines.push((
+            format!(
+                "Most Dangerous Enemy: {} (dealt {} damage)",
+                BattleStats::unit_type_name(enemy.unit_type),
+                enemy.total_damage as i32
+            ),
+            Color::srgb(0.9, 0.3, 0.3),
+        ));
+    }
+
+    let mvp = &battle_stats.mvp_ally;
+    if mvp.unit_type.is_some() {
+        lines.push((
+            format!(
+                "MVP Ally: {} ({} kills, {} damage)",
+                BattleStats::unit_type_name(mvp.unit_type),
+                mvp.kills,
+                mvp.damage_dealt as i32
+            ),
+            Color::srgb(0.3, 0.9, 0.3),
+        ));
+    }
+
+    if lines.is_empty() && battle_stats.total_matches == 0 {
+        lines.push(("No battle data recorded".to_string(), Color::srgb(0.7, 0.7, 0.7)));
+        return lines;
+    }
+
+    lines.push((format!("Total Matches: {}", battle_stats.total_matches), Color::WHITE));
+    lines.push((format!("Max Combo: {}", battle_stats.max_combo), Color::srgb(1.0, 0.8, 0.0)));
+    lines
+}
+
+/// Appends the battle summary below the game-over screen's retry button.
+/// Living inside the screen's column keeps it from overlapping the title and
+/// button, and it is torn down together with the screen.
 pub fn spawn_game_over_summary(
     mut commands: Commands,
     game_result: Res<GameResult>,
     battle_stats: Res<BattleStats>,
+    screens: Query<Entity, With<GameOverScreen>>,
     existing_summary: Query<Entity, With<GameOverSummary>>,
 ) {
     // Only show summary when game ends and no summary exists yet
     if !game_result.game_ended || !existing_summary.is_empty() {
         return;
     }
+    let Ok(screen) = screens.get_single() else { return };
 
-    let enemy_name = BattleStats::unit_type_name(battle_stats.most_dangerous_enemy.unit_type);
-    let enemy_damage = battle_stats.most_dangerous_enemy.total_damage as i32;
-
-    let mvp_name = BattleStats::unit_type_name(battle_stats.mvp_ally.unit_type);
-    let mvp_kills = battle_stats.mvp_ally.kills;
-    let mvp_damage = battle_stats.mvp_ally.damage_dealt as i32;
-
-    let total_matches = battle_stats.total_matches;
-    let max_combo = battle_stats.max_combo;
-
-    commands
+    let summary = commands
         .spawn((
             Node {
-                position_type: PositionType::Absolute,
-                width: Val::Percent(100.0),
-                bottom: Val::Px(50.0),
+                margin: UiRect::top(Val::Px(32.0)),
                 justify_content: JustifyContent::Center,
                 align_items: AlignItems::Center,
                 flex_direction: FlexDirection::Column,
@@ -40,7 +74,6 @@ pub fn spawn_game_over_summary(
             GameOverSummary,
         ))
         .with_children(|parent| {
-            // Title
             parent.spawn((
                 Text::new("=== BATTLE SUMMARY ==="),
                 TextFont {
@@ -50,50 +83,71 @@ pub fn spawn_game_over_summary(
                 TextColor(Color::srgb(1.0, 0.8, 0.2)),
             ));
 
-            // Most Dangerous Enemy
-            parent.spawn((
-                Text::new(format!(
-                    "Most Dangerous Enemy: {} (dealt {} damage)",
-                    enemy_name, enemy_damage
-                )),
-                TextFont {
-                    font_size: 18.0,
-                    ..default()
-                },
-                TextColor(Color::srgb(0.9, 0.3, 0.3)),
-            ));
+            for (line, color) in summary_lines(&battle_stats) {
+                parent.spawn((
+                    Text::new(line),
+                    TextFont {
+                        font_size: 18.0,
+                        ..default()
+                    },
+                    TextColor(color),
+                ));
+            }
+        })
+        .id();
+    commands.entity(screen).add_child(summary);
+}
 
-            // MVP Ally
-            parent.spawn((
-                Text::new(format!(
-                    "MVP Ally: {} ({} kills, {} damage)",
-                    mvp_name, mvp_kills, mvp_damage
-                )),
-                TextFont {
-                    font_size: 18.0,
-                    ..default()
-                },
-                TextColor(Color::srgb(0.3, 0.9, 0.3)),
-            ));
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::battle::PersistentStats;
+    use crate::puzzle::TileType;
+    use super::super::hud::show_game_over_screen;
 
-            // Total Matches
-            parent.spawn((
-                Text::new(format!("Total Matches: {}", total_matches)),
-                TextFont {
-                    font_size: 18.0,
-                    ..default()
-                },
-                TextColor(Color::WHITE),
-            ));
+    #[test]
+    fn test_summary_spawns_once_inside_game_over_screen() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .insert_resource(GameResult { game_ended: true, ..default() })
+            .init_resource::<BattleStats>()
+            .init_resource::<PersistentStats>()
+            .init_resource::<super::super::NewHighScore>()
+            .init_resource::<crate::daily::DailyChallenge>()
+            .init_resource::<crate::daily::DailyHighScores>()
+            .add_systems(Update, (show_game_over_screen, spawn_game_over_summary).chain());
 
-            // Max Combo
-            parent.spawn((
-                Text::new(format!("Max Combo: {}", max_combo)),
-                TextFont {
-                    font_size: 18.0,
-                    ..default()
-                },
-                TextColor(Color::srgb(1.0, 0.8, 0.0)),
-            ));
-        });
+        for _ in 0..3 {
+            app.update();
+        }
+
+        let world = app.world_mut();
+        let summaries: Vec<_> = world
+            .query_filtered::<&Parent, With<GameOverSummary>>()
+            .iter(world)
+            .map(|parent| parent.get())
+            .collect();
+        assert_eq!(summaries.len(), 1);
+        assert!(world.get::<GameOverScreen>(summaries[0]).is_some(), "summary sits inside the screen");
+    }
+
+    #[test]
+    fn test_summary_lines_guard_empty_stats() {
+        let lines = summary_lines(&BattleStats::default());
+        assert_eq!(lines.len(), 1);
+        assert_eq!(lines[0].0, "No battle data recorded");
+    }
+
+    #[test]
+    fn test_summary_lines_skip_missing_records() {
+        let mut stats = BattleStats::default();
+        stats.record_match();
+        stats.record_ally_damage(TileType::Red, 120.0);
+
+        let lines: Vec<_> = summary_lines(&stats).into_iter().map(|(line, _)| line).collect();
+        assert_eq!(
+            lines,
+            vec!["MVP Ally: Warrior (0 kills, 120 damage)", "Total Matches: 1", "Max Combo: 0"]
+        );
+    }
 }
@@ -1,31 +1,99 @@
 use crate::prelude::*;
-use crate::battle::{ActiveSynergies, SynergyLevel, WaveManager, GameResult};
-use crate::puzzle::{TileType, TilePreview};
+use crate::battle::{ActiveSynergies, SynergyLevel, WaveManager, GameResult, BaseHealth, PersistentStats, Unit, UnitStats, Team};
+use crate::puzzle::{TileType, TilePreview, MatchEnergy, ComboTimer};
+use crate::daily::{DailyChallenge, DailyHighScores, daily_best_line};
 
 #[derive(Resource, Default)]
 pub struct Score(pub u32);
 
+/// Whether the run that just ended beat `PersistentStats::high_score`
+#[derive(Resource, Default)]
+pub struct NewHighScore(pub bool);
+
+/// Top-level HUD node, despawned with the rest of a run on quit to title
+#[derive(Component)]
+pub struct HudRoot;
+
 #[derive(Component)]
 pub struct ScoreText;
 
+#[derive(Component)]
+pub struct GoldText;
+
 #[derive(Component)]
 pub struct WaveText;
 
+#[derive(Component)]
+pub struct NextWaveText;
+
+/// Enemies still to beat this wave, e.g. "Enemies: 4"
+#[derive(Component)]
+pub struct EnemyCountText;
+
+/// Current `GameSpeed`, e.g. "Speed: 2x"
+#[derive(Component)]
+pub struct GameSpeedText;
+
 #[derive(Component)]
 pub struct SynergyDisplay;
 
 #[derive(Component)]
 pub struct GameOverScreen;
 
+#[derive(Component)]
+pub struct RetryButton;
+
+type RetryInteractionQuery<'w, 's> = Query<
+    'w,
+    's,
+    (&'static Interaction, &'static mut BackgroundColor),
+    (Changed<Interaction>, With<RetryButton>),
+>;
+
+const RETRY_COLOR: Color = Color::srgb(0.2, 0.6, 0.2);
+const RETRY_HOVER_COLOR: Color = Color::srgb(0.3, 0.7, 0.3);
+
 #[derive(Component)]
 pub struct ComboText;
 
+/// Frame of the combo window bar under the combo text; shown alongside it
+#[derive(Component)]
+pub struct ComboTimerBar;
+
+/// Fill of the combo window bar; shrinks as the window runs out
+#[derive(Component)]
+pub struct ComboTimerBarFill;
+
 #[derive(Component)]
 pub struct PreviewDisplay;
 
 #[derive(Component)]
 pub struct PreviewTile(pub usize);
 
+/// Inner fill of the match energy bar; its width tracks the meter
+#[derive(Component)]
+pub struct EnergyBarFill;
+
+/// Fill of the base HP bar under the score
+#[derive(Component)]
+pub struct BaseHealthBarFill;
+
+const BASE_BAR_WIDTH: f32 = 160.0;
+const BASE_HEALTH_COLOR: Color = Color::srgb(0.3, 0.8, 0.3);
+/// Bar color once the base drops to a quarter of its health
+const BASE_CRITICAL_COLOR: Color = Color::srgb(0.9, 0.25, 0.2);
+const BASE_CRITICAL_FRACTION: f32 = 0.25;
+
+const COMBO_BAR_WIDTH: f32 = 120.0;
+const COMBO_BAR_COLOR: Color = Color::srgb(1.0, 0.8, 0.0);
+
+const ENERGY_BAR_WIDTH: f32 = 40.0;
+const ENERGY_FILL_COLOR: Color = Color::srgb(0.3, 0.7, 1.0);
+const ENERGY_FULL_COLOR: Color = Color::srgb(1.0, 0.85, 0.2);
+
+/// Trait synergies span colors, so they get a neutral color
+const TRAIT_SYNERGY_COLOR: Color = Color::srgb(0.85, 0.85, 0.85);
+
 pub fn setup_hud(mut commands: Commands) {
     commands.insert_resource(Score::default());
 
@@ -39,6 +107,7 @@ pub fn setup_hud(mut commands: Commands) {
                 row_gap: Val::Px(5.0),
                 ..default()
             },
+            HudRoot,
         ))
         .with_children(|parent| {
             parent.spawn((
@@ -50,6 +119,15 @@ pub fn setup_hud(mut commands: Commands) {
                 TextColor(Color::srgb(1.0, 0.8, 0.2)),
                 WaveText,
             ));
+            parent.spawn((
+                Text::new("Enemies: 0"),
+                TextFont {
+                    font_size: 20.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(1.0, 0.5, 0.45)),
+                EnemyCountText,
+            ));
             parent.spawn((
                 Text::new("Score: 0"),
                 TextFont {
@@ -59,6 +137,56 @@ pub fn setup_hud(mut commands: Commands) {
                 TextColor(Color::WHITE),
                 ScoreText,
             ));
+            parent.spawn((
+                Text::new("Gold: 0"),
+                TextFont {
+                    font_size: 20.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(1.0, 0.85, 0.3)),
+                GoldText,
+            ));
+            parent
+                .spawn((
+                    Node {
+                        width: Val::Px(BASE_BAR_WIDTH),
+                        height: Val::Px(12.0),
+                        border: UiRect::all(Val::Px(1.0)),
+                        ..default()
+                    },
+                    BorderColor(Color::srgb(0.5, 0.5, 0.5)),
+                    BackgroundColor(Color::srgb(0.15, 0.15, 0.15)),
+                ))
+                .with_children(|bar| {
+                    bar.spawn((
+                        Node {
+                            width: Val::Percent(100.0),
+                            height: Val::Percent(100.0),
+                            ..default()
+                        },
+                        BackgroundColor(BASE_HEALTH_COLOR),
+                        BaseHealthBarFill,
+                    ));
+                });
+            parent.spawn((
+                Text::new(""),
+                TextFont {
+                    font_size: 20.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.9, 0.5, 0.4)),
+                Visibility::Hidden,
+                NextWaveText,
+            ));
+            parent.spawn((
+                Text::new(""),
+                TextFont {
+                    font_size: 18.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.7, 0.8, 1.0)),
+                GameSpeedText,
+            ));
         });
 
     commands
@@ -71,6 +199,7 @@ pub fn setup_hud(mut commands: Commands) {
                 ..default()
             },
             SynergyDisplay,
+            HudRoot,
         ));
 
     commands
@@ -80,8 +209,12 @@ pub fn setup_hud(mut commands: Commands) {
                 left: Val::Percent(50.0),
                 top: Val::Px(80.0),
                 justify_content: JustifyContent::Center,
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                row_gap: Val::Px(4.0),
                 ..default()
             },
+            HudRoot,
         ))
         .with_children(|parent| {
             parent.spawn((
@@ -93,6 +226,28 @@ pub fn setup_hud(mut commands: Commands) {
                 TextColor(Color::srgb(1.0, 0.8, 0.0)),
                 ComboText,
             ));
+            parent
+                .spawn((
+                    Node {
+                        width: Val::Px(COMBO_BAR_WIDTH),
+                        height: Val::Px(6.0),
+                        ..default()
+                    },
+                    BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.4)),
+                    Visibility::Hidden,
+                    ComboTimerBar,
+                ))
+                .with_children(|bar| {
+                    bar.spawn((
+                        Node {
+                            width: Val::Percent(0.0),
+                            height: Val::Percent(100.0),
+                            ..default()
+                        },
+                        BackgroundColor(COMBO_BAR_COLOR),
+                        ComboTimerBarFill,
+                    ));
+                });
         });
 
     // Preview display (right side of puzzle board)
@@ -108,6 +263,7 @@ pub fn setup_hud(mut commands: Commands) {
                 ..default()
             },
             PreviewDisplay,
+            HudRoot,
         ))
         .with_children(|parent| {
             // "NEXT" label
@@ -134,6 +290,38 @@ pub fn setup_hud(mut commands: Commands) {
                     PreviewTile(i),
                 ));
             }
+
+            // Match energy bar, gold once a color clear is ready
+            parent.spawn((
+                Text::new("ENERGY"),
+                TextFont {
+                    font_size: 14.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.8, 0.8, 0.8)),
+            ));
+            parent
+                .spawn((
+                    Node {
+                        width: Val::Px(ENERGY_BAR_WIDTH),
+                        height: Val::Px(10.0),
+                        border: UiRect::all(Val::Px(1.0)),
+                        ..default()
+                    },
+                    BorderColor(Color::srgb(0.5, 0.5, 0.5)),
+                    BackgroundColor(Color::srgb(0.15, 0.15, 0.15)),
+                ))
+                .with_children(|bar| {
+                    bar.spawn((
+                        Node {
+                            width: Val::Percent(0.0),
+                            height: Val::Percent(100.0),
+                            ..default()
+                        },
+                        BackgroundColor(ENERGY_FILL_COLOR),
+                        EnergyBarFill,
+                    ));
+                });
         });
 }
 
@@ -148,13 +336,71 @@ pub fn update_score_display(
     }
 }
 
+pub fn update_gold_display(
+    gold: Res<Gold>,
+    mut query: Query<&mut Text, With<GoldText>>,
+) {
+    if gold.is_changed() {
+        for mut text in query.iter_mut() {
+            **text = format!("Gold: {}", gold.0);
+        }
+    }
+}
+
+/// Speed carries over between runs, so a freshly spawned HUD is filled in right away
+pub fn update_game_speed_display(
+    speed: Res<GameSpeed>,
+    mut query: Query<(Ref<GameSpeedText>, &mut Text)>,
+) {
+    for (marker, mut text) in query.iter_mut() {
+        if speed.is_changed() || marker.is_added() {
+            **text = format!("Speed: {}", speed.label());
+        }
+    }
+}
+
 pub fn update_wave_display(
     wave_manager: Res<WaveManager>,
     mut query: Query<&mut Text, With<WaveText>>,
 ) {
     if wave_manager.is_changed() {
         for mut text in query.iter_mut() {
-            **text = format!("Wave: {}", wave_manager.current_wave);
+            **text = wave_label(&wave_manager);
+        }
+    }
+}
+
+/// "Wave: N", with the active modifier's name appended
+fn wave_label(wave_manager: &WaveManager) -> String {
+    match wave_manager.modifier {
+        Some(modifier) => format!("Wave: {} [{}]", wave_manager.current_wave, modifier.label()),
+        None => format!("Wave: {}", wave_manager.current_wave),
+    }
+}
+
+/// Enemies left in the wave: those still to spawn (telegraphed ones included) plus
+/// those alive on the field. Nothing is left during a wave break.
+pub fn enemies_left(wave_manager: &WaveManager, alive_enemies: usize, phase: &PhaseState) -> u32 {
+    if *phase == PhaseState::WaveBreak {
+        return 0;
+    }
+    wave_manager.enemies_remaining + alive_enemies as u32
+}
+
+pub fn update_enemy_count_display(
+    wave_manager: Res<WaveManager>,
+    phase: Res<State<PhaseState>>,
+    enemies: Query<(&Team, &UnitStats), With<Unit>>,
+    mut query: Query<&mut Text, With<EnemyCountText>>,
+) {
+    let alive = enemies
+        .iter()
+        .filter(|(team, stats)| **team == Team::Enemy && !stats.is_dead())
+        .count();
+    let label = format!("Enemies: {}", enemies_left(&wave_manager, alive, phase.get()));
+    for mut text in query.iter_mut() {
+        if text.0 != label {
+            **text = label.clone();
         }
     }
 }
@@ -175,6 +421,77 @@ pub fn update_combo_display(
     }
 }
 
+/// Shrink the combo window bar; it only shows while a combo is on screen
+pub fn update_combo_timer_display(
+    combo: Res<ComboCounter>,
+    timer: Res<ComboTimer>,
+    mut bars: Query<&mut Visibility, With<ComboTimerBar>>,
+    mut fills: Query<&mut Node, With<ComboTimerBarFill>>,
+) {
+    if !timer.is_changed() && !combo.is_changed() {
+        return;
+    }
+    for mut visibility in bars.iter_mut() {
+        *visibility = if combo.current > 1 && timer.is_running() { Visibility::Inherited } else { Visibility::Hidden };
+    }
+    for mut node in fills.iter_mut() {
+        node.width = Val::Percent(timer.fraction() * 100.0);
+    }
+}
+
+pub fn update_energy_display(
+    energy: Res<MatchEnergy>,
+    mut query: Query<(&mut Node, &mut BackgroundColor), With<EnergyBarFill>>,
+) {
+    if !energy.is_changed() {
+        return;
+    }
+    for (mut node, mut bg_color) in query.iter_mut() {
+        node.width = Val::Percent(energy.fraction() * 100.0);
+        *bg_color = BackgroundColor(if energy.is_full() { ENERGY_FULL_COLOR } else { ENERGY_FILL_COLOR });
+    }
+}
+
+pub fn update_base_health_display(
+    base_health: Res<BaseHealth>,
+    mut query: Query<(&mut Node, &mut BackgroundColor), With<BaseHealthBarFill>>,
+) {
+    if !base_health.is_changed() {
+        return;
+    }
+    let fraction = base_health.fraction();
+    for (mut node, mut bg_color) in query.iter_mut() {
+        node.width = Val::Percent(fraction * 100.0);
+        *bg_color = BackgroundColor(if fraction <= BASE_CRITICAL_FRACTION { BASE_CRITICAL_COLOR } else { BASE_HEALTH_COLOR });
+    }
+}
+
+/// Countdown shown between waves; `None` before the first wave and while one is active
+pub fn next_wave_label(wave_manager: &WaveManager) -> Option<String> {
+    if wave_manager.wave_active || wave_manager.current_wave == 0 {
+        return None;
+    }
+    Some(format!("Next wave in: {:.1}", wave_manager.wave_timer.max(0.0)))
+}
+
+pub fn update_next_wave_display(
+    wave_manager: Res<WaveManager>,
+    mut query: Query<(&mut Text, &mut Visibility), With<NextWaveText>>,
+) {
+    if wave_manager.is_changed() {
+        let label = next_wave_label(&wave_manager);
+        for (mut text, mut visibility) in query.iter_mut() {
+            match &label {
+                Some(label) => {
+                    **text = label.clone();
+                    *visibility = Visibility::Inherited;
+                }
+                None => *visibility = Visibility::Hidden,
+            }
+        }
+    }
+}
+
 pub fn update_synergy_display(
     synergies: Res<ActiveSynergies>,
     mut commands: Commands,
@@ -219,6 +536,22 @@ pub fn update_synergy_display(
                 TextColor(tile_type.color()),
             ));
         }
+
+        for unit_trait in synergies.active_traits() {
+            parent.spawn((
+                Text::new(format!(
+                    "{} x{}: {}",
+                    unit_trait.label(),
+                    synergies.trait_count(unit_trait),
+                    unit_trait.bonus_description()
+                )),
+                TextFont {
+                    font_size: 18.0,
+                    ..default()
+                },
+                TextColor(TRAIT_SYNERGY_COLOR),
+            ));
+        }
     });
 }
 
@@ -242,6 +575,10 @@ pub fn update_preview_display(
 pub fn show_game_over_screen(
     mut commands: Commands,
     game_result: Res<GameResult>,
+    stats: Res<PersistentStats>,
+    new_high_score: Res<NewHighScore>,
+    daily: Res<DailyChallenge>,
+    daily_scores: Res<DailyHighScores>,
     existing_screen: Query<Entity, With<GameOverScreen>>,
 ) {
     if !game_result.game_ended || !existing_screen.is_empty() {
@@ -285,5 +622,251 @@ pub fn show_game_over_screen(
                 },
                 TextColor(Color::WHITE),
             ));
+            parent.spawn((
+                Text::new(format!("Best Wave: {}", stats.best_wave)),
+                TextFont {
+                    font_size: 24.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(1.0, 0.85, 0.3)),
+            ));
+            parent.spawn((
+                Text::new(format!("High Score: {}", stats.high_score)),
+                TextFont {
+                    font_size: 24.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(1.0, 0.85, 0.3)),
+            ));
+            if new_high_score.0 {
+                parent.spawn((
+                    Text::new("New High Score!"),
+                    TextFont {
+                        font_size: 28.0,
+                        ..default()
+                    },
+                    TextColor(Color::srgb(0.2, 0.9, 0.3)),
+                ));
+            }
+            if let Some(line) = daily_best_line(&daily, &daily_scores) {
+                parent.spawn((
+                    Text::new(line),
+                    TextFont {
+                        font_size: 24.0,
+                        ..default()
+                    },
+                    TextColor(Color::srgb(1.0, 0.85, 0.3)),
+                ));
+            }
+            parent
+                .spawn((
+                    Button,
+                    Node {
+                        width: Val::Px(200.0),
+                        height: Val::Px(50.0),
+                        margin: UiRect::top(Val::Px(24.0)),
+                        justify_content: JustifyContent::Center,
+                        align_items: AlignItems::Center,
+                        ..default()
+                    },
+                    BackgroundColor(RETRY_COLOR),
+                    RetryButton,
+                ))
+                .with_children(|button| {
+                    button.spawn((
+                        Text::new("Retry"),
+                        TextFont {
+                            font_size: 28.0,
+                            ..default()
+                        },
+                        TextColor(Color::WHITE),
+                    ));
+                });
         });
 }
+
+/// Start a new run; leaving GameOver tears the old one down (see `session`)
+pub fn handle_retry_button(
+    mut interaction_query: RetryInteractionQuery,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    for (interaction, mut bg_color) in interaction_query.iter_mut() {
+        match *interaction {
+            Interaction::Pressed => {
+                next_state.set(GameState::Playing);
+            }
+            Interaction::Hovered => {
+                *bg_color = BackgroundColor(RETRY_HOVER_COLOR);
+            }
+            Interaction::None => {
+                *bg_color = BackgroundColor(RETRY_COLOR);
+            }
+        }
+    }
+}
+
+/// The battle summary is a child of the screen and goes with it
+pub fn cleanup_game_over_screen(mut commands: Commands, screens: Query<Entity, With<GameOverScreen>>) {
+    for entity in screens.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::battle::WaveModifier;
+
+    fn wave_manager(current_wave: u32, wave_active: bool, wave_timer: f32) -> WaveManager {
+        WaveManager { current_wave, wave_active, wave_timer, ..default() }
+    }
+
+    #[test]
+    fn test_next_wave_label_between_waves() {
+        assert_eq!(next_wave_label(&wave_manager(1, false, 10.0)).as_deref(), Some("Next wave in: 10.0"));
+        assert_eq!(next_wave_label(&wave_manager(3, false, 2.96)).as_deref(), Some("Next wave in: 3.0"));
+        assert_eq!(next_wave_label(&wave_manager(3, false, 0.44)).as_deref(), Some("Next wave in: 0.4"));
+    }
+
+    #[test]
+    fn test_next_wave_label_never_negative() {
+        assert_eq!(next_wave_label(&wave_manager(2, false, -0.05)).as_deref(), Some("Next wave in: 0.0"));
+    }
+
+    #[test]
+    fn test_next_wave_label_hidden_during_combat_and_before_first_wave() {
+        assert_eq!(next_wave_label(&wave_manager(2, true, 10.0)), None, "wave in progress");
+        assert_eq!(next_wave_label(&wave_manager(0, false, 3.0)), None, "game hasn't started yet");
+    }
+
+    #[test]
+    fn test_next_wave_display_toggles_visibility() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .insert_resource(wave_manager(1, false, 5.0))
+            .add_systems(Update, update_next_wave_display);
+        let text = app.world_mut().spawn((Text::new(""), Visibility::Hidden, NextWaveText)).id();
+
+        app.update();
+        assert_eq!(app.world().get::<Text>(text).unwrap().0, "Next wave in: 5.0");
+        assert_eq!(*app.world().get::<Visibility>(text).unwrap(), Visibility::Inherited);
+
+        app.world_mut().resource_mut::<WaveManager>().wave_active = true;
+        app.update();
+        assert_eq!(*app.world().get::<Visibility>(text).unwrap(), Visibility::Hidden);
+    }
+
+    #[test]
+    fn test_enemy_count_combines_unspawned_and_alive() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .add_plugins(bevy::state::app::StatesPlugin)
+            .init_state::<PhaseState>()
+            .insert_resource(WaveManager { enemies_remaining: 3, wave_active: true, ..default() })
+            .add_systems(Update, update_enemy_count_display);
+        let text = app.world_mut().spawn((Text::new(""), EnemyCountText)).id();
+        app.world_mut().spawn((Unit, Team::Enemy, UnitStats::default()));
+        app.world_mut().spawn((Unit, Team::Enemy, UnitStats::default()));
+        app.world_mut().spawn((Unit, Team::Player, UnitStats::default()));
+
+        app.update();
+        assert_eq!(app.world().get::<Text>(text).unwrap().0, "Enemies: 5", "3 to spawn + 2 alive");
+
+        app.world_mut().resource_mut::<NextState<PhaseState>>().set(PhaseState::WaveBreak);
+        app.update();
+        assert_eq!(app.world().get::<Text>(text).unwrap().0, "Enemies: 0", "nothing is left during a wave break");
+    }
+
+    #[test]
+    fn test_wave_label_shows_modifier() {
+        let mut wave_manager = wave_manager(4, true, 0.0);
+        assert_eq!(wave_label(&wave_manager), "Wave: 4");
+        wave_manager.modifier = Some(WaveModifier::Armored);
+        assert_eq!(wave_label(&wave_manager), "Wave: 4 [Armored]");
+    }
+
+    #[test]
+    fn test_base_health_bar_tracks_damage() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .init_resource::<BaseHealth>()
+            .add_systems(Update, update_base_health_display);
+        let fill = app
+            .world_mut()
+            .spawn((Node::default(), BackgroundColor(BASE_HEALTH_COLOR), BaseHealthBarFill))
+            .id();
+
+        let max = app.world().resource::<BaseHealth>().max;
+        app.world_mut().resource_mut::<BaseHealth>().take_damage(max / 2.0);
+        app.update();
+        assert_eq!(app.world().get::<Node>(fill).unwrap().width, Val::Percent(50.0));
+        assert_eq!(app.world().get::<BackgroundColor>(fill).unwrap().0, BASE_HEALTH_COLOR);
+
+        app.world_mut().resource_mut::<BaseHealth>().take_damage(max * 0.4);
+        app.update();
+        assert_eq!(app.world().get::<BackgroundColor>(fill).unwrap().0, BASE_CRITICAL_COLOR);
+    }
+
+    #[test]
+    fn test_combo_timer_bar_shrinks_and_hides_with_combo() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .init_resource::<ComboCounter>()
+            .init_resource::<ComboTimer>()
+            .add_systems(Update, update_combo_timer_display);
+        let bar = app.world_mut().spawn((Node::default(), Visibility::Hidden, ComboTimerBar)).id();
+        let fill = app.world_mut().spawn((Node::default(), ComboTimerBarFill)).id();
+
+        app.world_mut().resource_mut::<ComboCounter>().current = 2;
+        app.world_mut().resource_mut::<ComboTimer>().remaining = crate::puzzle::COMBO_WINDOW / 4.0;
+        app.update();
+        assert_eq!(*app.world().get::<Visibility>(bar).unwrap(), Visibility::Inherited);
+        assert_eq!(app.world().get::<Node>(fill).unwrap().width, Val::Percent(25.0));
+
+        app.world_mut().resource_mut::<ComboCounter>().reset();
+        app.update();
+        assert_eq!(*app.world().get::<Visibility>(bar).unwrap(), Visibility::Hidden);
+    }
+
+    #[test]
+    fn test_game_speed_display_tracks_speed() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .insert_resource(GameSpeed(2.0))
+            .add_systems(Update, update_game_speed_display);
+        app.update();
+
+        // A HUD spawned mid-run picks up the carried-over speed
+        let label = app.world_mut().spawn((Text::new(""), GameSpeedText)).id();
+        app.update();
+        assert_eq!(app.world().get::<Text>(label).unwrap().0, "Speed: 2x");
+
+        app.world_mut().resource_mut::<GameSpeed>().0 = 3.0;
+        app.update();
+        assert_eq!(app.world().get::<Text>(label).unwrap().0, "Speed: 3x");
+    }
+
+    fn game_over_texts(new_high_score: bool) -> Vec<String> {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .insert_resource(GameResult { game_ended: true, ..default() })
+            .insert_resource(PersistentStats { best_wave: 4, high_score: 2500, games_played: 3 })
+            .insert_resource(NewHighScore(new_high_score))
+            .init_resource::<DailyChallenge>()
+            .init_resource::<DailyHighScores>()
+            .add_systems(Update, show_game_over_screen);
+        app.update();
+
+        let world = app.world_mut();
+        world.query::<&Text>().iter(world).map(|text| text.0.clone()).collect()
+    }
+
+    #[test]
+    fn test_game_over_screen_shows_high_score() {
+        let texts = game_over_texts(false);
+        assert!(texts.contains(&"High Score: 2500".to_string()));
+        assert!(!texts.contains(&"New High Score!".to_string()));
+
+        assert!(game_over_texts(true).contains(&"New High Score!".to_string()));
+    }
+}
@@ -0,0 +1,157 @@
+//! Battlefield minimap
+//!
+//! Small panel in the bottom-right HUD corner with one dot per unit, colored by
+//! team. The dots are rebuilt every frame from the units' `HexPosition`s, so units
+//! that died or were sold drop off on the next frame.
+
+use crate::prelude::*;
+use crate::battle::{Unit, Team, HexPosition};
+use super::HudRoot;
+
+/// Minimap pixels per hex radius
+const MINIMAP_SCALE: f32 = 8.0;
+const MINIMAP_PADDING: f32 = 8.0;
+const MINIMAP_DOT_SIZE: f32 = 6.0;
+const MINIMAP_BACKGROUND: Color = Color::srgba(0.05, 0.05, 0.1, 0.7);
+const MINIMAP_PLAYER_COLOR: Color = Color::srgb(0.3, 0.8, 1.0);
+const MINIMAP_ENEMY_COLOR: Color = Color::srgb(1.0, 0.3, 0.3);
+
+/// Panel the unit dots are drawn into
+#[derive(Component)]
+pub struct Minimap;
+
+/// One unit's dot on the minimap
+#[derive(Component)]
+pub struct MinimapDot;
+
+/// Panel size: the grid's pixel extent at `MINIMAP_SCALE`, plus padding
+pub fn minimap_size() -> Vec2 {
+    let half_cols = (BATTLE_GRID_COLS / 2) as f32;
+    let half_rows = (BATTLE_GRID_ROWS / 2) as f32;
+    // Same layout as `BattleGrid::axial_to_pixel`; rows shift half a hex per step
+    let half_width = 3.0_f32.sqrt() * (half_cols + half_rows / 2.0) * MINIMAP_SCALE;
+    let half_height = 1.5 * half_rows * MINIMAP_SCALE;
+    Vec2::new(half_width, half_height) * 2.0 + Vec2::splat(MINIMAP_PADDING * 2.0)
+}
+
+/// Position of a hex inside the panel, from its top-left corner. Matches the
+/// battlefield's layout, with y flipped because UI coordinates grow downward.
+pub fn axial_to_minimap(pos: &HexPosition) -> Vec2 {
+    let x = MINIMAP_SCALE * (3.0_f32.sqrt() * pos.q as f32 + 3.0_f32.sqrt() / 2.0 * pos.r as f32);
+    let y = MINIMAP_SCALE * (3.0 / 2.0 * pos.r as f32);
+    minimap_size() / 2.0 + Vec2::new(x, -y)
+}
+
+fn team_color(team: Team) -> Color {
+    match team {
+        Team::Player => MINIMAP_PLAYER_COLOR,
+        Team::Enemy => MINIMAP_ENEMY_COLOR,
+    }
+}
+
+pub fn spawn_minimap(mut commands: Commands) {
+    let size = minimap_size();
+    commands.spawn((
+        Node {
+            position_type: PositionType::Absolute,
+            right: Val::Px(10.0),
+            bottom: Val::Px(10.0),
+            width: Val::Px(size.x),
+            height: Val::Px(size.y),
+            ..default()
+        },
+        BackgroundColor(MINIMAP_BACKGROUND),
+        Minimap,
+        HudRoot,
+    ));
+}
+
+/// Redraw every dot from scratch so despawned units never leave one behind
+pub fn update_minimap(
+    mut commands: Commands,
+    minimaps: Query<Entity, With<Minimap>>,
+    units: Query<(&HexPosition, &Team), With<Unit>>,
+) {
+    for minimap in minimaps.iter() {
+        commands.entity(minimap).despawn_descendants().with_children(|panel| {
+            for (pos, team) in units.iter() {
+                let center = axial_to_minimap(pos);
+                panel.spawn((
+                    Node {
+                        position_type: PositionType::Absolute,
+                        left: Val::Px(center.x - MINIMAP_DOT_SIZE / 2.0),
+                        top: Val::Px(center.y - MINIMAP_DOT_SIZE / 2.0),
+                        width: Val::Px(MINIMAP_DOT_SIZE),
+                        height: Val::Px(MINIMAP_DOT_SIZE),
+                        ..default()
+                    },
+                    BackgroundColor(team_color(*team)),
+                    BorderRadius::MAX,
+                    MinimapDot,
+                ));
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::battle::BattleGrid;
+
+    #[test]
+    fn test_axial_to_minimap_mapping() {
+        let center = axial_to_minimap(&HexPosition::new(0, 0));
+        assert_eq!(center, minimap_size() / 2.0, "the middle hex sits in the middle of the panel");
+
+        let east = axial_to_minimap(&HexPosition::new(1, 0)) - center;
+        assert!((east.x - 3.0_f32.sqrt() * MINIMAP_SCALE).abs() < 1e-4);
+        assert_eq!(east.y, 0.0);
+
+        // Enemy rows (positive r) are drawn above the player's, as on the battlefield
+        let north = axial_to_minimap(&HexPosition::new(0, 1)) - center;
+        assert!((north.y + 1.5 * MINIMAP_SCALE).abs() < 1e-4);
+        assert!((north.x - 3.0_f32.sqrt() / 2.0 * MINIMAP_SCALE).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_every_hex_fits_inside_the_panel() {
+        let size = minimap_size();
+        for pos in BattleGrid::new().valid_positions() {
+            let dot = axial_to_minimap(&pos);
+            assert!(dot.x >= MINIMAP_DOT_SIZE / 2.0 && dot.x <= size.x - MINIMAP_DOT_SIZE / 2.0, "{pos:?} at {dot}");
+            assert!(dot.y >= MINIMAP_DOT_SIZE / 2.0 && dot.y <= size.y - MINIMAP_DOT_SIZE / 2.0, "{pos:?} at {dot}");
+        }
+    }
+
+    fn dot_count(app: &mut App) -> usize {
+        let world = app.world_mut();
+        world.query::<&MinimapDot>().iter(world).count()
+    }
+
+    #[test]
+    fn test_dots_follow_units_and_clear_on_despawn() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .add_systems(Startup, spawn_minimap)
+            .add_systems(Update, update_minimap);
+        app.world_mut().spawn((Unit, HexPosition::new(0, -1), Team::Player));
+        let enemy = app.world_mut().spawn((Unit, HexPosition::new(1, 2), Team::Enemy)).id();
+
+        app.update();
+        app.update();
+        assert_eq!(dot_count(&mut app), 2);
+
+        app.world_mut().entity_mut(enemy).despawn();
+        app.update();
+        assert_eq!(dot_count(&mut app), 1, "the despawned unit's dot is gone");
+
+        let world = app.world_mut();
+        let colors: Vec<Color> = world
+            .query_filtered::<&BackgroundColor, With<MinimapDot>>()
+            .iter(world)
+            .map(|color| color.0)
+            .collect();
+        assert_eq!(colors, vec![MINIMAP_PLAYER_COLOR]);
+    }
+}
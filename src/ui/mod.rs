@@ -2,45 +2,109 @@ mod hud;
 mod pause_menu;
 mod game_over_summary;
 mod wavebreak_countdown;
+mod title_screen;
+mod combo_vignette;
+mod settings_menu;
+mod minimap;
 
 use crate::prelude::*;
+use crate::bridge::DraftOffer;
+
+pub use hud::{Score, NewHighScore, HudRoot, GameOverScreen};
+pub use game_over_summary::GameOverSummary;
+pub use combo_vignette::AccessibilitySettings;
+pub use minimap::{Minimap, MinimapDot, axial_to_minimap};
 
 pub struct UIPlugin;
 
 impl Plugin for UIPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Startup, hud::setup_hud)
+        app.init_resource::<AccessibilitySettings>()
+            .init_resource::<NewHighScore>()
+            .add_systems(Startup, combo_vignette::spawn_combo_vignette)
+            // Run teardown despawns the HUD; every new run (from the title or a retry) rebuilds it
+            .add_systems(
+                OnTransition {
+                    exited: GameState::Title,
+                    entered: GameState::Playing,
+                },
+                (hud::setup_hud, minimap::spawn_minimap),
+            )
+            .add_systems(
+                OnTransition {
+                    exited: GameState::GameOver,
+                    entered: GameState::Playing,
+                },
+                (hud::setup_hud, minimap::spawn_minimap),
+            )
             .add_systems(
                 Update,
                 (
                     hud::update_score_display,
+                    hud::update_gold_display,
+                    hud::update_game_speed_display,
                     hud::update_wave_display,
+                    hud::update_next_wave_display,
+                    hud::update_enemy_count_display,
                     hud::update_synergy_display,
                     hud::update_combo_display,
+                    hud::update_combo_timer_display,
                     hud::update_preview_display,
+                    hud::update_energy_display,
+                    hud::update_base_health_display,
+                    combo_vignette::update_combo_vignette,
+                    minimap::update_minimap,
                 )
                     .run_if(in_state(GameState::Playing)),
             )
             .add_systems(
                 Update,
                 (
-                    hud::show_game_over_screen,
-                    game_over_summary::spawn_game_over_summary,
+                    (hud::show_game_over_screen, game_over_summary::spawn_game_over_summary).chain(),
+                    hud::handle_retry_button,
                 )
                     .run_if(in_state(GameState::GameOver)),
             )
+            .add_systems(OnExit(GameState::GameOver), hud::cleanup_game_over_screen)
             .add_systems(
                 Update,
                 pause_menu::handle_pause_input
                     .run_if(in_state(GameState::Playing).or(in_state(GameState::Paused))),
             )
+            .add_systems(OnEnter(GameState::Title), title_screen::setup_title_screen)
+            .add_systems(OnExit(GameState::Title), title_screen::cleanup_title_screen)
+            .add_systems(
+                Update,
+                (
+                    title_screen::handle_start_button,
+                    title_screen::handle_mode_button,
+                    title_screen::handle_difficulty_button,
+                    title_screen::handle_draft_button,
+                    title_screen::spawn_draft_panel.run_if(resource_added::<DraftOffer>),
+                    title_screen::handle_draft_choice_button,
+                    title_screen::handle_title_quit_button,
+                )
+                    .run_if(in_state(GameState::Title)),
+            )
             .add_systems(OnEnter(GameState::Paused), pause_menu::setup_pause_menu)
-            .add_systems(OnExit(GameState::Paused), pause_menu::cleanup_pause_menu)
+            .add_systems(
+                OnExit(GameState::Paused),
+                (pause_menu::cleanup_pause_menu, settings_menu::cleanup_settings_panel),
+            )
             .add_systems(
                 Update,
                 (
                     pause_menu::handle_resume_button,
                     pause_menu::handle_quit_button,
+                    pause_menu::handle_confirm_yes_button,
+                    pause_menu::handle_confirm_no_button,
+                    settings_menu::handle_settings_button,
+                    settings_menu::handle_mute_toggle,
+                    settings_menu::handle_colorblind_toggle,
+                    settings_menu::handle_manual_cast_toggle,
+                    settings_menu::handle_volume_sliders,
+                    settings_menu::update_settings_widgets,
+                    settings_menu::handle_settings_back_button,
                 )
                     .run_if(in_state(GameState::Paused)),
             )
@@ -49,7 +113,11 @@ impl Plugin for UIPlugin {
             .add_systems(OnExit(PhaseState::WaveBreak), wavebreak_countdown::despawn_wavebreak_countdown)
             .add_systems(
                 Update,
-                wavebreak_countdown::update_wavebreak_countdown
+                (
+                    wavebreak_countdown::update_wavebreak_countdown,
+                    wavebreak_countdown::handle_reroll_button,
+                    wavebreak_countdown::handle_reroll_key,
+                )
                     .run_if(in_state(PhaseState::WaveBreak)),
             );
     }
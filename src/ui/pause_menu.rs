@@ -1,4 +1,7 @@
+use bevy::ui::FocusPolicy;
+
 use crate::prelude::*;
+use super::settings_menu::SettingsButton;
 
 #[derive(Component)]
 pub struct PauseMenuRoot;
@@ -9,12 +12,31 @@ pub struct ResumeButton;
 #[derive(Component)]
 pub struct QuitButton;
 
+/// "Surrender this run?" overlay opened by the quit button. It is also tagged
+/// `PauseMenuRoot`, so resuming with it still open tears it down with the menu.
+#[derive(Component)]
+pub struct ConfirmQuitDialog;
+
+#[derive(Component)]
+pub struct ConfirmYesButton;
+
+#[derive(Component)]
+pub struct ConfirmNoButton;
+
+type ButtonInteractionQuery<'w, 's, T> = Query<
+    'w,
+    's,
+    (&'static Interaction, &'static mut BackgroundColor),
+    (Changed<Interaction>, With<T>),
+>;
+
 pub fn handle_pause_input(
     keyboard: Res<ButtonInput<KeyCode>>,
+    bindings: Res<KeyBindings>,
     current_state: Res<State<GameState>>,
     mut next_state: ResMut<NextState<GameState>>,
 ) {
-    if keyboard.just_pressed(KeyCode::Escape) {
+    if keyboard.just_pressed(bindings.pause) {
         match current_state.get() {
             GameState::Playing => {
                 next_state.set(GameState::Paused);
@@ -79,6 +101,31 @@ pub fn setup_pause_menu(mut commands: Commands) {
                     ));
                 });
 
+            // Settings button
+            parent
+                .spawn((
+                    Button,
+                    Node {
+                        width: Val::Px(200.0),
+                        height: Val::Px(50.0),
+                        justify_content: JustifyContent::Center,
+                        align_items: AlignItems::Center,
+                        ..default()
+                    },
+                    BackgroundColor(Color::srgb(0.3, 0.3, 0.5)),
+                    SettingsButton,
+                ))
+                .with_children(|btn| {
+                    btn.spawn((
+                        Text::new("Settings"),
+                        TextFont {
+                            font_size: 28.0,
+                            ..default()
+                        },
+                        TextColor(Color::WHITE),
+                    ));
+                });
+
             // Quit to Title button
             parent
                 .spawn((
@@ -137,16 +184,133 @@ pub fn handle_resume_button(
     }
 }
 
+/// Quitting loses the run, so ask first instead of leaving right away
 pub fn handle_quit_button(
+    mut commands: Commands,
     mut interaction_query: Query<
         (&Interaction, &mut BackgroundColor),
         (Changed<Interaction>, With<QuitButton>),
     >,
+    dialogs: Query<(), With<ConfirmQuitDialog>>,
+) {
+    for (interaction, mut bg_color) in interaction_query.iter_mut() {
+        match *interaction {
+            Interaction::Pressed => {
+                if dialogs.is_empty() {
+                    spawn_confirm_quit_dialog(&mut commands);
+                }
+            }
+            Interaction::Hovered => {
+                *bg_color = BackgroundColor(Color::srgb(0.7, 0.3, 0.3));
+            }
+            Interaction::None => {
+                *bg_color = BackgroundColor(Color::srgb(0.6, 0.2, 0.2));
+            }
+        }
+    }
+}
+
+fn spawn_confirm_quit_dialog(commands: &mut Commands) {
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                flex_direction: FlexDirection::Column,
+                row_gap: Val::Px(20.0),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.8)),
+            // Above the pause menu, and keeps its buttons from taking clicks
+            GlobalZIndex(10),
+            FocusPolicy::Block,
+            ConfirmQuitDialog,
+            PauseMenuRoot,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new("Surrender this run?"),
+                TextFont {
+                    font_size: 40.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+            ));
+
+            parent
+                .spawn(Node {
+                    column_gap: Val::Px(20.0),
+                    ..default()
+                })
+                .with_children(|row| {
+                    row.spawn((
+                        Button,
+                        Node {
+                            width: Val::Px(140.0),
+                            height: Val::Px(50.0),
+                            justify_content: JustifyContent::Center,
+                            align_items: AlignItems::Center,
+                            ..default()
+                        },
+                        BackgroundColor(Color::srgb(0.6, 0.2, 0.2)),
+                        ConfirmYesButton,
+                    ))
+                    .with_children(|btn| {
+                        btn.spawn((
+                            Text::new("Yes"),
+                            TextFont {
+                                font_size: 28.0,
+                                ..default()
+                            },
+                            TextColor(Color::WHITE),
+                        ));
+                    });
+
+                    row.spawn((
+                        Button,
+                        Node {
+                            width: Val::Px(140.0),
+                            height: Val::Px(50.0),
+                            justify_content: JustifyContent::Center,
+                            align_items: AlignItems::Center,
+                            ..default()
+                        },
+                        BackgroundColor(Color::srgb(0.3, 0.3, 0.5)),
+                        ConfirmNoButton,
+                    ))
+                    .with_children(|btn| {
+                        btn.spawn((
+                            Text::new("No"),
+                            TextFont {
+                                font_size: 28.0,
+                                ..default()
+                            },
+                            TextColor(Color::WHITE),
+                        ));
+                    });
+                });
+        });
+}
+
+fn close_confirm_quit_dialog(commands: &mut Commands, dialogs: &Query<Entity, With<ConfirmQuitDialog>>) {
+    for entity in dialogs.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+pub fn handle_confirm_yes_button(
+    mut commands: Commands,
+    mut interaction_query: ButtonInteractionQuery<ConfirmYesButton>,
+    dialogs: Query<Entity, With<ConfirmQuitDialog>>,
     mut next_state: ResMut<NextState<GameState>>,
 ) {
     for (interaction, mut bg_color) in interaction_query.iter_mut() {
         match *interaction {
             Interaction::Pressed => {
+                close_confirm_quit_dialog(&mut commands, &dialogs);
                 next_state.set(GameState::Loading);
             }
             Interaction::Hovered => {
@@ -159,6 +323,26 @@ pub fn handle_quit_button(
     }
 }
 
+pub fn handle_confirm_no_button(
+    mut commands: Commands,
+    mut interaction_query: ButtonInteractionQuery<ConfirmNoButton>,
+    dialogs: Query<Entity, With<ConfirmQuitDialog>>,
+) {
+    for (interaction, mut bg_color) in interaction_query.iter_mut() {
+        match *interaction {
+            Interaction::Pressed => {
+                close_confirm_quit_dialog(&mut commands, &dialogs);
+            }
+            Interaction::Hovered => {
+                *bg_color = BackgroundColor(Color::srgb(0.4, 0.4, 0.6));
+            }
+            Interaction::None => {
+                *bg_color = BackgroundColor(Color::srgb(0.3, 0.3, 0.5));
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -170,6 +354,7 @@ mod tests {
         app.add_plugins(MinimalPlugins)
             .add_plugins(StatesPlugin)
             .init_resource::<ButtonInput<KeyCode>>()
+            .init_resource::<KeyBindings>()
             .init_state::<GameState>()
             .add_systems(
                 Update,
@@ -185,7 +370,42 @@ mod tests {
             .add_plugins(StatesPlugin)
             .init_state::<GameState>()
             .add_systems(Update, handle_resume_button.run_if(in_state(GameState::Paused)))
-            .add_systems(Update, handle_quit_button.run_if(in_state(GameState::Paused)));
+            .add_systems(
+                Update,
+                (handle_quit_button, handle_confirm_yes_button, handle_confirm_no_button)
+                    .run_if(in_state(GameState::Paused)),
+            );
+        app
+    }
+
+    fn press<T: Component>(app: &mut App) {
+        let entity = app
+            .world_mut()
+            .query_filtered::<Entity, With<T>>()
+            .single(app.world());
+        app.world_mut().entity_mut(entity).insert(Interaction::None);
+        app.update();
+        app.world_mut().entity_mut(entity).insert(Interaction::Pressed);
+        app.update(); // System runs
+        app.update(); // State transition applies
+    }
+
+    fn dialog_count(app: &mut App) -> usize {
+        app.world_mut()
+            .query_filtered::<Entity, With<ConfirmQuitDialog>>()
+            .iter(app.world())
+            .count()
+    }
+
+    /// Paused, with the quit button pressed once
+    fn open_quit_dialog() -> App {
+        let mut app = setup_button_test_app();
+        app.world_mut()
+            .resource_mut::<NextState<GameState>>()
+            .set(GameState::Paused);
+        app.update();
+        app.world_mut().spawn((Button, QuitButton, BackgroundColor(Color::srgb(0.6, 0.2, 0.2))));
+        press::<QuitButton>(&mut app);
         app
     }
 
@@ -235,6 +455,31 @@ mod tests {
         assert_eq!(*state.get(), GameState::Playing);
     }
 
+    #[test]
+    fn test_rebound_pause_key_replaces_esc() {
+        let mut app = setup_test_app();
+        app.world_mut().resource_mut::<KeyBindings>().pause = KeyCode::KeyP;
+        app.world_mut()
+            .resource_mut::<NextState<GameState>>()
+            .set(GameState::Playing);
+        app.update();
+
+        // ESC is no longer bound to anything
+        app.world_mut()
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .press(KeyCode::Escape);
+        app.update();
+        app.update();
+        assert_eq!(*app.world().resource::<State<GameState>>().get(), GameState::Playing);
+
+        app.world_mut()
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .press(KeyCode::KeyP);
+        app.update();
+        app.update();
+        assert_eq!(*app.world().resource::<State<GameState>>().get(), GameState::Paused);
+    }
+
     #[test]
     fn test_resume_button_transitions_to_playing() {
         let mut app = setup_button_test_app();
@@ -272,38 +517,36 @@ mod tests {
     }
 
     #[test]
-    fn test_quit_button_transitions_to_loading() {
-        let mut app = setup_button_test_app();
+    fn test_quit_button_asks_before_leaving() {
+        let mut app = open_quit_dialog();
 
-        // Set state to Paused and apply
-        app.world_mut()
-            .resource_mut::<NextState<GameState>>()
-            .set(GameState::Paused);
-        app.update(); // Apply state transition
+        assert_eq!(*app.world().resource::<State<GameState>>().get(), GameState::Paused);
+        assert_eq!(dialog_count(&mut app), 1);
+    }
 
-        // Spawn a QuitButton with None interaction first
-        let entity = app
-            .world_mut()
-            .spawn((
-                Button,
-                QuitButton,
-                Interaction::None,
-                BackgroundColor(Color::srgb(0.6, 0.2, 0.2)),
-            ))
-            .id();
+    #[test]
+    fn test_confirm_yes_transitions_to_loading() {
+        let mut app = open_quit_dialog();
 
-        app.update(); // Entity registered
+        press::<ConfirmYesButton>(&mut app);
 
-        // Change to Pressed to trigger Changed<Interaction>
-        app.world_mut()
-            .entity_mut(entity)
-            .insert(Interaction::Pressed);
+        assert_eq!(*app.world().resource::<State<GameState>>().get(), GameState::Loading);
+        assert_eq!(dialog_count(&mut app), 0);
+    }
 
-        app.update(); // System runs, sets NextState
-        app.update(); // State transition applies
+    #[test]
+    fn test_confirm_no_dismisses_dialog() {
+        let mut app = open_quit_dialog();
 
-        // Verify state changed to Loading
-        let state = app.world().resource::<State<GameState>>();
-        assert_eq!(*state.get(), GameState::Loading);
+        press::<ConfirmNoButton>(&mut app);
+
+        assert_eq!(*app.world().resource::<State<GameState>>().get(), GameState::Paused);
+        assert_eq!(dialog_count(&mut app), 0);
+        let buttons = app
+            .world_mut()
+            .query_filtered::<Entity, Or<(With<ConfirmYesButton>, With<ConfirmNoButton>)>>()
+            .iter(app.world())
+            .count();
+        assert_eq!(buttons, 0, "the dialog's buttons go with it");
     }
 }
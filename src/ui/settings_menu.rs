@@ -0,0 +1,480 @@
+//! Audio and display settings panel
+//!
+//! Opened from the pause menu. Edits `AudioSettings` directly; the audio plugin
+//! writes every change back to disk. The colorblind toggle flips `ColorblindMode`
+//! and the manual casting toggle flips `ManualCast`.
+
+use crate::prelude::*;
+use crate::audio::AudioSettings;
+use crate::battle::ManualCast;
+use crate::puzzle::ColorblindMode;
+use bevy::ui::{FocusPolicy, RelativeCursorPosition};
+
+const SLIDER_WIDTH: f32 = 240.0;
+const SLIDER_HEIGHT: f32 = 16.0;
+const SLIDER_TRACK_COLOR: Color = Color::srgb(0.25, 0.25, 0.25);
+const SLIDER_FILL_COLOR: Color = Color::srgb(0.3, 0.6, 0.9);
+
+#[derive(Component)]
+pub struct SettingsButton;
+
+#[derive(Component)]
+pub struct SettingsPanelRoot;
+
+#[derive(Component)]
+pub struct SettingsBackButton;
+
+#[derive(Component)]
+pub struct MuteToggle;
+
+#[derive(Component)]
+pub struct MuteToggleText;
+
+#[derive(Component)]
+pub struct ColorblindToggle;
+
+#[derive(Component)]
+pub struct ColorblindToggleText;
+
+#[derive(Component)]
+pub struct ManualCastToggle;
+
+#[derive(Component)]
+pub struct ManualCastToggleText;
+
+/// Which volume a slider controls
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VolumeChannel {
+    Sfx,
+    Music,
+}
+
+impl VolumeChannel {
+    pub fn label(&self) -> &'static str {
+        match self {
+            VolumeChannel::Sfx => "SFX",
+            VolumeChannel::Music => "Music",
+        }
+    }
+
+    fn volume_mut<'a>(&self, settings: &'a mut AudioSettings) -> &'a mut f32 {
+        match self {
+            VolumeChannel::Sfx => &mut settings.sfx_volume,
+            VolumeChannel::Music => &mut settings.music_volume,
+        }
+    }
+
+    fn volume(&self, settings: &AudioSettings) -> f32 {
+        match self {
+            VolumeChannel::Sfx => settings.sfx_volume,
+            VolumeChannel::Music => settings.music_volume,
+        }
+    }
+}
+
+/// Slider track; dragging across it sets the channel's volume
+#[derive(Component)]
+pub struct VolumeSlider(pub VolumeChannel);
+
+/// Filled part of a slider track, sized to the current volume
+#[derive(Component)]
+pub struct VolumeSliderFill(pub VolumeChannel);
+
+#[derive(Component)]
+pub struct VolumeText(pub VolumeChannel);
+
+/// Volume for a cursor at `normalized_x` across a slider track (0 = left edge, 1 = right edge)
+pub fn slider_volume(normalized_x: f32) -> f32 {
+    normalized_x.clamp(0.0, 1.0)
+}
+
+pub fn mute_label(settings: &AudioSettings) -> &'static str {
+    if settings.enabled { "[x] Sound On" } else { "[ ] Sound On" }
+}
+
+pub fn colorblind_label(mode: &ColorblindMode) -> &'static str {
+    if mode.enabled { "[x] Tile Symbols" } else { "[ ] Tile Symbols" }
+}
+
+pub fn manual_cast_label(manual_cast: &ManualCast) -> &'static str {
+    if manual_cast.enabled { "[x] Manual Casting" } else { "[ ] Manual Casting" }
+}
+
+fn spawn_menu_button(parent: &mut ChildBuilder, label: &str, color: Color, marker: impl Bundle) {
+    parent
+        .spawn((
+            Button,
+            Node {
+                width: Val::Px(200.0),
+                height: Val::Px(50.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            BackgroundColor(color),
+            marker,
+        ))
+        .with_children(|btn| {
+            btn.spawn((
+                Text::new(label),
+                TextFont {
+                    font_size: 28.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+            ));
+        });
+}
+
+fn spawn_volume_row(parent: &mut ChildBuilder, channel: VolumeChannel, volume: f32) {
+    parent
+        .spawn(Node {
+            flex_direction: FlexDirection::Column,
+            align_items: AlignItems::Center,
+            row_gap: Val::Px(6.0),
+            ..default()
+        })
+        .with_children(|row| {
+            row.spawn((
+                Text::new(format!("{}: {:.0}%", channel.label(), volume * 100.0)),
+                TextFont {
+                    font_size: 22.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+                VolumeText(channel),
+            ));
+            row.spawn((
+                Button,
+                Node {
+                    width: Val::Px(SLIDER_WIDTH),
+                    height: Val::Px(SLIDER_HEIGHT),
+                    ..default()
+                },
+                BackgroundColor(SLIDER_TRACK_COLOR),
+                RelativeCursorPosition::default(),
+                VolumeSlider(channel),
+            ))
+            .with_children(|track| {
+                track.spawn((
+                    Node {
+                        width: Val::Percent(volume * 100.0),
+                        height: Val::Percent(100.0),
+                        ..default()
+                    },
+                    BackgroundColor(SLIDER_FILL_COLOR),
+                    VolumeSliderFill(channel),
+                ));
+            });
+        });
+}
+
+pub fn handle_settings_button(
+    mut commands: Commands,
+    settings: Res<AudioSettings>,
+    colorblind: Res<ColorblindMode>,
+    manual_cast: Res<ManualCast>,
+    interaction_query: Query<&Interaction, (Changed<Interaction>, With<SettingsButton>)>,
+    panels: Query<(), With<SettingsPanelRoot>>,
+) {
+    let pressed = interaction_query.iter().any(|i| *i == Interaction::Pressed);
+    if !pressed || !panels.is_empty() {
+        return;
+    }
+
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                flex_direction: FlexDirection::Column,
+                row_gap: Val::Px(20.0),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.9)),
+            GlobalZIndex(10),
+            // Keep clicks from reaching the pause menu buttons underneath
+            FocusPolicy::Block,
+            SettingsPanelRoot,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new("SETTINGS"),
+                TextFont {
+                    font_size: 48.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+            ));
+
+            parent
+                .spawn((
+                    Button,
+                    Node {
+                        width: Val::Px(240.0),
+                        height: Val::Px(44.0),
+                        justify_content: JustifyContent::Center,
+                        align_items: AlignItems::Center,
+                        ..default()
+                    },
+                    BackgroundColor(Color::srgb(0.3, 0.3, 0.3)),
+                    MuteToggle,
+                ))
+                .with_children(|btn| {
+                    btn.spawn((
+                        Text::new(mute_label(&settings)),
+                        TextFont {
+                            font_size: 24.0,
+                            ..default()
+                        },
+                        TextColor(Color::WHITE),
+                        MuteToggleText,
+                    ));
+                });
+
+            parent
+                .spawn((
+                    Button,
+                    Node {
+                        width: Val::Px(240.0),
+                        height: Val::Px(44.0),
+                        justify_content: JustifyContent::Center,
+                        align_items: AlignItems::Center,
+                        ..default()
+                    },
+                    BackgroundColor(Color::srgb(0.3, 0.3, 0.3)),
+                    ColorblindToggle,
+                ))
+                .with_children(|btn| {
+                    btn.spawn((
+                        Text::new(colorblind_label(&colorblind)),
+                        TextFont {
+                            font_size: 24.0,
+                            ..default()
+                        },
+                        TextColor(Color::WHITE),
+                        ColorblindToggleText,
+                    ));
+                });
+
+            parent
+                .spawn((
+                    Button,
+                    Node {
+                        width: Val::Px(240.0),
+                        height: Val::Px(44.0),
+                        justify_content: JustifyContent::Center,
+                        align_items: AlignItems::Center,
+                        ..default()
+                    },
+                    BackgroundColor(Color::srgb(0.3, 0.3, 0.3)),
+                    ManualCastToggle,
+                ))
+                .with_children(|btn| {
+                    btn.spawn((
+                        Text::new(manual_cast_label(&manual_cast)),
+                        TextFont {
+                            font_size: 24.0,
+                            ..default()
+                        },
+                        TextColor(Color::WHITE),
+                        ManualCastToggleText,
+                    ));
+                });
+
+            spawn_volume_row(parent, VolumeChannel::Sfx, settings.sfx_volume);
+            spawn_volume_row(parent, VolumeChannel::Music, settings.music_volume);
+
+            spawn_menu_button(parent, "Back", Color::srgb(0.4, 0.4, 0.4), SettingsBackButton);
+        });
+}
+
+pub fn handle_mute_toggle(
+    interaction_query: Query<&Interaction, (Changed<Interaction>, With<MuteToggle>)>,
+    mut settings: ResMut<AudioSettings>,
+) {
+    for interaction in interaction_query.iter() {
+        if *interaction == Interaction::Pressed {
+            settings.enabled = !settings.enabled;
+        }
+    }
+}
+
+pub fn handle_colorblind_toggle(
+    interaction_query: Query<&Interaction, (Changed<Interaction>, With<ColorblindToggle>)>,
+    mut mode: ResMut<ColorblindMode>,
+    mut texts: Query<&mut Text, With<ColorblindToggleText>>,
+) {
+    for interaction in interaction_query.iter() {
+        if *interaction == Interaction::Pressed {
+            mode.enabled = !mode.enabled;
+            for mut text in texts.iter_mut() {
+                **text = colorblind_label(&mode).to_string();
+            }
+        }
+    }
+}
+
+pub fn handle_manual_cast_toggle(
+    interaction_query: Query<&Interaction, (Changed<Interaction>, With<ManualCastToggle>)>,
+    mut manual_cast: ResMut<ManualCast>,
+    mut texts: Query<&mut Text, With<ManualCastToggleText>>,
+) {
+    for interaction in interaction_query.iter() {
+        if *interaction == Interaction::Pressed {
+            manual_cast.enabled = !manual_cast.enabled;
+            for mut text in texts.iter_mut() {
+                **text = manual_cast_label(&manual_cast).to_string();
+            }
+        }
+    }
+}
+
+/// While a slider is held down, follow the cursor across its track
+pub fn handle_volume_sliders(
+    sliders: Query<(&Interaction, &RelativeCursorPosition, &VolumeSlider)>,
+    mut settings: ResMut<AudioSettings>,
+) {
+    for (interaction, cursor, slider) in sliders.iter() {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        let Some(normalized) = cursor.normalized else { continue };
+        let volume = slider_volume(normalized.x);
+        // Only touch the resource on a real change so it isn't re-saved every frame
+        if slider.0.volume(&settings) != volume {
+            *slider.0.volume_mut(&mut settings) = volume;
+        }
+    }
+}
+
+/// Keep labels and slider fills in sync with `AudioSettings`
+pub fn update_settings_widgets(
+    settings: Res<AudioSettings>,
+    mut mute_texts: Query<&mut Text, (With<MuteToggleText>, Without<VolumeText>)>,
+    mut volume_texts: Query<(&mut Text, &VolumeText), Without<MuteToggleText>>,
+    mut fills: Query<(&mut Node, &VolumeSliderFill)>,
+) {
+    if !settings.is_changed() {
+        return;
+    }
+    for mut text in mute_texts.iter_mut() {
+        **text = mute_label(&settings).to_string();
+    }
+    for (mut text, label) in volume_texts.iter_mut() {
+        **text = format!("{}: {:.0}%", label.0.label(), label.0.volume(&settings) * 100.0);
+    }
+    for (mut node, fill) in fills.iter_mut() {
+        node.width = Val::Percent(fill.0.volume(&settings) * 100.0);
+    }
+}
+
+pub fn handle_settings_back_button(
+    mut commands: Commands,
+    interaction_query: Query<&Interaction, (Changed<Interaction>, With<SettingsBackButton>)>,
+    panels: Query<Entity, With<SettingsPanelRoot>>,
+) {
+    if interaction_query.iter().any(|i| *i == Interaction::Pressed) {
+        for entity in panels.iter() {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
+
+pub fn cleanup_settings_panel(
+    mut commands: Commands,
+    panels: Query<Entity, With<SettingsPanelRoot>>,
+) {
+    for entity in panels.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn setup_settings_app() -> App {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .init_resource::<AudioSettings>()
+            .init_resource::<ColorblindMode>()
+            .init_resource::<ManualCast>()
+            .add_systems(
+                Update,
+                (handle_mute_toggle, handle_colorblind_toggle, handle_manual_cast_toggle, handle_volume_sliders).chain(),
+            );
+        app
+    }
+
+    #[test]
+    fn test_slider_volume_clamps_to_track() {
+        assert_eq!(slider_volume(-0.2), 0.0);
+        assert_eq!(slider_volume(0.3), 0.3);
+        assert_eq!(slider_volume(1.4), 1.0);
+    }
+
+    #[test]
+    fn test_mute_toggle_flips_enabled() {
+        let mut app = setup_settings_app();
+        let toggle = app.world_mut().spawn((MuteToggle, Interaction::None)).id();
+        app.update();
+
+        app.world_mut().entity_mut(toggle).insert(Interaction::Pressed);
+        app.update();
+
+        assert!(!app.world().resource::<AudioSettings>().enabled);
+        assert_eq!(mute_label(app.world().resource::<AudioSettings>()), "[ ] Sound On");
+    }
+
+    #[test]
+    fn test_colorblind_toggle_flips_mode_and_label() {
+        let mut app = setup_settings_app();
+        let toggle = app.world_mut().spawn((ColorblindToggle, Interaction::None)).id();
+        let text = app.world_mut().spawn((Text::new(""), ColorblindToggleText)).id();
+        app.update();
+
+        app.world_mut().entity_mut(toggle).insert(Interaction::Pressed);
+        app.update();
+
+        assert!(app.world().resource::<ColorblindMode>().enabled);
+        assert_eq!(app.world().get::<Text>(text).unwrap().0, "[x] Tile Symbols");
+    }
+
+    #[test]
+    fn test_manual_cast_toggle_flips_mode_and_label() {
+        let mut app = setup_settings_app();
+        let toggle = app.world_mut().spawn((ManualCastToggle, Interaction::None)).id();
+        let text = app.world_mut().spawn((Text::new(""), ManualCastToggleText)).id();
+        app.update();
+        assert!(!app.world().resource::<ManualCast>().enabled, "auto-cast by default");
+
+        app.world_mut().entity_mut(toggle).insert(Interaction::Pressed);
+        app.update();
+
+        assert!(app.world().resource::<ManualCast>().enabled);
+        assert_eq!(app.world().get::<Text>(text).unwrap().0, "[x] Manual Casting");
+    }
+
+    #[test]
+    fn test_pressed_slider_sets_only_its_channel() {
+        let mut app = setup_settings_app();
+        app.world_mut().spawn((
+            VolumeSlider(VolumeChannel::Music),
+            Interaction::Pressed,
+            RelativeCursorPosition {
+                normalized: Some(Vec2::new(0.25, 0.5)),
+                ..default()
+            },
+        ));
+
+        app.update();
+
+        let settings = app.world().resource::<AudioSettings>();
+        assert_eq!(settings.music_volume, 0.25);
+        assert_eq!(settings.sfx_volume, 1.0);
+    }
+}
@@ -0,0 +1,475 @@
+use bevy::app::AppExit;
+
+use bevy::ui::FocusPolicy;
+
+use crate::prelude::*;
+use crate::battle::BattleStats;
+use crate::bridge::{DraftSettings, DraftOffer, DraftPickEvent};
+
+#[derive(Component)]
+pub struct TitleScreenRoot;
+
+#[derive(Component)]
+pub struct StartButton;
+
+#[derive(Component)]
+pub struct ModeButton;
+
+#[derive(Component)]
+pub struct ModeButtonText;
+
+#[derive(Component)]
+pub struct DifficultyButton;
+
+#[derive(Component)]
+pub struct DifficultyButtonText;
+
+#[derive(Component)]
+pub struct DraftButton;
+
+#[derive(Component)]
+pub struct DraftButtonText;
+
+#[derive(Component)]
+pub struct TitleQuitButton;
+
+/// Overlay listing the `DraftOffer`; lives under the title screen so it goes with it
+#[derive(Component)]
+pub struct DraftPanel;
+
+/// Picks choice `.0` of the `DraftOffer`
+#[derive(Component)]
+pub struct DraftChoiceButton(pub usize);
+
+type ButtonInteractionQuery<'w, 's, T> = Query<
+    'w,
+    's,
+    (&'static Interaction, &'static mut BackgroundColor),
+    (Changed<Interaction>, With<T>),
+>;
+
+const START_COLOR: Color = Color::srgb(0.2, 0.6, 0.2);
+const START_HOVER_COLOR: Color = Color::srgb(0.3, 0.7, 0.3);
+const MODE_COLOR: Color = Color::srgb(0.2, 0.3, 0.6);
+const MODE_HOVER_COLOR: Color = Color::srgb(0.3, 0.4, 0.7);
+const QUIT_COLOR: Color = Color::srgb(0.6, 0.2, 0.2);
+const QUIT_HOVER_COLOR: Color = Color::srgb(0.7, 0.3, 0.3);
+
+fn title_button_node() -> Node {
+    Node {
+        width: Val::Px(260.0),
+        height: Val::Px(56.0),
+        justify_content: JustifyContent::Center,
+        align_items: AlignItems::Center,
+        ..default()
+    }
+}
+
+fn button_label(text: impl Into<String>) -> (Text, TextFont, TextColor) {
+    (
+        Text::new(text),
+        TextFont {
+            font_size: 28.0,
+            ..default()
+        },
+        TextColor(Color::WHITE),
+    )
+}
+
+pub fn setup_title_screen(
+    mut commands: Commands,
+    game_mode: Res<GameMode>,
+    difficulty: Res<Difficulty>,
+    draft: Res<DraftSettings>,
+) {
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                flex_direction: FlexDirection::Column,
+                row_gap: Val::Px(24.0),
+                ..default()
+            },
+            // Opaque so the HUD behind it stays hidden until the run starts
+            BackgroundColor(Color::srgb(0.08, 0.08, 0.12)),
+            GlobalZIndex(100),
+            TitleScreenRoot,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new("PUZZLE TACTICS"),
+                TextFont {
+                    font_size: 72.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(1.0, 0.85, 0.3)),
+                Node {
+                    margin: UiRect::bottom(Val::Px(40.0)),
+                    ..default()
+                },
+            ));
+
+            parent
+                .spawn((Button, title_button_node(), BackgroundColor(START_COLOR), StartButton))
+                .with_children(|btn| {
+                    btn.spawn(button_label("Start"));
+                });
+
+            parent
+                .spawn((Button, title_button_node(), BackgroundColor(MODE_COLOR), ModeButton))
+                .with_children(|btn| {
+                    btn.spawn((button_label(mode_label(*game_mode)), ModeButtonText));
+                });
+
+            parent
+                .spawn((Button, title_button_node(), BackgroundColor(MODE_COLOR), DifficultyButton))
+                .with_children(|btn| {
+                    btn.spawn((button_label(difficulty_label(*difficulty)), DifficultyButtonText));
+                });
+
+            parent
+                .spawn((Button, title_button_node(), BackgroundColor(MODE_COLOR), DraftButton))
+                .with_children(|btn| {
+                    btn.spawn((button_label(draft_label(*draft)), DraftButtonText));
+                });
+
+            parent
+                .spawn((Button, title_button_node(), BackgroundColor(QUIT_COLOR), TitleQuitButton))
+                .with_children(|btn| {
+                    btn.spawn(button_label("Quit"));
+                });
+        });
+}
+
+fn mode_label(mode: GameMode) -> String {
+    format!("Mode: {}", mode.label())
+}
+
+fn difficulty_label(difficulty: Difficulty) -> String {
+    format!("Difficulty: {}", difficulty.label())
+}
+
+fn draft_label(draft: DraftSettings) -> String {
+    format!("Draft: {}", if draft.enabled { "On" } else { "Off" })
+}
+
+fn draft_choice_label(unit_type: TileType, star_rank: u8) -> String {
+    format!("{} {}-Star", BattleStats::unit_type_name(Some(unit_type)), star_rank)
+}
+
+pub fn cleanup_title_screen(
+    mut commands: Commands,
+    title_query: Query<Entity, With<TitleScreenRoot>>,
+) {
+    for entity in title_query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+/// Starts the run, or with the draft on, rolls the starting-unit offer first
+pub fn handle_start_button(
+    mut commands: Commands,
+    mut interaction_query: ButtonInteractionQuery<StartButton>,
+    mut next_state: ResMut<NextState<GameState>>,
+    draft: Res<DraftSettings>,
+    offer: Option<Res<DraftOffer>>,
+    mut rng: ResMut<GameRng>,
+) {
+    for (interaction, mut bg_color) in interaction_query.iter_mut() {
+        match *interaction {
+            Interaction::Pressed => {
+                if !draft.enabled {
+                    next_state.set(GameState::Playing);
+                } else if offer.is_none() {
+                    commands.insert_resource(DraftOffer::roll(&mut *rng));
+                }
+            }
+            Interaction::Hovered => {
+                *bg_color = BackgroundColor(START_HOVER_COLOR);
+            }
+            Interaction::None => {
+                *bg_color = BackgroundColor(START_COLOR);
+            }
+        }
+    }
+}
+
+pub fn handle_mode_button(
+    mut interaction_query: ButtonInteractionQuery<ModeButton>,
+    mut text_query: Query<&mut Text, With<ModeButtonText>>,
+    mut game_mode: ResMut<GameMode>,
+) {
+    for (interaction, mut bg_color) in interaction_query.iter_mut() {
+        match *interaction {
+            Interaction::Pressed => {
+                *game_mode = game_mode.next();
+                for mut text in text_query.iter_mut() {
+                    **text = mode_label(*game_mode);
+                }
+            }
+            Interaction::Hovered => {
+                *bg_color = BackgroundColor(MODE_HOVER_COLOR);
+            }
+            Interaction::None => {
+                *bg_color = BackgroundColor(MODE_COLOR);
+            }
+        }
+    }
+}
+
+pub fn handle_difficulty_button(
+    mut interaction_query: ButtonInteractionQuery<DifficultyButton>,
+    mut text_query: Query<&mut Text, With<DifficultyButtonText>>,
+    mut difficulty: ResMut<Difficulty>,
+) {
+    for (interaction, mut bg_color) in interaction_query.iter_mut() {
+        match *interaction {
+            Interaction::Pressed => {
+                *difficulty = difficulty.next();
+                for mut text in text_query.iter_mut() {
+                    **text = difficulty_label(*difficulty);
+                }
+            }
+            Interaction::Hovered => {
+                *bg_color = BackgroundColor(MODE_HOVER_COLOR);
+            }
+            Interaction::None => {
+                *bg_color = BackgroundColor(MODE_COLOR);
+            }
+        }
+    }
+}
+
+pub fn handle_draft_button(
+    mut interaction_query: ButtonInteractionQuery<DraftButton>,
+    mut text_query: Query<&mut Text, With<DraftButtonText>>,
+    mut draft: ResMut<DraftSettings>,
+) {
+    for (interaction, mut bg_color) in interaction_query.iter_mut() {
+        match *interaction {
+            Interaction::Pressed => {
+                draft.enabled = !draft.enabled;
+                for mut text in text_query.iter_mut() {
+                    **text = draft_label(*draft);
+                }
+            }
+            Interaction::Hovered => {
+                *bg_color = BackgroundColor(MODE_HOVER_COLOR);
+            }
+            Interaction::None => {
+                *bg_color = BackgroundColor(MODE_COLOR);
+            }
+        }
+    }
+}
+
+/// Show one button per offered unit over the title screen
+pub fn spawn_draft_panel(
+    mut commands: Commands,
+    offer: Res<DraftOffer>,
+    title_query: Query<Entity, With<TitleScreenRoot>>,
+) {
+    let Ok(title) = title_query.get_single() else { return };
+    commands.entity(title).with_children(|parent| {
+        parent
+            .spawn((
+                Node {
+                    position_type: PositionType::Absolute,
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    flex_direction: FlexDirection::Column,
+                    row_gap: Val::Px(16.0),
+                    ..default()
+                },
+                BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.85)),
+                // Keeps the title buttons underneath from taking clicks
+                FocusPolicy::Block,
+                DraftPanel,
+            ))
+            .with_children(|panel| {
+                panel.spawn(button_label("Pick a starting unit"));
+                for (index, &(unit_type, star_rank)) in offer.choices.iter().enumerate() {
+                    panel
+                        .spawn((Button, title_button_node(), BackgroundColor(START_COLOR), DraftChoiceButton(index)))
+                        .with_children(|btn| {
+                            btn.spawn(button_label(draft_choice_label(unit_type, star_rank)));
+                        });
+                }
+            });
+    });
+}
+
+pub fn handle_draft_choice_button(
+    mut commands: Commands,
+    mut interaction_query: Query<
+        (&Interaction, &mut BackgroundColor, &DraftChoiceButton),
+        Changed<Interaction>,
+    >,
+) {
+    for (interaction, mut bg_color, choice) in interaction_query.iter_mut() {
+        match *interaction {
+            Interaction::Pressed => {
+                commands.trigger(DraftPickEvent { index: choice.0 });
+            }
+            Interaction::Hovered => {
+                *bg_color = BackgroundColor(START_HOVER_COLOR);
+            }
+            Interaction::None => {
+                *bg_color = BackgroundColor(START_COLOR);
+            }
+        }
+    }
+}
+
+pub fn handle_title_quit_button(
+    mut interaction_query: ButtonInteractionQuery<TitleQuitButton>,
+    mut exit: EventWriter<AppExit>,
+) {
+    for (interaction, mut bg_color) in interaction_query.iter_mut() {
+        match *interaction {
+            Interaction::Pressed => {
+                exit.send(AppExit::Success);
+            }
+            Interaction::Hovered => {
+                *bg_color = BackgroundColor(QUIT_HOVER_COLOR);
+            }
+            Interaction::None => {
+                *bg_color = BackgroundColor(QUIT_COLOR);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::state::app::StatesPlugin;
+
+    fn setup_title_test_app() -> App {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .add_plugins(StatesPlugin)
+            .init_state::<GameState>()
+            .init_resource::<GameMode>()
+            .init_resource::<Difficulty>()
+            .init_resource::<DraftSettings>()
+            .init_resource::<GameRng>()
+            .add_systems(OnEnter(GameState::Title), setup_title_screen)
+            .add_systems(OnExit(GameState::Title), cleanup_title_screen)
+            .add_systems(
+                Update,
+                (
+                    handle_start_button,
+                    handle_mode_button,
+                    handle_difficulty_button,
+                    handle_draft_button,
+                    spawn_draft_panel.run_if(resource_added::<DraftOffer>),
+                )
+                    .run_if(in_state(GameState::Title)),
+            );
+        app.world_mut()
+            .resource_mut::<NextState<GameState>>()
+            .set(GameState::Title);
+        app.update(); // Apply state transition
+        app
+    }
+
+    fn press_button<T: Component>(app: &mut App) {
+        let entity = app
+            .world_mut()
+            .query_filtered::<Entity, With<T>>()
+            .single(app.world());
+        app.world_mut().entity_mut(entity).insert(Interaction::None);
+        app.update(); // Entity registered
+
+        // Change to Pressed to trigger Changed<Interaction>
+        app.world_mut()
+            .entity_mut(entity)
+            .insert(Interaction::Pressed);
+        app.update(); // System runs, sets NextState
+        app.update(); // State transition applies
+    }
+
+    #[test]
+    fn test_title_screen_spawns_on_enter() {
+        let mut app = setup_title_test_app();
+
+        let count = app
+            .world_mut()
+            .query_filtered::<Entity, With<TitleScreenRoot>>()
+            .iter(app.world())
+            .count();
+        assert_eq!(count, 1);
+    }
+
+    #[test]
+    fn test_start_button_transitions_to_playing() {
+        let mut app = setup_title_test_app();
+
+        press_button::<StartButton>(&mut app);
+
+        let state = app.world().resource::<State<GameState>>();
+        assert_eq!(*state.get(), GameState::Playing);
+
+        // Title screen is torn down on exit
+        let count = app
+            .world_mut()
+            .query_filtered::<Entity, With<TitleScreenRoot>>()
+            .iter(app.world())
+            .count();
+        assert_eq!(count, 0);
+    }
+
+    #[test]
+    fn test_mode_button_cycles_game_mode() {
+        let mut app = setup_title_test_app();
+
+        press_button::<ModeButton>(&mut app);
+
+        assert_eq!(*app.world().resource::<GameMode>(), GameMode::Endless);
+        let text = app
+            .world_mut()
+            .query_filtered::<&Text, With<ModeButtonText>>()
+            .single(app.world());
+        assert_eq!(text.0, "Mode: Endless");
+    }
+
+    #[test]
+    fn test_difficulty_button_cycles_difficulty() {
+        let mut app = setup_title_test_app();
+
+        press_button::<DifficultyButton>(&mut app);
+
+        assert_eq!(*app.world().resource::<Difficulty>(), Difficulty::Hard);
+        let text = app
+            .world_mut()
+            .query_filtered::<&Text, With<DifficultyButtonText>>()
+            .single(app.world());
+        assert_eq!(text.0, "Difficulty: Hard");
+    }
+
+    #[test]
+    fn test_start_with_draft_offers_units_instead_of_playing() {
+        let mut app = setup_title_test_app();
+        press_button::<DraftButton>(&mut app);
+        assert!(app.world().resource::<DraftSettings>().enabled);
+
+        press_button::<StartButton>(&mut app);
+
+        let state = app.world().resource::<State<GameState>>();
+        assert_eq!(*state.get(), GameState::Title, "the run waits for a pick");
+        assert_eq!(app.world().resource::<DraftOffer>().choices.len(), crate::bridge::DRAFT_CHOICES);
+        let choices = app
+            .world_mut()
+            .query::<&DraftChoiceButton>()
+            .iter(app.world())
+            .count();
+        assert_eq!(choices, crate::bridge::DRAFT_CHOICES);
+    }
+}
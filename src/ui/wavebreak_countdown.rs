@@ -1,8 +1,10 @@
 //! Wave Break countdown timer UI
 //!
-//! Displays remaining time during WaveBreak phase for unit repositioning.
+//! Displays remaining time during WaveBreak phase for unit repositioning,
+//! with a button (or the `reroll` key) to reroll the puzzle board's colors for gold.
 
 use crate::prelude::*;
+use crate::puzzle::{RerollBoardEvent, REROLL_COST};
 
 /// Marker component for the wave break countdown UI
 #[derive(Component)]
@@ -12,15 +14,34 @@ pub struct WaveBreakCountdown;
 #[derive(Component)]
 pub struct CountdownText;
 
+/// Pays `REROLL_COST` gold to recolor the puzzle board
+#[derive(Component)]
+pub struct RerollButton;
+
+const REROLL_COLOR: Color = Color::srgb(0.55, 0.45, 0.15);
+const REROLL_HOVER_COLOR: Color = Color::srgb(0.7, 0.58, 0.2);
+/// Button color while the player can't afford a reroll
+const REROLL_DISABLED_COLOR: Color = Color::srgb(0.3, 0.3, 0.3);
+
+type RerollInteractionQuery<'w, 's> = Query<
+    'w,
+    's,
+    (&'static Interaction, &'static mut BackgroundColor),
+    (Changed<Interaction>, With<RerollButton>),
+>;
+
 /// Spawns the countdown UI when entering WaveBreak phase
-pub fn spawn_wavebreak_countdown(mut commands: Commands) {
+pub fn spawn_wavebreak_countdown(mut commands: Commands, gold: Res<Gold>) {
+    let reroll_color = if gold.0 >= REROLL_COST { REROLL_COLOR } else { REROLL_DISABLED_COLOR };
     commands
         .spawn((
             Node {
                 position_type: PositionType::Absolute,
                 width: Val::Percent(100.0),
                 top: Val::Px(20.0),
-                justify_content: JustifyContent::Center,
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                row_gap: Val::Px(8.0),
                 ..default()
             },
             WaveBreakCountdown,
@@ -35,9 +56,62 @@ pub fn spawn_wavebreak_countdown(mut commands: Commands) {
                 TextColor(Color::WHITE),
                 CountdownText,
             ));
+            parent
+                .spawn((
+                    Button,
+                    Node {
+                        padding: UiRect::axes(Val::Px(16.0), Val::Px(6.0)),
+                        justify_content: JustifyContent::Center,
+                        align_items: AlignItems::Center,
+                        ..default()
+                    },
+                    BackgroundColor(reroll_color),
+                    RerollButton,
+                ))
+                .with_children(|button| {
+                    button.spawn((
+                        Text::new(format!("REROLL BOARD ({}G)", REROLL_COST)),
+                        TextFont {
+                            font_size: 22.0,
+                            ..default()
+                        },
+                        TextColor(Color::WHITE),
+                    ));
+                });
         });
 }
 
+/// Request a reroll on press; the puzzle side checks and charges the gold
+pub fn handle_reroll_button(
+    mut commands: Commands,
+    gold: Res<Gold>,
+    mut interaction_query: RerollInteractionQuery,
+) {
+    for (interaction, mut bg_color) in interaction_query.iter_mut() {
+        let affordable = gold.0 >= REROLL_COST;
+        *bg_color = BackgroundColor(match *interaction {
+            _ if !affordable => REROLL_DISABLED_COLOR,
+            Interaction::Pressed => {
+                commands.trigger(RerollBoardEvent);
+                REROLL_COLOR
+            }
+            Interaction::Hovered => REROLL_HOVER_COLOR,
+            Interaction::None => REROLL_COLOR,
+        });
+    }
+}
+
+/// The `reroll` binding does the same as pressing the button
+pub fn handle_reroll_key(
+    mut commands: Commands,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    bindings: Res<KeyBindings>,
+) {
+    if keyboard.just_pressed(bindings.reroll) {
+        commands.trigger(RerollBoardEvent);
+    }
+}
+
 /// Updates the countdown text every frame
 pub fn update_wavebreak_countdown(
     wave_break_timer: Res<WaveBreakTimer>,
@@ -91,4 +165,27 @@ mod tests {
         assert!(remaining_normal > 3.0, "Normal should be above threshold");
         assert!(remaining_warning <= 3.0, "Warning should be at or below threshold");
     }
+
+    #[derive(Resource, Default)]
+    struct Rerolls(u32);
+
+    #[test]
+    fn test_reroll_key_follows_binding() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .init_resource::<ButtonInput<KeyCode>>()
+            .insert_resource(KeyBindings { reroll: KeyCode::KeyG, ..default() })
+            .init_resource::<Rerolls>()
+            .add_observer(|_: Trigger<RerollBoardEvent>, mut rerolls: ResMut<Rerolls>| rerolls.0 += 1)
+            .add_systems(Update, handle_reroll_key);
+        app.world_mut().flush();
+
+        app.world_mut().resource_mut::<ButtonInput<KeyCode>>().press(KeyBindings::default().reroll);
+        app.update();
+        assert_eq!(app.world().resource::<Rerolls>().0, 0, "the old key no longer rerolls");
+
+        app.world_mut().resource_mut::<ButtonInput<KeyCode>>().press(KeyCode::KeyG);
+        app.update();
+        assert_eq!(app.world().resource::<Rerolls>().0, 1);
+    }
 }
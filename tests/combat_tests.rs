@@ -1,7 +1,9 @@
 //! Combat System Tests (TDD)
 //!
-//! Tests for critical hit and defense damage calculations.
+//! Tests for critical hit and defense damage calculations,
+//! plus deterministic stepping of the combat schedule.
 
+use bevy::prelude::{Entity, Transform, World};
 use puzzle_tactics::battle::*;
 use puzzle_tactics::puzzle::TileType;
 
@@ -116,8 +118,162 @@ fn test_unit_stats_take_damage_with_defense() {
     let initial_health = defender.health;
 
     // Apply 100 damage -> should reduce to 50 after defense
-    defender.take_calculated_damage(100.0);
+    defender.take_calculated_damage(100.0, DamageType::Physical);
 
     let expected_health = initial_health - 50.0;
     assert!((defender.health - expected_health).abs() < 0.01);
 }
+
+// ============================================================
+// Damage Type Tests
+// ============================================================
+
+/// Test: Physical damage checks defense, magical damage checks magic resist
+#[test]
+fn test_typed_defense_picks_matching_resistance() {
+    let physical = DamageCalculator::apply_typed_defense(100.0, DamageType::Physical, 50.0, 0.0);
+    let magical = DamageCalculator::apply_typed_defense(100.0, DamageType::Magical, 50.0, 0.0);
+    assert!((physical - 50.0).abs() < 0.01);
+    assert!((magical - 100.0).abs() < 0.01);
+}
+
+/// Test: Typed calculation applies crit before resistance and keeps the 1.0 floor
+#[test]
+fn test_calculate_typed() {
+    let result = DamageCalculator::calculate_typed(100.0, true, DamageType::Magical, 0.0, 20.0);
+    assert!((result - 120.0).abs() < 0.01);
+    let floored = DamageCalculator::calculate_typed(1.0, false, DamageType::Magical, 0.0, 100.0);
+    assert!(floored >= 1.0);
+}
+
+/// Test: A unit with split resistances takes different damage per type
+#[test]
+fn test_split_resistances_on_unit() {
+    let stats = UnitStats {
+        defense: 50.0,
+        magic_resist: 20.0,
+        ..Default::default()
+    };
+
+    let mut physical = stats.clone();
+    physical.take_calculated_damage(100.0, DamageType::Physical);
+    let mut magical = stats.clone();
+    magical.take_calculated_damage(100.0, DamageType::Magical);
+
+    assert!((physical.health - 50.0).abs() < 0.01);
+    assert!((magical.health - 20.0).abs() < 0.01);
+}
+
+/// Test: Mage basic attacks are magical, the rest physical
+#[test]
+fn test_damage_type_for_unit() {
+    assert_eq!(DamageType::for_unit(TileType::Purple), DamageType::Magical);
+    assert_eq!(DamageType::for_unit(TileType::Red), DamageType::Physical);
+    assert_eq!(DamageType::for_unit(TileType::Green), DamageType::Physical);
+}
+
+// ============================================================
+// Deterministic Combat Step Tests
+// ============================================================
+
+fn spawn_fighter(world: &mut World, pos: HexPosition, team: Team, stats: UnitStats) -> Entity {
+    spawn_typed_fighter(world, pos, team, TileType::Red, stats)
+}
+
+fn spawn_typed_fighter(world: &mut World, pos: HexPosition, team: Team, unit_type: TileType, stats: UnitStats) -> Entity {
+    let entity = world
+        .spawn((
+            Unit,
+            pos,
+            stats,
+            UnitType(unit_type),
+            team,
+            Target(None),
+            AttackCooldown(0.0),
+            Transform::default(),
+        ))
+        .id();
+    world.resource_mut::<BattleGrid>().place_unit(pos, entity);
+    entity
+}
+
+/// Player Warrior next to a poisoned enemy dummy that never swings back
+fn setup_duel(seed: u64, crit_chance: f32) -> (World, Entity) {
+    let mut world = World::new();
+    init_combat_world(&mut world, seed);
+
+    let attacker = UnitStats {
+        attack: 12.0,
+        crit_chance,
+        ..UnitStats::default()
+    };
+    spawn_fighter(&mut world, HexPosition::new(0, 0), Team::Player, attacker);
+    let dummy = UnitStats {
+        attack: 0.0,
+        attack_speed: 0.001,
+        ..UnitStats::default()
+    };
+    let enemy = spawn_fighter(&mut world, HexPosition::new(1, 0), Team::Enemy, dummy);
+    world.entity_mut(enemy).insert(PoisonDebuff::new(4.0, 10.0));
+    (world, enemy)
+}
+
+/// Test: N combat steps reduce the target's HP by one melee hit plus N steps of poison
+#[test]
+fn test_step_combat_reduces_target_hp_by_expected_amount() {
+    let (mut world, enemy) = setup_duel(7, 0.0);
+
+    // 4 × 0.25s: the first swing lands on step one, the next is still a second away
+    for _ in 0..4 {
+        step_combat(&mut world, 0.25);
+    }
+
+    let health = world.get::<UnitStats>(enemy).unwrap().health;
+    let expected = 100.0 - 12.0 - 4.0 * 1.0;
+    assert!((health - expected).abs() < 0.01, "expected {}, got {}", expected, health);
+}
+
+/// Test: Same seed, same steps, same outcome (crits included)
+#[test]
+fn test_step_combat_is_deterministic_with_seed() {
+    let run = |seed: u64| {
+        let (mut world, enemy) = setup_duel(seed, 0.5);
+        for _ in 0..40 {
+            step_combat(&mut world, 0.1);
+        }
+        world.get::<UnitStats>(enemy).unwrap().health
+    };
+
+    assert_eq!(run(42), run(42));
+}
+
+// ============================================================
+// Battle Stats Recording Tests
+// ============================================================
+
+/// Test: A headless fight fills in both the MVP ally and the most dangerous enemy
+#[test]
+fn test_combat_records_mvp_and_most_dangerous_enemy() {
+    let mut world = World::new();
+    init_combat_world(&mut world, 3);
+
+    let warrior = UnitStats { attack: 15.0, ..UnitStats::default() };
+    spawn_typed_fighter(&mut world, HexPosition::new(0, 0), Team::Player, TileType::Red, warrior);
+    // Adjacent weakling the Warrior kills with its first swing
+    let weakling = UnitStats { health: 10.0, attack: 0.0, ..UnitStats::default() };
+    spawn_typed_fighter(&mut world, HexPosition::new(1, 0), Team::Enemy, TileType::Blue, weakling);
+    // Enemy Ranger two hexes away keeps shooting the Warrior
+    let ranger = UnitStats { attack: 9.0, attack_range: 2, ..UnitStats::default() };
+    spawn_typed_fighter(&mut world, HexPosition::new(0, 2), Team::Enemy, TileType::Green, ranger);
+
+    for _ in 0..8 {
+        step_combat(&mut world, 0.25);
+    }
+
+    let stats = world.resource::<BattleStats>();
+    assert_eq!(stats.mvp_ally.unit_type, Some(TileType::Red));
+    assert_eq!(stats.mvp_ally.kills, 1);
+    assert!(stats.mvp_ally.damage_dealt >= 15.0);
+    assert_eq!(stats.most_dangerous_enemy.unit_type, Some(TileType::Green));
+    assert!(stats.most_dangerous_enemy.total_damage >= 9.0);
+}
@@ -1,5 +1,11 @@
 // Puzzle mechanics tests - TDD for bomb adjacent match defuse
-use puzzle_tactics::puzzle::{PuzzleBoard, ObstacleType};
+use bevy::prelude::*;
+use puzzle_tactics::bridge::MatchEvent;
+use puzzle_tactics::state::ComboCounter;
+use puzzle_tactics::puzzle::{
+    PuzzleBoard, ObstacleType, TileType, LastSwap, PendingSwapCheck, MatchedRuns, SwapTilesEvent, Matched,
+    detect_matches, handle_tile_swap, find_match_groups,
+};
 
 // ============================================================
 // Bomb Adjacent Match Defuse Tests (TDD)
@@ -125,3 +131,95 @@ fn test_bomb_defuse_at_boundary() {
     board.clear_obstacle(0, 0);
     assert!(!board.has_bomb(0, 0));
 }
+
+// ============================================================
+// Seeded Layout End-to-End Tests
+// ============================================================
+
+/// (tile_type, count, positions) of a fired MatchEvent
+type CapturedMatch = (TileType, usize, Vec<(usize, usize)>);
+
+#[derive(Resource, Default)]
+struct CapturedMatches(Vec<CapturedMatch>);
+
+fn layout_from_rows(rows: &[&str]) -> Vec<Vec<Option<TileType>>> {
+    rows.iter()
+        .map(|row| {
+            row.chars()
+                .map(|c| match c {
+                    'R' => Some(TileType::Red),
+                    'B' => Some(TileType::Blue),
+                    'G' => Some(TileType::Green),
+                    'Y' => Some(TileType::Yellow),
+                    'P' => Some(TileType::Purple),
+                    _ => None,
+                })
+                .collect()
+        })
+        .collect()
+}
+
+fn setup_layout_app(layout: &[Vec<Option<TileType>>]) -> App {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .init_resource::<ComboCounter>()
+        .init_resource::<LastSwap>()
+        .init_resource::<PendingSwapCheck>()
+        .init_resource::<MatchedRuns>()
+        .init_resource::<CapturedMatches>()
+        .add_observer(handle_tile_swap)
+        .add_observer(|trigger: Trigger<MatchEvent>, mut captured: ResMut<CapturedMatches>| {
+            let event = trigger.event();
+            captured.0.push((event.tile_type, event.count, event.positions.clone()));
+        })
+        .add_systems(Update, detect_matches);
+
+    let mut commands = app.world_mut().commands();
+    let board = PuzzleBoard::from_layout(&mut commands, layout);
+    commands.insert_resource(board);
+    app.world_mut().flush();
+    app
+}
+
+/// Test: A seeded near-match becomes a MatchEvent once the completing swap is made
+#[test]
+fn test_swap_into_seeded_near_match_fires_match_event() {
+    // Row 0 (bottom) is R R B with a red just above the gap: swapping (2,0) and (2,1) makes R R R
+    let layout = layout_from_rows(&[
+        "RRBG", // y = 0
+        "GBRY", // y = 1
+        "BYGB", // y = 2
+        "YGBY", // y = 3
+    ]);
+    let mut app = setup_layout_app(&layout);
+
+    // Snapshot matches the seeded layout and has no ready-made matches
+    let world = app.world();
+    let snapshot = world
+        .resource::<PuzzleBoard>()
+        .tile_type_grid(|e| world.get::<TileType>(e).copied());
+    assert_eq!(snapshot, layout);
+    assert!(find_match_groups(&snapshot).is_empty());
+
+    app.update();
+    assert!(app.world().resource::<CapturedMatches>().0.is_empty());
+
+    app.world_mut()
+        .commands()
+        .trigger(SwapTilesEvent { from: (2, 0), to: (2, 1) });
+    app.update();
+
+    let captured = &app.world().resource::<CapturedMatches>().0;
+    assert_eq!(captured.len(), 1);
+    let (tile_type, count, positions) = &captured[0];
+    assert_eq!(*tile_type, TileType::Red);
+    assert_eq!(*count, 3);
+    assert_eq!(positions, &vec![(0, 0), (1, 0), (2, 0)]);
+
+    let matched = app
+        .world_mut()
+        .query_filtered::<Entity, With<Matched>>()
+        .iter(app.world())
+        .count();
+    assert_eq!(matched, 3);
+}
@@ -0,0 +1,30 @@
+//! Headless Simulation Tests
+//!
+//! Runs the battle side with no puzzle input on a fixed timestep.
+
+use bevy::prelude::*;
+use puzzle_tactics::battle::*;
+
+/// Test: 3 simulated waves run to a terminal state
+#[test]
+fn test_headless_sim_reaches_terminal_state() {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugins(HeadlessSimPlugin { waves: 3, seed: 42, ..default() });
+
+    let max_frames = (SIM_TIME_LIMIT / SIM_TIMESTEP) as usize + 10;
+    for _ in 0..max_frames {
+        app.update();
+        if app.world().resource::<SimReport>().finished {
+            break;
+        }
+    }
+
+    let report = app.world().resource::<SimReport>().clone();
+    let result = app.world().resource::<GameResult>();
+    assert!(report.finished, "the run must end: {report:?}");
+    assert!(!report.timed_out, "3 waves resolve well within the time limit");
+    assert!(sim_finished(result, 3));
+    assert!(report.defeated || report.waves_survived >= 3);
+    assert_eq!(report.waves_survived, result.waves_completed);
+}